@@ -1,33 +1,86 @@
-use std::path::PathBuf;
+use monitor_core::paths;
+use monitor_core::plans::PlanConfig;
+use monitor_core::pricing::{default_pricing_map, ModelPricing};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 // ── Directory bootstrap ────────────────────────────────────────────────────────
 
-/// Ensure the standard `~/.claude-monitor/` directory hierarchy exists.
+/// Ensure the XDG config and state directories exist.
 ///
-/// Creates the following directories if absent (including any missing parents):
-/// - `~/.claude-monitor/`
-/// - `~/.claude-monitor/logs/`
-/// - `~/.claude-monitor/cache/`
+/// See [`ensure_directories_in`] for the layout. Does nothing when either
+/// directory cannot be determined; `main` warns about that once logging is
+/// set up.
 pub fn ensure_directories() -> anyhow::Result<()> {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    let monitor_dir = home.join(".claude-monitor");
-    std::fs::create_dir_all(&monitor_dir)?;
-    std::fs::create_dir_all(monitor_dir.join("logs"))?;
-    std::fs::create_dir_all(monitor_dir.join("cache"))?;
+    match (paths::config_dir(), paths::state_dir()) {
+        (Some(config_dir), Some(state_dir)) => ensure_directories_in(&config_dir, &state_dir),
+        _ => Ok(()),
+    }
+}
+
+/// Create the following directories if absent (including any missing parents):
+/// - `<config_dir>/`
+/// - `<state_dir>/`
+/// - `<state_dir>/logs/`
+/// - `<state_dir>/cache/`
+pub fn ensure_directories_in(config_dir: &Path, state_dir: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(config_dir)?;
+    std::fs::create_dir_all(state_dir.join("logs"))?;
+    std::fs::create_dir_all(state_dir.join("cache"))?;
     Ok(())
 }
 
+// ── Version report ─────────────────────────────────────────────────────────────
+
+/// The `--version-json` report: crate version, resolved data path, the
+/// effective plan configuration, and the built-in pricing table.
+///
+/// Pricing is keyed by model name in sorted order so the output is stable.
+pub fn version_json(plan: Option<&PlanConfig>, data_path: Option<&str>) -> serde_json::Value {
+    let pricing: BTreeMap<String, ModelPricing> = default_pricing_map().into_iter().collect();
+    serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "data_path": data_path,
+        "plan": plan,
+        "pricing": pricing,
+    })
+}
+
+/// The `--show-pricing` table: one line per model, sorted by name, with the
+/// input, output, cache-create and cache-read rates in USD per million tokens.
+pub fn pricing_table(pricing: &HashMap<String, ModelPricing>) -> String {
+    let models: BTreeMap<&String, &ModelPricing> = pricing.iter().collect();
+    let width = models.keys().map(|m| m.len()).max().unwrap_or(0).max(5);
+    let mut out = format!(
+        "{:<width$}  {:>10}  {:>10}  {:>16}  {:>14}\n",
+        "Model", "Input $/M", "Output $/M", "Cache create $/M", "Cache read $/M"
+    );
+    for (model, rates) in models {
+        out.push_str(&format!(
+            "{:<width$}  {:>10.2}  {:>10.2}  {:>16.2}  {:>14.2}\n",
+            model, rates.input, rates.output, rates.cache_creation, rates.cache_read
+        ));
+    }
+    out
+}
+
 // ── Logging bootstrap ──────────────────────────────────────────────────────────
 
 /// Initialise the global `tracing` subscriber.
 ///
 /// `log_level` is mapped to a [`tracing_subscriber::EnvFilter`] directive.
 /// Falls back to `"info"` if the level string is not recognised.
+/// `log_format` `"json"` emits one JSON object per event instead of the
+/// human-readable text lines.
 ///
 /// The `log_file` parameter is accepted for forward-compatibility but file
 /// logging is not yet wired – all output currently goes to stderr.
-pub fn setup_logging(log_level: &str, _log_file: Option<&PathBuf>) -> anyhow::Result<()> {
+pub fn setup_logging(
+    log_level: &str,
+    log_format: &str,
+    _log_file: Option<&PathBuf>,
+) -> anyhow::Result<()> {
     // Map Python log-level names to tracing level names (tracing uses lowercase).
     let upper = log_level.to_uppercase();
     let normalised = match upper.as_str() {
@@ -40,32 +93,134 @@ pub fn setup_logging(log_level: &str, _log_file: Option<&PathBuf>) -> anyhow::Re
 
     let filter = EnvFilter::try_new(normalised).unwrap_or_else(|_| EnvFilter::new("info"));
 
-    let subscriber = fmt::layer().with_target(false).with_thread_ids(false);
-
-    tracing_subscriber::registry()
-        .with(filter)
-        .with(subscriber)
-        .init();
+    let registry = tracing_subscriber::registry().with(filter);
+    if log_format == "json" {
+        registry
+            .with(
+                fmt::layer()
+                    .json()
+                    .with_target(false)
+                    .with_thread_ids(false),
+            )
+            .init();
+    } else {
+        registry
+            .with(fmt::layer().with_target(false).with_thread_ids(false))
+            .init();
+    }
 
     Ok(())
 }
 
 // ── Data-path discovery ────────────────────────────────────────────────────────
 
-/// Attempt to locate the Claude AI data directory on the local system.
+/// Environment variable pointing at the Claude configuration directory; its
+/// `projects/` subdirectory holds the usage files. May list several
+/// directories separated by commas.
+pub const CLAUDE_CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
+
+/// One place the data directory may live, in resolution order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataPathCandidate {
+    /// Where the candidate came from, e.g. `"--data-path"`.
+    pub source: String,
+    /// Directory to read JSONL files from.
+    pub path: PathBuf,
+    /// Explicit overrides are used even when the path does not exist.
+    pub explicit: bool,
+}
+
+/// Build the ordered candidate list.
 ///
-/// Checks the following paths in order and returns the first that exists:
-/// 1. `~/.claude/projects/`
-/// 2. `~/.config/claude/projects/`
+/// Resolution order:
+/// 1. `--data-path` (`cli`), used as-is
+/// 2. `$CLAUDE_CONFIG_DIR/projects` for each comma-separated entry
+/// 3. `~/.claude/projects/`
+/// 4. `~/.config/claude/projects/`
+pub fn data_path_candidates(
+    cli: Option<&str>,
+    config_dir: Option<&str>,
+    home: Option<&Path>,
+) -> Vec<DataPathCandidate> {
+    let mut candidates = Vec::new();
+
+    if let Some(path) = cli {
+        candidates.push(DataPathCandidate {
+            source: "--data-path".to_string(),
+            path: PathBuf::from(path),
+            explicit: true,
+        });
+    }
+    for dir in config_dir
+        .into_iter()
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    {
+        candidates.push(DataPathCandidate {
+            source: format!("${CLAUDE_CONFIG_DIR_ENV}"),
+            path: Path::new(dir).join("projects"),
+            explicit: false,
+        });
+    }
+    if let Some(home) = home {
+        candidates.push(DataPathCandidate {
+            source: "~/.claude/projects".to_string(),
+            path: home.join(".claude").join("projects"),
+            explicit: false,
+        });
+        candidates.push(DataPathCandidate {
+            source: "~/.config/claude/projects".to_string(),
+            path: home.join(".config").join("claude").join("projects"),
+            explicit: false,
+        });
+    }
+
+    candidates
+}
+
+/// Candidates for the current process: `cli` plus the environment and home
+/// directory.
+pub fn default_data_path_candidates(cli: Option<&str>) -> Vec<DataPathCandidate> {
+    let config_dir = std::env::var(CLAUDE_CONFIG_DIR_ENV).ok();
+    data_path_candidates(cli, config_dir.as_deref(), dirs::home_dir().as_deref())
+}
+
+/// Pick the first explicit candidate, or else the first one that exists.
+pub fn resolve_data_path(candidates: &[DataPathCandidate]) -> Option<&DataPathCandidate> {
+    candidates.iter().find(|c| c.explicit || c.path.exists())
+}
+
+/// Attempt to locate the Claude AI data directory on the local system.
 ///
-/// Returns `None` when neither path exists.
+/// Uses the resolution order of [`data_path_candidates`] without a CLI
+/// override. Returns `None` when no candidate exists.
 pub fn discover_data_path() -> Option<PathBuf> {
-    let home = dirs::home_dir()?;
-    let candidates = [
-        home.join(".claude").join("projects"),
-        home.join(".config").join("claude").join("projects"),
-    ];
-    candidates.into_iter().find(|p| p.exists())
+    resolve_data_path(&default_data_path_candidates(None)).map(|c| c.path.clone())
+}
+
+/// Human-readable report for `--print-data-path`: the chosen path followed by
+/// every candidate and whether it exists.
+pub fn describe_data_path_resolution(candidates: &[DataPathCandidate]) -> String {
+    let mut out = match resolve_data_path(candidates) {
+        Some(chosen) => format!("{} (from {})\n", chosen.path.display(), chosen.source),
+        None => "No data directory found\n".to_string(),
+    };
+    out.push_str("Candidates, in order:\n");
+    for candidate in candidates {
+        let status = if candidate.path.exists() {
+            "exists"
+        } else {
+            "missing"
+        };
+        out.push_str(&format!(
+            "  {:<26} {} [{}]\n",
+            candidate.source,
+            candidate.path.display(),
+            status
+        ));
+    }
+    out
 }
 
 // ── Tests ──────────────────────────────────────────────────────────────────────
@@ -80,28 +235,55 @@ mod tests {
     #[test]
     fn test_ensure_directories() {
         let tmp = TempDir::new().expect("tempdir");
+        let config_dir = tmp.path().join("config").join("claude-monitor");
+        let state_dir = tmp.path().join("state").join("claude-monitor");
 
-        // Override HOME so that dirs::home_dir() resolves to our temp dir.
-        let original_home = std::env::var_os("HOME");
-        std::env::set_var("HOME", tmp.path());
+        ensure_directories_in(&config_dir, &state_dir).expect("ensure_directories should succeed");
 
-        let result = ensure_directories();
+        assert!(config_dir.is_dir(), "config dir must exist");
+        assert!(state_dir.is_dir(), "state dir must exist");
+        assert!(state_dir.join("logs").is_dir(), "logs subdir must exist");
+        assert!(state_dir.join("cache").is_dir(), "cache subdir must exist");
+    }
 
-        // Restore HOME.
-        match original_home {
-            Some(v) => std::env::set_var("HOME", v),
-            None => std::env::remove_var("HOME"),
-        }
+    // ── test_version_json ─────────────────────────────────────────────────────
 
-        result.expect("ensure_directories should succeed");
+    #[test]
+    fn test_version_json_reports_plan_and_pricing() {
+        let plan = monitor_core::plans::Plans::get_plan_by_name("max5");
+        let report = version_json(plan.as_ref(), Some("/data/projects"));
+
+        assert_eq!(report["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(report["data_path"], "/data/projects");
+        assert_eq!(report["plan"]["name"], "max5");
+        assert_eq!(report["plan"]["token_limit"], 88_000);
+        assert_eq!(report["pricing"]["claude-3-opus"]["input"], 15.0);
+    }
 
-        let monitor_dir = tmp.path().join(".claude-monitor");
-        assert!(monitor_dir.is_dir(), ".claude-monitor dir must exist");
-        assert!(monitor_dir.join("logs").is_dir(), "logs subdir must exist");
-        assert!(
-            monitor_dir.join("cache").is_dir(),
-            "cache subdir must exist"
-        );
+    #[test]
+    fn test_version_json_without_plan_or_data_path() {
+        let report = version_json(None, None);
+        assert!(report["plan"].is_null());
+        assert!(report["data_path"].is_null());
+    }
+
+    // ── test_pricing_table ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pricing_table_lists_sorted_models() {
+        let table = pricing_table(&default_pricing_map());
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("Model "), "{table}");
+        assert!(lines[0].ends_with("Cache read $/M"), "{table}");
+        assert_eq!(lines.len(), default_pricing_map().len() + 1);
+        assert!(lines[1].starts_with("claude-3-5-haiku "), "{table}");
+
+        let opus = lines
+            .iter()
+            .find(|l| l.starts_with("claude-3-opus "))
+            .unwrap();
+        let rates: Vec<&str> = opus.split_whitespace().skip(1).collect();
+        assert_eq!(rates, ["15.00", "75.00", "18.75", "1.50"]);
     }
 
     // ── test_discover_data_path ───────────────────────────────────────────────
@@ -166,4 +348,70 @@ mod tests {
 
         assert_eq!(path, Some(projects));
     }
+
+    // ── data_path_candidates ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_data_path_candidates_order() {
+        let home = Path::new("/home/me");
+        let candidates = data_path_candidates(Some("/cli"), Some("/cfg-a, /cfg-b"), Some(home));
+        let paths: Vec<PathBuf> = candidates.iter().map(|c| c.path.clone()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/cli"),
+                PathBuf::from("/cfg-a/projects"),
+                PathBuf::from("/cfg-b/projects"),
+                home.join(".claude/projects"),
+                home.join(".config/claude/projects"),
+            ]
+        );
+        assert!(candidates[0].explicit);
+        assert!(candidates[1..].iter().all(|c| !c.explicit));
+    }
+
+    #[test]
+    fn test_resolve_data_path_cli_wins_even_if_missing() {
+        let tmp = TempDir::new().expect("tempdir");
+        std::fs::create_dir_all(tmp.path().join(".claude/projects")).unwrap();
+
+        let candidates = data_path_candidates(Some("/does/not/exist"), None, Some(tmp.path()));
+        let chosen = resolve_data_path(&candidates).unwrap();
+        assert_eq!(chosen.source, "--data-path");
+    }
+
+    #[test]
+    fn test_resolve_data_path_config_dir_before_home() {
+        let tmp = TempDir::new().expect("tempdir");
+        let config = tmp.path().join("cfg");
+        std::fs::create_dir_all(config.join("projects")).unwrap();
+        std::fs::create_dir_all(tmp.path().join(".claude/projects")).unwrap();
+
+        let candidates = data_path_candidates(None, config.to_str(), Some(tmp.path()));
+        let chosen = resolve_data_path(&candidates).unwrap();
+        assert_eq!(chosen.path, config.join("projects"));
+
+        // A config dir without `projects/` falls through to the home dirs.
+        std::fs::remove_dir(config.join("projects")).unwrap();
+        let chosen = resolve_data_path(&candidates).unwrap();
+        assert_eq!(chosen.path, tmp.path().join(".claude/projects"));
+    }
+
+    #[test]
+    fn test_describe_data_path_resolution() {
+        let tmp = TempDir::new().expect("tempdir");
+        let candidates = data_path_candidates(None, None, Some(tmp.path()));
+        let report = describe_data_path_resolution(&candidates);
+        assert!(report.starts_with("No data directory found"), "{report}");
+
+        std::fs::create_dir_all(tmp.path().join(".config/claude/projects")).unwrap();
+        let report = describe_data_path_resolution(&candidates);
+        assert!(
+            report.contains("(from ~/.config/claude/projects)"),
+            "{report}"
+        );
+        assert!(report.contains("[missing]"), "{report}");
+        assert!(report.contains("[exists]"), "{report}");
+    }
 }
@@ -1,19 +1,47 @@
 mod bootstrap;
 
 use anyhow::Result;
+use monitor_core::lifetime::LifetimeTracker;
+use monitor_core::models::{CostMode, TokensBasis};
+use monitor_core::notifications::NotificationManager;
+use monitor_core::p90::LimitDetect;
+use monitor_core::plans::Plans;
+use monitor_core::pricing::{load_pricing_file, PricingCalculator};
 use monitor_core::settings::Settings;
-use monitor_data::aggregator::UsageAggregator;
-use monitor_data::analysis::analyze_usage;
+use monitor_data::aggregator::{AggregatedPeriod, UsageAggregator};
+use monitor_data::analysis::{analyze_usage_with_progress, AnalysisResult};
+use monitor_data::analyzer::BlockStrategy;
+use monitor_data::export::{self, ExportLevel, ModelAnonymization};
+use monitor_data::follow::{format_follow_line, FollowReader};
+use monitor_data::parse_cache::PARSE_CACHE_FILE_NAME;
+use monitor_data::reader::{DedupKey, FileFilter, LoadOptions};
 use monitor_runtime::orchestrator::MonitoringOrchestrator;
-use monitor_ui::app::{App, ViewMode};
-use monitor_ui::table_view::{TableRowData, TableTotals};
+use monitor_runtime::session_monitor::SessionMonitor;
+use monitor_ui::app::{self, ActiveBlockPolicy, App, ViewMode};
+use monitor_ui::glyphs::{Glyphs, ASCII_DECORATION};
+use monitor_ui::markdown;
+use monitor_ui::model_labels::ModelLabels;
+use monitor_ui::session_view::{HeaderBranding, SessionLayout};
+use monitor_ui::table_view::{TableColumns, TableData, TableRowData, TableTotals};
+use std::io::Write;
+use std::time::Duration;
+
+/// How often `--follow` checks the JSONL files for new lines.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let settings = Settings::load_with_last_used();
 
+    // Make sure a panic anywhere in the TUI leaves the shell usable.
+    app::install_panic_hook();
+
     bootstrap::ensure_directories()?;
-    bootstrap::setup_logging(&settings.log_level, settings.log_file.as_ref())?;
+    bootstrap::setup_logging(
+        &settings.log_level,
+        &settings.log_format,
+        settings.log_file.as_ref(),
+    )?;
 
     tracing::info!("Claude Monitor v{} starting", env!("CARGO_PKG_VERSION"));
     tracing::info!(
@@ -22,104 +50,291 @@ async fn main() -> Result<()> {
         settings.view,
         settings.theme
     );
+    if monitor_core::paths::config_dir().is_none() {
+        tracing::warn!(
+            "No home directory and no $XDG_CONFIG_HOME: settings will not be remembered"
+        );
+    }
+
+    if settings.print_data_path {
+        let candidates =
+            bootstrap::default_data_path_candidates(settings.data_path_override().as_deref());
+        print!("{}", bootstrap::describe_data_path_resolution(&candidates));
+        return Ok(());
+    }
 
-    let data_path = bootstrap::discover_data_path();
+    // `--data-path` / `--stdin` win over `$CLAUDE_CONFIG_DIR` and the home
+    // directory defaults.
+    let data_path_str = settings
+        .data_path_override()
+        .or_else(|| bootstrap::discover_data_path().map(|p| p.to_string_lossy().to_string()));
+
+    if settings.version_json {
+        let plan = Plans::resolve_plan(&settings.plan, &settings.custom_plan_limits());
+        let report = bootstrap::version_json(plan.as_ref(), data_path_str.as_deref());
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let pricing_overrides = settings
+        .pricing_file
+        .as_deref()
+        .map(load_pricing_file)
+        .transpose()?;
+
+    if settings.show_pricing {
+        let pricing = PricingCalculator::new(pricing_overrides);
+        print!("{}", bootstrap::pricing_table(pricing.pricing_map()));
+        return Ok(());
+    }
+
+    let load_options = LoadOptions {
+        count_empty_messages: settings.count_empty_messages,
+        read_timeout: settings.read_timeout(),
+        dedup_key: DedupKey::from_name(&settings.dedup_key),
+        include_synthetic: settings.include_synthetic,
+        cost_mode: CostMode::from_name(&settings.cost_mode),
+        track_sources: settings.track_sources,
+        pricing_overrides,
+        max_entry_tokens: settings.max_entry_tokens,
+        file_filter: FileFilter::new(&settings.include, &settings.exclude)?,
+        parse_cache: if settings.no_cache {
+            None
+        } else {
+            monitor_core::paths::state_dir()
+                .map(|dir| dir.join("cache").join(PARSE_CACHE_FILE_NAME))
+        },
+        block_strategy: if settings.fixed_blocks {
+            BlockStrategy::FixedSchedule {
+                anchor_hour: settings.reset_hour.unwrap_or(0),
+                tz: settings.display_timezone(),
+            }
+        } else {
+            BlockStrategy::default()
+        },
+    };
+
+    if settings.validate {
+        let analysis = analyze_usage_with_progress(
+            None,
+            false,
+            data_path_str.as_deref(),
+            &load_options,
+            |_| {},
+        );
+        print!("{}", analysis.validation_report());
+        if !analysis.has_usable_data() {
+            anyhow::bail!("no usable usage data found");
+        }
+        return Ok(());
+    }
+
+    if settings.follow {
+        if settings.reads_stdin() {
+            anyhow::bail!("--follow cannot be combined with reading from stdin");
+        }
+        return follow(data_path_str.as_deref(), &load_options).await;
+    }
 
     match settings.view.as_str() {
         "realtime" | "session" => {
+            if settings.reads_stdin() {
+                anyhow::bail!(
+                    "reading from stdin is only supported with --view daily, monthly or projects"
+                );
+            }
+
             tracing::info!("Starting real-time monitoring...");
 
-            let data_path_str = data_path.map(|p| p.to_string_lossy().to_string());
+            // Loader progress feeds the "Loading usage data" screen.
+            let (progress_tx, progress_rx) = tokio::sync::mpsc::channel(64);
+            // `r` / F5 in the TUI ask for an immediate refresh.
+            let (refresh_tx, refresh_rx) = tokio::sync::mpsc::channel(1);
 
-            let orchestrator = MonitoringOrchestrator::new(
+            let mut orchestrator = MonitoringOrchestrator::new(
                 u64::from(settings.refresh_rate),
-                data_path_str,
+                data_path_str.clone(),
                 settings.plan.clone(),
-            );
+            )
+            .with_analyze_interval(Duration::from_secs(u64::from(settings.analyze_interval)))
+            .with_custom_limits(settings.custom_plan_limits())
+            .with_limit_detect(LimitDetect::from_name(&settings.limit_detect))
+            .with_load_options(load_options.clone())
+            .with_progress(progress_tx)
+            .with_refresh_requests(refresh_rx);
+            if let Some(path) = SessionMonitor::default_state_path() {
+                orchestrator = orchestrator.with_session_state(&path);
+            }
 
             let (rx, handle) = orchestrator.start();
+            let glyphs = Glyphs::resolve(settings.ascii);
 
-            let app = App::new(
+            let mut app = App::new(
                 &settings.theme,
                 ViewMode::Realtime,
                 settings.plan.clone(),
                 settings.timezone.clone(),
-            );
-
-            // Run the TUI event loop. The loop exits on 'q' / Ctrl+C inside the TUI.
-            // We also listen for Ctrl+C at the OS level so that signals received
-            // while the terminal is in raw mode are handled cleanly.
-            tokio::select! {
-                result = app.run_realtime(rx) => {
-                    handle.abort();
-                    result?;
-                }
-                _ = tokio::signal::ctrl_c() => {
-                    tracing::info!("Ctrl+C received; shutting down monitoring task");
-                    handle.abort();
+            )
+            .with_custom_limits(settings.custom_plan_limits())
+            .with_utc(settings.utc)
+            .with_number_format(settings.number_formatter())
+            .with_time_format(settings.time_format.clone())
+            .with_model_labels(ModelLabels::from_rules(&settings.model_labels)?)
+            .with_max_models(settings.max_models as usize)
+            .with_layout(SessionLayout::from_name(&settings.layout))
+            .with_active_block_policy(ActiveBlockPolicy::from_name(&settings.active_block))
+            .with_glyphs(glyphs)
+            .with_inline(settings.inline)
+            .with_branding(HeaderBranding::new(
+                settings.title.clone(),
+                settings
+                    .decoration
+                    .clone()
+                    .or_else(|| (glyphs == Glyphs::Ascii).then(|| ASCII_DECORATION.to_string())),
+            ))
+            .with_message_limit(settings.message_limit)
+            .with_reset_hour(settings.reset_hour)
+            .with_daily_budget(settings.daily_budget)
+            .with_tokens_basis(settings.tokens_basis_or(TokensBasis::Io))
+            .with_pricing_overrides(load_options.pricing_overrides)
+            .with_staleness_minutes(settings.staleness_minutes)
+            .with_prediction_warmup_minutes(settings.prediction_warmup_minutes)
+            .with_model_window_minutes(settings.model_window_minutes)
+            .with_include_synthetic(settings.include_synthetic)
+            .with_model_token_limits(settings.model_token_limits())
+            .with_data_path(data_path_str)
+            .with_progress(progress_rx)
+            .with_refresh(refresh_tx)
+            .with_shutdown(spawn_shutdown_listener());
+            match LifetimeTracker::with_default_path() {
+                Some(tracker) => app = app.with_lifetime(tracker),
+                None => tracing::warn!("All-time totals disabled: no home directory"),
+            }
+            // Only the interactive realtime view rings; table and --follow
+            // output never do.
+            if settings.bell_on_limit {
+                match NotificationManager::with_default_path() {
+                    Some(notifier) => {
+                        let cooldowns = settings.notification_cooldowns();
+                        app = app.with_limit_bell(notifier.with_cooldowns(cooldowns));
+                    }
+                    None => tracing::warn!("--bell-on-limit ignored: no home directory"),
                 }
             }
+
+            // Run the TUI event loop. It exits on 'q' / Ctrl+C inside the TUI
+            // and on SIGTERM, restoring the terminal either way. Session
+            // history, notification states and lifetime totals are written as
+            // they change, so stopping the monitoring task loses nothing.
+            let result = app.run_realtime(rx).await;
+            handle.abort();
+            result?;
         }
 
-        "daily" | "monthly" => {
+        "daily" | "monthly" | "projects" => {
             tracing::info!("Running {} view...", settings.view);
 
-            let data_path_str = data_path.map(|p| p.to_string_lossy().to_string());
+            if settings.output == "markdown" && settings.view == "projects" {
+                anyhow::bail!("--output markdown is only supported with --view daily or monthly");
+            }
 
-            // Run the full analysis pipeline to get all session blocks.
-            let analysis = analyze_usage(None, false, data_path_str.as_deref());
-
-            // Aggregate the blocks into per-period rows.
-            let periods = UsageAggregator::aggregate_from_blocks(&analysis.blocks, &settings.view);
-
-            // Compute cross-period totals.
-            let agg_totals = UsageAggregator::calculate_totals(&periods);
-
-            // Convert AggregatedPeriod → TableRowData.
-            let rows: Vec<TableRowData> = periods
-                .into_iter()
-                .map(|p| {
-                    let total_tokens = p.stats.total_tokens();
-                    let mut models: Vec<String> = p.models_used.into_iter().collect();
-                    models.sort();
-                    TableRowData {
-                        period: p.period_key,
-                        models,
-                        input_tokens: p.stats.input_tokens,
-                        output_tokens: p.stats.output_tokens,
-                        cache_creation: p.stats.cache_creation_tokens,
-                        cache_read: p.stats.cache_read_tokens,
-                        total_tokens,
-                        cost: p.stats.cost,
-                    }
-                })
-                .collect();
-
-            // Build the totals row shown at the bottom of the table.
-            let totals = TableTotals {
-                input_tokens: agg_totals.input_tokens,
-                output_tokens: agg_totals.output_tokens,
-                cache_creation: agg_totals.cache_creation_tokens,
-                cache_read: agg_totals.cache_read_tokens,
-                total_tokens: agg_totals.total_tokens(),
-                total_cost: agg_totals.cost,
-                entries_count: agg_totals.count,
+            let view_mode = match settings.view.as_str() {
+                "monthly" => ViewMode::Monthly,
+                "projects" => ViewMode::Projects,
+                _ => ViewMode::Daily,
             };
 
-            let view_mode = if settings.view == "monthly" {
-                ViewMode::Monthly
-            } else {
-                ViewMode::Daily
-            };
+            // The interactive table analyses behind a spinner on the
+            // alternate screen instead of leaving the terminal blank.
+            if settings.output == "table" {
+                let (progress_tx, progress_rx) = tokio::sync::mpsc::channel(64);
+                let app = App::new(
+                    &settings.theme,
+                    view_mode,
+                    settings.plan.clone(),
+                    settings.timezone.clone(),
+                )
+                .with_number_format(settings.number_formatter())
+                .with_table_columns(TableColumns::parse(&settings.table_columns)?)
+                .with_heatmap(settings.heatmap)
+                .with_inline(settings.inline)
+                .with_progress(progress_rx)
+                .with_shutdown(spawn_shutdown_listener());
 
-            let app = App::new(
-                &settings.theme,
-                view_mode,
-                settings.plan.clone(),
-                settings.timezone.clone(),
+                let view = settings.view.clone();
+                let (min_tokens, min_cost) = (settings.min_tokens, settings.min_cost);
+                let basis = settings.tokens_basis_or(TokensBasis::All);
+                let shown = app
+                    .run_table_loading(move || {
+                        let analysis = analyze_usage_with_progress(
+                            None,
+                            false,
+                            data_path_str.as_deref(),
+                            &load_options,
+                            |p| {
+                                let _ = progress_tx.try_send(p);
+                            },
+                        );
+                        let periods = aggregate_periods(&analysis, &view);
+                        build_table(periods, min_tokens, min_cost, basis)
+                    })
+                    .await?;
+                if !shown {
+                    // Quit mid-load: the runtime would wait for the load on
+                    // its blocking thread before letting main return.
+                    std::io::stdout().flush()?;
+                    std::process::exit(0);
+                }
+                return Ok(());
+            }
+
+            // Run the full analysis pipeline to get all session blocks.
+            let analysis = analyze_usage_with_progress(
+                None,
+                false,
+                data_path_str.as_deref(),
+                &load_options,
+                |_| {},
             );
 
-            app.run_table(rows, totals).await?;
+            // Exports only: the interactive table above keeps model ids.
+            let anonymize = settings
+                .anonymize_models
+                .as_deref()
+                .map_or(ModelAnonymization::Keep, ModelAnonymization::from_name);
+
+            // JSON dumps the whole analysis rather than aggregated rows.
+            if settings.output == "json" {
+                let level = ExportLevel::from_name(&settings.export_level);
+                let analysis = export::anonymize_analysis(analysis, anonymize);
+                let mut stdout = std::io::stdout().lock();
+                export::write_analysis_json(&mut stdout, &analysis, level)?;
+                writeln!(stdout)?;
+                return Ok(());
+            }
+
+            // JSON Lines streams one session block per line.
+            if settings.output == "jsonl" {
+                let level = ExportLevel::from_name(&settings.export_level);
+                let analysis = export::anonymize_analysis(analysis, anonymize);
+                export::write_blocks_jsonl(std::io::stdout().lock(), &analysis, level)?;
+                return Ok(());
+            }
+
+            let periods =
+                export::anonymize_periods(aggregate_periods(&analysis, &settings.view), anonymize);
+            let basis = settings.tokens_basis_or(TokensBasis::All);
+            match settings.output.as_str() {
+                "csv" => print!("{}", export::periods_to_csv_for(&periods, basis)),
+                "csv-wide" => print!("{}", export::periods_to_wide_csv_for(&periods, basis)),
+                _ => {
+                    let table = build_table(periods, settings.min_tokens, settings.min_cost, basis);
+                    print!(
+                        "{}",
+                        markdown::format_markdown_table(&table.rows, &table.totals)
+                    );
+                }
+            }
         }
 
         unknown => {
@@ -129,3 +344,156 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Aggregate the analysed blocks into per-period (or, for `projects`,
+/// per-project) rows.
+fn aggregate_periods(analysis: &AnalysisResult, view: &str) -> Vec<AggregatedPeriod> {
+    if view == "projects" {
+        let entries: Vec<_> = analysis
+            .blocks
+            .iter()
+            .filter(|b| !b.is_gap)
+            .flat_map(|b| b.entries.iter().cloned())
+            .collect();
+        UsageAggregator::aggregate_by_project(&entries)
+    } else {
+        UsageAggregator::aggregate_from_blocks(&analysis.blocks, view)
+    }
+}
+
+/// Table rows and totals for `periods`.
+///
+/// Totals cover every period; rows below `min_tokens` / `min_cost` are then
+/// hidden. Token totals and model shares are counted on `basis`.
+fn build_table(
+    periods: Vec<AggregatedPeriod>,
+    min_tokens: Option<u64>,
+    min_cost: Option<f64>,
+    basis: TokensBasis,
+) -> TableData {
+    let agg_totals = UsageAggregator::calculate_totals(&periods);
+    let model_totals = UsageAggregator::calculate_model_totals(&periods);
+    let (periods, hidden_rows) =
+        UsageAggregator::filter_by_threshold_for(periods, min_tokens, min_cost, basis);
+
+    // Convert AggregatedPeriod → TableRowData.
+    let rows: Vec<TableRowData> = periods
+        .into_iter()
+        .map(|p| {
+            let total_tokens = p.stats.total_for(basis);
+            let mut models: Vec<String> = p.models_used.into_iter().collect();
+            models.sort();
+            TableRowData {
+                period: p.period_key,
+                models,
+                input_tokens: p.stats.input_tokens,
+                output_tokens: p.stats.output_tokens,
+                cache_creation: p.stats.cache_creation_tokens,
+                cache_read: p.stats.cache_read_tokens,
+                total_tokens,
+                cost: p.stats.cost,
+            }
+        })
+        .collect();
+
+    // Share of all tokens per model, largest first.
+    let grand_total = agg_totals.total_for(basis);
+    let mut model_shares: Vec<(String, f64)> = if grand_total > 0 {
+        model_totals
+            .into_iter()
+            .map(|(model, stats)| {
+                let pct = stats.total_for(basis) as f64 / grand_total as f64 * 100.0;
+                (model, pct)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    model_shares.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Build the totals row shown at the bottom of the table.
+    let totals = TableTotals {
+        input_tokens: agg_totals.input_tokens,
+        output_tokens: agg_totals.output_tokens,
+        cache_creation: agg_totals.cache_creation_tokens,
+        cache_read: agg_totals.cache_read_tokens,
+        total_tokens: agg_totals.total_for(basis),
+        total_cost: agg_totals.cost,
+        entries_count: agg_totals.count,
+        model_shares,
+    };
+
+    TableData {
+        rows,
+        totals,
+        hidden_rows,
+    }
+}
+
+/// Listen for Ctrl+C and SIGTERM on a background task; the returned
+/// receiver turns `true` when either arrives.
+///
+/// Only the TUI views install it: plain exports keep the default signal
+/// handling, which terminates them at once.
+fn spawn_shutdown_listener() -> tokio::sync::watch::Receiver<bool> {
+    let (tx, rx) = tokio::sync::watch::channel(false);
+    tokio::spawn(async move {
+        let signal = shutdown_signal().await;
+        tracing::info!("{signal} received; shutting down");
+        let _ = tx.send(true);
+    });
+    rx
+}
+
+/// Wait for Ctrl+C or, on Unix, SIGTERM (as sent by `systemctl stop`), and
+/// return the name of the signal received.
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => "Ctrl+C",
+                    _ = terminate.recv() => "SIGTERM",
+                }
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "cannot listen for SIGTERM");
+                let _ = tokio::signal::ctrl_c().await;
+                "Ctrl+C"
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "Ctrl+C"
+    }
+}
+
+/// Print every usage entry appended after startup until Ctrl+C or SIGTERM.
+async fn follow(data_path: Option<&str>, options: &LoadOptions) -> Result<()> {
+    let mut reader = FollowReader::new(data_path, options);
+    reader.seek_to_end();
+
+    let mut interval = tokio::time::interval(FOLLOW_POLL_INTERVAL);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let entries = reader.poll();
+                if entries.is_empty() {
+                    continue;
+                }
+                let mut stdout = std::io::stdout().lock();
+                for entry in &entries {
+                    writeln!(stdout, "{}", format_follow_line(entry))?;
+                }
+                stdout.flush()?;
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
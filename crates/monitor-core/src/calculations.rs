@@ -88,8 +88,8 @@ impl BurnRateCalculator {
         let remaining_minutes = remaining_secs as f64 / 60.0;
         let remaining_hours = remaining_minutes / 60.0;
 
-        let projected_total_tokens =
-            current_tokens + (burn_rate.tokens_per_minute * remaining_minutes).round() as u64;
+        let projected_total_tokens = current_tokens
+            .saturating_add((burn_rate.tokens_per_minute * remaining_minutes).round() as u64);
         let projected_total_cost = current_cost + burn_rate.cost_per_hour * remaining_hours;
 
         Some(UsageProjection {
@@ -159,6 +159,7 @@ mod tests {
     ) -> SessionBlock {
         SessionBlock {
             id: "test".to_string(),
+            label: String::new(),
             start_time: start,
             end_time: end,
             entries: vec![],
@@ -0,0 +1,112 @@
+//! The current time as a dependency.
+//!
+//! Active-block marking, burn rates, predictions and notification cooldowns
+//! all compare against "now". Taking it from a [`Clock`] instead of calling
+//! `Utc::now()` directly lets tests pin it with a `MockClock` (behind the
+//! `testutil` feature) and assert exact values; production code uses
+//! [`SystemClock`].
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time.
+pub trait Clock: Send + Sync {
+    /// The current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// A clock shared between the components that read it.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// The system clock as a [`SharedClock`].
+    pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that stands still until moved, for tests.
+///
+/// Clones share the same time, so a test can keep one and move the time
+/// under the component holding the other.
+#[cfg(any(test, feature = "testutil"))]
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<std::sync::Mutex<DateTime<Utc>>>,
+}
+
+#[cfg(any(test, feature = "testutil"))]
+impl MockClock {
+    /// A clock fixed at `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(std::sync::Mutex::new(now)),
+        }
+    }
+
+    /// A clone of this clock as a [`SharedClock`].
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+
+    /// Move the clock to `now`.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// Move the clock forward by `by`.
+    pub fn advance(&self, by: chrono::Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+}
+
+#[cfg(any(test, feature = "testutil"))]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_mock_clock_moves_only_when_told() {
+        let clock = MockClock::new(at("2024-01-15T10:00:00Z"));
+        let shared = clock.shared();
+        assert_eq!(shared.now(), at("2024-01-15T10:00:00Z"));
+
+        clock.advance(chrono::Duration::minutes(90));
+        assert_eq!(shared.now(), at("2024-01-15T11:30:00Z"));
+        clock.set(at("2024-02-01T00:00:00Z"));
+        assert_eq!(shared.now(), at("2024-02-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_system_clock_is_current() {
+        let before = Utc::now();
+        let now = SystemClock.now();
+        assert!(now >= before && now <= Utc::now());
+    }
+}
@@ -108,10 +108,10 @@ impl TokenExtractor {
     /// Extract token counts from any shape of JSON entry.
     ///
     /// For `type == "assistant"` entries the lookup order is:
-    ///   `message.usage` → `usage` → root object.
+    ///   `message.usage` → `usage` → `response.usage` → root object.
     ///
     /// For all other entries:
-    ///   `usage` → `message.usage` → root object.
+    ///   `usage` → `message.usage` → `response.usage` → root object.
     ///
     /// Within each candidate object, the first alternative key that yields a
     /// non-zero value wins.
@@ -125,11 +125,12 @@ impl TokenExtractor {
         // Build the ordered list of source objects to probe.
         let message_usage = data.get("message").and_then(|m| m.get("usage"));
         let usage = data.get("usage");
+        let response_usage = data.get("response").and_then(|r| r.get("usage"));
 
         let sources: Vec<Option<&Value>> = if is_assistant {
-            vec![message_usage, usage, Some(data)]
+            vec![message_usage, usage, response_usage, Some(data)]
         } else {
-            vec![usage, message_usage, Some(data)]
+            vec![usage, message_usage, response_usage, Some(data)]
         };
 
         for source_opt in sources {
@@ -158,7 +159,10 @@ impl TokenExtractor {
                         "cacheReadInputTokens",
                     ],
                 );
-                let total = input + output + cache_create + cache_read;
+                let total = input
+                    .saturating_add(output)
+                    .saturating_add(cache_create)
+                    .saturating_add(cache_read);
                 return ExtractedTokens {
                     input_tokens: input,
                     output_tokens: output,
@@ -254,6 +258,7 @@ impl DataConverter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{Datelike, Timelike};
     use serde_json::json;
 
     // ── TimestampProcessor ───────────────────────────────────────────────────
@@ -350,6 +355,27 @@ mod tests {
         assert_eq!(t.output_tokens, 100);
     }
 
+    #[test]
+    fn test_extract_response_usage_nesting() {
+        for kind in ["assistant", "user"] {
+            let data = json!({
+                "type": kind,
+                "response": {
+                    "usage": {
+                        "input_tokens": 120u64,
+                        "output_tokens": 80u64,
+                        "cache_read_input_tokens": 40u64,
+                    }
+                }
+            });
+            let t = TokenExtractor::extract(&data);
+            assert_eq!(t.input_tokens, 120, "type {kind}");
+            assert_eq!(t.output_tokens, 80, "type {kind}");
+            assert_eq!(t.cache_read_input_tokens, 40, "type {kind}");
+            assert_eq!(t.total_tokens, 240, "type {kind}");
+        }
+    }
+
     #[test]
     fn test_extract_assistant_message_usage_priority() {
         let data = json!({
@@ -486,7 +512,3 @@ mod tests {
         );
     }
 }
-
-// Re-export chrono items used in tests for brevity.
-#[allow(unused_imports)]
-use chrono::{Datelike, Timelike};
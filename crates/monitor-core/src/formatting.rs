@@ -12,40 +12,27 @@
 /// assert_eq!(format_number(-9876.5, 1), "-9,876.5");
 /// ```
 pub fn format_number(value: f64, decimals: u32) -> String {
-    // Handle the sign separately so the thousands grouping works on the
-    // absolute value.
-    let negative = value < 0.0;
-    let abs_value = value.abs();
-
-    // Round to the requested decimal places.
-    // Add a tiny epsilon (half ULP at the target precision) before rounding
-    // to avoid IEEE 754 binary-representation issues at exact midpoints.
-    let factor = 10_f64.powi(decimals as i32);
-    let epsilon = f64::EPSILON * abs_value * factor;
-    let rounded = ((abs_value * factor) + epsilon).round() / factor;
-
-    let integer_part = rounded.trunc() as u64;
-    let frac_part = rounded - rounded.trunc();
-
-    // Build the thousands-separated integer portion.
-    let int_str = integer_part.to_string();
-    let grouped = group_thousands(&int_str);
-
-    let result = if decimals == 0 {
-        grouped
-    } else {
-        // Format the fractional part to the exact number of decimals.
-        let frac_str = format!("{:.prec$}", frac_part, prec = decimals as usize);
-        // `frac_str` starts with "0.", e.g. "0.50". Strip the leading "0".
-        let decimal_digits = &frac_str[1..]; // ".50"
-        format!("{}{}", grouped, decimal_digits)
-    };
-
-    if negative {
-        format!("-{}", result)
-    } else {
-        result
-    }
+    NumberFormatter::default().format_number(value, decimals)
+}
+
+/// Abbreviate a count as `999`, `1.2k`, `235k`, `1.2M` or `3.4B`.
+///
+/// Values below 100 of a unit keep one decimal (dropped when it is `.0`);
+/// larger values are rounded to a whole number of that unit.
+///
+/// # Examples
+///
+/// ```
+/// use monitor_core::formatting::format_compact;
+///
+/// assert_eq!(format_compact(999),       "999");
+/// assert_eq!(format_compact(1_234),     "1.2k");
+/// assert_eq!(format_compact(19_000),    "19k");
+/// assert_eq!(format_compact(235_123),   "235k");
+/// assert_eq!(format_compact(1_234_567), "1.2M");
+/// ```
+pub fn format_compact(n: u64) -> String {
+    NumberFormatter::default().format_compact(n)
 }
 
 /// Format a monetary amount as a USD string with two decimal places and
@@ -121,23 +108,212 @@ pub fn percentage(part: f64, whole: f64, decimal_places: u32) -> f64 {
     (raw * factor).round() / factor
 }
 
-// ── Internal helpers ──────────────────────────────────────────────────────────
+// ── NumberFormatter ───────────────────────────────────────────────────────────
+
+/// Separator characters used when displaying numbers.
+///
+/// The default (`,` thousands, `.` decimal) matches [`format_number`].
+///
+/// # Examples
+///
+/// ```
+/// use monitor_core::formatting::NumberFormatter;
+///
+/// let de = NumberFormatter::from_name("dot");
+/// assert_eq!(de.format_count(1_234_567), "1.234.567");
+/// assert_eq!(de.format_number(1234.5, 1), "1.234,5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormatter {
+    /// Inserted between groups of three integer digits.
+    pub thousands_separator: char,
+    /// Placed between the integer and fractional digits.
+    pub decimal_separator: char,
+    /// Decimal places shown for costs; cost rates use two more.
+    pub cost_precision: u32,
+    /// Abbreviate token counts with [`format_compact`] (`--compact-numbers`).
+    pub compact: bool,
+}
+
+impl Default for NumberFormatter {
+    fn default() -> Self {
+        Self::new(',', '.')
+    }
+}
+
+impl NumberFormatter {
+    /// Names accepted by [`NumberFormatter::from_name`].
+    pub const NAMES: [&'static str; 3] = ["comma", "dot", "space"];
+
+    /// Decimal places used for costs unless `--cost-precision` says otherwise.
+    pub const DEFAULT_COST_PRECISION: u32 = 2;
+
+    /// Create a formatter with explicit separators.
+    pub const fn new(thousands_separator: char, decimal_separator: char) -> Self {
+        Self {
+            thousands_separator,
+            decimal_separator,
+            cost_precision: Self::DEFAULT_COST_PRECISION,
+            compact: false,
+        }
+    }
+
+    /// Abbreviate token counts in [`format_tokens`](Self::format_tokens).
+    pub const fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Set the number of decimal places used by [`format_currency`](Self::format_currency).
+    pub const fn with_cost_precision(mut self, cost_precision: u32) -> Self {
+        self.cost_precision = cost_precision;
+        self
+    }
+
+    /// Resolve a `--number-format` name.
+    ///
+    /// * `"comma"` → `1,234,567.89` (default for unknown names)
+    /// * `"dot"`   → `1.234.567,89`
+    /// * `"space"` → `1 234 567,89` (narrow no-break space)
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "dot" => Self::new('.', ','),
+            "space" => Self::new('\u{202F}', ','),
+            _ => Self::default(),
+        }
+    }
+
+    /// Format an integer count with thousands separators.
+    pub fn format_count(&self, n: u64) -> String {
+        self.group_thousands(&n.to_string())
+    }
+
+    /// Abbreviate a count with `k`/`M`/`B` suffixes, like [`format_compact`].
+    pub fn format_compact(&self, n: u64) -> String {
+        const UNITS: [(f64, &str); 3] = [(1e3, "k"), (1e6, "M"), (1e9, "B")];
 
-/// Insert commas every three digits from the right of an integer string.
-fn group_thousands(s: &str) -> String {
-    if s.len() <= 3 {
-        return s.to_string();
+        if n < 1_000 {
+            return n.to_string();
+        }
+        let value = n as f64;
+        for (i, &(scale, suffix)) in UNITS.iter().enumerate() {
+            let scaled = value / scale;
+            let text = if scaled < 99.95 {
+                let one_decimal = self.format_number(scaled, 1);
+                let zero = format!("{}0", self.decimal_separator);
+                one_decimal
+                    .strip_suffix(&zero)
+                    .map(str::to_string)
+                    .unwrap_or(one_decimal)
+            } else if scaled.round() < 1_000.0 || i == UNITS.len() - 1 {
+                self.format_number(scaled, 0)
+            } else {
+                // Rounds up to 1000 of this unit; use the next one.
+                continue;
+            };
+            return format!("{text}{suffix}");
+        }
+        unreachable!("the last unit always returns")
     }
-    let chars: Vec<char> = s.chars().collect();
-    let mut result = String::with_capacity(s.len() + s.len() / 3);
-    let remainder = chars.len() % 3;
-    for (i, &c) in chars.iter().enumerate() {
-        if i != 0 && (i % 3 == remainder) {
-            result.push(',');
+
+    /// Format a token count: abbreviated when `compact` is set, otherwise
+    /// with full thousands grouping.
+    pub fn format_tokens(&self, n: u64) -> String {
+        if self.compact {
+            self.format_compact(n)
+        } else {
+            self.format_count(n)
         }
-        result.push(c);
     }
-    result
+
+    /// Format a floating-point number with a fixed number of decimal places.
+    pub fn format_number(&self, value: f64, decimals: u32) -> String {
+        // Handle the sign separately so the thousands grouping works on the
+        // absolute value.
+        let negative = value < 0.0;
+        let abs_value = value.abs();
+
+        // Round to the requested decimal places.
+        // Add a tiny epsilon (half ULP at the target precision) before rounding
+        // to avoid IEEE 754 binary-representation issues at exact midpoints.
+        let factor = 10_f64.powi(decimals as i32);
+        let epsilon = f64::EPSILON * abs_value * factor;
+        let rounded = ((abs_value * factor) + epsilon).round() / factor;
+
+        let integer_part = rounded.trunc() as u64;
+        let frac_part = rounded - rounded.trunc();
+
+        // Build the thousands-separated integer portion.
+        let grouped = self.format_count(integer_part);
+
+        let result = if decimals == 0 {
+            grouped
+        } else {
+            // Format the fractional part to the exact number of decimals.
+            let frac_str = format!("{:.prec$}", frac_part, prec = decimals as usize);
+            // `frac_str` starts with "0.", e.g. "0.50". Keep only the digits.
+            format!("{}{}{}", grouped, self.decimal_separator, &frac_str[2..])
+        };
+
+        if negative {
+            format!("-{}", result)
+        } else {
+            result
+        }
+    }
+
+    /// Format a USD amount with `cost_precision` decimal places.
+    pub fn format_currency(&self, amount: f64) -> String {
+        self.format_usd(amount, self.cost_precision)
+    }
+
+    /// Format a USD rate (e.g. $/min) with two more decimals than
+    /// [`format_currency`](Self::format_currency), since rates are small.
+    pub fn format_cost_rate(&self, amount: f64) -> String {
+        self.format_usd(amount, self.cost_precision + 2)
+    }
+
+    fn format_usd(&self, amount: f64, decimals: u32) -> String {
+        if amount < 0.0 {
+            format!("$-{}", self.format_number(amount.abs(), decimals))
+        } else {
+            format!("${}", self.format_number(amount, decimals))
+        }
+    }
+
+    /// Insert the thousands separator every three digits from the right.
+    fn group_thousands(&self, s: &str) -> String {
+        if s.len() <= 3 {
+            return s.to_string();
+        }
+        let chars: Vec<char> = s.chars().collect();
+        let mut result = String::with_capacity(s.len() + s.len() / 3);
+        let remainder = chars.len() % 3;
+        for (i, &c) in chars.iter().enumerate() {
+            if i != 0 && (i % 3 == remainder) {
+                result.push(self.thousands_separator);
+            }
+            result.push(c);
+        }
+        result
+    }
+}
+
+/// Pick a `--number-format` name from a POSIX locale string such as
+/// `"de_DE.UTF-8"`.
+///
+/// Falls back to `"comma"` for unknown or empty locales.
+pub fn number_format_for_locale(locale: &str) -> &'static str {
+    let lang = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    match lang.as_str() {
+        "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" | "ro" => "dot",
+        "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "uk" | "hu" => "space",
+        _ => "comma",
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────────────────────────
@@ -291,4 +467,77 @@ mod tests {
     fn test_group_thousands_seven_digits() {
         assert_eq!(format_number(1_234_567.0, 0), "1,234,567");
     }
+
+    // ── NumberFormatter ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_number_formatter_default_matches_format_number() {
+        let fmt = NumberFormatter::default();
+        assert_eq!(fmt.format_count(1_234_567), "1,234,567");
+        assert_eq!(fmt.format_number(-9_876.5, 1), format_number(-9_876.5, 1));
+        assert_eq!(fmt.format_currency(1234.56), format_currency(1234.56));
+    }
+
+    #[test]
+    fn test_number_formatter_dot() {
+        let fmt = NumberFormatter::from_name("dot");
+        assert_eq!(fmt.format_count(1_234_567), "1.234.567");
+        assert_eq!(fmt.format_number(1_234.5, 2), "1.234,50");
+        assert_eq!(fmt.format_currency(-9.99), "$-9,99");
+    }
+
+    #[test]
+    fn test_number_formatter_space() {
+        let fmt = NumberFormatter::from_name("SPACE");
+        assert_eq!(fmt.format_count(1_000), "1\u{202F}000");
+        assert_eq!(fmt.format_count(999), "999");
+    }
+
+    #[test]
+    fn test_number_formatter_unknown_name_is_comma() {
+        assert_eq!(
+            NumberFormatter::from_name("bogus"),
+            NumberFormatter::default()
+        );
+    }
+
+    #[test]
+    fn test_format_compact() {
+        assert_eq!(format_compact(0), "0");
+        assert_eq!(format_compact(1_000), "1k");
+        assert_eq!(format_compact(1_250), "1.3k");
+        assert_eq!(format_compact(99_949), "99.9k");
+        assert_eq!(format_compact(99_950), "100k");
+        assert_eq!(format_compact(999_600), "1M");
+        assert_eq!(format_compact(12_500_000), "12.5M");
+        assert_eq!(format_compact(3_400_000_000), "3.4B");
+        assert_eq!(format_compact(1_234_000_000_000), "1,234B");
+    }
+
+    #[test]
+    fn test_number_formatter_format_tokens() {
+        let fmt = NumberFormatter::from_name("dot");
+        assert_eq!(fmt.format_tokens(1_234_567), "1.234.567");
+        assert_eq!(fmt.with_compact(true).format_tokens(1_234_567), "1,2M");
+    }
+
+    #[test]
+    fn test_number_formatter_cost_precision() {
+        let fmt = NumberFormatter::default().with_cost_precision(4);
+        assert_eq!(fmt.format_currency(0.0012), "$0.0012");
+        assert_eq!(fmt.format_cost_rate(0.0012), "$0.001200");
+
+        let default = NumberFormatter::default();
+        assert_eq!(default.format_currency(0.0012), "$0.00");
+        assert_eq!(default.format_cost_rate(0.0012), "$0.0012");
+    }
+
+    #[test]
+    fn test_number_format_for_locale() {
+        assert_eq!(number_format_for_locale("de_DE.UTF-8"), "dot");
+        assert_eq!(number_format_for_locale("fr_FR"), "space");
+        assert_eq!(number_format_for_locale("en_US.UTF-8"), "comma");
+        assert_eq!(number_format_for_locale("C"), "comma");
+        assert_eq!(number_format_for_locale(""), "comma");
+    }
 }
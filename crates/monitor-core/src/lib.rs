@@ -1,11 +1,16 @@
 pub mod calculations;
+pub mod clock;
 pub mod data_processors;
 pub mod error;
 pub mod formatting;
+pub mod lifetime;
 pub mod models;
 pub mod notifications;
 pub mod p90;
+pub mod paths;
 pub mod plans;
 pub mod pricing;
 pub mod settings;
+#[cfg(any(test, feature = "testutil"))]
+pub mod testutil;
 pub mod time_utils;
@@ -0,0 +1,356 @@
+//! All-time usage totals that persist across runs.
+//!
+//! The realtime view only loads a limited look-back window, so the running
+//! tally is kept in `lifetime.json` in the
+//! [state directory](crate::paths::state_dir). Each snapshot adds only the
+//! entries whose key (the message and request ids, like the loader's
+//! deduplication) has not been counted yet, so reloading the same data never
+//! adds it twice while late entries are still picked up.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::UsageEntry;
+
+/// File name of the persisted totals inside the tracker's directory.
+const LIFETIME_FILE_NAME: &str = "lifetime.json";
+
+/// How long the keys of counted entries are kept, matching the realtime
+/// view's look-back; older entries are never loaded again.
+const KEY_RETENTION_HOURS: i64 = 192;
+
+// ── LifetimeStats ─────────────────────────────────────────────────────────────
+
+/// Token and cost totals accumulated since the monitor first ran.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    /// All tokens (including cache tokens) counted so far.
+    pub total_tokens: u64,
+    /// Cost in USD counted so far.
+    pub total_cost: f64,
+    /// Timestamp of the oldest entry counted, `None` until one is counted.
+    pub first_seen: Option<DateTime<Utc>>,
+    /// Timestamp of the newest entry counted.
+    #[serde(default)]
+    pub counted_until: Option<DateTime<Utc>>,
+    /// Keys of the counted entries within [`KEY_RETENTION_HOURS`] of
+    /// `counted_until`, with their timestamps.
+    #[serde(default)]
+    pub counted: HashMap<String, DateTime<Utc>>,
+}
+
+impl LifetimeStats {
+    /// Add every entry not counted yet.
+    ///
+    /// Entries older than the retention window before
+    /// [`counted_until`](Self::counted_until) are taken as counted. Returns
+    /// `true` when anything was added.
+    pub fn record<'a>(&mut self, entries: impl IntoIterator<Item = &'a UsageEntry>) -> bool {
+        let retention = chrono::Duration::hours(KEY_RETENTION_HOURS);
+        let floor = self.counted_until.map(|t| t - retention);
+        // A file written before keys were kept only has the watermark.
+        let legacy_until = self.counted_until.filter(|_| self.counted.is_empty());
+        let mut changed = false;
+        for entry in entries {
+            if floor.is_some_and(|floor| entry.timestamp < floor)
+                || legacy_until.is_some_and(|until| entry.timestamp <= until)
+            {
+                continue;
+            }
+            if self
+                .counted
+                .insert(entry_key(entry), entry.timestamp)
+                .is_some()
+            {
+                continue;
+            }
+            self.total_tokens += entry.input_tokens
+                + entry.output_tokens
+                + entry.cache_creation_tokens
+                + entry.cache_read_tokens;
+            self.total_cost += entry.cost_usd;
+            self.first_seen = Some(
+                self.first_seen
+                    .map_or(entry.timestamp, |t| t.min(entry.timestamp)),
+            );
+            self.counted_until = Some(
+                self.counted_until
+                    .map_or(entry.timestamp, |t| t.max(entry.timestamp)),
+            );
+            changed = true;
+        }
+        if changed {
+            if let Some(floor) = self.counted_until.map(|t| t - retention) {
+                self.counted.retain(|_, ts| *ts >= floor);
+            }
+        }
+        changed
+    }
+}
+
+/// Key identifying `entry` across reloads: its message and request ids, or
+/// its timestamp, model and token counts when it has none.
+fn entry_key(entry: &UsageEntry) -> String {
+    if !entry.message_id.is_empty() && !entry.request_id.is_empty() {
+        return format!("{}:{}", entry.message_id, entry.request_id);
+    }
+    format!(
+        "{}|{}|{}|{}|{}|{}",
+        entry.timestamp.to_rfc3339(),
+        entry.model,
+        entry.input_tokens,
+        entry.output_tokens,
+        entry.cache_creation_tokens,
+        entry.cache_read_tokens,
+    )
+}
+
+// ── LifetimeTracker ───────────────────────────────────────────────────────────
+
+/// [`LifetimeStats`] backed by a JSON file.
+///
+/// Saves happen on a writer thread so recording never blocks on disk; the
+/// last snapshot is written before the tracker is dropped. Load and save
+/// errors are logged and otherwise ignored, so the tracker keeps working in
+/// memory when the file cannot be written.
+pub struct LifetimeTracker {
+    /// Path to the JSON file holding the stats.
+    file: PathBuf,
+    /// Current totals.
+    stats: LifetimeStats,
+    /// Sends snapshots to the writer thread, started on the first save.
+    writer: Option<(Sender<LifetimeStats>, JoinHandle<()>)>,
+}
+
+impl LifetimeTracker {
+    /// Create a tracker that persists to `lifetime.json` in `dir`.
+    pub fn new(dir: &Path) -> Self {
+        let file = dir.join(LIFETIME_FILE_NAME);
+        let stats = Self::load(&file);
+        Self {
+            file,
+            stats,
+            writer: None,
+        }
+    }
+
+    /// Create a tracker in the default state directory
+    /// (`$XDG_STATE_HOME/claude-monitor/`), moving a file left in the legacy
+    /// `~/.claude-monitor/` there first.
+    ///
+    /// Returns `None` when the state directory cannot be determined (no home
+    /// directory and no `$XDG_STATE_HOME`).
+    pub fn with_default_path() -> Option<Self> {
+        let path = crate::paths::migrated_file(crate::paths::state_dir(), LIFETIME_FILE_NAME)?;
+        Some(Self::new(path.parent()?))
+    }
+
+    /// Current totals.
+    pub fn stats(&self) -> &LifetimeStats {
+        &self.stats
+    }
+
+    /// Add the uncounted `entries` and save when the totals changed.
+    pub fn record<'a>(&mut self, entries: impl IntoIterator<Item = &'a UsageEntry>) {
+        if self.stats.record(entries) {
+            self.save();
+        }
+    }
+
+    // ── Private helpers ───────────────────────────────────────────────────────
+
+    /// Load stats from `path`, starting from zero on any error.
+    fn load(path: &Path) -> LifetimeStats {
+        if !path.exists() {
+            return LifetimeStats::default();
+        }
+        let parsed = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+        parsed.unwrap_or_else(|e| {
+            tracing::warn!(
+                error = %e,
+                path = %path.display(),
+                "failed to load lifetime stats; starting from zero"
+            );
+            LifetimeStats::default()
+        })
+    }
+
+    /// Hand the current stats to the writer thread.
+    fn save(&mut self) {
+        if self.writer.is_none() {
+            let (tx, rx) = std::sync::mpsc::channel::<LifetimeStats>();
+            let file = self.file.clone();
+            let spawned = std::thread::Builder::new()
+                .name("lifetime-save".to_string())
+                .spawn(move || {
+                    while let Ok(stats) = rx.recv() {
+                        // Only the newest of any queued snapshots matters.
+                        let latest = rx.try_iter().last().unwrap_or(stats);
+                        Self::write(&file, &latest);
+                    }
+                });
+            match spawned {
+                Ok(handle) => self.writer = Some((tx, handle)),
+                Err(_) => return Self::write(&self.file, &self.stats),
+            }
+        }
+        if let Some((tx, _)) = &self.writer {
+            let _ = tx.send(self.stats.clone());
+        }
+    }
+
+    /// Write `stats` to `path` via a temporary file, logging any error.
+    fn write(path: &Path, stats: &LifetimeStats) {
+        let tmp = path.with_extension("json.tmp");
+        let written = serde_json::to_string_pretty(stats)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&tmp, json).map_err(|e| e.to_string()))
+            .and_then(|()| std::fs::rename(&tmp, path).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            tracing::warn!(
+                error = %e,
+                path = %path.display(),
+                "failed to save lifetime stats"
+            );
+        }
+    }
+}
+
+impl Drop for LifetimeTracker {
+    /// Wait for the writer thread to save the last snapshot.
+    fn drop(&mut self) {
+        if let Some((tx, handle)) = self.writer.take() {
+            drop(tx);
+            let _ = handle.join();
+        }
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_entry(ts: &str, tokens: u64, cost: f64) -> UsageEntry {
+        UsageEntry {
+            timestamp: DateTime::parse_from_rfc3339(ts)
+                .unwrap()
+                .with_timezone(&Utc),
+            input_tokens: tokens,
+            output_tokens: 0,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd: cost,
+            model: "claude-3-5-sonnet".to_string(),
+            message_id: ts.to_string(),
+            request_id: ts.to_string(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        }
+    }
+
+    // ── LifetimeStats::record ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_record_adds_entries_once() {
+        let entries = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 0.5),
+            make_entry("2024-01-15T11:00:00Z", 50, 0.25),
+        ];
+        let mut stats = LifetimeStats::default();
+        assert!(stats.record(&entries));
+        assert!(!stats.record(&entries), "same data must not be re-added");
+
+        assert_eq!(stats.total_tokens, 150);
+        assert!((stats.total_cost - 0.75).abs() < 1e-9);
+        assert_eq!(stats.first_seen, Some(entries[0].timestamp));
+        assert_eq!(stats.counted_until, Some(entries[1].timestamp));
+    }
+
+    #[test]
+    fn test_record_adds_late_and_same_timestamp_entries() {
+        let mut stats = LifetimeStats::default();
+        stats.record(&[make_entry("2024-01-15T12:00:00Z", 100, 0.5)]);
+
+        let mut same_time = make_entry("2024-01-15T12:00:00Z", 10, 0.1);
+        same_time.message_id = "other".to_string();
+        let late = make_entry("2024-01-15T11:00:00Z", 1, 0.01);
+        assert!(stats.record(&[same_time, late]));
+        assert_eq!(stats.total_tokens, 111);
+    }
+
+    #[test]
+    fn test_record_drops_keys_past_retention() {
+        let mut stats = LifetimeStats::default();
+        stats.record(&[make_entry("2024-01-01T10:00:00Z", 100, 0.5)]);
+        stats.record(&[make_entry("2024-01-15T10:00:00Z", 10, 0.1)]);
+        assert_eq!(stats.counted.len(), 1);
+
+        // Too old to be reloaded; taken as already counted.
+        assert!(!stats.record(&[make_entry("2024-01-01T10:00:00Z", 100, 0.5)]));
+        assert_eq!(stats.total_tokens, 110);
+    }
+
+    #[test]
+    fn test_record_keeps_legacy_watermark() {
+        let mut stats = LifetimeStats {
+            total_tokens: 100,
+            counted_until: Some("2024-01-15T10:00:00Z".parse().unwrap()),
+            ..LifetimeStats::default()
+        };
+        let reloaded = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 0.5),
+            make_entry("2024-01-15T12:00:00Z", 10, 0.1),
+        ];
+        assert!(stats.record(&reloaded));
+        assert_eq!(stats.total_tokens, 110);
+    }
+
+    #[test]
+    fn test_record_adds_only_newer_entries() {
+        let mut stats = LifetimeStats::default();
+        stats.record(&[make_entry("2024-01-15T10:00:00Z", 100, 0.5)]);
+
+        let reloaded = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 0.5),
+            make_entry("2024-01-15T12:00:00Z", 10, 0.1),
+        ];
+        assert!(stats.record(&reloaded));
+        assert_eq!(stats.total_tokens, 110);
+    }
+
+    // ── LifetimeTracker ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_tracker_persists_across_instances() {
+        let dir = TempDir::new().unwrap();
+        let entries = vec![make_entry("2024-01-15T10:00:00Z", 100, 0.5)];
+
+        let mut tracker = LifetimeTracker::new(dir.path());
+        tracker.record(&entries);
+        drop(tracker);
+
+        let mut reopened = LifetimeTracker::new(dir.path());
+        assert_eq!(reopened.stats().total_tokens, 100);
+        reopened.record(&entries);
+        assert_eq!(reopened.stats().total_tokens, 100);
+    }
+
+    #[test]
+    fn test_tracker_corrupt_file_starts_from_zero() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(LIFETIME_FILE_NAME), "not json").unwrap();
+        let tracker = LifetimeTracker::new(dir.path());
+        assert_eq!(tracker.stats(), &LifetimeStats::default());
+    }
+}
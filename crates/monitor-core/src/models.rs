@@ -1,6 +1,7 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Determines how usage cost is calculated for a session.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -15,6 +16,22 @@ pub enum CostMode {
     Calculated,
 }
 
+impl CostMode {
+    /// Parse a `--cost-mode` value (`auto`, `cached`, `calculated`); unknown
+    /// names are [`Auto`](Self::Auto).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "cached" => Self::Cached,
+            "calculated" | "calculate" => Self::Calculated,
+            _ => Self::Auto,
+        }
+    }
+}
+
+/// Model name of entries generated by the CLI itself rather than by a model.
+/// They are never priced.
+pub const SYNTHETIC_MODEL: &str = "<synthetic>";
+
 /// A single API call record read from a JSONL usage file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UsageEntry {
@@ -42,6 +59,42 @@ pub struct UsageEntry {
     /// Unique request identifier.
     #[serde(default)]
     pub request_id: String,
+    /// Top-level project directory the entry was read from, relative to the
+    /// data root. Empty when unknown (e.g. entries read from stdin).
+    #[serde(default)]
+    pub project: String,
+    /// JSONL file the entry was read from. Only recorded when loading with
+    /// `LoadOptions::track_sources`, to keep the default load
+    /// allocation-free.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<PathBuf>,
+    /// Read from a `"type": "system"` line written by the CLI. Priced like
+    /// any other entry but not counted as a sent message.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_system: bool,
+}
+
+impl UsageEntry {
+    /// `true` for CLI-generated entries (model [`SYNTHETIC_MODEL`]).
+    pub fn is_synthetic(&self) -> bool {
+        self.model == SYNTHETIC_MODEL
+    }
+
+    /// `true` when the entry counts as a sent message and towards per-model
+    /// stats: synthetic and system entries only with `include_synthetic`.
+    pub fn counts_as_message(&self, include_synthetic: bool) -> bool {
+        include_synthetic || !(self.is_synthetic() || self.is_system)
+    }
+
+    /// Key of the entry in per-model stats: its normalized model name, or
+    /// `"unknown"` when the model is missing.
+    pub fn stats_model(&self) -> String {
+        if self.model.is_empty() || self.model == "unknown" {
+            "unknown".to_string()
+        } else {
+            normalize_model_name(&self.model)
+        }
+    }
 }
 
 /// Aggregated token counts across multiple usage entries.
@@ -62,9 +115,56 @@ pub struct TokenCounts {
 }
 
 impl TokenCounts {
-    /// Sum of all four token categories.
+    /// Sum of all four token categories, saturating at `u64::MAX`.
     pub fn total_tokens(&self) -> u64 {
-        self.input_tokens + self.output_tokens + self.cache_creation_tokens + self.cache_read_tokens
+        self.total_for(TokensBasis::All)
+    }
+
+    /// Total counted on `basis`.
+    pub fn total_for(&self, basis: TokensBasis) -> u64 {
+        basis.total(
+            self.input_tokens,
+            self.output_tokens,
+            self.cache_creation_tokens,
+            self.cache_read_tokens,
+        )
+    }
+}
+
+/// Which token categories make up a displayed or exported token total
+/// (`--tokens-basis`).
+///
+/// Costs always include cache tokens; only token totals are affected. The
+/// default counts every category; [`Io`](Self::Io) matches the plan token
+/// limits, which count input and output only.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokensBasis {
+    /// Input and output tokens only.
+    Io,
+    /// Input, output, cache-creation and cache-read tokens.
+    #[default]
+    All,
+}
+
+impl TokensBasis {
+    /// Parse a `--tokens-basis` value (`io`, `all`); unknown names are
+    /// [`All`](Self::All).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "io" => Self::Io,
+            _ => Self::All,
+        }
+    }
+
+    /// Sum the token categories that count on this basis.
+    pub fn total(self, input: u64, output: u64, cache_creation: u64, cache_read: u64) -> u64 {
+        match self {
+            Self::Io => input.saturating_add(output),
+            Self::All => input
+                .saturating_add(output)
+                .saturating_add(cache_creation)
+                .saturating_add(cache_read),
+        }
     }
 }
 
@@ -77,6 +177,68 @@ pub struct BurnRate {
     pub cost_per_hour: f64,
 }
 
+impl BurnRate {
+    /// Shortest elapsed time, in minutes, a rate is computed over; shorter
+    /// spans give meaningless spikes.
+    pub const MIN_ELAPSED_MINUTES: f64 = 0.5;
+
+    /// Burn rate of `block` as of `now`.
+    ///
+    /// Counts the tokens on `basis` and cost since the block started, or,
+    /// with `window`, only the entries in the last `window` before `now`.
+    ///
+    /// Returns `None` when less than [`MIN_ELAPSED_MINUTES`](Self::MIN_ELAPSED_MINUTES)
+    /// have elapsed or the token rate is not finite. `cost_per_hour` is
+    /// returned as computed; check it is finite before display.
+    pub fn from_block(
+        block: &SessionBlock,
+        now: DateTime<Utc>,
+        window: Option<Duration>,
+        basis: TokensBasis,
+    ) -> Option<Self> {
+        let (since, tokens, cost) = match window {
+            None => (
+                block.start_time,
+                block.token_counts.total_for(basis),
+                block.cost_usd,
+            ),
+            Some(window) => {
+                let since = block.start_time.max(now - window);
+                let recent = block
+                    .entries
+                    .iter()
+                    .filter(|e| e.timestamp >= since && e.timestamp <= now);
+                recent.fold((since, 0u64, 0.0), |(since, tokens, cost), e| {
+                    let entry_tokens = basis.total(
+                        e.input_tokens,
+                        e.output_tokens,
+                        e.cache_creation_tokens,
+                        e.cache_read_tokens,
+                    );
+                    (
+                        since,
+                        tokens.saturating_add(entry_tokens),
+                        cost + e.cost_usd,
+                    )
+                })
+            }
+        };
+
+        let elapsed_minutes = (now - since).num_seconds().max(0) as f64 / 60.0;
+        if elapsed_minutes <= Self::MIN_ELAPSED_MINUTES {
+            return None;
+        }
+        let tokens_per_minute = tokens as f64 / elapsed_minutes;
+        if !tokens_per_minute.is_finite() {
+            return None;
+        }
+        Some(Self {
+            tokens_per_minute,
+            cost_per_hour: cost / elapsed_minutes * 60.0,
+        })
+    }
+}
+
 /// Forward projection of how many tokens / dollars a session will consume.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UsageProjection {
@@ -121,8 +283,13 @@ pub struct LimitMessage {
 /// A 5-hour usage window aggregating all API calls within that period.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionBlock {
-    /// Unique identifier for this block.
+    /// Unique identifier for this block: the UTC start time, with a `-N`
+    /// counter appended when several blocks share a start time.
     pub id: String,
+    /// Human-readable start time in the local timezone, e.g.
+    /// `"2024-01-15 11:00"`.
+    #[serde(default)]
+    pub label: String,
     /// Inclusive start of the 5-hour window (UTC).
     pub start_time: DateTime<Utc>,
     /// Exclusive end of the 5-hour window (UTC).
@@ -160,7 +327,10 @@ pub struct SessionBlock {
     /// Structured limit-hit notifications embedded in the block.
     #[serde(default)]
     pub limit_messages: Vec<LimitMessage>,
-    /// Opaque projection data, serialised for downstream consumers.
+    /// End-of-window [`UsageProjection`] of an active block as
+    /// `{"totalTokens", "totalCost", "remainingMinutes"}`; write it with
+    /// [`set_projection`](Self::set_projection) and read it back with
+    /// [`projection`](Self::projection).
     #[serde(default)]
     pub projection_data: Option<serde_json::Value>,
     /// Snapshot of the burn rate captured at block close time.
@@ -174,6 +344,26 @@ impl SessionBlock {
         self.token_counts.total_tokens()
     }
 
+    /// The projection stored in [`projection_data`](Self::projection_data),
+    /// `None` when absent or not a [`UsageProjection`].
+    pub fn projection(&self) -> Option<UsageProjection> {
+        let data = self.projection_data.as_ref()?;
+        Some(UsageProjection {
+            projected_total_tokens: data.get("totalTokens")?.as_u64()?,
+            projected_total_cost: data.get("totalCost")?.as_f64()?,
+            remaining_minutes: data.get("remainingMinutes")?.as_f64()?,
+        })
+    }
+
+    /// Store `projection` in [`projection_data`](Self::projection_data).
+    pub fn set_projection(&mut self, projection: &UsageProjection) {
+        self.projection_data = Some(serde_json::json!({
+            "totalTokens": projection.projected_total_tokens,
+            "totalCost": projection.projected_total_cost,
+            "remainingMinutes": projection.remaining_minutes,
+        }));
+    }
+
     /// Alias for `cost_usd`.
     pub fn total_cost(&self) -> f64 {
         self.cost_usd
@@ -287,6 +477,15 @@ mod tests {
             cache_read_tokens: 25,
         };
         assert_eq!(tc.total_tokens(), 375);
+        assert_eq!(tc.total_for(TokensBasis::All), 375);
+        assert_eq!(tc.total_for(TokensBasis::Io), 300);
+    }
+
+    #[test]
+    fn test_tokens_basis_from_name() {
+        assert_eq!(TokensBasis::from_name("io"), TokensBasis::Io);
+        assert_eq!(TokensBasis::from_name("all"), TokensBasis::All);
+        assert_eq!(TokensBasis::from_name("bogus"), TokensBasis::All);
     }
 
     // ── SessionBlock ───────────────────────────────────────────────────────
@@ -298,6 +497,7 @@ mod tests {
     ) -> SessionBlock {
         SessionBlock {
             id: "test-block".to_string(),
+            label: String::new(),
             start_time: start,
             end_time: end,
             entries: vec![],
@@ -314,13 +514,74 @@ mod tests {
             per_model_stats: HashMap::new(),
             models: vec![],
             sent_messages_count: 0,
-            cost_usd: 3.14,
+            cost_usd: 3.25,
             limit_messages: vec![],
             projection_data: None,
             burn_rate_snapshot: None,
         }
     }
 
+    // ── BurnRate::from_block ──────────────────────────────────────────────
+
+    #[test]
+    fn test_burn_rate_from_block_over_elapsed_time() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let block = make_block(start, start + Duration::hours(5), None);
+        let now = start + Duration::minutes(30);
+
+        let rate = BurnRate::from_block(&block, now, None, TokensBasis::Io).unwrap();
+        // 1,500 input + output tokens (cache excluded) over 30 minutes.
+        assert!((rate.tokens_per_minute - 50.0).abs() < 1e-9);
+        assert!((rate.cost_per_hour - 6.5).abs() < 1e-9);
+
+        // 1,650 tokens with the cache included.
+        let rate = BurnRate::from_block(&block, now, None, TokensBasis::All).unwrap();
+        assert!((rate.tokens_per_minute - 55.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_burn_rate_from_block_zero_elapsed_is_none() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let block = make_block(start, start + Duration::hours(5), None);
+        assert!(BurnRate::from_block(&block, start, None, TokensBasis::Io).is_none());
+        assert!(
+            BurnRate::from_block(&block, start + Duration::seconds(20), None, TokensBasis::Io)
+                .is_none()
+        );
+        // A clock behind the block start counts as zero elapsed.
+        assert!(
+            BurnRate::from_block(&block, start - Duration::hours(1), None, TokensBasis::Io)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_burn_rate_from_block_window_counts_recent_entries() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut block = make_block(start, start + Duration::hours(5), None);
+        let entry = |minutes: i64, tokens: u64| UsageEntry {
+            timestamp: start + Duration::minutes(minutes),
+            input_tokens: tokens,
+            output_tokens: 0,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd: 0.1,
+            model: "claude-3-5-sonnet".to_string(),
+            message_id: String::new(),
+            request_id: String::new(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        };
+        block.entries = vec![entry(5, 10_000), entry(100, 300), entry(110, 300)];
+        let now = start + Duration::minutes(120);
+
+        let rate = BurnRate::from_block(&block, now, Some(Duration::minutes(30)), TokensBasis::Io)
+            .unwrap();
+        assert!((rate.tokens_per_minute - 20.0).abs() < 1e-9);
+        assert!((rate.cost_per_hour - 0.4).abs() < 1e-9);
+    }
+
     #[test]
     fn test_session_block_duration_minutes_without_actual_end() {
         let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
@@ -360,7 +621,7 @@ mod tests {
         let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
         let end = Utc.with_ymd_and_hms(2024, 1, 1, 5, 0, 0).unwrap();
         let block = make_block(start, end, None);
-        assert!((block.total_cost() - 3.14).abs() < f64::EPSILON);
+        assert!((block.total_cost() - 3.25).abs() < f64::EPSILON);
     }
 
     // ── normalize_model_name ───────────────────────────────────────────────
@@ -476,4 +737,12 @@ mod tests {
         let back: CostMode = serde_json::from_str(&json).unwrap();
         assert_eq!(back, CostMode::Calculated);
     }
+
+    #[test]
+    fn test_cost_mode_from_name() {
+        assert_eq!(CostMode::from_name("cached"), CostMode::Cached);
+        assert_eq!(CostMode::from_name("calculated"), CostMode::Calculated);
+        assert_eq!(CostMode::from_name("auto"), CostMode::Auto);
+        assert_eq!(CostMode::from_name("bogus"), CostMode::Auto);
+    }
 }
@@ -3,7 +3,8 @@
 //! Ports the Python `NotificationManager` class from
 //! `src/claude_monitor/utils/notifications.py`.
 //!
-//! States are persisted to `~/.claude-monitor/notification_states.json` and
+//! States are persisted to `notification_states.json` in the
+//! [state directory](crate::paths::state_dir) and
 //! use a cooldown-based scheme so that notifications are not shown more often
 //! than once per `cooldown_hours`.
 
@@ -13,12 +14,101 @@ use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::clock::{SharedClock, SystemClock};
+use crate::error::MonitorError;
+
 // ── Notification keys ─────────────────────────────────────────────────────────
 
-/// The three canonical notification keys recognised by the manager.
+/// The canonical notification keys recognised by the manager.
 pub const KEY_SWITCH_TO_CUSTOM: &str = "switch_to_custom";
 pub const KEY_EXCEED_MAX_LIMIT: &str = "exceed_max_limit";
 pub const KEY_TOKENS_WILL_RUN_OUT: &str = "tokens_will_run_out";
+/// Today's cost has reached `--daily-budget`.
+pub const KEY_DAILY_BUDGET_REACHED: &str = "daily_budget_reached";
+
+/// Every canonical notification key.
+const KEYS: [&str; 4] = [
+    KEY_TOKENS_WILL_RUN_OUT,
+    KEY_EXCEED_MAX_LIMIT,
+    KEY_SWITCH_TO_CUSTOM,
+    KEY_DAILY_BUDGET_REACHED,
+];
+
+/// File name of the persisted states inside the manager's directory.
+const NOTIFICATION_FILE_NAME: &str = "notification_states.json";
+
+// ── NotificationCooldowns ─────────────────────────────────────────────────────
+
+/// Cooldown for keys without an entry of their own: one session window.
+pub const DEFAULT_COOLDOWN_HOURS: f64 = 5.0;
+
+/// Cooldown, in hours, to pass to [`NotificationManager::should_notify`] for
+/// each notification key.
+///
+/// Defaults: [`KEY_TOKENS_WILL_RUN_OUT`] 0.25 h, [`KEY_EXCEED_MAX_LIMIT`]
+/// 0.5 h, [`KEY_DAILY_BUDGET_REACHED`] 6 h and [`KEY_SWITCH_TO_CUSTOM`]
+/// 24 h; any other key uses [`DEFAULT_COOLDOWN_HOURS`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationCooldowns {
+    hours: HashMap<String, f64>,
+}
+
+impl Default for NotificationCooldowns {
+    fn default() -> Self {
+        let hours = [
+            (KEY_TOKENS_WILL_RUN_OUT, 0.25),
+            (KEY_EXCEED_MAX_LIMIT, 0.5),
+            (KEY_DAILY_BUDGET_REACHED, 6.0),
+            (KEY_SWITCH_TO_CUSTOM, 24.0),
+        ]
+        .into_iter()
+        .map(|(key, hours)| (key.to_string(), hours))
+        .collect();
+        Self { hours }
+    }
+}
+
+impl NotificationCooldowns {
+    /// Parse one `KEY=HOURS` rule, e.g. `"tokens_will_run_out=1.5"`.
+    ///
+    /// `KEY` must be one of the canonical notification keys and `HOURS` a
+    /// non-negative number.
+    pub fn parse_rule(spec: &str) -> Result<(String, f64), MonitorError> {
+        let invalid = || {
+            MonitorError::Config(format!(
+                "invalid notification cooldown '{spec}', expected KEY=HOURS with KEY one of {}",
+                KEYS.join(", ")
+            ))
+        };
+        let (key, hours) = spec.split_once('=').ok_or_else(invalid)?;
+        let key = key.trim();
+        if !KEYS.contains(&key) {
+            return Err(invalid());
+        }
+        match hours.trim().parse::<f64>() {
+            Ok(hours) if hours.is_finite() && hours >= 0.0 => Ok((key.to_string(), hours)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// The defaults with every `KEY=HOURS` rule in `specs` applied.
+    pub fn from_rules<S: AsRef<str>>(specs: &[S]) -> Result<Self, MonitorError> {
+        let mut cooldowns = Self::default();
+        for spec in specs {
+            let (key, hours) = Self::parse_rule(spec.as_ref())?;
+            cooldowns.hours.insert(key, hours);
+        }
+        Ok(cooldowns)
+    }
+
+    /// Cooldown in hours for `key`.
+    pub fn hours(&self, key: &str) -> f64 {
+        self.hours
+            .get(key)
+            .copied()
+            .unwrap_or(DEFAULT_COOLDOWN_HOURS)
+    }
+}
 
 // ── NotificationState ─────────────────────────────────────────────────────────
 
@@ -64,12 +154,20 @@ impl Default for NotificationState {
 /// if mgr.should_notify("my_key", 24.0) {
 ///     mgr.mark_notified("my_key");
 /// }
+/// // Or with the manager's per-key cooldowns:
+/// if mgr.notify_if_due("tokens_will_run_out") {
+///     // show the notification
+/// }
 /// ```
 pub struct NotificationManager {
     /// Path to the JSON file that stores notification states.
     notification_file: PathBuf,
     /// In-memory map of notification key → state.
     states: HashMap<String, NotificationState>,
+    /// "Now" for cooldowns and trigger timestamps.
+    clock: SharedClock,
+    /// Cooldown of each key for [`notify_if_due`](Self::notify_if_due).
+    cooldowns: NotificationCooldowns,
 }
 
 impl NotificationManager {
@@ -80,21 +178,37 @@ impl NotificationManager {
     /// If `config_dir` does not exist the manager still works in memory only;
     /// save errors are logged as warnings but never panic.
     pub fn new(config_dir: &Path) -> Self {
-        let notification_file = config_dir.join("notification_states.json");
+        let notification_file = config_dir.join(NOTIFICATION_FILE_NAME);
         let states = Self::load_states(&notification_file);
         Self {
             notification_file,
             states,
+            clock: SystemClock::shared(),
+            cooldowns: NotificationCooldowns::default(),
         }
     }
 
-    /// Create a `NotificationManager` using the default `~/.claude-monitor/`
-    /// config directory.
+    /// Use `cooldowns` in [`notify_if_due`](Self::notify_if_due).
+    pub fn with_cooldowns(mut self, cooldowns: NotificationCooldowns) -> Self {
+        self.cooldowns = cooldowns;
+        self
+    }
+
+    /// Take "now" from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Create a `NotificationManager` in the default state directory
+    /// (`$XDG_STATE_HOME/claude-monitor/`), moving a state file left in the
+    /// legacy `~/.claude-monitor/` there first.
     ///
-    /// Returns `None` when the home directory cannot be determined.
+    /// Returns `None` when the state directory cannot be determined (no home
+    /// directory and no `$XDG_STATE_HOME`).
     pub fn with_default_path() -> Option<Self> {
-        let config_dir = dirs::home_dir()?.join(".claude-monitor");
-        Some(Self::new(&config_dir))
+        let path = crate::paths::migrated_file(crate::paths::state_dir(), NOTIFICATION_FILE_NAME)?;
+        Some(Self::new(path.parent()?))
     }
 
     // ── Public API ────────────────────────────────────────────────────────────
@@ -119,13 +233,23 @@ impl NotificationManager {
         match state.timestamp {
             None => true,
             Some(ts) => {
-                let elapsed_secs = (Utc::now() - ts).num_seconds() as f64;
+                let elapsed_secs = (self.clock.now() - ts).num_seconds() as f64;
                 let cooldown_secs = cooldown_hours * 3600.0;
                 elapsed_secs >= cooldown_secs
             }
         }
     }
 
+    /// [`should_notify`](Self::should_notify) with the key's configured
+    /// cooldown, marking the notification as triggered when it is due.
+    pub fn notify_if_due(&mut self, key: &str) -> bool {
+        let due = self.should_notify(key, self.cooldowns.hours(key));
+        if due {
+            self.mark_notified(key);
+        }
+        due
+    }
+
     /// Mark the notification identified by `key` as triggered right now.
     ///
     /// Persists the updated states to disk.
@@ -134,7 +258,7 @@ impl NotificationManager {
             key.to_string(),
             NotificationState {
                 triggered: true,
-                timestamp: Some(Utc::now()),
+                timestamp: Some(self.clock.now()),
             },
         );
         self.save_states();
@@ -160,7 +284,7 @@ impl NotificationManager {
 
     // ── Private helpers ───────────────────────────────────────────────────────
 
-    /// Load states from `path`, returning the three default keys on any error.
+    /// Load states from `path`, returning the default keys on any error.
     fn load_states(path: &Path) -> HashMap<String, NotificationState> {
         if !path.exists() {
             return Self::default_states();
@@ -211,22 +335,11 @@ impl NotificationManager {
         }
     }
 
-    /// Build the default three-key state map (all untriggered).
+    /// Build the default state map of every canonical key (all untriggered).
     fn default_states() -> HashMap<String, NotificationState> {
-        let mut map = HashMap::new();
-        map.insert(
-            KEY_SWITCH_TO_CUSTOM.to_string(),
-            NotificationState::default_state(),
-        );
-        map.insert(
-            KEY_EXCEED_MAX_LIMIT.to_string(),
-            NotificationState::default_state(),
-        );
-        map.insert(
-            KEY_TOKENS_WILL_RUN_OUT.to_string(),
-            NotificationState::default_state(),
-        );
-        map
+        KEYS.into_iter()
+            .map(|key| (key.to_string(), NotificationState::default_state()))
+            .collect()
     }
 }
 
@@ -283,6 +396,57 @@ mod tests {
         assert!(back.timestamp.is_none());
     }
 
+    // ── NotificationCooldowns ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_cooldowns_defaults_per_key() {
+        let cooldowns = NotificationCooldowns::default();
+        assert_eq!(cooldowns.hours(KEY_TOKENS_WILL_RUN_OUT), 0.25);
+        assert_eq!(cooldowns.hours(KEY_EXCEED_MAX_LIMIT), 0.5);
+        assert_eq!(cooldowns.hours(KEY_SWITCH_TO_CUSTOM), 24.0);
+        assert_eq!(cooldowns.hours(KEY_DAILY_BUDGET_REACHED), 6.0);
+        assert_eq!(cooldowns.hours("other"), DEFAULT_COOLDOWN_HOURS);
+    }
+
+    #[test]
+    fn test_cooldowns_from_rules_overrides_defaults() {
+        let cooldowns =
+            NotificationCooldowns::from_rules(&["tokens_will_run_out = 2", "switch_to_custom=0"])
+                .unwrap();
+        assert_eq!(cooldowns.hours(KEY_TOKENS_WILL_RUN_OUT), 2.0);
+        assert_eq!(cooldowns.hours(KEY_SWITCH_TO_CUSTOM), 0.0);
+        assert_eq!(cooldowns.hours(KEY_EXCEED_MAX_LIMIT), 0.5);
+    }
+
+    #[test]
+    fn test_cooldowns_parse_rule_rejects_bad_specs() {
+        for spec in [
+            "tokens_will_run_out",
+            "unknown_key=1",
+            "exceed_max_limit=-1",
+            "exceed_max_limit=soon",
+        ] {
+            assert!(NotificationCooldowns::parse_rule(spec).is_err(), "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_notify_if_due_uses_per_key_cooldown() {
+        let dir = TempDir::new().unwrap();
+        let clock = crate::clock::MockClock::new(Utc::now());
+        let cooldowns = NotificationCooldowns::from_rules(&["exceed_max_limit=0"]).unwrap();
+        let mut mgr = make_mgr(&dir)
+            .with_clock(clock.shared())
+            .with_cooldowns(cooldowns);
+
+        assert!(mgr.notify_if_due(KEY_EXCEED_MAX_LIMIT));
+        assert!(mgr.notify_if_due(KEY_EXCEED_MAX_LIMIT), "zero cooldown");
+        assert!(mgr.notify_if_due(KEY_TOKENS_WILL_RUN_OUT));
+        assert!(!mgr.notify_if_due(KEY_TOKENS_WILL_RUN_OUT));
+        clock.advance(chrono::Duration::minutes(15));
+        assert!(mgr.notify_if_due(KEY_TOKENS_WILL_RUN_OUT), "default 0.25 h");
+    }
+
     // ── NotificationManager construction ─────────────────────────────────────
 
     #[test]
@@ -290,7 +454,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let mgr = make_mgr(&dir);
 
-        // All three canonical keys should exist after construction.
+        // All canonical keys should exist after construction.
         assert!(!mgr.is_notification_active(KEY_SWITCH_TO_CUSTOM));
         assert!(!mgr.is_notification_active(KEY_EXCEED_MAX_LIMIT));
         assert!(!mgr.is_notification_active(KEY_TOKENS_WILL_RUN_OUT));
@@ -365,6 +529,29 @@ mod tests {
         assert!(mgr.should_notify(KEY_EXCEED_MAX_LIMIT, 24.0));
     }
 
+    #[test]
+    fn test_should_notify_cooldown_ends_exactly_on_time() {
+        use crate::clock::{Clock, MockClock};
+
+        let dir = TempDir::new().unwrap();
+        let clock = MockClock::new(
+            DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        let mut mgr = make_mgr(&dir).with_clock(clock.shared());
+        mgr.mark_notified(KEY_EXCEED_MAX_LIMIT);
+        assert_eq!(
+            mgr.get_notification_state(KEY_EXCEED_MAX_LIMIT).timestamp,
+            Some(clock.now())
+        );
+
+        clock.advance(chrono::Duration::hours(2) - chrono::Duration::seconds(1));
+        assert!(!mgr.should_notify(KEY_EXCEED_MAX_LIMIT, 2.0));
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(mgr.should_notify(KEY_EXCEED_MAX_LIMIT, 2.0));
+    }
+
     #[test]
     fn test_should_notify_triggered_recent_timestamp_returns_false() {
         let dir = TempDir::new().unwrap();
@@ -1,3 +1,4 @@
+use crate::models::{SessionBlock, TokensBasis};
 use crate::plans::{COMMON_TOKEN_LIMITS, DEFAULT_TOKEN_LIMIT, LIMIT_DETECTION_THRESHOLD};
 
 // ── Percentile helper ─────────────────────────────────────────────────────────
@@ -104,7 +105,116 @@ pub fn calculate_p90_from_blocks(blocks: &[serde_json::Value], config: &P90Confi
     if completed.is_empty() {
         return config.default_min_limit;
     }
+    p90_of_totals(&completed, config)
+}
+
+/// P90 token limit detected from the completed (neither gap nor active)
+/// `blocks`, counting input and output tokens like the plan limits.
+///
+/// Returns `None` when no block has completed yet.
+pub fn detect_token_limit(blocks: &[SessionBlock], config: &P90Config) -> Option<u64> {
+    let completed: Vec<u64> = blocks
+        .iter()
+        .filter(|b| !b.is_gap && !b.is_active)
+        .map(|b| b.token_counts.total_for(TokensBasis::Io))
+        .collect();
+    (!completed.is_empty()).then(|| p90_of_totals(&completed, config))
+}
+
+// ── Snapping to known limits ──────────────────────────────────────────────────
+
+/// A detected limit is "loose" when the observed peak sits more than this
+/// fraction below it.
+pub const LOOSE_LIMIT_MARGIN: f64 = 0.2;
+
+/// How a detected P90 is matched to the well-known limit steps
+/// (`--limit-detect`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimitDetect {
+    /// The P90 itself, not snapped to a step.
+    #[default]
+    P90,
+    /// The step closest to the P90, which may be below it.
+    Nearest,
+    /// The smallest step at or above the P90.
+    Ceil,
+    /// No detection: the custom plan keeps its built-in limit.
+    Off,
+}
+
+impl LimitDetect {
+    /// Parse a `--limit-detect` value; unknown names map to `P90`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "nearest" => Self::Nearest,
+            "ceil" => Self::Ceil,
+            "off" => Self::Off,
+            _ => Self::P90,
+        }
+    }
+}
 
+/// A token limit snapped to a known step, with the peak it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedLimit {
+    /// The chosen limit.
+    pub limit: u64,
+    /// The P90 of the completed sessions.
+    pub peak: u64,
+}
+
+impl DetectedLimit {
+    /// Whether the peak is more than [`LOOSE_LIMIT_MARGIN`] below the
+    /// limit, so the usage bar may read low.
+    pub fn is_loose(&self) -> bool {
+        (self.peak as f64) < self.limit as f64 * (1.0 - LOOSE_LIMIT_MARGIN)
+    }
+
+    /// Whether the limit was snapped below the peak, so the usage bar may
+    /// read over 100% in an ordinary session.
+    pub fn is_tight(&self) -> bool {
+        self.peak > self.limit
+    }
+
+    /// Whether the limit is [loose](Self::is_loose) or
+    /// [tight](Self::is_tight) and worth a warning.
+    pub fn is_poor_fit(&self) -> bool {
+        self.is_loose() || self.is_tight()
+    }
+}
+
+/// [`detect_token_limit`] snapped to one of `config.common_limits` with
+/// `strategy`.
+///
+/// A peak above the largest step is kept as is. Returns `None` when no block
+/// has completed yet or `strategy` is [`LimitDetect::Off`].
+pub fn detect_limit_from_blocks(
+    blocks: &[SessionBlock],
+    config: &P90Config,
+    strategy: LimitDetect,
+) -> Option<DetectedLimit> {
+    if strategy == LimitDetect::Off {
+        return None;
+    }
+    let peak = detect_token_limit(blocks, config)?;
+    if strategy == LimitDetect::P90 {
+        return Some(DetectedLimit { limit: peak, peak });
+    }
+    let steps = config.common_limits.iter().copied();
+    let snapped = match strategy {
+        LimitDetect::Ceil => steps.filter(|&step| step >= peak).min(),
+        _ => steps.min_by_key(|&step| step.abs_diff(peak)),
+    };
+    let limit = match snapped {
+        Some(step) if config.common_limits.iter().any(|&s| s >= peak) => step,
+        _ => peak,
+    };
+    Some(DetectedLimit { limit, peak })
+}
+
+/// P90 of the non-empty `completed` block totals, preferring blocks that hit
+/// a known limit, floored at `config.default_min_limit`.
+fn p90_of_totals(completed: &[u64], config: &P90Config) -> u64 {
     // 1. Try to use only sessions that hit a known token limit.
     let limit_hitting: Vec<f64> = completed
         .iter()
@@ -137,6 +247,7 @@ pub fn calculate_p90_from_blocks(blocks: &[serde_json::Value], config: &P90Confi
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutil::completed_block;
     use serde_json::json;
 
     fn make_block(tokens: u64, is_gap: bool, is_active: bool) -> serde_json::Value {
@@ -308,4 +419,71 @@ mod tests {
         // p90 of [46000] = 46000; max(46000, 10000) = 46000
         assert_eq!(result, 46_000);
     }
+
+    // ── detect_limit_from_blocks ─────────────────────────────────────────────
+
+    fn detect(peak: u64, strategy: LimitDetect) -> Option<DetectedLimit> {
+        detect_limit_from_blocks(&[completed_block(peak)], &default_config(), strategy)
+    }
+
+    #[test]
+    fn test_detect_limit_between_steps_per_strategy() {
+        // 120k sits between the 88k and 220k steps, closer to 88k.
+        let p90 = detect(120_000, LimitDetect::P90).unwrap();
+        assert_eq!(p90.limit, 120_000);
+        assert!(!p90.is_poor_fit());
+
+        // Snapping down leaves the limit below the peak.
+        let nearest = detect(120_000, LimitDetect::Nearest).unwrap();
+        assert_eq!(nearest.limit, 88_000);
+        assert!(!nearest.is_loose());
+        assert!(nearest.is_tight());
+        assert!(nearest.is_poor_fit());
+
+        let ceil = detect(120_000, LimitDetect::Ceil).unwrap();
+        assert_eq!(ceil.limit, 220_000);
+        assert_eq!(ceil.peak, 120_000);
+        // 120k is far below 80% of 220k (176k).
+        assert!(ceil.is_loose());
+        assert!(ceil.is_poor_fit());
+
+        assert_eq!(detect(120_000, LimitDetect::Off), None);
+    }
+
+    #[test]
+    fn test_detect_limit_close_to_a_step_is_not_loose() {
+        for strategy in [LimitDetect::Nearest, LimitDetect::Ceil] {
+            let detected = detect(200_000, strategy).unwrap();
+            assert_eq!(detected.limit, 220_000);
+            assert!(!detected.is_loose());
+        }
+    }
+
+    #[test]
+    fn test_detect_limit_above_largest_step_keeps_peak() {
+        let detected = detect(1_000_000, LimitDetect::Nearest).unwrap();
+        assert_eq!(detected.limit, 1_000_000);
+        assert_eq!(
+            detect(1_000_000, LimitDetect::Ceil).unwrap().limit,
+            1_000_000
+        );
+    }
+
+    #[test]
+    fn test_detect_limit_without_completed_sessions() {
+        let config = default_config();
+        assert_eq!(
+            detect_limit_from_blocks(&[], &config, LimitDetect::Nearest),
+            None
+        );
+    }
+
+    #[test]
+    fn test_limit_detect_from_name() {
+        assert_eq!(LimitDetect::from_name("nearest"), LimitDetect::Nearest);
+        assert_eq!(LimitDetect::from_name("ceil"), LimitDetect::Ceil);
+        assert_eq!(LimitDetect::from_name("off"), LimitDetect::Off);
+        assert_eq!(LimitDetect::from_name("p90"), LimitDetect::P90);
+        assert_eq!(LimitDetect::from_name("bogus"), LimitDetect::P90);
+    }
 }
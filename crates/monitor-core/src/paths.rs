@@ -0,0 +1,212 @@
+//! Locations of Claude Monitor's own config and state files.
+//!
+//! Follows the XDG base-directory spec:
+//!
+//! * config (`last_used.json`) → `$XDG_CONFIG_HOME/claude-monitor`, default
+//!   `~/.config/claude-monitor`
+//! * state (notification cooldowns, logs) → `$XDG_STATE_HOME/claude-monitor`,
+//!   default `~/.local/state/claude-monitor`
+//!
+//! Older releases kept everything in `~/.claude-monitor`; files found there
+//! are moved to the new location on first use (see [`migrate_legacy_file`]).
+//!
+//! Without a home directory only an explicit XDG variable locates a
+//! directory; the functions return `None` rather than guess.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+/// Directory name used under the XDG base directories.
+pub const APP_DIR_NAME: &str = "claude-monitor";
+
+/// Pre-XDG directory under `$HOME`, still read for backward compatibility.
+pub const LEGACY_DIR_NAME: &str = ".claude-monitor";
+
+/// Directory for user configuration such as `last_used.json`, or `None`
+/// when neither `$XDG_CONFIG_HOME` nor the home directory is known.
+pub fn config_dir() -> Option<PathBuf> {
+    config_dir_from(
+        std::env::var_os("XDG_CONFIG_HOME"),
+        dirs::home_dir().as_deref(),
+    )
+}
+
+/// Directory for persistent state such as notification cooldowns and logs,
+/// or `None` when neither `$XDG_STATE_HOME` nor the home directory is known.
+pub fn state_dir() -> Option<PathBuf> {
+    state_dir_from(
+        std::env::var_os("XDG_STATE_HOME"),
+        dirs::home_dir().as_deref(),
+    )
+}
+
+/// The pre-XDG `~/.claude-monitor` directory, `None` without a home
+/// directory.
+pub fn legacy_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(LEGACY_DIR_NAME))
+}
+
+/// Resolve the config directory from an explicit `$XDG_CONFIG_HOME` value
+/// and home directory.
+pub fn config_dir_from(xdg_config_home: Option<OsString>, home: Option<&Path>) -> Option<PathBuf> {
+    xdg_base(xdg_config_home)
+        .or_else(|| home.map(|home| home.join(".config")))
+        .map(|base| base.join(APP_DIR_NAME))
+}
+
+/// Resolve the state directory from an explicit `$XDG_STATE_HOME` value and
+/// home directory.
+pub fn state_dir_from(xdg_state_home: Option<OsString>, home: Option<&Path>) -> Option<PathBuf> {
+    xdg_base(xdg_state_home)
+        .or_else(|| home.map(|home| home.join(".local").join("state")))
+        .map(|base| base.join(APP_DIR_NAME))
+}
+
+/// Where a file named `file_name` lives in `dir` (from [`config_dir`] or
+/// [`state_dir`]), moving a copy left in the legacy directory there first.
+///
+/// Returns `None` when `dir` is unknown.
+pub fn migrated_file(dir: Option<PathBuf>, file_name: &str) -> Option<PathBuf> {
+    let target = dir?.join(file_name);
+    Some(match legacy_dir() {
+        Some(legacy) => migrate_legacy_file(&legacy.join(file_name), &target),
+        None => target,
+    })
+}
+
+/// Move `legacy` to `target` when only the legacy file exists.
+///
+/// Returns the path the caller should use: `target` when it exists or the
+/// move succeeded, otherwise `legacy` if it exists, otherwise `target`.
+pub fn migrate_legacy_file(legacy: &Path, target: &Path) -> PathBuf {
+    if target.exists() || !legacy.exists() {
+        return target.to_path_buf();
+    }
+
+    let moved = target
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::copy(legacy, target))
+        .and_then(|_| std::fs::remove_file(legacy));
+    match moved {
+        Ok(()) => target.to_path_buf(),
+        Err(e) => {
+            warn!(
+                "Could not move {} to {}: {}",
+                legacy.display(),
+                target.display(),
+                e
+            );
+            if target.exists() {
+                target.to_path_buf()
+            } else {
+                legacy.to_path_buf()
+            }
+        }
+    }
+}
+
+/// An XDG base directory from the environment, ignoring empty and relative
+/// values as the spec requires.
+fn xdg_base(value: Option<OsString>) -> Option<PathBuf> {
+    value.map(PathBuf::from).filter(|path| path.is_absolute())
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    // ── config_dir_from / state_dir_from ──────────────────────────────────────
+
+    #[test]
+    fn test_config_dir_defaults_to_dot_config() {
+        let home = Some(Path::new("/home/user"));
+        assert_eq!(
+            config_dir_from(None, home),
+            Some(PathBuf::from("/home/user/.config/claude-monitor"))
+        );
+        assert_eq!(
+            config_dir_from(Some("/xdg/config".into()), home),
+            Some(PathBuf::from("/xdg/config/claude-monitor"))
+        );
+    }
+
+    #[test]
+    fn test_state_dir_defaults_to_local_state() {
+        let home = Some(Path::new("/home/user"));
+        assert_eq!(
+            state_dir_from(None, home),
+            Some(PathBuf::from("/home/user/.local/state/claude-monitor"))
+        );
+        assert_eq!(
+            state_dir_from(Some("/xdg/state".into()), home),
+            Some(PathBuf::from("/xdg/state/claude-monitor"))
+        );
+    }
+
+    #[test]
+    fn test_empty_or_relative_xdg_values_are_ignored() {
+        let home = Some(Path::new("/home/user"));
+        let expected = Some(PathBuf::from("/home/user/.config/claude-monitor"));
+        assert_eq!(config_dir_from(Some("".into()), home), expected);
+        assert_eq!(config_dir_from(Some("relative/dir".into()), home), expected);
+    }
+
+    #[test]
+    fn test_dirs_without_home_need_xdg() {
+        assert_eq!(config_dir_from(None, None), None);
+        assert_eq!(state_dir_from(Some("relative".into()), None), None);
+        assert_eq!(
+            state_dir_from(Some("/xdg/state".into()), None),
+            Some(PathBuf::from("/xdg/state/claude-monitor"))
+        );
+        assert_eq!(migrated_file(None, "lifetime.json"), None);
+    }
+
+    // ── migrate_legacy_file ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_migrate_moves_legacy_file() {
+        let tmp = TempDir::new().unwrap();
+        let legacy = tmp.path().join(".claude-monitor").join("last_used.json");
+        let target = tmp.path().join("config").join("last_used.json");
+        std::fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        std::fs::write(&legacy, "{\"theme\":\"dark\"}").unwrap();
+
+        assert_eq!(migrate_legacy_file(&legacy, &target), target);
+        assert!(!legacy.exists());
+        assert_eq!(
+            std::fs::read_to_string(&target).unwrap(),
+            "{\"theme\":\"dark\"}"
+        );
+    }
+
+    #[test]
+    fn test_migrate_keeps_existing_target() {
+        let tmp = TempDir::new().unwrap();
+        let legacy = tmp.path().join("legacy.json");
+        let target = tmp.path().join("target.json");
+        std::fs::write(&legacy, "old").unwrap();
+        std::fs::write(&target, "new").unwrap();
+
+        assert_eq!(migrate_legacy_file(&legacy, &target), target);
+        assert!(legacy.exists(), "legacy file is left alone");
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_migrate_without_legacy_file_uses_target() {
+        let tmp = TempDir::new().unwrap();
+        let target = tmp.path().join("target.json");
+        assert_eq!(
+            migrate_legacy_file(&tmp.path().join("missing.json"), &target),
+            target
+        );
+        assert!(!target.exists());
+    }
+}
@@ -1,4 +1,7 @@
 use crate::error::{MonitorError, Result};
+use crate::formatting::format_compact;
+use crate::models::SessionBlock;
+use crate::p90::{detect_limit_from_blocks, LimitDetect, P90Config};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -65,11 +68,73 @@ pub struct PlanConfig {
 impl PlanConfig {
     /// Compact human-readable token limit string (e.g. `"19k"`, `"220k"`).
     pub fn formatted_token_limit(&self) -> String {
-        if self.token_limit >= 1_000 {
-            format!("{}k", self.token_limit / 1_000)
-        } else {
-            self.token_limit.to_string()
-        }
+        format_compact(self.token_limit)
+    }
+}
+
+// ── CustomPlanLimits ──────────────────────────────────────────────────────────
+
+/// User-supplied overrides for the [`PlanType::Custom`] plan.
+///
+/// Any field left as `None` keeps the built-in Custom plan value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomPlanLimits {
+    /// Token limit override (`--custom-limit-tokens`).
+    pub token_limit: Option<u64>,
+    /// Cost limit override in USD (`--custom-cost-limit`).
+    pub cost_limit: Option<f64>,
+    /// Message limit override (`--custom-message-limit`).
+    pub message_limit: Option<u32>,
+}
+
+impl CustomPlanLimits {
+    /// Returns `true` when no override is set.
+    pub fn is_empty(&self) -> bool {
+        self.token_limit.is_none() && self.cost_limit.is_none() && self.message_limit.is_none()
+    }
+}
+
+// ── ModelTokenLimits ──────────────────────────────────────────────────────────
+
+/// Token limits for single model families (`--opus-limit`,
+/// `--sonnet-limit`, `--haiku-limit`), on top of the plan's overall limit.
+///
+/// A model belongs to a family when its name contains the family name, so
+/// `claude-opus-4-20250514` and `claude-3-opus` both count as Opus.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelTokenLimits {
+    /// Opus token limit per session.
+    pub opus: Option<u64>,
+    /// Sonnet token limit per session.
+    pub sonnet: Option<u64>,
+    /// Haiku token limit per session.
+    pub haiku: Option<u64>,
+}
+
+impl ModelTokenLimits {
+    /// Returns `true` when no family has a limit.
+    pub fn is_empty(&self) -> bool {
+        self.configured().is_empty()
+    }
+
+    /// `(family, limit)` for every family with a limit, as Opus, Sonnet,
+    /// Haiku.
+    pub fn configured(&self) -> Vec<(&'static str, u64)> {
+        [
+            ("Opus", self.opus),
+            ("Sonnet", self.sonnet),
+            ("Haiku", self.haiku),
+        ]
+        .into_iter()
+        .filter_map(|(family, limit)| limit.map(|limit| (family, limit)))
+        .collect()
+    }
+
+    /// Whether `model` belongs to `family` (case-insensitive substring).
+    pub fn in_family(model: &str, family: &str) -> bool {
+        model
+            .to_ascii_lowercase()
+            .contains(&family.to_ascii_lowercase())
     }
 }
 
@@ -84,6 +149,9 @@ pub const DEFAULT_COST_LIMIT: f64 = 50.0;
 /// Fallback message limit used when no plan is recognised (mirrors Pro limit).
 pub const DEFAULT_MESSAGE_LIMIT: u32 = 250;
 
+/// Observed message maxima are rounded up to a multiple of this.
+pub const MESSAGE_LIMIT_STEP: u32 = 10;
+
 /// Well-known token limits, in ascending order, used for auto-detection.
 pub const COMMON_TOKEN_LIMITS: &[u64] = &[19_000, 88_000, 220_000, 880_000];
 
@@ -172,6 +240,28 @@ impl Plans {
         Some(Self::get_plan(pt))
     }
 
+    /// Resolve the effective configuration for a named plan.
+    ///
+    /// For `"custom"` the built-in Custom plan is used as a base and every
+    /// value present in `custom` replaces the corresponding limit.  Other
+    /// plans ignore `custom`.  Returns `None` if the name is not recognised.
+    pub fn resolve_plan(name: &str, custom: &CustomPlanLimits) -> Option<PlanConfig> {
+        let pt = name.parse::<PlanType>().ok()?;
+        let mut config = Self::get_plan(pt);
+        if pt == PlanType::Custom {
+            if let Some(tokens) = custom.token_limit {
+                config.token_limit = tokens;
+            }
+            if let Some(cost) = custom.cost_limit {
+                config.cost_limit = cost;
+            }
+            if let Some(messages) = custom.message_limit {
+                config.message_limit = messages;
+            }
+        }
+        Some(config)
+    }
+
     /// Token limit for the named plan, or [`DEFAULT_TOKEN_LIMIT`] if unknown.
     pub fn get_token_limit(plan: &str) -> u64 {
         Self::get_plan_by_name(plan)
@@ -197,6 +287,22 @@ impl Plans {
     pub fn is_valid_plan(plan: &str) -> bool {
         Self::get_plan_by_name(plan).is_some()
     }
+
+    /// Message limit derived from the most messages sent in any completed
+    /// session window, rounded up to the next multiple of
+    /// [`MESSAGE_LIMIT_STEP`].
+    ///
+    /// Returns `None` when no messages have been observed.
+    pub fn observed_message_limit(max_sent: u32) -> Option<u32> {
+        if max_sent == 0 {
+            return None;
+        }
+        Some(
+            max_sent
+                .div_ceil(MESSAGE_LIMIT_STEP)
+                .saturating_mul(MESSAGE_LIMIT_STEP),
+        )
+    }
 }
 
 // ── Module-level free functions (mirror Python module-level helpers) ───────────
@@ -208,6 +314,32 @@ pub fn get_token_limit(plan: &str) -> u64 {
     Plans::get_token_limit(plan)
 }
 
+/// Effective token limit of `plan`, in order of precedence:
+///
+/// 1. `custom`, the explicit `--custom-limit-tokens`, for the custom plan;
+/// 2. the fixed limit of the `pro`, `max5` and `max20` plans;
+/// 3. for the custom plan, the limit detected from the completed `blocks`
+///    and snapped to a known step with `detect` (see
+///    [`detect_limit_from_blocks`]);
+/// 4. the custom plan's built-in limit, or [`DEFAULT_TOKEN_LIMIT`] for an
+///    unknown plan.
+pub fn resolve_token_limit(
+    plan: &str,
+    custom: Option<u64>,
+    blocks: &[SessionBlock],
+    detect: LimitDetect,
+) -> u64 {
+    match plan.parse::<PlanType>() {
+        Ok(PlanType::Custom) => custom
+            .or_else(|| {
+                detect_limit_from_blocks(blocks, &P90Config::default(), detect).map(|d| d.limit)
+            })
+            .unwrap_or_else(|| Plans::get_plan(PlanType::Custom).token_limit),
+        Ok(plan_type) => Plans::get_plan(plan_type).token_limit,
+        Err(_) => DEFAULT_TOKEN_LIMIT,
+    }
+}
+
 /// Cost limit for the named plan, or [`DEFAULT_COST_LIMIT`] if unknown.
 ///
 /// Wraps [`Plans::get_cost_limit`] as a free function for ergonomic use.
@@ -218,6 +350,7 @@ pub fn get_cost_limit(plan: &str) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutil::completed_block;
 
     // ── PlanType::from_str (via std::str::FromStr) ─────────────────────────
 
@@ -414,6 +547,162 @@ mod tests {
         assert_eq!(Plans::get_message_limit("ghost"), DEFAULT_MESSAGE_LIMIT);
     }
 
+    // ── Plans::observed_message_limit ──────────────────────────────────────
+
+    #[test]
+    fn test_observed_message_limit_rounds_up() {
+        assert_eq!(Plans::observed_message_limit(0), None);
+        assert_eq!(Plans::observed_message_limit(1), Some(10));
+        assert_eq!(Plans::observed_message_limit(40), Some(40));
+        assert_eq!(Plans::observed_message_limit(143), Some(150));
+    }
+
+    // ── Plans::resolve_plan ────────────────────────────────────────────────
+
+    #[test]
+    fn test_resolve_plan_custom_applies_overrides() {
+        let custom = CustomPlanLimits {
+            token_limit: Some(120_000),
+            cost_limit: Some(75.5),
+            message_limit: Some(600),
+        };
+        let cfg = Plans::resolve_plan("custom", &custom).unwrap();
+        assert_eq!(cfg.name, "custom");
+        assert_eq!(cfg.token_limit, 120_000);
+        assert!((cfg.cost_limit - 75.5).abs() < f64::EPSILON);
+        assert_eq!(cfg.message_limit, 600);
+    }
+
+    #[test]
+    fn test_resolve_plan_custom_partial_overrides_keep_defaults() {
+        let custom = CustomPlanLimits {
+            cost_limit: Some(20.0),
+            ..Default::default()
+        };
+        let cfg = Plans::resolve_plan("custom", &custom).unwrap();
+        assert_eq!(cfg.token_limit, 44_000);
+        assert!((cfg.cost_limit - 20.0).abs() < f64::EPSILON);
+        assert_eq!(cfg.message_limit, 250);
+    }
+
+    #[test]
+    fn test_resolve_plan_non_custom_ignores_overrides() {
+        let custom = CustomPlanLimits {
+            token_limit: Some(1),
+            cost_limit: Some(1.0),
+            message_limit: Some(1),
+        };
+        let cfg = Plans::resolve_plan("max5", &custom).unwrap();
+        assert_eq!(cfg.token_limit, 88_000);
+        assert!((cfg.cost_limit - 35.0).abs() < f64::EPSILON);
+        assert_eq!(cfg.message_limit, 1_000);
+    }
+
+    #[test]
+    fn test_resolve_plan_unknown_returns_none() {
+        assert!(Plans::resolve_plan("enterprise", &CustomPlanLimits::default()).is_none());
+    }
+
+    #[test]
+    fn test_custom_plan_limits_is_empty() {
+        assert!(CustomPlanLimits::default().is_empty());
+        let custom = CustomPlanLimits {
+            message_limit: Some(10),
+            ..Default::default()
+        };
+        assert!(!custom.is_empty());
+    }
+
+    // ── resolve_token_limit ───────────────────────────────────────────────
+
+    #[test]
+    fn test_resolve_token_limit_explicit_custom_wins() {
+        let blocks = [completed_block(300_000)];
+        assert_eq!(
+            resolve_token_limit("custom", Some(123_000), &blocks, LimitDetect::Ceil),
+            123_000
+        );
+    }
+
+    #[test]
+    fn test_resolve_token_limit_fixed_plan_ignores_custom_and_history() {
+        let blocks = [completed_block(300_000)];
+        let detect = LimitDetect::default();
+        assert_eq!(
+            resolve_token_limit("max5", Some(1), &blocks, detect),
+            88_000
+        );
+        assert_eq!(resolve_token_limit("pro", None, &blocks, detect), 19_000);
+    }
+
+    #[test]
+    fn test_resolve_token_limit_custom_detects_from_history() {
+        // Cache tokens are not counted, like the plan limits.
+        let mut block = completed_block(300_000);
+        block.token_counts.cache_read_tokens = 1_000_000;
+        let blocks = [block];
+        assert_eq!(
+            resolve_token_limit("custom", None, &blocks, LimitDetect::default()),
+            300_000
+        );
+        assert_eq!(
+            resolve_token_limit("custom", None, &blocks, LimitDetect::Nearest),
+            220_000
+        );
+        assert_eq!(
+            resolve_token_limit("custom", None, &blocks, LimitDetect::Ceil),
+            880_000
+        );
+        assert_eq!(
+            resolve_token_limit("custom", None, &blocks, LimitDetect::Off),
+            44_000
+        );
+
+        let mut active = completed_block(500_000);
+        active.is_active = true;
+        assert_eq!(
+            resolve_token_limit("custom", None, &[active], LimitDetect::Nearest),
+            44_000
+        );
+    }
+
+    #[test]
+    fn test_resolve_token_limit_defaults() {
+        let detect = LimitDetect::default();
+        assert_eq!(resolve_token_limit("custom", None, &[], detect), 44_000);
+        assert_eq!(
+            resolve_token_limit("enterprise", Some(1), &[], detect),
+            DEFAULT_TOKEN_LIMIT
+        );
+    }
+
+    // ── ModelTokenLimits ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_model_token_limits_configured_in_family_order() {
+        assert!(ModelTokenLimits::default().is_empty());
+        let limits = ModelTokenLimits {
+            opus: None,
+            sonnet: Some(200_000),
+            haiku: Some(500_000),
+        };
+        assert!(!limits.is_empty());
+        assert_eq!(
+            limits.configured(),
+            [("Sonnet", 200_000), ("Haiku", 500_000)]
+        );
+    }
+
+    #[test]
+    fn test_model_token_limits_in_family() {
+        assert!(ModelTokenLimits::in_family(
+            "claude-opus-4-20250514",
+            "Opus"
+        ));
+        assert!(ModelTokenLimits::in_family("claude-3-opus", "opus"));
+        assert!(!ModelTokenLimits::in_family("claude-3-5-sonnet", "Opus"));
+    }
+
     // ── Constants ─────────────────────────────────────────────────────────
 
     #[test]
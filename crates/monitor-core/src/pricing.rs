@@ -1,18 +1,26 @@
 use std::collections::HashMap;
+use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MonitorError, Result};
 use crate::models::normalize_model_name;
-use crate::models::{CostMode, TokenCounts};
+use crate::models::{CostMode, TokenCounts, SYNTHETIC_MODEL};
 
 /// Per-model pricing rates in US dollars per million tokens.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModelPricing {
     /// Price per million input (prompt) tokens.
+    #[serde(deserialize_with = "deserialize_rate")]
     pub input: f64,
     /// Price per million output (completion) tokens.
+    #[serde(deserialize_with = "deserialize_rate")]
     pub output: f64,
     /// Price per million cache-creation tokens.
+    #[serde(deserialize_with = "deserialize_rate")]
     pub cache_creation: f64,
     /// Price per million cache-read tokens.
+    #[serde(deserialize_with = "deserialize_rate")]
     pub cache_read: f64,
 }
 
@@ -29,6 +37,71 @@ impl ModelPricing {
 
 // ── Fallback pricing constants ($/million tokens) ─────────────────────────────
 
+/// Parse an amount of money written with either `.` or `,` as the decimal
+/// separator, so `"0,92"` and `"0.92"` are the same value.
+///
+/// The result must be finite and positive.
+pub fn parse_cost(text: &str) -> Result<f64> {
+    positive_cost(parse_amount(text)?, text)
+}
+
+/// Like [`parse_cost`], but also accepts zero, for thresholds such as
+/// `--min-cost 0`.
+pub fn parse_cost_threshold(text: &str) -> Result<f64> {
+    non_negative_cost(parse_amount(text)?, text)
+}
+
+/// `text` as a number, with `,` read as the decimal separator.
+fn parse_amount(text: &str) -> Result<f64> {
+    text.trim()
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| MonitorError::Config(format!("invalid amount {text:?}")))
+}
+
+/// `value` (written as `text`) when it is finite and positive.
+fn positive_cost(value: f64, text: &str) -> Result<f64> {
+    if !value.is_finite() || value <= 0.0 {
+        return Err(MonitorError::Config(format!(
+            "amount must be a positive number, got {text:?}"
+        )));
+    }
+    Ok(value)
+}
+
+/// `value` (written as `text`) when it is zero, or finite and positive.
+fn non_negative_cost(value: f64, text: &str) -> Result<f64> {
+    if value == 0.0 {
+        return Ok(0.0);
+    }
+    positive_cost(value, text).map_err(|_| {
+        MonitorError::Config(format!(
+            "amount must be zero or a positive number, got {text:?}"
+        ))
+    })
+}
+
+/// A pricing-file rate: a JSON number, or a string parsed with
+/// [`parse_cost_threshold`] so `"0,30"` works too. Both must be finite and
+/// not negative; zero prices a token category as free.
+fn deserialize_rate<'de, D>(deserializer: D) -> std::result::Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Rate {
+        Number(f64),
+        Text(String),
+    }
+    match Rate::deserialize(deserializer)? {
+        Rate::Number(value) => {
+            non_negative_cost(value, &value.to_string()).map_err(serde::de::Error::custom)
+        }
+        Rate::Text(text) => parse_cost_threshold(&text).map_err(serde::de::Error::custom),
+    }
+}
+
 fn opus_pricing() -> ModelPricing {
     ModelPricing::new(15.0, 75.0, 18.75, 1.50)
 }
@@ -41,8 +114,8 @@ fn haiku_pricing() -> ModelPricing {
     ModelPricing::new(0.25, 1.25, 0.30, 0.03)
 }
 
-/// Build the default model pricing map keyed by canonical model name.
-fn default_pricing_map() -> HashMap<String, ModelPricing> {
+/// The built-in model pricing map keyed by canonical model name.
+pub fn default_pricing_map() -> HashMap<String, ModelPricing> {
     let mut map = HashMap::new();
     map.insert("claude-3-opus".to_string(), opus_pricing());
     map.insert("claude-3-sonnet".to_string(), sonnet_pricing());
@@ -54,6 +127,16 @@ fn default_pricing_map() -> HashMap<String, ModelPricing> {
     map
 }
 
+/// Read per-model price overrides from a JSON file such as
+/// `{"claude-3-5-sonnet": {"input": 3.0, "output": 15.0, "cache_creation": 3.75, "cache_read": 0.3}}`.
+pub fn load_pricing_file(path: &Path) -> Result<HashMap<String, ModelPricing>> {
+    let content = std::fs::read_to_string(path).map_err(|source| MonitorError::FileRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(serde_json::from_str(&content)?)
+}
+
 /// Calculator that resolves per-model pricing and computes costs from token
 /// counts, with an optional result cache to avoid redundant recalculation.
 pub struct PricingCalculator {
@@ -81,6 +164,12 @@ impl PricingCalculator {
         }
     }
 
+    /// The built-in rates merged with the overrides passed to
+    /// [`new`](Self::new), keyed by model name.
+    pub fn pricing_map(&self) -> &HashMap<String, ModelPricing> {
+        &self.pricing_map
+    }
+
     // ── Private helpers ──────────────────────────────────────────────────────
 
     /// Resolve the pricing for `model`, consulting the map in priority order:
@@ -133,7 +222,7 @@ impl PricingCalculator {
         cache_read_tokens: u64,
     ) -> f64 {
         // Skip synthetic entries.
-        if model == "<synthetic>" {
+        if model == SYNTHETIC_MODEL {
             return 0.0;
         }
 
@@ -172,6 +261,20 @@ impl PricingCalculator {
         )
     }
 
+    /// Split the cache portion of a model's cost into
+    /// `(cache_creation_cost, cache_read_cost)` in USD.
+    pub fn calculate_cache_costs(
+        &mut self,
+        model: &str,
+        cache_creation_tokens: u64,
+        cache_read_tokens: u64,
+    ) -> (f64, f64) {
+        (
+            self.calculate_cost(model, 0, 0, cache_creation_tokens, 0),
+            self.calculate_cost(model, 0, 0, 0, cache_read_tokens),
+        )
+    }
+
     /// Extract cost from a raw JSON entry, honouring the given [`CostMode`].
     ///
     /// * `CostMode::Cached` — try `entry["costUSD"]` then `entry["cost_usd"]`.
@@ -310,6 +413,18 @@ mod tests {
         assert!((cost - 0.30).abs() < 1e-4, "cache_read cost = {cost}");
     }
 
+    #[test]
+    fn test_calculate_cache_costs_splits_by_kind() {
+        let mut c = calc();
+        let (creation, read) = c.calculate_cache_costs("claude-3-opus", 1_000_000, 2_000_000);
+        assert!((creation - 18.75).abs() < 1e-4, "creation = {creation}");
+        assert!((read - 3.0).abs() < 1e-4, "read = {read}");
+        assert_eq!(
+            c.calculate_cache_costs("<synthetic>", 1_000, 1_000),
+            (0.0, 0.0)
+        );
+    }
+
     // ── Special / edge cases ─────────────────────────────────────────────────
 
     #[test]
@@ -484,6 +599,113 @@ mod tests {
         assert!((cost - 300.0).abs() < 1e-4, "custom pricing cost = {cost}");
     }
 
+    #[test]
+    fn test_pricing_map_merges_overrides() {
+        let mut overrides = HashMap::new();
+        overrides.insert(
+            "claude-next".to_string(),
+            ModelPricing::new(1.0, 2.0, 3.0, 4.0),
+        );
+        let c = PricingCalculator::new(Some(overrides));
+        let map = c.pricing_map();
+        assert_eq!(map.len(), default_pricing_map().len() + 1);
+        assert_eq!(map["claude-next"].cache_read, 4.0);
+        assert_eq!(map["claude-3-opus"], opus_pricing());
+    }
+
+    #[test]
+    fn test_load_pricing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("pricing.json");
+        std::fs::write(
+            &path,
+            r#"{"claude-3-opus": {"input": 1, "output": 2, "cache_creation": 3, "cache_read": 0.5}}"#,
+        )
+        .unwrap();
+        let map = load_pricing_file(&path).unwrap();
+        assert_eq!(map["claude-3-opus"], ModelPricing::new(1.0, 2.0, 3.0, 0.5));
+
+        std::fs::write(&path, r#"{"claude-3-opus": {"input": 1}}"#).unwrap();
+        assert!(matches!(
+            load_pricing_file(&path),
+            Err(MonitorError::JsonParse(_))
+        ));
+        assert!(matches!(
+            load_pricing_file(&dir.path().join("missing.json")),
+            Err(MonitorError::FileRead { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_pricing_file_accepts_decimal_comma_strings() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("pricing.json");
+        std::fs::write(
+            &path,
+            r#"{"claude-3-opus": {"input": "1,5", "output": "2.5", "cache_creation": 3, "cache_read": "0,3"}}"#,
+        )
+        .unwrap();
+        let map = load_pricing_file(&path).unwrap();
+        assert_eq!(map["claude-3-opus"], ModelPricing::new(1.5, 2.5, 3.0, 0.3));
+
+        std::fs::write(
+            &path,
+            r#"{"claude-3-opus": {"input": "abc", "output": 2, "cache_creation": 3, "cache_read": 1}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            load_pricing_file(&path),
+            Err(MonitorError::JsonParse(_))
+        ));
+    }
+
+    // ── parse_cost ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_parse_cost_accepts_both_decimal_separators() {
+        assert_eq!(parse_cost("0,92").unwrap(), parse_cost("0.92").unwrap());
+        assert_eq!(parse_cost(" 12,5 ").unwrap(), 12.5);
+        assert_eq!(parse_cost("40").unwrap(), 40.0);
+    }
+
+    #[test]
+    fn test_parse_cost_rejects_invalid_amounts() {
+        for text in ["", "abc", "1,000.50", "0", "-1,5", "inf", "NaN"] {
+            assert!(
+                matches!(parse_cost(text), Err(MonitorError::Config(_))),
+                "{text:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_cost_threshold_accepts_zero() {
+        assert_eq!(parse_cost_threshold("0").unwrap(), 0.0);
+        assert_eq!(parse_cost_threshold("0,00").unwrap(), 0.0);
+        assert_eq!(parse_cost_threshold("0,5").unwrap(), 0.5);
+        for text in ["", "-1", "inf", "NaN"] {
+            assert!(
+                parse_cost_threshold(text).is_err(),
+                "{text:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_numeric_rates_are_validated_like_text_rates() {
+        let rates = |input: &str| {
+            format!(r#"{{"input":{input},"output":15,"cache_creation":3.75,"cache_read":0.3}}"#)
+        };
+        let parse = |json: String| serde_json::from_str::<ModelPricing>(&json);
+        assert_eq!(parse(rates("3")).unwrap().input, 3.0);
+        assert_eq!(parse(rates(r#""3,0""#)).unwrap().input, 3.0);
+        assert_eq!(parse(rates("0")).unwrap().input, 0.0);
+        assert_eq!(parse(rates(r#""0,0""#)).unwrap().input, 0.0);
+        for bad in ["-3", r#""-3""#, r#""inf""#, r#""NaN""#] {
+            assert!(parse(rates(bad)).is_err(), "{bad} should be rejected");
+        }
+    }
+
     // ── Rounding ─────────────────────────────────────────────────────────────
 
     #[test]
@@ -2,6 +2,12 @@ use clap::{CommandFactory, Parser};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::formatting::NumberFormatter;
+use crate::models::TokensBasis;
+use crate::notifications::NotificationCooldowns;
+use crate::plans::{CustomPlanLimits, ModelTokenLimits};
+use crate::pricing::{parse_cost, parse_cost_threshold};
+
 // ── Settings (CLI) ─────────────────────────────────────────────────────────────
 
 /// Real-time token usage monitoring for Claude AI
@@ -17,29 +23,137 @@ pub struct Settings {
     pub plan: String,
 
     /// View mode
-    #[arg(long, default_value = "realtime", value_parser = ["realtime", "daily", "monthly", "session"])]
+    #[arg(long, default_value = "realtime", value_parser = ["realtime", "daily", "monthly", "projects", "session"])]
     pub view: String,
 
     /// Timezone (auto-detected if not specified)
     #[arg(long, default_value = "auto")]
     pub timezone: String,
 
+    /// Show every time in UTC, overriding `--timezone`
+    #[arg(long)]
+    pub utc: bool,
+
     /// Time format
     #[arg(long, default_value = "auto", value_parser = ["12h", "24h", "auto"])]
     pub time_format: String,
 
+    /// Ring the terminal bell when a token or cost limit is reached in the realtime view
+    #[arg(long)]
+    pub bell_on_limit: bool,
+
+    /// Block shown when several are active at once: `most-recent`,
+    /// `highest-tokens` or `earliest`
+    #[arg(long, default_value = "most-recent", value_parser = ["most-recent", "highest-tokens", "earliest"])]
+    pub active_block: String,
+
+    /// Realtime screen layout (compact fits a ~40-column pane)
+    #[arg(long, default_value = "full", value_parser = ["full", "compact"])]
+    pub layout: String,
+
+    /// Draw in the normal screen instead of the alternate screen, leaving
+    /// the last frame in the scrollback on exit
+    #[arg(long)]
+    pub inline: bool,
+
+    /// Title text on the realtime header (default: CLAUDE CODE USAGE MONITOR)
+    #[arg(long)]
+    pub title: Option<String>,
+
+    /// Decoration shown either side of the title; empty for none (default: ✦ ✧ ✦ ✧)
+    #[arg(long)]
+    pub decoration: Option<String>,
+
     /// Display theme
     #[arg(long, default_value = "auto", value_parser = ["light", "dark", "classic", "auto"])]
     pub theme: String,
 
+    /// Thousands separator style (auto uses the locale)
+    #[arg(long, default_value = "comma", value_parser = ["comma", "dot", "space", "auto"])]
+    pub number_format: String,
+
+    /// Decimal places shown for costs (cost rates use two more)
+    #[arg(long, default_value_t = NumberFormatter::DEFAULT_COST_PRECISION, value_parser = clap::value_parser!(u32).range(0..=8))]
+    pub cost_precision: u32,
+
+    /// Abbreviate large token counts as 1.2M / 235k in tables and limits
+    #[arg(long)]
+    pub compact_numbers: bool,
+
+    /// Short label for models containing SUBSTRING, as SUBSTRING=LABEL (repeatable)
+    #[arg(long = "model-label", value_name = "SUBSTRING=LABEL")]
+    pub model_labels: Vec<String>,
+
+    /// Models named in the realtime distribution row; the rest are shown as "Other"
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_models: u32,
+
+    /// Warn in the realtime view when the newest entry is this many minutes
+    /// old (0 disables the warning)
+    #[arg(long, default_value_t = 60)]
+    pub staleness_minutes: u32,
+
+    /// Minutes a session must run (with a few entries) before the realtime
+    /// view shows burn rates and predictions instead of "calculating…"
+    #[arg(long, default_value_t = 5)]
+    pub prediction_warmup_minutes: u32,
+
+    /// Compute the realtime model distribution from only the last this many
+    /// minutes of entries (default: the whole session)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub model_window_minutes: Option<u32>,
+
+    /// Hours before a notification repeats, e.g. `tokens_will_run_out=1` (repeatable)
+    #[arg(long = "notification-cooldown", value_name = "KEY=HOURS", value_parser = parse_notification_cooldown)]
+    pub notification_cooldowns: Vec<String>,
+
     /// Custom token limit for custom plan
     #[arg(long)]
     pub custom_limit_tokens: Option<u64>,
 
+    /// How the custom plan snaps its detected P90 limit to the known plan
+    /// steps: `p90` keeps the P90 itself
+    #[arg(long, default_value = "p90", value_parser = ["p90", "nearest", "ceil", "off"])]
+    pub limit_detect: String,
+
+    /// Custom cost limit (USD) for custom plan
+    #[arg(long, value_parser = parse_cost_arg)]
+    pub custom_cost_limit: Option<f64>,
+
+    /// Custom message limit for custom plan
+    #[arg(long)]
+    pub custom_message_limit: Option<u32>,
+
+    /// Opus token limit per session, shown as its own bar in the realtime view
+    #[arg(long)]
+    pub opus_limit: Option<u64>,
+
+    /// Sonnet token limit per session, shown as its own bar in the realtime view
+    #[arg(long)]
+    pub sonnet_limit: Option<u64>,
+
+    /// Haiku token limit per session, shown as its own bar in the realtime view
+    #[arg(long)]
+    pub haiku_limit: Option<u64>,
+
+    /// Message limit for the messages bar on any plan (default: the plan's
+    /// limit; the custom plan uses the most messages seen in a past session)
+    #[arg(long)]
+    pub message_limit: Option<u32>,
+
+    /// Seconds to wait for one usage file before skipping it (0 = wait forever)
+    #[arg(long, default_value_t = 10)]
+    pub read_timeout: u64,
+
     /// Refresh rate in seconds (1-60)
     #[arg(long, default_value = "10", value_parser = clap::value_parser!(u32).range(1..=60))]
     pub refresh_rate: u32,
 
+    /// Seconds between re-reads of the usage data in the realtime view
+    /// (1-3600), independent of the refresh rate and the display tick
+    #[arg(long, visible_alias = "watch-interval", default_value = "30", value_parser = clap::value_parser!(u32).range(1..=3600))]
+    pub analyze_interval: u32,
+
     /// Display refresh rate per second (Hz)
     #[arg(long, default_value = "0.75")]
     pub refresh_per_second: f64,
@@ -48,10 +162,20 @@ pub struct Settings {
     #[arg(long)]
     pub reset_hour: Option<u8>,
 
+    /// Start session windows on a fixed daily schedule from `--reset-hour`
+    /// (midnight by default) in the display timezone, instead of at each
+    /// window's first message
+    #[arg(long)]
+    pub fixed_blocks: bool,
+
     /// Logging level
     #[arg(long, default_value = "INFO", value_parser = ["DEBUG", "INFO", "WARNING", "ERROR", "CRITICAL"])]
     pub log_level: String,
 
+    /// Log line format: `text` for people, `json` for log pipelines
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    pub log_format: String,
+
     /// Log file path
     #[arg(long)]
     pub log_file: Option<PathBuf>,
@@ -63,11 +187,139 @@ pub struct Settings {
     /// Clear saved configuration
     #[arg(long)]
     pub clear: bool,
+
+    /// Directory containing Claude JSONL files (`-` reads from stdin)
+    #[arg(long)]
+    pub data_path: Option<String>,
+
+    /// Read JSONL usage records from stdin (same as `--data-path -`)
+    #[arg(long)]
+    pub stdin: bool,
+
+    /// Count zero-token entries as messages (they never add tokens or cost)
+    #[arg(long)]
+    pub count_empty_messages: bool,
+
+    /// Count synthetic and system entries as sent messages
+    #[arg(long)]
+    pub include_synthetic: bool,
+
+    /// Output format for the daily, monthly and projects views (`jsonl`
+    /// writes one session block per line)
+    #[arg(long, default_value = "table", value_parser = ["table", "csv", "csv-wide", "markdown", "json", "jsonl"])]
+    pub output: String,
+
+    /// Hide table rows with fewer total tokens (totals still include them)
+    #[arg(long)]
+    pub min_tokens: Option<u64>,
+
+    /// Hide table rows costing less than this many USD (totals still include them)
+    #[arg(long, value_parser = parse_cost_threshold_arg)]
+    pub min_cost: Option<f64>,
+
+    /// Tokens counted in every token total (session bar, burn rate, tables
+    /// and CSV): `io` counts input and output only, `all` adds cache tokens.
+    /// Defaults to `all` for tables and CSV and to `io`, like the plan
+    /// limits, in the realtime view
+    #[arg(long, value_parser = ["io", "all"])]
+    pub tokens_basis: Option<String>,
+
+    /// Daily spend target in USD: shows today's cost against it in the
+    /// realtime view and alerts once it is reached
+    #[arg(long, value_parser = parse_cost_arg)]
+    pub daily_budget: Option<f64>,
+
+    /// Aggregate table columns: `all`, `compact` (period, total and cost) or
+    /// a comma-separated list such as `period,total,cost`; names are checked
+    /// when a table is drawn
+    #[arg(long, default_value = "all")]
+    pub table_columns: String,
+
+    /// Colour each table row's total and cost by its share of the heaviest
+    /// row (green light, red heavy)
+    #[arg(long)]
+    pub heatmap: bool,
+
+    /// Draw the realtime view with ASCII instead of emoji and block
+    /// characters; chosen automatically for a non-UTF-8 locale
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Coarsen model ids in csv, markdown and json output: `family`
+    /// (`sonnet`, `opus`, …, the default) or `total` (no per-model detail)
+    #[arg(long, num_args = 0..=1, default_missing_value = "family", value_parser = ["family", "total"])]
+    pub anonymize_models: Option<String>,
+
+    /// Detail of `--output json`: `summary` leaves out each block's entries
+    #[arg(long, default_value = "full", value_parser = ["summary", "full"])]
+    pub export_level: String,
+
+    /// How entry costs are priced: `cached` trusts the `costUSD` recorded in
+    /// the data, `calculated` always uses the pricing table
+    #[arg(long, default_value = "auto", value_parser = ["auto", "cached", "calculated"])]
+    pub cost_mode: String,
+
+    /// Fields that identify a duplicate usage line
+    #[arg(long, default_value = "message+request", value_parser = ["message+request", "message", "content-hash"])]
+    pub dedup_key: String,
+
+    /// Only read JSONL files whose path under the data directory (or a
+    /// parent directory) matches GLOB (repeatable)
+    #[arg(long, value_name = "GLOB", allow_hyphen_values = true)]
+    pub include: Vec<String>,
+
+    /// Skip JSONL files whose path under the data directory (or a parent
+    /// directory) matches GLOB (repeatable)
+    #[arg(long, value_name = "GLOB", allow_hyphen_values = true)]
+    pub exclude: Vec<String>,
+
+    /// Drop usage lines with any single token count above this (corrupted
+    /// data) instead of adding them to the totals
+    #[arg(long, default_value_t = 100_000_000)]
+    pub max_entry_tokens: u64,
+
+    /// Print which data directory would be used (and all candidates), then exit
+    #[arg(long)]
+    pub print_data_path: bool,
+
+    /// Print version, data path, plan and pricing details as JSON, then exit
+    #[arg(long)]
+    pub version_json: bool,
+
+    /// JSON file of per-model rates (USD per million tokens) overriding the
+    /// built-in pricing
+    #[arg(long)]
+    pub pricing_file: Option<PathBuf>,
+
+    /// Print the per-model pricing table (including `--pricing-file`
+    /// overrides), then exit
+    #[arg(long)]
+    pub show_pricing: bool,
+
+    /// Load and analyse the usage data, print a diagnostic report, then exit
+    /// (non-zero when no usable entries were found)
+    #[arg(long)]
+    pub validate: bool,
+
+    /// Record which file each entry came from, so `--validate` can attribute
+    /// entries and duplicates to files (uses more memory)
+    #[arg(long)]
+    pub track_sources: bool,
+
+    /// Parse every JSONL file again instead of reusing the results cached
+    /// for unchanged files
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Print each new usage entry as a line of text as it is written (like `tail -f`)
+    #[arg(long)]
+    pub follow: bool,
 }
 
 // ── LastUsedParams ─────────────────────────────────────────────────────────────
 
-/// Persisted last-used parameters saved to `~/.claude-monitor/last_used.json`.
+/// Persisted last-used parameters saved to `last_used.json` in the
+/// [config directory](crate::paths::config_dir).
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct LastUsedParams {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -77,6 +329,16 @@ pub struct LastUsedParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_precision: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_rate: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reset_hour: Option<u8>,
@@ -84,24 +346,38 @@ pub struct LastUsedParams {
     pub view: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_limit_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_cost_limit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_message_limit: Option<u32>,
 }
 
 impl LastUsedParams {
+    /// File name of the persisted params inside the config directory.
+    pub const FILE_NAME: &'static str = "last_used.json";
+
     /// Return the default path to the persisted config file.
-    /// Uses `~/.claude-monitor/last_used.json`.
-    pub fn config_path() -> PathBuf {
-        Self::config_path_in(&dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")))
+    ///
+    /// Uses `$XDG_CONFIG_HOME/claude-monitor/last_used.json`. A file left in
+    /// the legacy `~/.claude-monitor/` is moved there first; if that fails
+    /// the legacy path is returned. `None` when there is neither a home
+    /// directory nor `$XDG_CONFIG_HOME`.
+    pub fn config_path() -> Option<PathBuf> {
+        crate::paths::migrated_file(crate::paths::config_dir(), Self::FILE_NAME)
     }
 
-    /// Return the config path rooted at `base_dir` (used for testing).
-    pub fn config_path_in(base_dir: &std::path::Path) -> PathBuf {
-        base_dir.join(".claude-monitor").join("last_used.json")
+    /// Return the config path inside `config_dir` (used for testing).
+    pub fn config_path_in(config_dir: &std::path::Path) -> PathBuf {
+        config_dir.join(Self::FILE_NAME)
     }
 
     /// Load persisted params from the default path.
-    /// Returns `Default` when the file is absent or cannot be parsed.
+    /// Returns `Default` when the file is absent or cannot be parsed, or the
+    /// config directory is unknown.
     pub fn load() -> Self {
-        Self::load_from(&Self::config_path())
+        Self::config_path()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default()
     }
 
     /// Load persisted params from an explicit path.
@@ -115,7 +391,7 @@ impl LastUsedParams {
     /// Atomically write params to the default path, creating parent directories
     /// if needed.
     pub fn save(&self) -> Result<(), std::io::Error> {
-        self.save_to(&Self::config_path())
+        self.save_to(&Self::config_path().ok_or_else(no_config_dir)?)
     }
 
     /// Atomically write params to an explicit path.
@@ -136,7 +412,7 @@ impl LastUsedParams {
 
     /// Delete the default config file if it exists.
     pub fn clear() -> Result<(), std::io::Error> {
-        Self::clear_at(&Self::config_path())
+        Self::clear_at(&Self::config_path().ok_or_else(no_config_dir)?)
     }
 
     /// Delete the config file at an explicit path if it exists.
@@ -153,17 +429,23 @@ impl LastUsedParams {
 impl Settings {
     /// Parse CLI arguments, merge with last-used params where no explicit CLI
     /// value was provided, resolve `"auto"` values, and persist the result.
+    ///
+    /// Without a config directory (see [`LastUsedParams::config_path`]) the
+    /// CLI arguments are used as they are and nothing is persisted.
     pub fn load_with_last_used() -> Self {
-        Self::load_with_last_used_impl(
-            std::env::args_os().collect(),
-            &LastUsedParams::config_path(),
-        )
+        Self::load_with_last_used_from_args(std::env::args_os().collect())
     }
 
     /// Same as [`load_with_last_used`] but accepts an explicit argument list,
     /// enabling unit-testing without spawning subprocesses.
     pub fn load_with_last_used_from_args(args: Vec<std::ffi::OsString>) -> Self {
-        Self::load_with_last_used_impl(args, &LastUsedParams::config_path())
+        match LastUsedParams::config_path() {
+            Some(path) => Self::load_with_last_used_impl(args, &path),
+            None => {
+                let matches = Settings::command().get_matches_from(args.clone());
+                Self::resolve_auto_values(Settings::parse_from(args), &matches)
+            }
+        }
     }
 
     /// Full implementation – accepts args and an explicit config path so that
@@ -205,6 +487,27 @@ impl Settings {
                 settings.time_format = v;
             }
         }
+        if !is_arg_explicitly_set(&matches, "number_format") {
+            if let Some(v) = last.number_format {
+                settings.number_format = v;
+            }
+        }
+        if !is_arg_explicitly_set(&matches, "model_labels") {
+            if let Some(v) = last.model_labels {
+                settings.model_labels = v;
+            }
+        }
+        if !is_arg_explicitly_set(&matches, "cost_precision") {
+            if let Some(v) = last.cost_precision {
+                settings.cost_precision = v;
+            }
+        }
+        if !is_arg_explicitly_set(&matches, "title") && settings.title.is_none() {
+            settings.title = last.title;
+        }
+        if !is_arg_explicitly_set(&matches, "decoration") && settings.decoration.is_none() {
+            settings.decoration = last.decoration;
+        }
         if !is_arg_explicitly_set(&matches, "theme") {
             if let Some(v) = last.theme {
                 settings.theme = v;
@@ -223,6 +526,16 @@ impl Settings {
         {
             settings.custom_limit_tokens = last.custom_limit_tokens;
         }
+        if !is_arg_explicitly_set(&matches, "custom_cost_limit")
+            && settings.custom_cost_limit.is_none()
+        {
+            settings.custom_cost_limit = last.custom_cost_limit;
+        }
+        if !is_arg_explicitly_set(&matches, "custom_message_limit")
+            && settings.custom_message_limit.is_none()
+        {
+            settings.custom_message_limit = last.custom_message_limit;
+        }
 
         settings = Self::resolve_auto_values(settings, &matches);
 
@@ -250,6 +563,17 @@ impl Settings {
             };
         }
 
+        // Resolve "auto" number_format → locale environment.
+        if settings.number_format == "auto" {
+            let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+                .iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find(|v| !v.is_empty())
+                .unwrap_or_default();
+            settings.number_format =
+                crate::formatting::number_format_for_locale(&locale).to_string();
+        }
+
         // --debug overrides log level.
         if settings.debug {
             settings.log_level = "DEBUG".to_string();
@@ -257,6 +581,75 @@ impl Settings {
 
         settings
     }
+
+    /// Formatter selected by `--number-format`, `--cost-precision` and
+    /// `--compact-numbers`.
+    pub fn number_formatter(&self) -> NumberFormatter {
+        NumberFormatter::from_name(&self.number_format)
+            .with_cost_precision(self.cost_precision)
+            .with_compact(self.compact_numbers)
+    }
+
+    /// Notification cooldowns: the defaults with `--notification-cooldown`
+    /// rules applied.
+    pub fn notification_cooldowns(&self) -> NotificationCooldowns {
+        NotificationCooldowns::from_rules(&self.notification_cooldowns).unwrap_or_default()
+    }
+
+    /// Timezone times are shown in: UTC under `--utc`, else `--timezone`
+    /// (UTC when it does not name a known zone).
+    pub fn display_timezone(&self) -> chrono_tz::Tz {
+        if self.utc {
+            return chrono_tz::Tz::UTC;
+        }
+        self.timezone.parse().unwrap_or(chrono_tz::Tz::UTC)
+    }
+
+    /// Basis from `--tokens-basis`, or `default` when it is not given.
+    pub fn tokens_basis_or(&self, default: TokensBasis) -> TokensBasis {
+        self.tokens_basis
+            .as_deref()
+            .map_or(default, TokensBasis::from_name)
+    }
+
+    /// Per-file read timeout from `--read-timeout`; `None` when disabled.
+    pub fn read_timeout(&self) -> Option<std::time::Duration> {
+        (self.read_timeout > 0).then(|| std::time::Duration::from_secs(self.read_timeout))
+    }
+
+    /// `true` when usage records should be read from stdin instead of files.
+    pub fn reads_stdin(&self) -> bool {
+        self.stdin || self.data_path.as_deref() == Some("-")
+    }
+
+    /// Data path to hand to the loader: `"-"` for stdin, the `--data-path`
+    /// value when given, or `None` to fall back to discovery.
+    pub fn data_path_override(&self) -> Option<String> {
+        if self.reads_stdin() {
+            Some("-".to_string())
+        } else {
+            self.data_path.clone()
+        }
+    }
+
+    /// Collect the `--custom-*` overrides for resolving the Custom plan.
+    pub fn custom_plan_limits(&self) -> CustomPlanLimits {
+        CustomPlanLimits {
+            token_limit: self.custom_limit_tokens,
+            cost_limit: self.custom_cost_limit,
+            message_limit: self.custom_message_limit,
+        }
+    }
+
+    /// Per-model-family token limits from `--opus-limit`, `--sonnet-limit`
+    /// and `--haiku-limit`.
+    pub fn model_token_limits(&self) -> ModelTokenLimits {
+        ModelTokenLimits {
+            opus: self.opus_limit,
+            sonnet: self.sonnet_limit,
+            haiku: self.haiku_limit,
+        }
+    }
 }
 
 // ── Conversion ─────────────────────────────────────────────────────────────────
@@ -267,14 +660,48 @@ impl From<&Settings> for LastUsedParams {
             theme: Some(s.theme.clone()),
             timezone: Some(s.timezone.clone()),
             time_format: Some(s.time_format.clone()),
+            number_format: Some(s.number_format.clone()),
+            cost_precision: Some(s.cost_precision),
+            model_labels: (!s.model_labels.is_empty()).then(|| s.model_labels.clone()),
+            title: s.title.clone(),
+            decoration: s.decoration.clone(),
             refresh_rate: Some(s.refresh_rate),
             reset_hour: s.reset_hour,
             view: Some(s.view.clone()),
             custom_limit_tokens: s.custom_limit_tokens,
+            custom_cost_limit: s.custom_cost_limit,
+            custom_message_limit: s.custom_message_limit,
         }
     }
 }
 
+/// Error for saving or clearing last-used params without a config directory.
+fn no_config_dir() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "cannot determine the config directory: set $HOME or $XDG_CONFIG_HOME",
+    )
+}
+
+/// Clap value parser for `--notification-cooldown`.
+fn parse_notification_cooldown(spec: &str) -> Result<String, String> {
+    NotificationCooldowns::parse_rule(spec)
+        .map(|_| spec.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Clap value parser for USD amounts, accepting `,` as well as `.` as the
+/// decimal separator (see [`parse_cost`]).
+fn parse_cost_arg(text: &str) -> Result<f64, String> {
+    parse_cost(text).map_err(|e| e.to_string())
+}
+
+/// Clap value parser for USD thresholds: like [`parse_cost_arg`], but zero
+/// is allowed (see [`parse_cost_threshold`]).
+fn parse_cost_threshold_arg(text: &str) -> Result<f64, String> {
+    parse_cost_threshold(text).map_err(|e| e.to_string())
+}
+
 // ── Helper: check if an arg was explicitly set on the command line ─────────────
 
 /// Returns `true` when `name` was supplied explicitly on the command line
@@ -314,10 +741,17 @@ mod tests {
             theme: Some("dark".to_string()),
             timezone: Some("Europe/Berlin".to_string()),
             time_format: Some("24h".to_string()),
+            number_format: Some("space".to_string()),
+            cost_precision: Some(4),
+            model_labels: Some(vec!["opus-4-5=Opus 4.5".to_string()]),
+            title: Some("ACME USAGE".to_string()),
+            decoration: Some(String::new()),
             refresh_rate: Some(5),
             reset_hour: Some(9),
             view: Some("daily".to_string()),
             custom_limit_tokens: Some(50_000),
+            custom_cost_limit: Some(42.5),
+            custom_message_limit: Some(500),
         };
 
         let loaded = round_trip(&tmp, &params);
@@ -325,10 +759,20 @@ mod tests {
         assert_eq!(loaded.theme, Some("dark".to_string()));
         assert_eq!(loaded.timezone, Some("Europe/Berlin".to_string()));
         assert_eq!(loaded.time_format, Some("24h".to_string()));
+        assert_eq!(loaded.number_format, Some("space".to_string()));
+        assert_eq!(loaded.cost_precision, Some(4));
+        assert_eq!(
+            loaded.model_labels,
+            Some(vec!["opus-4-5=Opus 4.5".to_string()])
+        );
+        assert_eq!(loaded.title, Some("ACME USAGE".to_string()));
+        assert_eq!(loaded.decoration, Some(String::new()));
         assert_eq!(loaded.refresh_rate, Some(5));
         assert_eq!(loaded.reset_hour, Some(9));
         assert_eq!(loaded.view, Some("daily".to_string()));
         assert_eq!(loaded.custom_limit_tokens, Some(50_000));
+        assert_eq!(loaded.custom_cost_limit, Some(42.5));
+        assert_eq!(loaded.custom_message_limit, Some(500));
     }
 
     // ── test_last_used_params_clear ───────────────────────────────────────────
@@ -365,6 +809,8 @@ mod tests {
         assert!(loaded.reset_hour.is_none());
         assert!(loaded.view.is_none());
         assert!(loaded.custom_limit_tokens.is_none());
+        assert!(loaded.custom_cost_limit.is_none());
+        assert!(loaded.custom_message_limit.is_none());
     }
 
     // ── test_settings_default_values ─────────────────────────────────────────
@@ -379,7 +825,11 @@ mod tests {
         assert_eq!(settings.timezone, "auto");
         assert_eq!(settings.time_format, "auto");
         assert_eq!(settings.theme, "auto");
+        assert_eq!(settings.number_format, "comma");
+        assert_eq!(settings.cost_precision, 2);
         assert!(settings.custom_limit_tokens.is_none());
+        assert!(settings.custom_cost_limit.is_none());
+        assert!(settings.custom_message_limit.is_none());
         assert_eq!(settings.refresh_rate, 10);
         assert!((settings.refresh_per_second - 0.75).abs() < f64::EPSILON);
         assert!(settings.reset_hour.is_none());
@@ -397,16 +847,70 @@ mod tests {
             plan: "pro".to_string(),
             view: "daily".to_string(),
             timezone: "America/New_York".to_string(),
+            utc: false,
             time_format: "12h".to_string(),
+            active_block: "most-recent".to_string(),
+            layout: "full".to_string(),
+            inline: false,
+            bell_on_limit: false,
+            title: None,
+            decoration: None,
             theme: "dark".to_string(),
+            number_format: "dot".to_string(),
+            cost_precision: 3,
+            model_labels: vec!["opus-4=Opus 4".to_string()],
+            max_models: 4,
+            staleness_minutes: 60,
+            prediction_warmup_minutes: 5,
+            model_window_minutes: None,
+            notification_cooldowns: Vec::new(),
+            compact_numbers: false,
             custom_limit_tokens: Some(100_000),
+            limit_detect: "p90".to_string(),
+            custom_cost_limit: Some(30.0),
+            custom_message_limit: Some(400),
+            opus_limit: None,
+            sonnet_limit: None,
+            haiku_limit: None,
+            message_limit: None,
+            read_timeout: 10,
             refresh_rate: 30,
+            analyze_interval: 30,
             refresh_per_second: 1.0,
             reset_hour: Some(6),
+            fixed_blocks: false,
             log_level: "INFO".to_string(),
+            log_format: "text".to_string(),
             log_file: None,
             debug: false,
             clear: false,
+            data_path: None,
+            stdin: false,
+            count_empty_messages: false,
+            include_synthetic: false,
+            output: "table".to_string(),
+            min_tokens: None,
+            min_cost: None,
+            tokens_basis: None,
+            daily_budget: None,
+            dedup_key: "message+request".to_string(),
+            cost_mode: "auto".to_string(),
+            table_columns: "all".to_string(),
+            heatmap: false,
+            ascii: false,
+            export_level: "full".to_string(),
+            anonymize_models: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_entry_tokens: 100_000_000,
+            print_data_path: false,
+            version_json: false,
+            pricing_file: None,
+            show_pricing: false,
+            validate: false,
+            track_sources: false,
+            no_cache: false,
+            follow: false,
         };
 
         let last = LastUsedParams::from(&settings);
@@ -415,9 +919,14 @@ mod tests {
         assert_eq!(last.timezone, Some("America/New_York".to_string()));
         assert_eq!(last.time_format, Some("12h".to_string()));
         assert_eq!(last.theme, Some("dark".to_string()));
+        assert_eq!(last.number_format, Some("dot".to_string()));
+        assert_eq!(last.cost_precision, Some(3));
+        assert_eq!(last.model_labels, Some(vec!["opus-4=Opus 4".to_string()]));
         assert_eq!(last.refresh_rate, Some(30));
         assert_eq!(last.reset_hour, Some(6));
         assert_eq!(last.custom_limit_tokens, Some(100_000));
+        assert_eq!(last.custom_cost_limit, Some(30.0));
+        assert_eq!(last.custom_message_limit, Some(400));
         // 'plan' is NOT stored in LastUsedParams.
     }
 
@@ -441,6 +950,61 @@ mod tests {
         assert_eq!(settings.custom_limit_tokens, Some(75_000));
     }
 
+    #[test]
+    fn test_settings_cli_custom_cost_and_message_limits() {
+        let settings = Settings::parse_from([
+            "claude-monitor",
+            "--custom-cost-limit",
+            "12.5",
+            "--custom-message-limit",
+            "300",
+        ]);
+        assert_eq!(settings.custom_cost_limit, Some(12.5));
+        assert_eq!(settings.custom_message_limit, Some(300));
+
+        let limits = settings.custom_plan_limits();
+        assert!(limits.token_limit.is_none());
+        assert_eq!(limits.cost_limit, Some(12.5));
+        assert_eq!(limits.message_limit, Some(300));
+    }
+
+    #[test]
+    fn test_settings_cli_stdin_flag() {
+        let settings = Settings::parse_from(["claude-monitor", "--stdin"]);
+        assert!(settings.reads_stdin());
+        assert_eq!(settings.data_path_override(), Some("-".to_string()));
+    }
+
+    #[test]
+    fn test_settings_cli_data_path_dash_means_stdin() {
+        let settings = Settings::parse_from(["claude-monitor", "--data-path", "-"]);
+        assert!(settings.reads_stdin());
+
+        let settings = Settings::parse_from(["claude-monitor", "--data-path", "/tmp/projects"]);
+        assert!(!settings.reads_stdin());
+        assert_eq!(
+            settings.data_path_override(),
+            Some("/tmp/projects".to_string())
+        );
+
+        let settings = Settings::parse_from(["claude-monitor"]);
+        assert!(settings.data_path_override().is_none());
+        assert_eq!(settings.output, "table");
+    }
+
+    #[test]
+    fn test_settings_cli_count_empty_messages() {
+        assert!(!Settings::parse_from(["claude-monitor"]).count_empty_messages);
+        let settings = Settings::parse_from(["claude-monitor", "--count-empty-messages"]);
+        assert!(settings.count_empty_messages);
+    }
+
+    #[test]
+    fn test_settings_cli_follow() {
+        assert!(!Settings::parse_from(["claude-monitor"]).follow);
+        assert!(Settings::parse_from(["claude-monitor", "--follow"]).follow);
+    }
+
     #[test]
     fn test_settings_cli_log_file() {
         let settings = Settings::parse_from(["claude-monitor", "--log-file", "/tmp/monitor.log"]);
@@ -470,6 +1034,243 @@ mod tests {
         assert_eq!(settings.theme, "dark");
     }
 
+    #[test]
+    fn test_load_with_last_used_merges_persisted_number_format() {
+        let tmp = TempDir::new().expect("tempdir");
+        let config_path = tmp_config_path(&tmp);
+
+        let params = LastUsedParams {
+            timezone: Some("UTC".to_string()),
+            time_format: Some("24h".to_string()),
+            number_format: Some("dot".to_string()),
+            ..Default::default()
+        };
+        params.save_to(&config_path).expect("save");
+
+        let settings =
+            Settings::load_with_last_used_impl(vec!["claude-monitor".into()], &config_path);
+        assert_eq!(settings.number_format, "dot");
+        assert_eq!(settings.number_formatter().format_count(1_000), "1.000");
+
+        // An explicit flag beats the persisted value.
+        let settings = Settings::load_with_last_used_impl(
+            vec![
+                "claude-monitor".into(),
+                "--number-format".into(),
+                "comma".into(),
+            ],
+            &config_path,
+        );
+        assert_eq!(settings.number_format, "comma");
+    }
+
+    #[test]
+    fn test_load_with_last_used_merges_persisted_branding() {
+        let tmp = TempDir::new().expect("tempdir");
+        let config_path = tmp_config_path(&tmp);
+
+        let settings = Settings::load_with_last_used_impl(
+            vec![
+                "claude-monitor".into(),
+                "--timezone".into(),
+                "UTC".into(),
+                "--title".into(),
+                "ACME USAGE".into(),
+                "--decoration".into(),
+                "".into(),
+            ],
+            &config_path,
+        );
+        assert_eq!(settings.title.as_deref(), Some("ACME USAGE"));
+
+        // Later runs keep the branding without repeating the flags.
+        let settings =
+            Settings::load_with_last_used_impl(vec!["claude-monitor".into()], &config_path);
+        assert_eq!(settings.title.as_deref(), Some("ACME USAGE"));
+        assert_eq!(settings.decoration.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_settings_cli_max_models() {
+        assert_eq!(Settings::parse_from(["claude-monitor"]).max_models, 4);
+        let settings = Settings::parse_from(["claude-monitor", "--max-models", "2"]);
+        assert_eq!(settings.max_models, 2);
+        assert!(Settings::try_parse_from(["claude-monitor", "--max-models", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_settings_cli_anonymize_models() {
+        assert_eq!(
+            Settings::parse_from(["claude-monitor"]).anonymize_models,
+            None
+        );
+        let settings = Settings::parse_from(["claude-monitor", "--anonymize-models"]);
+        assert_eq!(settings.anonymize_models.as_deref(), Some("family"));
+        let settings = Settings::parse_from(["claude-monitor", "--anonymize-models=total"]);
+        assert_eq!(settings.anonymize_models.as_deref(), Some("total"));
+    }
+
+    #[test]
+    fn test_settings_cli_staleness_minutes() {
+        assert_eq!(
+            Settings::parse_from(["claude-monitor"]).staleness_minutes,
+            60
+        );
+        let settings = Settings::parse_from(["claude-monitor", "--staleness-minutes", "0"]);
+        assert_eq!(settings.staleness_minutes, 0);
+    }
+
+    #[test]
+    fn test_settings_cli_compact_numbers() {
+        assert!(
+            !Settings::parse_from(["claude-monitor"])
+                .number_formatter()
+                .compact
+        );
+        let settings = Settings::parse_from(["claude-monitor", "--compact-numbers"]);
+        assert_eq!(settings.number_formatter().format_tokens(1_234_567), "1.2M");
+    }
+
+    #[test]
+    fn test_settings_cli_read_timeout() {
+        let settings = Settings::parse_from(["claude-monitor"]);
+        assert_eq!(
+            settings.read_timeout(),
+            Some(std::time::Duration::from_secs(10))
+        );
+        let settings = Settings::parse_from(["claude-monitor", "--read-timeout", "0"]);
+        assert_eq!(settings.read_timeout(), None);
+    }
+
+    #[test]
+    fn test_settings_cli_inline() {
+        assert!(!Settings::parse_from(["claude-monitor"]).inline);
+        assert!(Settings::parse_from(["claude-monitor", "--inline"]).inline);
+    }
+
+    #[test]
+    fn test_settings_cli_bell_on_limit() {
+        assert!(!Settings::parse_from(["claude-monitor"]).bell_on_limit);
+        assert!(Settings::parse_from(["claude-monitor", "--bell-on-limit"]).bell_on_limit);
+    }
+
+    #[test]
+    fn test_settings_cli_notification_cooldowns() {
+        let settings = Settings::parse_from([
+            "claude-monitor",
+            "--notification-cooldown",
+            "exceed_max_limit=2",
+        ]);
+        let cooldowns = settings.notification_cooldowns();
+        assert_eq!(cooldowns.hours("exceed_max_limit"), 2.0);
+        assert_eq!(cooldowns.hours("tokens_will_run_out"), 0.25);
+        assert!(
+            Settings::try_parse_from(["claude-monitor", "--notification-cooldown", "typo=1"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_settings_cli_log_format() {
+        assert_eq!(Settings::parse_from(["claude-monitor"]).log_format, "text");
+        let settings = Settings::parse_from(["claude-monitor", "--log-format", "json"]);
+        assert_eq!(settings.log_format, "json");
+        assert!(Settings::try_parse_from(["claude-monitor", "--log-format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_settings_cli_table_columns() {
+        assert_eq!(
+            Settings::parse_from(["claude-monitor"]).table_columns,
+            "all"
+        );
+        let settings =
+            Settings::parse_from(["claude-monitor", "--table-columns", "period,total,cost"]);
+        assert_eq!(settings.table_columns, "period,total,cost");
+        assert!(Settings::try_parse_from(["claude-monitor", "--table-columns", "compact"]).is_ok());
+    }
+
+    #[test]
+    fn test_settings_cost_values_accept_decimal_comma() {
+        let settings = Settings::parse_from([
+            "claude-monitor",
+            "--daily-budget",
+            "0,92",
+            "--min-cost",
+            "0.92",
+            "--custom-cost-limit",
+            "12,5",
+        ]);
+        assert_eq!(settings.daily_budget, settings.min_cost);
+        assert_eq!(settings.custom_cost_limit, Some(12.5));
+        assert!(Settings::try_parse_from(["claude-monitor", "--daily-budget", "-5"]).is_err());
+        assert!(Settings::try_parse_from(["claude-monitor", "--min-cost", "1,2,3"]).is_err());
+        let settings = Settings::parse_from(["claude-monitor", "--min-cost", "0"]);
+        assert_eq!(settings.min_cost, Some(0.0));
+        assert!(Settings::try_parse_from(["claude-monitor", "--daily-budget", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_settings_cli_include_exclude() {
+        let settings = Settings::parse_from([
+            "claude-monitor",
+            "--include",
+            "-home-me-*",
+            "--include",
+            "-work-*",
+            "--exclude",
+            "**/archive/**",
+        ]);
+        assert_eq!(settings.include, vec!["-home-me-*", "-work-*"]);
+        assert_eq!(settings.exclude, vec!["**/archive/**"]);
+        assert!(Settings::parse_from(["claude-monitor"]).include.is_empty());
+    }
+
+    #[test]
+    fn test_settings_cli_active_block() {
+        assert_eq!(
+            Settings::parse_from(["claude-monitor"]).active_block,
+            "most-recent"
+        );
+        let settings = Settings::parse_from(["claude-monitor", "--active-block", "earliest"]);
+        assert_eq!(settings.active_block, "earliest");
+        assert!(Settings::try_parse_from(["claude-monitor", "--active-block", "newest"]).is_err());
+    }
+
+    #[test]
+    fn test_settings_cli_layout() {
+        assert_eq!(Settings::parse_from(["claude-monitor"]).layout, "full");
+        let settings = Settings::parse_from(["claude-monitor", "--layout", "compact"]);
+        assert_eq!(settings.layout, "compact");
+        assert!(Settings::try_parse_from(["claude-monitor", "--layout", "tiny"]).is_err());
+    }
+
+    #[test]
+    fn test_settings_cli_model_labels() {
+        let settings = Settings::parse_from([
+            "claude-monitor",
+            "--model-label",
+            "opus-4-5=Opus 4.5",
+            "--model-label",
+            "3-opus=Opus 3",
+        ]);
+        assert_eq!(
+            settings.model_labels,
+            vec!["opus-4-5=Opus 4.5".to_string(), "3-opus=Opus 3".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_settings_cli_cost_precision() {
+        let settings = Settings::parse_from(["claude-monitor", "--cost-precision", "4"]);
+        assert_eq!(settings.cost_precision, 4);
+        assert_eq!(
+            settings.number_formatter().format_currency(0.0012),
+            "$0.0012"
+        );
+        assert!(Settings::try_parse_from(["claude-monitor", "--cost-precision", "12"]).is_err());
+    }
+
     #[test]
     fn test_load_with_last_used_cli_overrides_persisted() {
         let tmp = TempDir::new().expect("tempdir");
@@ -537,6 +1338,33 @@ mod tests {
         assert_eq!(settings.plan, "pro");
     }
 
+    #[test]
+    fn test_load_with_last_used_merges_persisted_custom_limits() {
+        let tmp = TempDir::new().expect("tempdir");
+        let config_path = tmp_config_path(&tmp);
+
+        let params = LastUsedParams {
+            timezone: Some("UTC".to_string()),
+            time_format: Some("24h".to_string()),
+            custom_cost_limit: Some(99.0),
+            custom_message_limit: Some(750),
+            ..Default::default()
+        };
+        params.save_to(&config_path).expect("save");
+
+        // Explicit --custom-message-limit wins; cost comes from last-used.
+        let settings = Settings::load_with_last_used_impl(
+            vec![
+                "claude-monitor".into(),
+                "--custom-message-limit".into(),
+                "100".into(),
+            ],
+            &config_path,
+        );
+        assert_eq!(settings.custom_cost_limit, Some(99.0));
+        assert_eq!(settings.custom_message_limit, Some(100));
+    }
+
     #[test]
     fn test_load_with_last_used_persists_after_run() {
         let tmp = TempDir::new().expect("tempdir");
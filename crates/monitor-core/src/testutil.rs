@@ -0,0 +1,36 @@
+//! Session block fixtures for tests.
+//!
+//! Compiled for this crate's own tests and, behind the `testutil` feature,
+//! for other crates' tests.
+
+use chrono::{Duration, Utc};
+
+use crate::models::{SessionBlock, TokenCounts};
+
+/// A finished five-hour block that ended seven hours ago, holding `tokens`
+/// input tokens and nothing else.
+pub fn completed_block(tokens: u64) -> SessionBlock {
+    let start = Utc::now() - Duration::hours(12);
+    SessionBlock {
+        id: String::new(),
+        label: String::new(),
+        start_time: start,
+        end_time: start + Duration::hours(5),
+        entries: vec![],
+        token_counts: TokenCounts {
+            input_tokens: tokens,
+            ..Default::default()
+        },
+        is_active: false,
+        is_gap: false,
+        burn_rate: None,
+        actual_end_time: None,
+        per_model_stats: Default::default(),
+        models: vec![],
+        sent_messages_count: 0,
+        cost_usd: 0.0,
+        limit_messages: vec![],
+        projection_data: None,
+        burn_rate_snapshot: None,
+    }
+}
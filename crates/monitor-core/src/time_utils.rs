@@ -1,15 +1,101 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use chrono_tz::Tz;
 use tracing::warn;
 
 // ── System timezone detection ─────────────────────────────────────────────────
 
+/// Where Linux and macOS keep the system timezone as a zoneinfo symlink.
+const LOCALTIME_PATH: &str = "/etc/localtime";
+
 /// Detect the IANA timezone name of the running system.
 ///
-/// Uses the `iana-time-zone` crate directly – no subprocess calls.
-/// Falls back to `"UTC"` if detection fails.
+/// Tries, in order, and uses the first valid IANA name:
+/// 1. the `TZ` environment variable (a name or a zoneinfo path);
+/// 2. the target of the `/etc/localtime` symlink
+///    (`…/zoneinfo/Europe/Berlin` → `Europe/Berlin`);
+/// 3. `timedatectl show --property=Timezone --value`;
+/// 4. the `iana-time-zone` crate.
+///
+/// Falls back to `"UTC"` if all of them fail.
 pub fn get_system_timezone() -> String {
-    iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string())
+    std::env::var("TZ")
+        .ok()
+        .and_then(|tz| zone_from_tz_var(&tz))
+        .or_else(|| zone_from_localtime(std::path::Path::new(LOCALTIME_PATH)))
+        .or_else(zone_from_timedatectl)
+        .or_else(|| iana_time_zone::get_timezone().ok().and_then(valid_zone))
+        .unwrap_or_else(|| "UTC".to_string())
+}
+
+/// IANA name from a `TZ` value: a zone name (`Europe/Berlin`) or a path into
+/// a zoneinfo directory, either optionally prefixed with `:`.
+fn zone_from_tz_var(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value.strip_prefix(':').unwrap_or(value);
+    if value.starts_with('/') {
+        zone_from_zoneinfo_path(std::path::Path::new(value))
+    } else {
+        valid_zone(value.to_string())
+    }
+}
+
+/// IANA name from the target of the `localtime` symlink at `link`.
+fn zone_from_localtime(link: &std::path::Path) -> Option<String> {
+    let target = std::fs::read_link(link).ok()?;
+    zone_from_zoneinfo_path(&target)
+}
+
+/// IANA name from a path inside a zoneinfo directory: everything after the
+/// last `zoneinfo` component, minus a `posix/` or `right/` variant prefix.
+fn zone_from_zoneinfo_path(path: &std::path::Path) -> Option<String> {
+    let components: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let start = components.iter().rposition(|c| c == "zoneinfo")? + 1;
+    let mut name = &components[start..];
+    if name
+        .first()
+        .is_some_and(|first| first == "posix" || first == "right")
+    {
+        name = &name[1..];
+    }
+    valid_zone(name.join("/"))
+}
+
+/// IANA name reported by `timedatectl`, if it is installed and succeeds.
+fn zone_from_timedatectl() -> Option<String> {
+    let output = std::process::Command::new("timedatectl")
+        .args(["show", "--property=Timezone", "--value"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    parse_timedatectl(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Zone name from `timedatectl` output: a bare `--value` line, a
+/// `Timezone=…` property line, or the `Time zone: … (…)` status line. Any
+/// other line, such as `Universal time: …`, is skipped.
+fn parse_timedatectl(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let line = line.trim();
+        let name = if let Some(value) = line.strip_prefix("Timezone=") {
+            value.trim()
+        } else if let Some(value) = line.strip_prefix("Time zone:") {
+            value.split_whitespace().next()?
+        } else if !line.contains(char::is_whitespace) {
+            line
+        } else {
+            return None;
+        };
+        valid_zone(name.to_string())
+    })
+}
+
+/// `name` when it is a timezone chrono-tz knows.
+fn valid_zone(name: String) -> Option<String> {
+    name.parse::<Tz>().is_ok().then_some(name)
 }
 
 // ── TimezoneHandler ───────────────────────────────────────────────────────────
@@ -228,19 +314,45 @@ fn country_from_timezone(tz: &str) -> Option<String> {
     None
 }
 
+// ── Daily reset boundary ──────────────────────────────────────────────────────
+
+/// The most recent `reset_hour:00` in `tz` at or before `now`.
+///
+/// When DST skips the reset hour that day, the first valid local time after
+/// it is used. Returns `None` when `reset_hour` is not in `0..=23`.
+pub fn last_reset_boundary(now: DateTime<Utc>, reset_hour: u8, tz: Tz) -> Option<DateTime<Utc>> {
+    let today = now.with_timezone(&tz).date_naive();
+    for date in [today, today.pred_opt()?] {
+        let local = date.and_hms_opt(u32::from(reset_hour), 0, 0)?;
+        let resolved = tz.from_local_datetime(&local).earliest().or_else(|| {
+            tz.from_local_datetime(&(local + chrono::Duration::hours(1)))
+                .earliest()
+        })?;
+        let boundary = resolved.with_timezone(&Utc);
+        if boundary <= now {
+            return Some(boundary);
+        }
+    }
+    None
+}
+
 // ── format_display_time ───────────────────────────────────────────────────────
 
-/// Format a UTC [`DateTime`] as a displayable time string.
+/// Format a [`DateTime`] in its own timezone as a displayable time string.
 ///
 /// * `use_12h = Some(true)`  → 12-hour format (e.g. `"02:30 PM"`).
 /// * `use_12h = Some(false)` → 24-hour format (e.g. `"14:30"`).
 /// * `use_12h = None`        → auto-detect using [`detect_time_format`].
 /// * `include_seconds = true` adds `":SS"` to the output.
-pub fn format_display_time(
-    dt: &DateTime<Utc>,
+pub fn format_display_time<Z>(
+    dt: &DateTime<Z>,
     use_12h: Option<bool>,
     include_seconds: bool,
-) -> String {
+) -> String
+where
+    Z: TimeZone,
+    Z::Offset: std::fmt::Display,
+{
     let twelve_hour = use_12h.unwrap_or_else(|| detect_time_format(None, None));
 
     if twelve_hour {
@@ -261,7 +373,7 @@ pub fn format_display_time(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone as _;
+    use chrono::Timelike;
 
     // ── TimezoneHandler::validate_timezone ───────────────────────────────────
 
@@ -430,6 +542,35 @@ mod tests {
         assert!(formatted.contains("09:05:03") || formatted.contains("9:05:03"));
     }
 
+    // ── last_reset_boundary ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_last_reset_boundary_today_or_yesterday() {
+        let after = Utc.with_ymd_and_hms(2024, 1, 15, 14, 0, 0).unwrap();
+        assert_eq!(
+            last_reset_boundary(after, 6, Tz::UTC),
+            Some(Utc.with_ymd_and_hms(2024, 1, 15, 6, 0, 0).unwrap())
+        );
+
+        let before = Utc.with_ymd_and_hms(2024, 1, 15, 3, 0, 0).unwrap();
+        assert_eq!(
+            last_reset_boundary(before, 6, Tz::UTC),
+            Some(Utc.with_ymd_and_hms(2024, 1, 14, 6, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_last_reset_boundary_uses_timezone() {
+        // 14:00 UTC is 09:00 in New York (EST, UTC-5): today's 06:00 local
+        // reset was at 11:00 UTC.
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 14, 0, 0).unwrap();
+        assert_eq!(
+            last_reset_boundary(now, 6, Tz::America__New_York),
+            Some(Utc.with_ymd_and_hms(2024, 1, 15, 11, 0, 0).unwrap())
+        );
+        assert_eq!(last_reset_boundary(now, 24, Tz::UTC), None);
+    }
+
     // ── get_system_timezone ──────────────────────────────────────────────────
 
     #[test]
@@ -437,8 +578,79 @@ mod tests {
         let tz = get_system_timezone();
         assert!(!tz.is_empty(), "system timezone should not be empty");
     }
-}
 
-// Re-export chrono items used in tests.
-#[allow(unused_imports)]
-use chrono::{Datelike, Timelike};
+    #[test]
+    fn test_zone_from_zoneinfo_path() {
+        let zone = |p: &str| zone_from_zoneinfo_path(std::path::Path::new(p));
+        assert_eq!(
+            zone("/usr/share/zoneinfo/Europe/Berlin").as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            zone("../usr/share/zoneinfo/America/Argentina/Buenos_Aires").as_deref(),
+            Some("America/Argentina/Buenos_Aires")
+        );
+        assert_eq!(
+            zone("/usr/share/zoneinfo/posix/Asia/Tokyo").as_deref(),
+            Some("Asia/Tokyo")
+        );
+        assert_eq!(
+            zone("/var/db/timezone/zoneinfo/UTC").as_deref(),
+            Some("UTC")
+        );
+        assert_eq!(zone("/usr/share/zoneinfo/Not/AZone"), None);
+        assert_eq!(zone("/etc/some-file"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_zone_from_localtime_symlink() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let zone_file = dir.path().join("zoneinfo").join("Europe").join("Berlin");
+        std::fs::create_dir_all(zone_file.parent().unwrap()).unwrap();
+        std::fs::write(&zone_file, b"TZif").unwrap();
+        let link = dir.path().join("localtime");
+        std::os::unix::fs::symlink(&zone_file, &link).unwrap();
+
+        assert_eq!(zone_from_localtime(&link).as_deref(), Some("Europe/Berlin"));
+        // A plain file (no symlink) gives no name.
+        assert_eq!(zone_from_localtime(&zone_file), None);
+    }
+
+    #[test]
+    fn test_zone_from_tz_var() {
+        assert_eq!(
+            zone_from_tz_var("Europe/Paris").as_deref(),
+            Some("Europe/Paris")
+        );
+        assert_eq!(
+            zone_from_tz_var(":/usr/share/zoneinfo/Europe/Paris").as_deref(),
+            Some("Europe/Paris")
+        );
+        assert_eq!(zone_from_tz_var("CET-1CEST"), None);
+        assert_eq!(zone_from_tz_var(""), None);
+    }
+
+    #[test]
+    fn test_parse_timedatectl() {
+        assert_eq!(
+            parse_timedatectl("Europe/Berlin\n").as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            parse_timedatectl("Timezone=Asia/Tokyo\n").as_deref(),
+            Some("Asia/Tokyo")
+        );
+        let status = "               Local time: Mon 2024-01-15 10:00:00 CET\n\
+                                Time zone: Europe/Berlin (CET, +0100)\n";
+        assert_eq!(parse_timedatectl(status).as_deref(), Some("Europe/Berlin"));
+        assert_eq!(parse_timedatectl("n/a"), None);
+
+        // "Universal" is a valid zone name but not the configured zone.
+        let status = "               Local time: Mon 2024-01-15 10:00:00 JST\n\
+                                Universal time: Mon 2024-01-15 01:00:00 UTC\n\
+                                Time zone: Asia/Tokyo (JST, +0900)\n";
+        assert_eq!(parse_timedatectl(status).as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(parse_timedatectl("Universal time: Mon 2024-01-15"), None);
+    }
+}
@@ -1,21 +1,37 @@
-//! Usage aggregation over daily and monthly time windows.
+//! Usage aggregation over daily and monthly time windows, and per project.
 //!
 //! Ports the Python `UsageAggregator` class from `data/aggregator.py`.
+//!
+//! Invariants of the returned [`AggregatedPeriod`]s:
+//!
+//! * Period keys are unique within one result.
+//! * Time-based aggregations are sorted chronologically by [`Period`];
+//!   [`UsageAggregator::aggregate_by_project`] is sorted by cost instead.
+//! * `stats` equals the merge of all `model_breakdowns`, and `models_used`
+//!   holds exactly the breakdown keys.
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 
-use monitor_core::models::{normalize_model_name, SessionBlock, UsageEntry};
+use monitor_core::models::{normalize_model_name, SessionBlock, TokensBasis, UsageEntry};
+
+use crate::period::{Period, PeriodKind};
 
 // ── AggregatedStats ───────────────────────────────────────────────────────────
 
 /// Token and cost totals accumulated across multiple usage entries.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct AggregatedStats {
+    /// Input (prompt) tokens.
     pub input_tokens: u64,
+    /// Output (completion) tokens.
     pub output_tokens: u64,
+    /// Tokens written to the prompt cache.
     pub cache_creation_tokens: u64,
+    /// Tokens read from the prompt cache.
     pub cache_read_tokens: u64,
+    /// Cost in USD.
     pub cost: f64,
+    /// Number of entries added.
     pub count: u32,
 }
 
@@ -32,27 +48,50 @@ impl AggregatedStats {
 
     /// Sum of all four token categories.
     pub fn total_tokens(&self) -> u64 {
-        self.input_tokens + self.output_tokens + self.cache_creation_tokens + self.cache_read_tokens
+        self.total_for(TokensBasis::All)
+    }
+
+    /// Total counted on `basis`.
+    pub fn total_for(&self, basis: TokensBasis) -> u64 {
+        basis.total(
+            self.input_tokens,
+            self.output_tokens,
+            self.cache_creation_tokens,
+            self.cache_read_tokens,
+        )
+    }
+
+    /// Add another set of totals into this one.
+    pub fn merge(&mut self, other: &AggregatedStats) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_creation_tokens += other.cache_creation_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cost += other.cost;
+        self.count += other.count;
     }
 }
 
 // ── AggregatedPeriod ──────────────────────────────────────────────────────────
 
-/// All usage data within one period (one day or one month).
-#[derive(Debug, Clone)]
+/// All usage data within one period (one day or one month) or one project.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AggregatedPeriod {
-    /// The period key, e.g. `"2024-01-15"` (daily) or `"2024-01"` (monthly).
+    /// The period key, e.g. `"2024-01-15"` (daily) or `"2024-01"` (monthly),
+    /// or the project name for [`UsageAggregator::aggregate_by_project`].
     pub period_key: String,
     /// Combined stats for the period.
     pub stats: AggregatedStats,
-    /// Canonical model names seen in this period.
+    /// Canonical model names seen in this period (unordered; `"unknown"` for
+    /// entries without a model).
     pub models_used: HashSet<String>,
-    /// Per-model breakdown.
+    /// Per-model breakdown keyed like `models_used`.
     pub model_breakdowns: HashMap<String, AggregatedStats>,
 }
 
 impl AggregatedPeriod {
-    fn new(period_key: impl Into<String>) -> Self {
+    /// Create an empty period with the given key.
+    pub fn new(period_key: impl Into<String>) -> Self {
         Self {
             period_key: period_key.into(),
             stats: AggregatedStats::default(),
@@ -61,8 +100,8 @@ impl AggregatedPeriod {
         }
     }
 
-    /// Accumulate `entry` into the period's aggregate.
-    fn add_entry(&mut self, entry: &UsageEntry) {
+    /// Accumulate `entry` into the period's aggregate and its model breakdown.
+    pub fn add_entry(&mut self, entry: &UsageEntry) {
         self.stats.add_entry(entry);
 
         let model = if entry.model.is_empty() {
@@ -81,68 +120,164 @@ impl AggregatedPeriod {
 
 // ── UsageAggregator ───────────────────────────────────────────────────────────
 
-/// Stateless helper that groups usage entries by time period.
+/// Stateless helper that groups usage entries by time period or project.
+#[derive(Debug, Clone, Copy, Default)]
 pub struct UsageAggregator;
 
 impl UsageAggregator {
+    /// Key used by [`aggregate_by_project`](Self::aggregate_by_project) for
+    /// entries without a project (e.g. read from stdin).
+    pub const UNKNOWN_PROJECT: &'static str = "(unknown)";
+
     /// Aggregate `entries` by calendar day.  Key format: `"%Y-%m-%d"`.
     ///
-    /// Returns periods sorted by key (ascending).
+    /// Returns periods sorted chronologically.
     pub fn aggregate_daily(entries: &[UsageEntry]) -> Vec<AggregatedPeriod> {
-        Self::aggregate_by_period(entries, |ts| ts.format("%Y-%m-%d").to_string())
+        Self::aggregate_by_kind(entries, PeriodKind::Daily)
     }
 
     /// Aggregate `entries` by calendar month.  Key format: `"%Y-%m"`.
     ///
-    /// Returns periods sorted by key (ascending).
+    /// Returns periods sorted chronologically.
     pub fn aggregate_monthly(entries: &[UsageEntry]) -> Vec<AggregatedPeriod> {
-        Self::aggregate_by_period(entries, |ts| ts.format("%Y-%m").to_string())
+        Self::aggregate_by_kind(entries, PeriodKind::Monthly)
+    }
+
+    /// Aggregate `entries` into `kind` periods keyed by
+    /// [`Period::to_key`], sorted chronologically.
+    pub fn aggregate_by_kind(entries: &[UsageEntry], kind: PeriodKind) -> Vec<AggregatedPeriod> {
+        let mut map: BTreeMap<Period, AggregatedPeriod> = BTreeMap::new();
+
+        for entry in entries {
+            let period = Period::of(kind, entry.timestamp);
+            map.entry(period)
+                .or_insert_with(|| AggregatedPeriod::new(period.to_key()))
+                .add_entry(entry);
+        }
+
+        map.into_values().collect()
     }
 
     /// Aggregate all entries from non-gap session blocks.
     ///
-    /// `view_type` must be `"daily"` or `"monthly"`; anything else falls back
-    /// to `"daily"`.
+    /// Each block is attributed to exactly one period, chosen by its
+    /// `start_time`: a block that starts at 23:30 and runs past midnight (or
+    /// past the end of a month) counts entirely towards the day/month it
+    /// started in. This keeps the monthly total equal to the sum of the daily
+    /// totals.
+    ///
+    /// `view_type` names a [`PeriodKind`] (`"daily"`, `"monthly"`, …);
+    /// anything unrecognised falls back to `"daily"`.
     pub fn aggregate_from_blocks(
         blocks: &[SessionBlock],
         view_type: &str,
     ) -> Vec<AggregatedPeriod> {
-        let all_entries: Vec<&UsageEntry> = blocks
-            .iter()
-            .filter(|b| !b.is_gap)
-            .flat_map(|b| b.entries.iter())
-            .collect();
+        let kind = PeriodKind::from_name(view_type);
+
+        // Use BTreeMap for chronologically sorted periods.
+        let mut map: BTreeMap<Period, AggregatedPeriod> = BTreeMap::new();
+
+        for block in blocks.iter().filter(|b| !b.is_gap) {
+            let key = Period::of(kind, block.start_time);
+            let period = map
+                .entry(key)
+                .or_insert_with(|| AggregatedPeriod::new(key.to_key()));
+            for entry in &block.entries {
+                period.add_entry(entry);
+            }
+        }
 
-        // Build owned Vec<UsageEntry> to satisfy the slice signature.
-        let owned: Vec<UsageEntry> = all_entries.into_iter().cloned().collect();
+        map.into_values().collect()
+    }
 
-        if view_type == "monthly" {
-            Self::aggregate_monthly(&owned)
-        } else {
-            Self::aggregate_daily(&owned)
+    /// Aggregate `entries` by the project directory they were read from.
+    ///
+    /// Entries without a project are grouped under
+    /// [`UNKNOWN_PROJECT`](Self::UNKNOWN_PROJECT). Returns one
+    /// [`AggregatedPeriod`] per project, keyed by project name, with the most
+    /// expensive project first (ties broken by name).
+    pub fn aggregate_by_project(entries: &[UsageEntry]) -> Vec<AggregatedPeriod> {
+        let mut map: BTreeMap<String, AggregatedPeriod> = BTreeMap::new();
+
+        for entry in entries {
+            let key = if entry.project.is_empty() {
+                Self::UNKNOWN_PROJECT.to_string()
+            } else {
+                entry.project.clone()
+            };
+            map.entry(key.clone())
+                .or_insert_with(|| AggregatedPeriod::new(key))
+                .add_entry(entry);
         }
+
+        let mut projects: Vec<AggregatedPeriod> = map.into_values().collect();
+        projects.sort_by(|a, b| {
+            b.stats
+                .cost
+                .partial_cmp(&a.stats.cost)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.period_key.cmp(&b.period_key))
+        });
+        projects
     }
 
     /// Sum up the stats from all periods into a single [`AggregatedStats`].
     pub fn calculate_totals(data: &[AggregatedPeriod]) -> AggregatedStats {
         let mut totals = AggregatedStats::default();
         for period in data {
-            totals.input_tokens += period.stats.input_tokens;
-            totals.output_tokens += period.stats.output_tokens;
-            totals.cache_creation_tokens += period.stats.cache_creation_tokens;
-            totals.cache_read_tokens += period.stats.cache_read_tokens;
-            totals.cost += period.stats.cost;
-            totals.count += period.stats.count;
+            totals.merge(&period.stats);
         }
         totals
     }
 
-    // ── Private ───────────────────────────────────────────────────────────────
+    /// Drop the periods below `min_tokens` total tokens or `min_cost` USD.
+    ///
+    /// A period is kept only when it meets every threshold that is set.
+    /// Returns the kept periods (in their original order) and the number
+    /// dropped.
+    pub fn filter_by_threshold(
+        periods: Vec<AggregatedPeriod>,
+        min_tokens: Option<u64>,
+        min_cost: Option<f64>,
+    ) -> (Vec<AggregatedPeriod>, usize) {
+        Self::filter_by_threshold_for(periods, min_tokens, min_cost, TokensBasis::All)
+    }
+
+    /// [`filter_by_threshold`](Self::filter_by_threshold) comparing
+    /// `min_tokens` with the total on `basis`.
+    pub fn filter_by_threshold_for(
+        periods: Vec<AggregatedPeriod>,
+        min_tokens: Option<u64>,
+        min_cost: Option<f64>,
+        basis: TokensBasis,
+    ) -> (Vec<AggregatedPeriod>, usize) {
+        let before = periods.len();
+        let kept: Vec<AggregatedPeriod> = periods
+            .into_iter()
+            .filter(|p| min_tokens.is_none_or(|min| p.stats.total_for(basis) >= min))
+            .filter(|p| min_cost.is_none_or(|min| p.stats.cost >= min))
+            .collect();
+        let dropped = before - kept.len();
+        (kept, dropped)
+    }
 
-    /// Generic aggregation driver.
+    /// Combine the per-model breakdowns of all periods into one map keyed by
+    /// canonical model name.
+    pub fn calculate_model_totals(data: &[AggregatedPeriod]) -> HashMap<String, AggregatedStats> {
+        let mut totals: HashMap<String, AggregatedStats> = HashMap::new();
+        for period in data {
+            for (model, stats) in &period.model_breakdowns {
+                totals.entry(model.clone()).or_default().merge(stats);
+            }
+        }
+        totals
+    }
+
+    /// Aggregate `entries` by an arbitrary timestamp-derived key.
     ///
-    /// `key_fn` maps a UTC timestamp to the string period key.
-    fn aggregate_by_period(
+    /// `key_fn` maps a UTC timestamp to the string period key. Returns periods
+    /// sorted by key (ascending), so keys should sort in the intended order.
+    pub fn aggregate_by_period(
         entries: &[UsageEntry],
         key_fn: impl Fn(chrono::DateTime<chrono::Utc>) -> String,
     ) -> Vec<AggregatedPeriod> {
@@ -180,6 +315,9 @@ mod tests {
             model: model.to_string(),
             message_id: ts_str.to_string(),
             request_id: ts_str.to_string(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
         }
     }
 
@@ -271,6 +409,7 @@ mod tests {
 
         let block = SessionBlock {
             id: "block1".to_string(),
+            label: String::new(),
             start_time: DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z")
                 .unwrap()
                 .with_timezone(&Utc),
@@ -292,8 +431,78 @@ mod tests {
             burn_rate_snapshot: None,
         };
 
+        // The whole block belongs to the day it started on.
         let periods = UsageAggregator::aggregate_from_blocks(&[block], "daily");
-        assert_eq!(periods.len(), 2);
+        assert_eq!(periods.len(), 1);
+        assert_eq!(periods[0].period_key, "2024-01-15");
+        assert_eq!(periods[0].stats.count, 2);
+        assert_eq!(periods[0].stats.total_tokens(), 450);
+    }
+
+    fn make_block(id: &str, start: &str, entries: Vec<UsageEntry>) -> SessionBlock {
+        let start_time = DateTime::parse_from_rfc3339(start)
+            .unwrap()
+            .with_timezone(&Utc);
+        SessionBlock {
+            id: id.to_string(),
+            label: String::new(),
+            start_time,
+            end_time: start_time + chrono::Duration::hours(5),
+            entries,
+            token_counts: Default::default(),
+            is_active: false,
+            is_gap: false,
+            burn_rate: None,
+            actual_end_time: None,
+            per_model_stats: HashMap::new(),
+            models: vec![],
+            sent_messages_count: 0,
+            cost_usd: 0.0,
+            limit_messages: vec![],
+            projection_data: None,
+            burn_rate_snapshot: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_from_blocks_month_boundary_counted_once() {
+        // Block starts 2024-01-31 23:30 and runs into February.
+        let straddling = make_block(
+            "block-straddle",
+            "2024-01-31T23:30:00Z",
+            vec![
+                make_entry("2024-01-31T23:45:00Z", 100, 50, 0.01, "claude-3-5-sonnet"),
+                make_entry("2024-02-01T01:00:00Z", 200, 100, 0.02, "claude-3-5-sonnet"),
+            ],
+        );
+        let february = make_block(
+            "block-feb",
+            "2024-02-01T10:00:00Z",
+            vec![make_entry(
+                "2024-02-01T10:30:00Z",
+                10,
+                5,
+                0.005,
+                "claude-3-opus",
+            )],
+        );
+        let blocks = vec![straddling, february];
+
+        let monthly = UsageAggregator::aggregate_from_blocks(&blocks, "monthly");
+        assert_eq!(monthly.len(), 2);
+        assert_eq!(monthly[0].period_key, "2024-01");
+        assert_eq!(monthly[0].stats.total_tokens(), 450);
+        assert_eq!(monthly[1].period_key, "2024-02");
+        assert_eq!(monthly[1].stats.total_tokens(), 15);
+
+        // Monthly total equals the sum of the daily totals.
+        let daily = UsageAggregator::aggregate_from_blocks(&blocks, "daily");
+        assert_eq!(daily[0].period_key, "2024-01-31");
+        let daily_total = UsageAggregator::calculate_totals(&daily);
+        let monthly_total = UsageAggregator::calculate_totals(&monthly);
+        assert_eq!(daily_total.total_tokens(), monthly_total.total_tokens());
+        assert_eq!(daily_total.count, monthly_total.count);
+        assert!((daily_total.cost - monthly_total.cost).abs() < 1e-12);
     }
 
     #[test]
@@ -303,6 +512,7 @@ mod tests {
 
         let gap_block = SessionBlock {
             id: "gap-1".to_string(),
+            label: String::new(),
             start_time: DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z")
                 .unwrap()
                 .with_timezone(&Utc),
@@ -328,6 +538,34 @@ mod tests {
         assert!(periods.is_empty());
     }
 
+    // ── aggregate_by_project ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_aggregate_by_project_most_expensive_first() {
+        let mut entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.5, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T09:00:00Z", 200, 50, 2.0, "claude-3-opus"),
+            make_entry("2024-01-16T10:00:00Z", 300, 50, 1.0, "claude-3-5-sonnet"),
+            make_entry("2024-01-16T11:00:00Z", 10, 5, 0.1, "claude-3-haiku"),
+        ];
+        entries[0].project = "web".to_string();
+        entries[1].project = "api".to_string();
+        entries[2].project = "web".to_string();
+
+        let projects = UsageAggregator::aggregate_by_project(&entries);
+        let keys: Vec<&str> = projects.iter().map(|p| p.period_key.as_str()).collect();
+
+        assert_eq!(keys, vec!["api", "web", UsageAggregator::UNKNOWN_PROJECT]);
+        assert!((projects[1].stats.cost - 1.5).abs() < 1e-9);
+        assert_eq!(projects[1].stats.input_tokens, 400);
+        assert_eq!(projects[1].stats.count, 2);
+    }
+
+    #[test]
+    fn test_aggregate_by_project_empty() {
+        assert!(UsageAggregator::aggregate_by_project(&[]).is_empty());
+    }
+
     // ── calculate_totals ──────────────────────────────────────────────────────
 
     #[test]
@@ -354,8 +592,137 @@ mod tests {
         assert_eq!(totals.cost, 0.0);
     }
 
+    // ── filter_by_threshold ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_filter_by_threshold_drops_small_periods() {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 10, 5, 0.001, "claude-3-5-sonnet"),
+            make_entry(
+                "2024-01-16T08:00:00Z",
+                2_000,
+                1_000,
+                0.02,
+                "claude-3-5-sonnet",
+            ),
+            make_entry(
+                "2024-01-17T08:00:00Z",
+                5_000,
+                1_000,
+                0.5,
+                "claude-3-5-sonnet",
+            ),
+        ];
+        let periods = UsageAggregator::aggregate_daily(&entries);
+
+        let (kept, dropped) =
+            UsageAggregator::filter_by_threshold(periods.clone(), Some(1_000), None);
+        assert_eq!(dropped, 1);
+        assert_eq!(kept[0].period_key, "2024-01-16");
+
+        let (kept, dropped) =
+            UsageAggregator::filter_by_threshold(periods.clone(), Some(1_000), Some(0.1));
+        assert_eq!(dropped, 2);
+        assert_eq!(kept[0].period_key, "2024-01-17");
+
+        let (kept, dropped) = UsageAggregator::filter_by_threshold(periods, None, None);
+        assert_eq!((kept.len(), dropped), (3, 0));
+    }
+
+    #[test]
+    fn test_filter_by_threshold_counts_tokens_on_basis() {
+        let mut entry = make_entry("2024-01-15T08:00:00Z", 100, 50, 0.01, "claude-3-5-sonnet");
+        entry.cache_read_tokens = 5_000;
+        let periods = UsageAggregator::aggregate_daily(&[entry]);
+
+        let (_, dropped) = UsageAggregator::filter_by_threshold(periods.clone(), Some(1_000), None);
+        assert_eq!(dropped, 0);
+        let (_, dropped) =
+            UsageAggregator::filter_by_threshold_for(periods, Some(1_000), None, TokensBasis::Io);
+        assert_eq!(dropped, 1);
+    }
+
+    // ── calculate_model_totals ────────────────────────────────────────────────
+
+    #[test]
+    fn test_calculate_model_totals_combines_periods() {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.01, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T09:00:00Z", 400, 100, 0.50, "claude-3-opus"),
+            make_entry("2024-01-16T08:00:00Z", 200, 100, 0.02, "claude-3-5-sonnet"),
+        ];
+        let periods = UsageAggregator::aggregate_daily(&entries);
+        let by_model = UsageAggregator::calculate_model_totals(&periods);
+
+        assert_eq!(by_model.len(), 2);
+        let sonnet = &by_model["claude-3-5-sonnet"];
+        assert_eq!(sonnet.input_tokens, 300);
+        assert_eq!(sonnet.output_tokens, 150);
+        assert_eq!(sonnet.count, 2);
+        assert!((sonnet.cost - 0.03).abs() < 1e-9);
+        let opus = &by_model["claude-3-opus"];
+        assert_eq!(opus.total_tokens(), 500);
+    }
+
+    #[test]
+    fn test_calculate_model_totals_matches_overall_totals() {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.01, "claude-3-5-sonnet"),
+            make_entry("2024-02-15T09:00:00Z", 400, 100, 0.50, "claude-3-haiku"),
+        ];
+        let periods = UsageAggregator::aggregate_monthly(&entries);
+        let totals = UsageAggregator::calculate_totals(&periods);
+        let by_model = UsageAggregator::calculate_model_totals(&periods);
+
+        let summed: u64 = by_model.values().map(|s| s.total_tokens()).sum();
+        assert_eq!(summed, totals.total_tokens());
+    }
+
+    #[test]
+    fn test_calculate_model_totals_empty() {
+        assert!(UsageAggregator::calculate_model_totals(&[]).is_empty());
+    }
+
     // ── models_used ───────────────────────────────────────────────────────────
 
+    #[test]
+    fn test_aggregate_by_period_custom_key() {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.5, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T20:00:00Z", 10, 5, 0.1, "claude-3-5-sonnet"),
+            make_entry("2024-01-16T08:00:00Z", 1, 1, 0.0, "claude-3-5-sonnet"),
+        ];
+        let periods =
+            UsageAggregator::aggregate_by_period(&entries, |ts| ts.format("%p").to_string());
+        let keys: Vec<&str> = periods.iter().map(|p| p.period_key.as_str()).collect();
+        assert_eq!(keys, vec!["AM", "PM"]);
+        assert_eq!(periods[0].stats.count, 2);
+    }
+
+    #[test]
+    fn test_aggregated_period_serializes() {
+        let mut period = AggregatedPeriod::new("2024-01-15");
+        period.add_entry(&make_entry(
+            "2024-01-15T08:00:00Z",
+            100,
+            50,
+            0.5,
+            "claude-3-5-sonnet",
+        ));
+
+        let json = serde_json::to_value(&period).unwrap();
+        assert_eq!(json["period_key"], "2024-01-15");
+        assert_eq!(json["stats"]["input_tokens"], 100);
+        assert_eq!(json["models_used"][0], "claude-3-5-sonnet");
+        assert_eq!(
+            json["model_breakdowns"]["claude-3-5-sonnet"]["output_tokens"],
+            50
+        );
+
+        let back: AggregatedPeriod = serde_json::from_value(json).unwrap();
+        assert_eq!(back.stats.total_tokens(), 150);
+    }
+
     #[test]
     fn test_models_used_tracking() {
         let entries = vec![
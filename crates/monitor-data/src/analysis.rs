@@ -3,12 +3,15 @@
 //! Orchestrates loading, block-creation, burn-rate computation and limit
 //! detection, returning an [`AnalysisResult`] ready for the UI layer.
 
+use std::collections::BTreeMap;
+
 use chrono::Utc;
 use monitor_core::calculations::BurnRateCalculator;
-use monitor_core::models::{CostMode, LimitMessage, SessionBlock};
+use monitor_core::models::{LimitMessage, SessionBlock, UsageEntry};
+use tracing::debug;
 
-use crate::analyzer::{LimitDetection, SessionAnalyzer};
-use crate::reader::load_usage_entries;
+use crate::analyzer::{BlockStrategy, LimitDetection, SessionAnalyzer, DEFAULT_SESSION_HOURS};
+use crate::reader::{load_usage_entries_with_stats, LoadOptions, LoadProgress, LoadStats};
 
 // ── Public types ──────────────────────────────────────────────────────────────
 
@@ -25,14 +28,24 @@ pub struct AnalysisMetadata {
     pub blocks_created: usize,
     /// Number of rate-limit notifications detected.
     pub limits_detected: usize,
+    /// Session-window width used for the blocks, inferred from observed
+    /// limit resets when possible.
+    pub session_hours: u64,
+    /// Number of JSONL files found when loading (`0` when the entries were
+    /// supplied by the caller).
+    pub files_loaded: usize,
     /// Wall-clock seconds spent loading the JSONL files.
     pub load_time_seconds: f64,
     /// Wall-clock seconds spent building session blocks.
     pub transform_time_seconds: f64,
+    /// What the loader read and skipped (all zero when the entries were
+    /// supplied by the caller).
+    #[serde(default)]
+    pub load_stats: LoadStats,
 }
 
 /// The complete output of [`analyze_usage`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct AnalysisResult {
     /// Session blocks (may include gap blocks).
     pub blocks: Vec<SessionBlock>,
@@ -50,11 +63,8 @@ pub struct AnalysisResult {
 
 /// Run the full analysis pipeline.
 ///
-/// 1. Load usage entries (and raw JSONL) from `data_path`.
-/// 2. Build 5-hour session blocks via [`SessionAnalyzer`].
-/// 3. Compute burn rates for active blocks.
-/// 4. Detect limits and attach them to the matching blocks.
-/// 5. Return an [`AnalysisResult`].
+/// Loads usage entries (and raw JSONL) from `data_path`, then hands them to
+/// [`analyze_entries`] for block building, burn rates and limit detection.
 ///
 /// When `quick_start` is `true` and `hours_back` is `None`, the analysis is
 /// limited to the last 24 hours for a faster startup experience.
@@ -62,6 +72,28 @@ pub fn analyze_usage(
     hours_back: Option<u64>,
     quick_start: bool,
     data_path: Option<&str>,
+) -> AnalysisResult {
+    analyze_usage_with_progress(
+        hours_back,
+        quick_start,
+        data_path,
+        &LoadOptions::default(),
+        |_| {},
+    )
+}
+
+/// Run the full analysis pipeline with loader `options`, reporting loader
+/// progress to `progress`.
+///
+/// Identical to [`analyze_usage`] except that `progress` receives a
+/// [`LoadProgress`] snapshot after each JSONL file is read, which lets a UI
+/// show a loading indicator while large histories are parsed.
+pub fn analyze_usage_with_progress(
+    hours_back: Option<u64>,
+    quick_start: bool,
+    data_path: Option<&str>,
+    options: &LoadOptions,
+    progress: impl Fn(LoadProgress),
 ) -> AnalysisResult {
     // Apply quick-start override.
     let effective_hours = if quick_start && hours_back.is_none() {
@@ -70,45 +102,91 @@ pub fn analyze_usage(
         hours_back
     };
 
-    // ── Step 1: Load entries ──────────────────────────────────────────────────
     let load_start = std::time::Instant::now();
-    let (entries, raw_entries) = load_usage_entries(
+    let (entries, raw_entries, load_stats) = load_usage_entries_with_stats(
         data_path,
         effective_hours,
-        CostMode::Auto,
+        options.cost_mode.clone(),
         true, // always include raw for limit detection
+        options,
+        |report: LoadProgress| progress(report),
     );
     let load_time = load_start.elapsed().as_secs_f64();
 
+    let mut result = analyze_entries(
+        entries,
+        raw_entries,
+        options.include_synthetic,
+        options.block_strategy,
+    );
+    result.metadata.hours_analyzed = effective_hours;
+    result.metadata.files_loaded = load_stats.files_found;
+    result.metadata.load_time_seconds = load_time;
+    result.metadata.load_stats = load_stats;
+    result
+}
+
+/// Run every pipeline stage after loading on already-parsed entries.
+///
+/// 1. Detect limits in `raw` (when given).
+/// 2. Build session blocks via [`SessionAnalyzer`], 5 hours wide unless the
+///    detected limit resets show a different window.
+/// 3. Compute burn rates for active blocks and attach the limits.
+/// 4. Return an [`AnalysisResult`].
+///
+/// `entries` should be sorted by timestamp, as returned by the reader.
+/// `include_synthetic` is passed to
+/// [`SessionAnalyzer::with_include_synthetic`] and `strategy` to
+/// [`SessionAnalyzer::transform_to_blocks`].
+/// The metadata reports `hours_analyzed: None` and a zero load time; callers
+/// that loaded the entries themselves may fill those in.
+pub fn analyze_entries(
+    entries: Vec<UsageEntry>,
+    raw: Option<Vec<serde_json::Value>>,
+    include_synthetic: bool,
+    strategy: BlockStrategy,
+) -> AnalysisResult {
+    // ── Step 1: Limits ────────────────────────────────────────────────────────
+    let default_analyzer =
+        SessionAnalyzer::new(DEFAULT_SESSION_HOURS).with_include_synthetic(include_synthetic);
+    let detections = raw
+        .as_ref()
+        .map(|raw| default_analyzer.detect_limits(raw))
+        .unwrap_or_default();
+
     // ── Step 2: Build blocks ──────────────────────────────────────────────────
     let transform_start = std::time::Instant::now();
-    let analyzer = SessionAnalyzer::new(5);
-    let mut blocks = analyzer.transform_to_blocks(&entries);
+    let mut blocks = default_analyzer.transform_to_blocks(&entries, strategy);
+    let session_hours =
+        SessionAnalyzer::infer_session_hours(&blocks, &detections).unwrap_or(DEFAULT_SESSION_HOURS);
+    if session_hours != DEFAULT_SESSION_HOURS {
+        debug!("Using a {session_hours}h session window inferred from limit resets");
+        blocks = SessionAnalyzer::new(session_hours)
+            .with_include_synthetic(include_synthetic)
+            .transform_to_blocks(&entries, strategy);
+    }
     let transform_time = transform_start.elapsed().as_secs_f64();
 
-    // ── Step 3: Burn rates ────────────────────────────────────────────────────
+    // ── Step 3: Burn rates and limits ─────────────────────────────────────────
     process_burn_rates(&mut blocks);
+    let limits_detected = detections.len();
+    assign_limits_to_blocks(&mut blocks, &detections);
 
-    // ── Step 4: Limits ────────────────────────────────────────────────────────
-    let mut limits_detected = 0usize;
-    if let Some(raw) = &raw_entries {
-        let detections = analyzer.detect_limits(raw);
-        limits_detected = detections.len();
-        assign_limits_to_blocks(&mut blocks, &detections);
-    }
-
-    // ── Step 5: Build result ──────────────────────────────────────────────────
+    // ── Step 4: Build result ──────────────────────────────────────────────────
     let total_tokens: u64 = blocks.iter().map(|b| b.total_tokens()).sum();
     let total_cost: f64 = blocks.iter().map(|b| b.cost_usd).sum();
 
     let metadata = AnalysisMetadata {
         generated_at: Utc::now().to_rfc3339(),
-        hours_analyzed: effective_hours,
+        hours_analyzed: None,
         entries_processed: entries.len(),
         blocks_created: blocks.len(),
         limits_detected,
-        load_time_seconds: load_time,
+        session_hours,
+        files_loaded: 0,
+        load_time_seconds: 0.0,
         transform_time_seconds: transform_time,
+        load_stats: LoadStats::default(),
     };
 
     AnalysisResult {
@@ -120,9 +198,99 @@ pub fn analyze_usage(
     }
 }
 
+impl AnalysisResult {
+    /// `true` when at least one usage entry was loaded.
+    pub fn has_usable_data(&self) -> bool {
+        self.entries_count > 0
+    }
+
+    /// Plain-text diagnostic report printed by `--validate`: what the loader
+    /// read and skipped, the blocks built from it and any loader warnings.
+    pub fn validation_report(&self) -> String {
+        let stats = &self.metadata.load_stats;
+        let sessions = self.blocks.iter().filter(|b| !b.is_gap);
+        let gaps = self.blocks.iter().filter(|b| b.is_gap).count();
+        let active = sessions.clone().filter(|b| b.is_active).count();
+
+        let mut out = String::new();
+        let mut row = |label: &str, value: String| {
+            out.push_str(&format!("{label:<22}{value}\n"));
+        };
+        row("Files found:", stats.files_found.to_string());
+        row("Lines read:", stats.lines_read.to_string());
+        row("  malformed:", stats.malformed_lines.to_string());
+        row("  before cutoff:", stats.filtered_by_cutoff.to_string());
+        row("  duplicates:", stats.duplicates.to_string());
+        row("  without usage:", stats.without_usage.to_string());
+        row("  absurd tokens:", stats.absurd_tokens.to_string());
+        row("Entries parsed:", self.entries_count.to_string());
+        row(
+            "Blocks created:",
+            format!(
+                "{} ({} sessions, {} gaps, {} active)",
+                self.blocks.len(),
+                sessions.count(),
+                gaps,
+                active
+            ),
+        );
+        row(
+            "Limits detected:",
+            self.metadata.limits_detected.to_string(),
+        );
+        row(
+            "Session window:",
+            format!("{}h", self.metadata.session_hours),
+        );
+
+        let sources = self.source_counts();
+        if !sources.is_empty() {
+            out.push_str("Sources:\n");
+            for (file, (entries, duplicates)) in &sources {
+                out.push_str(&format!(
+                    "  - {file}: {entries} entries, {duplicates} duplicates\n"
+                ));
+            }
+        }
+
+        if stats.warnings.is_empty() {
+            out.push_str("Warnings:             none\n");
+        } else {
+            out.push_str("Warnings:\n");
+            for warning in &stats.warnings {
+                out.push_str(&format!("  - {warning}\n"));
+            }
+        }
+        out
+    }
+
+    /// Entries and duplicate lines per source file, keyed by path. Empty
+    /// unless the data was loaded with
+    /// [`LoadOptions::track_sources`](crate::reader::LoadOptions::track_sources).
+    fn source_counts(&self) -> BTreeMap<String, (usize, usize)> {
+        let mut counts: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        let entries = self
+            .blocks
+            .iter()
+            .filter(|b| !b.is_gap)
+            .flat_map(|b| &b.entries);
+        for path in entries.filter_map(|e| e.source_file.as_ref()) {
+            counts.entry(path.display().to_string()).or_default().0 += 1;
+        }
+        for (file, duplicates) in &self.metadata.load_stats.duplicates_by_file {
+            counts.entry(file.clone()).or_default().1 += duplicates;
+        }
+        counts
+    }
+}
+
 // ── Private helpers ───────────────────────────────────────────────────────────
 
 /// Compute and attach burn rates (and projections) to every active block.
+///
+/// Sets `burn_rate` and `burn_rate_snapshot`, and stores the end-of-window
+/// [`UsageProjection`](monitor_core::models::UsageProjection) in
+/// `projection_data` while the window is still open.
 fn process_burn_rates(blocks: &mut [SessionBlock]) {
     for block in blocks.iter_mut() {
         if !block.is_active {
@@ -135,13 +303,10 @@ fn process_burn_rates(blocks: &mut [SessionBlock]) {
                 block.total_tokens(),
                 block.cost_usd,
             );
+            block.burn_rate = Some(burn_rate.clone());
             block.burn_rate_snapshot = Some(burn_rate);
-            if let Some(proj) = projection {
-                block.projection_data = Some(serde_json::json!({
-                    "totalTokens": proj.projected_total_tokens,
-                    "totalCost": proj.projected_total_cost,
-                    "remainingMinutes": proj.remaining_minutes,
-                }));
+            if let Some(projection) = projection {
+                block.set_projection(&projection);
             }
         }
     }
@@ -172,6 +337,7 @@ fn assign_limits_to_blocks(blocks: &mut [SessionBlock], detections: &[LimitDetec
 #[cfg(test)]
 mod tests {
     use super::*;
+    use monitor_core::models::CostMode;
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -221,6 +387,28 @@ mod tests {
         assert_eq!(result.total_tokens, 450); // 100+50+200+100
     }
 
+    #[test]
+    fn test_analyze_usage_with_progress_reports_final_count() {
+        let dir = TempDir::new().unwrap();
+        let line1 = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let line2 = sample_entry("2024-01-15T11:00:00Z", 200, 100, "msg2", "req2");
+        write_jsonl(dir.path(), "usage.jsonl", &[&line1, &line2]);
+
+        let last = std::cell::Cell::new(None);
+        let result = analyze_usage_with_progress(
+            None,
+            false,
+            Some(dir.path().to_str().unwrap()),
+            &LoadOptions::default(),
+            |p| last.set(Some(p)),
+        );
+
+        let last = last.get().expect("progress was reported");
+        assert_eq!(last.files_processed, 1);
+        assert_eq!(last.files_total, 1);
+        assert_eq!(last.entries_parsed, result.entries_count);
+    }
+
     #[test]
     fn test_analyze_usage_quick_start_sets_24h() {
         let dir = TempDir::new().unwrap();
@@ -261,6 +449,7 @@ mod tests {
         assert!(result.metadata.transform_time_seconds >= 0.0);
         assert_eq!(result.metadata.entries_processed, 1);
         assert_eq!(result.metadata.blocks_created, result.blocks.len());
+        assert_eq!(result.metadata.files_loaded, 1);
     }
 
     #[test]
@@ -292,6 +481,267 @@ mod tests {
         assert_eq!(result.metadata.limits_detected, 1);
     }
 
+    #[test]
+    fn test_zero_token_message_counts_towards_messages_only() {
+        let dir = TempDir::new().unwrap();
+        let real = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let empty = sample_entry("2024-01-15T10:05:00Z", 0, 0, "msg2", "req2");
+        write_jsonl(dir.path(), "usage.jsonl", &[&real, &empty]);
+        let path = Some(dir.path().to_str().unwrap());
+
+        let default = analyze_usage(None, false, path);
+        let counted = analyze_usage_with_progress(
+            None,
+            false,
+            path,
+            &LoadOptions {
+                count_empty_messages: true,
+                ..LoadOptions::default()
+            },
+            |_| {},
+        );
+
+        let messages = |r: &AnalysisResult| -> u32 {
+            r.blocks
+                .iter()
+                .filter(|b| !b.is_gap)
+                .map(|b| b.sent_messages_count)
+                .sum()
+        };
+        assert_eq!(messages(&default), 1);
+        assert_eq!(messages(&counted), 2);
+        assert_eq!(counted.total_tokens, default.total_tokens);
+        assert!((counted.total_cost - default.total_cost).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_analyze_usage_cached_cost_mode_uses_recorded_cost() {
+        let dir = TempDir::new().unwrap();
+        let line = serde_json::json!({
+            "timestamp": "2024-01-15T10:00:00Z",
+            "input_tokens": 1_000,
+            "output_tokens": 500,
+            "model": "claude-3-5-sonnet-20241022",
+            "message_id": "msg1",
+            "requestId": "req1",
+            "costUSD": 1.2345,
+        })
+        .to_string();
+        write_jsonl(dir.path(), "usage.jsonl", &[&line]);
+        let path = Some(dir.path().to_str().unwrap());
+
+        let analyze = |cost_mode: CostMode| {
+            let options = LoadOptions {
+                cost_mode,
+                ..LoadOptions::default()
+            };
+            analyze_usage_with_progress(None, false, path, &options, |_| {}).total_cost
+        };
+
+        assert!((analyze(CostMode::Cached) - 1.2345).abs() < 1e-9);
+        let calculated = analyze(CostMode::Calculated);
+        assert!(calculated > 0.0);
+        assert!((calculated - 1.2345).abs() > 1e-6);
+        assert!((analyze(CostMode::Auto) - calculated).abs() < 1e-12);
+    }
+
+    // ── validation_report ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_validation_report_counts() {
+        let dir = TempDir::new().unwrap();
+        let line1 = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let line2 = sample_entry("2024-01-15T18:00:00Z", 200, 100, "msg2", "req2");
+        write_jsonl(dir.path(), "usage.jsonl", &[&line1, &line1, "oops", &line2]);
+
+        let result = analyze_usage(None, false, Some(dir.path().to_str().unwrap()));
+        assert!(result.has_usable_data());
+
+        let report = result.validation_report();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Files found:          1");
+        assert_eq!(lines[1], "Lines read:           3");
+        assert_eq!(lines[2], "  malformed:          1");
+        assert_eq!(lines[4], "  duplicates:         1");
+        assert_eq!(lines[6], "  absurd tokens:      0");
+        assert_eq!(lines[7], "Entries parsed:       2");
+        assert_eq!(
+            lines[8],
+            "Blocks created:       3 (2 sessions, 1 gaps, 0 active)"
+        );
+        assert_eq!(lines.last(), Some(&"Warnings:             none"));
+        assert!(!report.contains("Sources:"), "{report}");
+    }
+
+    #[test]
+    fn test_validation_report_lists_sources() {
+        let dir = TempDir::new().unwrap();
+        let line = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        write_jsonl(dir.path(), "usage.jsonl", &[&line, &line]);
+
+        let options = LoadOptions {
+            track_sources: true,
+            ..LoadOptions::default()
+        };
+        let result = analyze_usage_with_progress(
+            None,
+            false,
+            Some(dir.path().to_str().unwrap()),
+            &options,
+            |_| {},
+        );
+
+        let file = dir.path().join("usage.jsonl");
+        let report = result.validation_report();
+        assert!(
+            report.contains(&format!(
+                "Sources:\n  - {}: 1 entries, 1 duplicates\n",
+                file.display()
+            )),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn test_validation_report_lists_warnings() {
+        let dir = TempDir::new().unwrap();
+        let result = analyze_usage(None, false, Some(dir.path().to_str().unwrap()));
+        assert!(!result.has_usable_data());
+
+        let report = result.validation_report();
+        assert!(
+            report.contains("Warnings:\n  - No JSONL files found in "),
+            "{report}"
+        );
+    }
+
+    // ── analyze_entries ───────────────────────────────────────────────────────
+
+    fn make_entry(ts: &str, input: u64, output: u64) -> UsageEntry {
+        UsageEntry {
+            timestamp: chrono::DateTime::parse_from_rfc3339(ts)
+                .unwrap()
+                .with_timezone(&Utc),
+            input_tokens: input,
+            output_tokens: output,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd: 0.5,
+            model: "claude-3-5-sonnet".to_string(),
+            message_id: ts.to_string(),
+            request_id: ts.to_string(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        }
+    }
+
+    #[test]
+    fn test_analyze_entries_without_files() {
+        let entries = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 50),
+            make_entry("2024-01-15T11:00:00Z", 200, 100),
+        ];
+
+        let result = analyze_entries(entries, None, false, BlockStrategy::default());
+
+        assert_eq!(result.entries_count, 2);
+        assert_eq!(result.total_tokens, 450);
+        assert!((result.total_cost - 1.0).abs() < 1e-9);
+        assert!(result.metadata.hours_analyzed.is_none());
+        assert_eq!(result.metadata.limits_detected, 0);
+        assert_eq!(result.metadata.blocks_created, result.blocks.len());
+    }
+
+    #[test]
+    fn test_analyze_entries_projects_active_block() {
+        let now = Utc::now();
+        let entries = vec![
+            make_entry(&(now - chrono::Duration::minutes(30)).to_rfc3339(), 600, 0),
+            make_entry(&(now - chrono::Duration::minutes(10)).to_rfc3339(), 600, 0),
+        ];
+
+        let result = analyze_entries(entries, None, false, BlockStrategy::default());
+        let active = result.blocks.iter().find(|b| b.is_active).unwrap();
+
+        let burn_rate = active.burn_rate.as_ref().unwrap();
+        assert!(burn_rate.tokens_per_minute > 0.0);
+        let snapshot = active.burn_rate_snapshot.as_ref().unwrap();
+        assert_eq!(snapshot.tokens_per_minute, burn_rate.tokens_per_minute);
+        let projection = active.projection().unwrap();
+        assert!(projection.remaining_minutes > 0.0);
+        assert!(projection.projected_total_tokens > active.total_tokens());
+        assert!(projection.projected_total_cost > active.cost_usd);
+
+        // Consumers of `--output json` read the camelCase keys.
+        let data = active.projection_data.as_ref().unwrap();
+        assert_eq!(
+            data["totalTokens"].as_u64(),
+            Some(projection.projected_total_tokens)
+        );
+        assert!(data.get("totalCost").is_some() && data.get("remainingMinutes").is_some());
+    }
+
+    #[test]
+    fn test_analyze_entries_leaves_past_blocks_unprojected() {
+        let entries = vec![make_entry("2024-01-15T10:00:00Z", 100, 50)];
+        let result = analyze_entries(entries, None, false, BlockStrategy::default());
+        assert!(result.blocks.iter().all(|b| b.projection_data.is_none()));
+        assert!(result.blocks.iter().all(|b| b.burn_rate.is_none()));
+        assert!(result.blocks.iter().all(|b| b.burn_rate_snapshot.is_none()));
+    }
+
+    #[test]
+    fn test_analyze_entries_detects_limits_in_raw() {
+        let entries = vec![make_entry("2024-01-15T10:00:00Z", 100, 50)];
+        let raw = vec![serde_json::json!({
+            "type": "system",
+            "timestamp": "2024-01-15T10:30:00Z",
+            "content": "You have hit a rate limit. Please wait.",
+        })];
+
+        let result = analyze_entries(entries, Some(raw), false, BlockStrategy::default());
+        assert_eq!(result.metadata.limits_detected, 1);
+    }
+
+    #[test]
+    fn test_analyze_entries_infers_session_window_from_resets() {
+        let entries = vec![make_entry("2024-01-15T10:00:00Z", 100, 50)];
+        // Reset at 16:00 for a session that started at 10:00 → 6h window.
+        let raw = vec![serde_json::json!({
+            "type": "user",
+            "timestamp": "2024-01-15T12:00:00Z",
+            "message": {
+                "content": [{
+                    "type": "tool_result",
+                    "content": [{"text": "limit reached|1705334400"}]
+                }]
+            }
+        })];
+
+        let result = analyze_entries(entries, Some(raw), false, BlockStrategy::default());
+        assert_eq!(result.metadata.session_hours, 6);
+        let block = result.blocks.iter().find(|b| !b.is_gap).unwrap();
+        assert_eq!(
+            block.end_time - block.start_time,
+            chrono::TimeDelta::hours(6)
+        );
+    }
+
+    #[test]
+    fn test_analyze_entries_defaults_to_five_hour_window() {
+        let entries = vec![make_entry("2024-01-15T10:00:00Z", 100, 50)];
+        let result = analyze_entries(entries, None, false, BlockStrategy::default());
+        assert_eq!(result.metadata.session_hours, DEFAULT_SESSION_HOURS);
+    }
+
+    #[test]
+    fn test_analyze_entries_empty() {
+        let result = analyze_entries(Vec::new(), None, false, BlockStrategy::default());
+        assert!(result.blocks.is_empty());
+        assert_eq!(result.total_tokens, 0);
+    }
+
     #[test]
     fn test_assign_limits_to_blocks_correct_block() {
         use chrono::TimeZone;
@@ -303,6 +753,7 @@ mod tests {
 
         let block = SessionBlock {
             id: "test".to_string(),
+            label: String::new(),
             start_time: block_start,
             end_time: block_end,
             entries: vec![],
@@ -1,15 +1,20 @@
 //! Session block analyser for Claude Monitor.
 //!
-//! Groups [`UsageEntry`] records into 5-hour session windows and detects
-//! rate-limit notifications embedded in raw JSONL data.
+//! Groups [`UsageEntry`] records into session windows (5 hours unless
+//! observed limit resets say otherwise) and detects rate-limit notifications
+//! embedded in raw JSONL data.
 
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
-use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use chrono::{DateTime, DurationRound, Local, TimeDelta, Utc};
+use chrono_tz::Tz;
+use monitor_core::clock::{SharedClock, SystemClock};
 use monitor_core::data_processors::TimestampProcessor;
-use monitor_core::models::{normalize_model_name, SessionBlock, TokenCounts, UsageEntry};
+use monitor_core::models::{SessionBlock, TokenCounts, UsageEntry};
+use monitor_core::time_utils::last_reset_boundary;
 use regex::Regex;
-use tracing::debug;
+use tracing::{debug, warn};
 
 // ── LimitDetection ────────────────────────────────────────────────────────────
 
@@ -26,12 +31,41 @@ pub struct LimitDetection {
     pub reset_time: Option<DateTime<Utc>>,
 }
 
+// ── BlockStrategy ─────────────────────────────────────────────────────────────
+
+/// How [`SessionAnalyzer::transform_to_blocks`] places session windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockStrategy {
+    /// Each window starts at the hour of the first entry it contains.
+    #[default]
+    RollingFromFirstEntry,
+    /// Windows follow a fixed daily schedule: one starts at `anchor_hour`
+    /// in `tz` every day and every session length after it. The last window
+    /// of a day is cut short at the next day's anchor.
+    FixedSchedule {
+        /// Hour of day (0–23, in `tz`) the first window of each day starts.
+        anchor_hour: u8,
+        /// Timezone the anchor hour is read in.
+        tz: Tz,
+    },
+}
+
 // ── SessionAnalyzer ───────────────────────────────────────────────────────────
 
+/// Session-window width used when the data contains no reset events.
+pub const DEFAULT_SESSION_HOURS: u64 = 5;
+
+/// Observed windows outside this range (in hours) are ignored as noise.
+const PLAUSIBLE_SESSION_HOURS: std::ops::RangeInclusive<f64> = 1.0..=24.0;
+
 /// Groups usage entries into fixed-size session windows and detects limits.
 pub struct SessionAnalyzer {
     /// Width of each session window (default: 5 hours).
     session_duration_hours: u64,
+    /// Count synthetic entries as messages and in per-model stats.
+    include_synthetic: bool,
+    /// "Now" for deciding which blocks are still active.
+    clock: SharedClock,
 }
 
 impl SessionAnalyzer {
@@ -39,9 +73,25 @@ impl SessionAnalyzer {
     pub fn new(session_duration_hours: u64) -> Self {
         Self {
             session_duration_hours,
+            include_synthetic: false,
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Count synthetic entries (see [`UsageEntry::is_synthetic`]) as sent
+    /// messages and in per-model stats. By default only their tokens and
+    /// cost are added to the block.
+    pub fn with_include_synthetic(mut self, include_synthetic: bool) -> Self {
+        self.include_synthetic = include_synthetic;
+        self
+    }
+
+    /// Take "now" from `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// The session duration as a [`TimeDelta`].
     fn session_delta(&self) -> TimeDelta {
         TimeDelta::hours(self.session_duration_hours as i64)
@@ -52,19 +102,37 @@ impl SessionAnalyzer {
     /// Transform a slice of sorted [`UsageEntry`] records into [`SessionBlock`]s.
     ///
     /// The algorithm:
-    /// 1. Entries must be pre-sorted by timestamp (the reader guarantees this).
+    /// 1. Entries should be sorted by timestamp (the reader guarantees this);
+    ///    unsorted input is logged as a warning and a sorted copy is used.
     /// 2. A new block is opened when the entry falls outside the current
     ///    block's 5-hour window **or** the gap since the last entry exceeds 5h.
+    ///    Where the new window starts is chosen by `strategy`.
     /// 3. Gap blocks (is_gap = true) are inserted between consecutive real
     ///    blocks when the inactivity period is >= 5h.
     /// 4. Active blocks (end_time > now) are marked `is_active = true`.
-    pub fn transform_to_blocks(&self, entries: &[UsageEntry]) -> Vec<SessionBlock> {
+    /// 5. Blocks that share a start time get a `-2`, `-3`, … id suffix.
+    pub fn transform_to_blocks(
+        &self,
+        entries: &[UsageEntry],
+        strategy: BlockStrategy,
+    ) -> Vec<SessionBlock> {
         if entries.is_empty() {
             return Vec::new();
         }
+        if !entries.is_sorted_by_key(|e| e.timestamp) {
+            warn!(
+                "SessionAnalyzer: {} entries are not sorted by timestamp; sorting a copy",
+                entries.len()
+            );
+            let mut sorted = entries.to_vec();
+            sorted.sort_by_key(|e| e.timestamp);
+            return self.transform_to_blocks(&sorted, strategy);
+        }
 
         let mut blocks: Vec<SessionBlock> = Vec::new();
         let mut current_block: Option<SessionBlock> = None;
+        // Blocks opened so far per start-time id.
+        let mut id_counts: HashMap<String, u32> = HashMap::new();
 
         for entry in entries {
             let need_new = match &current_block {
@@ -74,28 +142,35 @@ impl SessionAnalyzer {
 
             if need_new {
                 if let Some(mut block) = current_block.take() {
-                    Self::finalize_block(&mut block);
+                    self.finalize_block(&mut block);
                     // Insert a gap block if necessary.
                     if let Some(gap) = Self::check_for_gap(&block, entry, self.session_delta()) {
                         blocks.push(gap);
                     }
                     blocks.push(block);
                 }
-                current_block = Some(Self::create_new_block(entry, self.session_delta()));
+                let (start_time, end_time) = self.window_for(entry.timestamp, strategy);
+                let mut block = Self::create_new_block(start_time, end_time);
+                let seen = id_counts.entry(block.id.clone()).or_insert(0);
+                *seen += 1;
+                if *seen > 1 {
+                    block.id = format!("{}-{}", block.id, seen);
+                }
+                current_block = Some(block);
             }
 
             if let Some(ref mut block) = current_block {
-                Self::add_entry_to_block(block, entry);
+                self.add_entry_to_block(block, entry);
             }
         }
 
         // Finalize the last block.
         if let Some(mut block) = current_block {
-            Self::finalize_block(&mut block);
+            self.finalize_block(&mut block);
             blocks.push(block);
         }
 
-        Self::mark_active_blocks(&mut blocks);
+        self.mark_active_blocks(&mut blocks);
 
         debug!(
             "SessionAnalyzer: created {} blocks from {} entries",
@@ -113,8 +188,54 @@ impl SessionAnalyzer {
             .collect()
     }
 
+    /// Infer the session-window width from observed limit resets.
+    ///
+    /// Each `general_limit` detection with a reset timestamp measures one
+    /// window as `reset_time - start_time` of the non-gap block that contains
+    /// it. Returns the median of the plausible (1–24 h) measurements rounded
+    /// to whole hours, or `None` when there is nothing to measure.
+    pub fn infer_session_hours(
+        blocks: &[SessionBlock],
+        detections: &[LimitDetection],
+    ) -> Option<u64> {
+        let mut windows: Vec<f64> = detections
+            .iter()
+            .filter(|d| d.limit_type == "general_limit")
+            .filter_map(|d| {
+                let reset = d.reset_time?;
+                let block = blocks.iter().find(|b| {
+                    !b.is_gap && b.start_time <= d.timestamp && d.timestamp <= b.end_time
+                })?;
+                Some((reset - block.start_time).num_seconds() as f64 / 3600.0)
+            })
+            .filter(|hours| PLAUSIBLE_SESSION_HOURS.contains(hours))
+            .collect();
+        if windows.is_empty() {
+            return None;
+        }
+
+        windows.sort_by(|a, b| a.total_cmp(b));
+        let mid = windows.len() / 2;
+        // `usize::is_multiple_of` needs a newer toolchain than the MSRV.
+        #[allow(clippy::manual_is_multiple_of)]
+        let median = if windows.len() % 2 == 0 {
+            (windows[mid - 1] + windows[mid]) / 2.0
+        } else {
+            windows[mid]
+        };
+        Some(median.round() as u64)
+    }
+
     // ── Block-building helpers ────────────────────────────────────────────────
 
+    /// Local-time label for a block starting at `start`.
+    fn block_label(start: DateTime<Utc>) -> String {
+        start
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
     /// Round a UTC timestamp down to the start of its hour.
     fn round_to_hour(ts: DateTime<Utc>) -> DateTime<Utc> {
         ts.duration_trunc(TimeDelta::hours(1)).unwrap_or(ts)
@@ -135,14 +256,43 @@ impl SessionAnalyzer {
         false
     }
 
-    /// Open a new, empty [`SessionBlock`] anchored to the hour containing `entry`.
-    fn create_new_block(entry: &UsageEntry, session_delta: TimeDelta) -> SessionBlock {
-        let start_time = Self::round_to_hour(entry.timestamp);
-        let end_time = start_time + session_delta;
+    /// Start and end of the session window containing `ts`.
+    fn window_for(
+        &self,
+        ts: DateTime<Utc>,
+        strategy: BlockStrategy,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let session_delta = self.session_delta();
+        let rolling = || {
+            let start = Self::round_to_hour(ts);
+            (start, start + session_delta)
+        };
+        let BlockStrategy::FixedSchedule { anchor_hour, tz } = strategy else {
+            return rolling();
+        };
+        let Some(anchor) = last_reset_boundary(ts, anchor_hour.min(23), tz) else {
+            return rolling();
+        };
+        // Anchors are 23 to 25 hours apart, so the latest one 26 hours on is
+        // the next day's.
+        let next_anchor =
+            last_reset_boundary(anchor + TimeDelta::hours(26), anchor_hour.min(23), tz)
+                .unwrap_or(anchor + TimeDelta::days(1));
+
+        let step = session_delta.num_seconds().max(1);
+        let windows = (ts - anchor).num_seconds() / step;
+        let start = anchor + TimeDelta::seconds(windows * step);
+        let end = (start + session_delta).min(next_anchor);
+        (start, end)
+    }
+
+    /// Open a new, empty [`SessionBlock`] covering `start_time..end_time`.
+    fn create_new_block(start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> SessionBlock {
         let id = start_time.format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
         SessionBlock {
             id,
+            label: Self::block_label(start_time),
             start_time,
             end_time,
             entries: Vec::new(),
@@ -161,37 +311,49 @@ impl SessionAnalyzer {
         }
     }
 
-    /// Accumulate `entry`'s tokens and cost into `block`, updating per-model stats.
-    fn add_entry_to_block(block: &mut SessionBlock, entry: &UsageEntry) {
+    /// `true` when `entry` counts as a sent message and towards per-model
+    /// stats: synthetic and system entries only with `include_synthetic`.
+    fn counts_as_message(&self, entry: &UsageEntry) -> bool {
+        entry.counts_as_message(self.include_synthetic)
+    }
+
+    /// Accumulate `entry`'s tokens and cost into `block`, updating per-model
+    /// stats unless the entry is an excluded synthetic one.
+    fn add_entry_to_block(&self, block: &mut SessionBlock, entry: &UsageEntry) {
         block.entries.push(entry.clone());
 
-        let raw_model = if entry.model.is_empty() {
-            "unknown"
-        } else {
-            &entry.model
-        };
-        let model = if raw_model == "unknown" {
-            "unknown".to_string()
-        } else {
-            normalize_model_name(raw_model)
-        };
+        // Block-level aggregation. The reader drops absurd counts; saturating
+        // adds keep any that slip through from wrapping the totals.
+        let counts = &mut block.token_counts;
+        counts.input_tokens = counts.input_tokens.saturating_add(entry.input_tokens);
+        counts.output_tokens = counts.output_tokens.saturating_add(entry.output_tokens);
+        counts.cache_creation_tokens = counts
+            .cache_creation_tokens
+            .saturating_add(entry.cache_creation_tokens);
+        counts.cache_read_tokens = counts
+            .cache_read_tokens
+            .saturating_add(entry.cache_read_tokens);
+        block.cost_usd += entry.cost_usd;
+
+        if !self.counts_as_message(entry) {
+            return;
+        }
+
+        let model = entry.stats_model();
 
         // Per-model stats.
         let stats = block.per_model_stats.entry(model.clone()).or_default();
-        stats.input_tokens += entry.input_tokens;
-        stats.output_tokens += entry.output_tokens;
-        stats.cache_creation_tokens += entry.cache_creation_tokens;
-        stats.cache_read_tokens += entry.cache_read_tokens;
+        stats.input_tokens = stats.input_tokens.saturating_add(entry.input_tokens);
+        stats.output_tokens = stats.output_tokens.saturating_add(entry.output_tokens);
+        stats.cache_creation_tokens = stats
+            .cache_creation_tokens
+            .saturating_add(entry.cache_creation_tokens);
+        stats.cache_read_tokens = stats
+            .cache_read_tokens
+            .saturating_add(entry.cache_read_tokens);
         stats.cost_usd += entry.cost_usd;
         stats.entries_count += 1;
 
-        // Block-level aggregation.
-        block.token_counts.input_tokens += entry.input_tokens;
-        block.token_counts.output_tokens += entry.output_tokens;
-        block.token_counts.cache_creation_tokens += entry.cache_creation_tokens;
-        block.token_counts.cache_read_tokens += entry.cache_read_tokens;
-        block.cost_usd += entry.cost_usd;
-
         // Model list (no duplicates, preserve insertion order).
         if !block.models.contains(&model) {
             block.models.push(model);
@@ -201,12 +363,16 @@ impl SessionAnalyzer {
     }
 
     /// Set `actual_end_time` to the timestamp of the last entry in `block`.
-    fn finalize_block(block: &mut SessionBlock) {
+    fn finalize_block(&self, block: &mut SessionBlock) {
         if let Some(last) = block.entries.last() {
             block.actual_end_time = Some(last.timestamp);
         }
-        // Keep sent_messages_count consistent with actual entry count.
-        block.sent_messages_count = block.entries.len() as u32;
+        // Keep sent_messages_count consistent with the counted entries.
+        block.sent_messages_count = block
+            .entries
+            .iter()
+            .filter(|e| self.counts_as_message(e))
+            .count() as u32;
     }
 
     /// Build a gap [`SessionBlock`] if the inactivity between `last_block` and
@@ -225,6 +391,7 @@ impl SessionAnalyzer {
         let gap_id = format!("gap-{}", actual_end.format("%Y-%m-%dT%H:%M:%SZ"));
         Some(SessionBlock {
             id: gap_id,
+            label: Self::block_label(actual_end),
             start_time: actual_end,
             end_time: next_entry.timestamp,
             entries: Vec::new(),
@@ -244,8 +411,8 @@ impl SessionAnalyzer {
     }
 
     /// Mark each non-gap block whose `end_time` is in the future as active.
-    fn mark_active_blocks(blocks: &mut [SessionBlock]) {
-        let now = Utc::now();
+    fn mark_active_blocks(&self, blocks: &mut [SessionBlock]) {
+        let now = self.clock.now();
         for block in blocks.iter_mut() {
             if !block.is_gap && block.end_time > now {
                 block.is_active = true;
@@ -352,7 +519,9 @@ fn extract_wait_time(
     content: &str,
     timestamp: DateTime<Utc>,
 ) -> (Option<DateTime<Utc>>, Option<u64>) {
-    let re = Regex::new(r"wait\s+(\d+)\s+minutes?").expect("regex is valid");
+    static WAIT_TIME: OnceLock<Regex> = OnceLock::new();
+    let re =
+        WAIT_TIME.get_or_init(|| Regex::new(r"wait\s+(\d+)\s+minutes?").expect("regex is valid"));
     if let Some(cap) = re.captures(&content.to_lowercase()) {
         if let Ok(minutes) = cap[1].parse::<u64>() {
             let reset = timestamp + TimeDelta::minutes(minutes as i64);
@@ -364,7 +533,9 @@ fn extract_wait_time(
 
 /// Extract a Unix-timestamp reset time from `"limit reached|<unix_ts>"`.
 fn parse_reset_timestamp(text: &str) -> Option<DateTime<Utc>> {
-    let re = Regex::new(r"limit reached\|(\d+)").expect("regex is valid");
+    static RESET_TIMESTAMP: OnceLock<Regex> = OnceLock::new();
+    let re = RESET_TIMESTAMP
+        .get_or_init(|| Regex::new(r"limit reached\|(\d+)").expect("regex is valid"));
     if let Some(cap) = re.captures(&text.to_lowercase()) {
         if let Ok(unix_secs) = cap[1].parse::<i64>() {
             return TimestampProcessor::parse(&serde_json::Value::Number(
@@ -381,6 +552,8 @@ fn parse_reset_timestamp(text: &str) -> Option<DateTime<Utc>> {
 mod tests {
     use super::*;
     use chrono::TimeZone;
+    use monitor_core::clock::MockClock;
+    use monitor_core::models::SYNTHETIC_MODEL;
 
     fn make_entry(ts_str: &str, input: u64, output: u64, model: &str) -> UsageEntry {
         let ts = DateTime::parse_from_rfc3339(ts_str)
@@ -396,6 +569,9 @@ mod tests {
             model: model.to_string(),
             message_id: format!("msg-{}", ts_str),
             request_id: format!("req-{}", ts_str),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
         }
     }
 
@@ -403,11 +579,18 @@ mod tests {
         SessionAnalyzer::new(5)
     }
 
+    fn fixed_utc(anchor_hour: u8) -> BlockStrategy {
+        BlockStrategy::FixedSchedule {
+            anchor_hour,
+            tz: Tz::UTC,
+        }
+    }
+
     // ── transform_to_blocks ───────────────────────────────────────────────────
 
     #[test]
     fn test_empty_entries_returns_empty_blocks() {
-        let blocks = analyzer().transform_to_blocks(&[]);
+        let blocks = analyzer().transform_to_blocks(&[], BlockStrategy::default());
         assert!(blocks.is_empty());
     }
 
@@ -420,7 +603,7 @@ mod tests {
             50,
             "claude-3-5-sonnet",
         )];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
 
         assert_eq!(blocks.len(), 1);
         assert_eq!(blocks[0].token_counts.input_tokens, 100);
@@ -429,6 +612,72 @@ mod tests {
         assert_eq!(blocks[0].start_time.minute(), 0);
     }
 
+    #[test]
+    fn test_huge_token_counts_saturate() {
+        let entries = vec![
+            make_entry("2024-01-15T10:00:00Z", u64::MAX, 0, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T10:05:00Z", 100, 50, "claude-3-5-sonnet"),
+        ];
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+
+        assert_eq!(blocks[0].token_counts.input_tokens, u64::MAX);
+        assert_eq!(blocks[0].token_counts.total_tokens(), u64::MAX);
+        assert_eq!(blocks[0].total_tokens(), u64::MAX);
+        assert_eq!(
+            blocks[0].per_model_stats["claude-3-5-sonnet"].input_tokens,
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_synthetic_entries_not_counted_as_messages() {
+        let entries = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T10:05:00Z", 10, 0, SYNTHETIC_MODEL),
+        ];
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+
+        assert_eq!(blocks[0].sent_messages_count, 1);
+        assert!(!blocks[0].per_model_stats.contains_key(SYNTHETIC_MODEL));
+        assert_eq!(blocks[0].models, vec!["claude-3-5-sonnet".to_string()]);
+        // Tokens still count towards the block.
+        assert_eq!(blocks[0].token_counts.input_tokens, 110);
+        assert_eq!(blocks[0].entries.len(), 2);
+    }
+
+    #[test]
+    fn test_system_entries_keep_cost_but_are_not_messages() {
+        let mut system = make_entry("2024-01-15T10:05:00Z", 10, 0, "claude-3-5-sonnet");
+        system.is_system = true;
+        system.cost_usd = 0.25;
+        let entries = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            system,
+        ];
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+        assert_eq!(blocks[0].sent_messages_count, 1);
+        assert!(blocks[0].cost_usd >= 0.25);
+
+        let blocks = analyzer()
+            .with_include_synthetic(true)
+            .transform_to_blocks(&entries, BlockStrategy::default());
+        assert_eq!(blocks[0].sent_messages_count, 2);
+    }
+
+    #[test]
+    fn test_include_synthetic_counts_synthetic_messages() {
+        let entries = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T10:05:00Z", 10, 0, SYNTHETIC_MODEL),
+        ];
+        let blocks = analyzer()
+            .with_include_synthetic(true)
+            .transform_to_blocks(&entries, BlockStrategy::default());
+
+        assert_eq!(blocks[0].sent_messages_count, 2);
+        assert_eq!(blocks[0].models.len(), 2);
+    }
+
     #[test]
     fn test_block_start_time_rounded_to_hour() {
         let entries = vec![make_entry(
@@ -437,7 +686,7 @@ mod tests {
             5,
             "claude-3-5-sonnet",
         )];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         assert_eq!(
             blocks[0].start_time,
             Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap()
@@ -455,7 +704,7 @@ mod tests {
             make_entry("2024-01-15T12:00:00Z", 200, 100, "claude-3-5-sonnet"),
             make_entry("2024-01-15T14:30:00Z", 50, 25, "claude-3-5-sonnet"),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         // All within 10:00-15:00 window → 1 block (no gap entries).
         let real_blocks: Vec<_> = blocks.iter().filter(|b| !b.is_gap).collect();
         assert_eq!(real_blocks.len(), 1);
@@ -468,11 +717,174 @@ mod tests {
             make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
             make_entry("2024-01-15T16:00:00Z", 200, 100, "claude-3-5-sonnet"),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         let real_blocks: Vec<_> = blocks.iter().filter(|b| !b.is_gap).collect();
         assert_eq!(real_blocks.len(), 2);
     }
 
+    #[test]
+    fn test_unsorted_entries_are_sorted_first() {
+        let sorted = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T12:00:00Z", 200, 100, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T16:00:00Z", 50, 25, "claude-3-5-sonnet"),
+        ];
+        let unsorted = vec![sorted[2].clone(), sorted[0].clone(), sorted[1].clone()];
+
+        let expected = analyzer().transform_to_blocks(&sorted, BlockStrategy::default());
+        let blocks = analyzer().transform_to_blocks(&unsorted, BlockStrategy::default());
+
+        let summary = |blocks: &[SessionBlock]| -> Vec<(String, usize, u64)> {
+            blocks
+                .iter()
+                .map(|b| (b.id.clone(), b.entries.len(), b.total_tokens()))
+                .collect()
+        };
+        assert_eq!(summary(&blocks), summary(&expected));
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[test]
+    fn test_fixed_schedule_splits_at_wall_clock_boundary() {
+        let entries = vec![
+            make_entry("2024-01-15T09:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T11:00:00Z", 200, 100, "claude-3-5-sonnet"),
+        ];
+        let windows = |blocks: &[SessionBlock]| -> Vec<(String, String, usize)> {
+            blocks
+                .iter()
+                .map(|b| {
+                    (
+                        b.start_time.format("%H:%M").to_string(),
+                        b.end_time.format("%H:%M").to_string(),
+                        b.entries.len(),
+                    )
+                })
+                .collect()
+        };
+
+        // Rolling: one window opened by the 09:00 entry.
+        let rolling = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+        assert_eq!(windows(&rolling), vec![("09:00".into(), "14:00".into(), 2)]);
+
+        // Fixed from midnight: 05:00–10:00 and 10:00–15:00.
+        let fixed = analyzer().transform_to_blocks(&entries, fixed_utc(0));
+        assert_eq!(
+            windows(&fixed),
+            vec![
+                ("05:00".into(), "10:00".into(), 1),
+                ("10:00".into(), "15:00".into(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixed_schedule_restarts_at_daily_anchor() {
+        let strategy = fixed_utc(6);
+
+        // 03:00 falls in the previous day's last window, cut short at 06:00.
+        let early = vec![make_entry(
+            "2024-01-15T03:00:00Z",
+            100,
+            50,
+            "claude-3-5-sonnet",
+        )];
+        let blocks = analyzer().transform_to_blocks(&early, strategy);
+        assert_eq!(
+            blocks[0].start_time,
+            "2024-01-15T02:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            blocks[0].end_time,
+            "2024-01-15T06:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        // The rolling window from 03:00 would have held both entries.
+        let entries = vec![
+            make_entry("2024-01-15T03:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T06:30:00Z", 100, 50, "claude-3-5-sonnet"),
+        ];
+        let rolling = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+        let fixed = analyzer().transform_to_blocks(&entries, strategy);
+        assert_eq!(rolling.len(), 1);
+        assert_eq!(fixed.len(), 2);
+        assert_eq!(
+            fixed[1].start_time,
+            "2024-01-15T06:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            fixed[1].end_time,
+            "2024-01-15T11:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fixed_schedule_anchor_follows_timezone() {
+        // Midnight in Tokyo is 15:00 UTC the day before.
+        let strategy = BlockStrategy::FixedSchedule {
+            anchor_hour: 0,
+            tz: chrono_tz::Asia::Tokyo,
+        };
+        let entries = vec![make_entry(
+            "2024-01-15T16:00:00Z",
+            100,
+            50,
+            "claude-3-5-sonnet",
+        )];
+        let blocks = analyzer().transform_to_blocks(&entries, strategy);
+        assert_eq!(
+            blocks[0].start_time,
+            "2024-01-15T15:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            blocks[0].end_time,
+            "2024-01-15T20:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_block_ids_unique_when_start_times_collide() {
+        // A zero-hour window stands in for sub-hour windows: every entry
+        // opens a block, all rounded to the same 10:00 start.
+        let entries = vec![
+            make_entry("2024-01-15T10:05:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T10:20:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T10:40:00Z", 100, 50, "claude-3-5-sonnet"),
+        ];
+        let blocks =
+            SessionAnalyzer::new(0).transform_to_blocks(&entries, BlockStrategy::default());
+        let ids: Vec<&str> = blocks
+            .iter()
+            .filter(|b| !b.is_gap)
+            .map(|b| b.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                "2024-01-15T10:00:00Z",
+                "2024-01-15T10:00:00Z-2",
+                "2024-01-15T10:00:00Z-3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_block_label_is_local_start_time() {
+        let entries = vec![make_entry(
+            "2024-01-15T10:30:00Z",
+            100,
+            50,
+            "claude-3-5-sonnet",
+        )];
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+        let expected = blocks[0]
+            .start_time
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string();
+        assert_eq!(blocks[0].label, expected);
+    }
+
     #[test]
     fn test_gap_block_inserted_between_sessions() {
         let entries = vec![
@@ -480,7 +892,7 @@ mod tests {
             // 10 hours later – gap >= 5h.
             make_entry("2024-01-15T20:00:00Z", 200, 100, "claude-3-5-sonnet"),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         let gap_blocks: Vec<_> = blocks.iter().filter(|b| b.is_gap).collect();
         assert_eq!(gap_blocks.len(), 1);
         assert!(gap_blocks[0].id.starts_with("gap-"));
@@ -493,22 +905,33 @@ mod tests {
             // 3 hours later – within 5h window.
             make_entry("2024-01-15T13:00:00Z", 200, 100, "claude-3-5-sonnet"),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         let gap_blocks: Vec<_> = blocks.iter().filter(|b| b.is_gap).collect();
         assert!(gap_blocks.is_empty());
     }
 
     #[test]
     fn test_active_block_marked_when_end_time_is_future() {
-        // Use an entry from now – its block end_time will be in the future.
-        let recent_ts = (Utc::now() - chrono::Duration::minutes(30))
-            .format("%Y-%m-%dT%H:%M:%SZ")
-            .to_string();
-        let entries = vec![make_entry(&recent_ts, 10, 5, "claude-3-5-sonnet")];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let entries = vec![make_entry(
+            "2024-01-15T10:30:00Z",
+            10,
+            5,
+            "claude-3-5-sonnet",
+        )];
+        // The block runs 10:00–15:00.
+        let clock = MockClock::new(
+            DateTime::parse_from_rfc3339("2024-01-15T14:59:59Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        let analyzer = analyzer().with_clock(clock.shared());
 
-        let active: Vec<_> = blocks.iter().filter(|b| b.is_active).collect();
-        assert_eq!(active.len(), 1);
+        let blocks = analyzer.transform_to_blocks(&entries, BlockStrategy::default());
+        assert_eq!(blocks.iter().filter(|b| b.is_active).count(), 1);
+
+        clock.advance(chrono::Duration::seconds(1));
+        let blocks = analyzer.transform_to_blocks(&entries, BlockStrategy::default());
+        assert_eq!(blocks.iter().filter(|b| b.is_active).count(), 0);
     }
 
     #[test]
@@ -519,7 +942,7 @@ mod tests {
             50,
             "claude-3-5-sonnet",
         )];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         assert!(!blocks[0].is_active);
     }
 
@@ -539,7 +962,7 @@ mod tests {
                 "claude-3-5-sonnet-20241022",
             ),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         let stats = blocks[0].per_model_stats.get("claude-3-5-sonnet").unwrap();
         assert_eq!(stats.input_tokens, 300);
         assert_eq!(stats.output_tokens, 150);
@@ -557,7 +980,7 @@ mod tests {
             ),
             make_entry("2024-01-15T11:00:00Z", 200, 100, "claude-3-haiku-20240307"),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         assert!(blocks[0].per_model_stats.contains_key("claude-3-5-sonnet"));
         assert!(blocks[0].per_model_stats.contains_key("claude-3-haiku"));
     }
@@ -568,7 +991,7 @@ mod tests {
             make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
             make_entry("2024-01-15T12:00:00Z", 200, 100, "claude-3-5-sonnet"),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         let expected = DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z")
             .unwrap()
             .with_timezone(&Utc);
@@ -619,6 +1042,37 @@ mod tests {
         assert_eq!(limits[0].limit_type, "general_limit");
     }
 
+    #[test]
+    fn test_detect_limits_over_many_entries() {
+        let raw: Vec<serde_json::Value> = (0..2_000)
+            .map(|i| {
+                let content = match i % 4 {
+                    0 => format!("Opus rate limit exceeded. Please wait {} minutes.", i % 60),
+                    1 => "You have hit a rate limit. Please wait.".to_string(),
+                    _ => "All good".to_string(),
+                };
+                serde_json::json!({
+                    "type": "system",
+                    "timestamp": "2024-01-15T10:00:00Z",
+                    "content": content,
+                })
+            })
+            .collect();
+
+        let limits = analyzer().detect_limits(&raw);
+        assert_eq!(limits.len(), 1_000);
+        let opus: Vec<_> = limits
+            .iter()
+            .filter(|l| l.limit_type == "opus_limit")
+            .collect();
+        assert_eq!(opus.len(), 500);
+        assert!(opus.iter().all(|l| l.reset_time.is_some()));
+        assert_eq!(
+            opus[1].reset_time,
+            Some(opus[1].timestamp + TimeDelta::minutes(4))
+        );
+    }
+
     #[test]
     fn test_detect_limits_no_limit_content() {
         let raw = vec![serde_json::json!({
@@ -641,6 +1095,53 @@ mod tests {
         assert!(limits.is_empty());
     }
 
+    // ── infer_session_hours ───────────────────────────────────────────────────
+
+    fn reset_detection(at: &str, reset: &str) -> LimitDetection {
+        let parse = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        LimitDetection {
+            limit_type: "general_limit".to_string(),
+            timestamp: parse(at),
+            content: "limit reached".to_string(),
+            reset_time: Some(parse(reset)),
+        }
+    }
+
+    #[test]
+    fn test_infer_session_hours_uses_median_window() {
+        let entries = vec![
+            make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-16T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
+            make_entry("2024-01-17T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
+        ];
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+        let detections = vec![
+            reset_detection("2024-01-15T12:00:00Z", "2024-01-15T16:00:00Z"),
+            reset_detection("2024-01-16T12:00:00Z", "2024-01-16T16:00:00Z"),
+            reset_detection("2024-01-17T12:00:00Z", "2024-01-17T20:00:00Z"),
+        ];
+        assert_eq!(
+            SessionAnalyzer::infer_session_hours(&blocks, &detections),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_infer_session_hours_none_without_resets() {
+        let entries = vec![make_entry(
+            "2024-01-15T10:00:00Z",
+            100,
+            50,
+            "claude-3-5-sonnet",
+        )];
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
+        assert_eq!(SessionAnalyzer::infer_session_hours(&blocks, &[]), None);
+
+        // A reset two days out is not a session window.
+        let far = reset_detection("2024-01-15T12:00:00Z", "2024-01-17T12:00:00Z");
+        assert_eq!(SessionAnalyzer::infer_session_hours(&blocks, &[far]), None);
+    }
+
     // ── Helpers ───────────────────────────────────────────────────────────────
 
     #[test]
@@ -676,7 +1177,7 @@ mod tests {
             make_entry("2024-01-15T10:00:00Z", 100, 50, "claude-3-5-sonnet"),
             make_entry("2024-01-15T22:00:00Z", 200, 100, "claude-3-5-sonnet"),
         ];
-        let blocks = analyzer().transform_to_blocks(&entries);
+        let blocks = analyzer().transform_to_blocks(&entries, BlockStrategy::default());
         let gap = blocks.iter().find(|b| b.is_gap).unwrap();
         assert!(gap.id.starts_with("gap-2024-01-15T10:00:00Z"));
     }
@@ -0,0 +1,584 @@
+//! Plain-text export formats for aggregated usage and the full analysis.
+//!
+//! Used by the daily/monthly/projects views when `--output` selects
+//! something other than the interactive table.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use monitor_core::models::{
+    BurnRate, LimitMessage, ModelStats, SessionBlock, TokenCounts, TokensBasis,
+};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Serialize, Serializer};
+
+use crate::aggregator::AggregatedPeriod;
+use crate::analysis::AnalysisResult;
+
+/// How much of each session block a JSON export contains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportLevel {
+    /// Blocks without their per-call `entries` arrays.
+    Summary,
+    /// Every block field, including all entries.
+    #[default]
+    Full,
+}
+
+impl ExportLevel {
+    /// Parse an `--export-level` value; anything but `"summary"` is
+    /// [`Full`](Self::Full).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "summary" => Self::Summary,
+            _ => Self::Full,
+        }
+    }
+}
+
+/// How model names appear in exported data (`--anonymize-models`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModelAnonymization {
+    /// Model ids as recorded.
+    #[default]
+    Keep,
+    /// Model ids coarsened to their family (`opus`, `sonnet`, `haiku`,
+    /// `other`); per-model figures of one family are merged.
+    Family,
+    /// No per-model detail at all, only the totals.
+    Total,
+}
+
+impl ModelAnonymization {
+    /// Parse an `--anonymize-models` value; unknown names keep the models.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "family" => Self::Family,
+            "total" => Self::Total,
+            _ => Self::Keep,
+        }
+    }
+}
+
+/// Family of `model` as used by [`ModelAnonymization::Family`], e.g.
+/// `"sonnet"` for `claude-3-5-sonnet-20241022`.
+pub fn model_family(model: &str) -> String {
+    let lower = model.to_lowercase();
+    let family = ["opus", "sonnet", "haiku"]
+        .into_iter()
+        .find(|family| lower.contains(family))
+        .unwrap_or("other");
+    family.to_string()
+}
+
+/// Apply `mode` to the model names and per-model breakdowns of `periods`.
+/// Token and cost totals are unchanged.
+pub fn anonymize_periods(
+    mut periods: Vec<AggregatedPeriod>,
+    mode: ModelAnonymization,
+) -> Vec<AggregatedPeriod> {
+    if mode == ModelAnonymization::Keep {
+        return periods;
+    }
+    for period in &mut periods {
+        let breakdowns = std::mem::take(&mut period.model_breakdowns);
+        let models = std::mem::take(&mut period.models_used);
+        if mode == ModelAnonymization::Total {
+            continue;
+        }
+        period.models_used = models.iter().map(|m| model_family(m)).collect();
+        for (model, stats) in &breakdowns {
+            period
+                .model_breakdowns
+                .entry(model_family(model))
+                .or_default()
+                .merge(stats);
+        }
+    }
+    periods
+}
+
+/// Apply `mode` to every model name in `result`: block model lists,
+/// per-model stats and entries. Token and cost totals are unchanged.
+pub fn anonymize_analysis(mut result: AnalysisResult, mode: ModelAnonymization) -> AnalysisResult {
+    if mode == ModelAnonymization::Keep {
+        return result;
+    }
+    for block in &mut result.blocks {
+        let per_model = std::mem::take(&mut block.per_model_stats);
+        let models = std::mem::take(&mut block.models);
+        for entry in &mut block.entries {
+            entry.model = match mode {
+                ModelAnonymization::Family => model_family(&entry.model),
+                _ => String::new(),
+            };
+        }
+        if mode == ModelAnonymization::Total {
+            continue;
+        }
+
+        let mut seen = HashSet::new();
+        block.models = models
+            .iter()
+            .map(|m| model_family(m))
+            .filter(|family| seen.insert(family.clone()))
+            .collect();
+        for (model, stats) in per_model {
+            let merged = block
+                .per_model_stats
+                .entry(model_family(&model))
+                .or_default();
+            merged.input_tokens += stats.input_tokens;
+            merged.output_tokens += stats.output_tokens;
+            merged.cache_creation_tokens += stats.cache_creation_tokens;
+            merged.cache_read_tokens += stats.cache_read_tokens;
+            merged.cost_usd += stats.cost_usd;
+            merged.entries_count += stats.entries_count;
+        }
+    }
+    result
+}
+
+/// Serialize `result` as pretty-printed JSON to `writer`.
+///
+/// The layout matches `AnalysisResult`'s own serialization; with
+/// [`ExportLevel::Summary`] each block's `entries` field is left out.
+pub fn write_analysis_json(
+    writer: impl std::io::Write,
+    result: &AnalysisResult,
+    level: ExportLevel,
+) -> serde_json::Result<()> {
+    match level {
+        ExportLevel::Full => serde_json::to_writer_pretty(writer, result),
+        ExportLevel::Summary => serde_json::to_writer_pretty(writer, &SummaryAnalysis(result)),
+    }
+}
+
+/// Header row written by [`periods_to_csv`].
+pub const CSV_HEADER: &str = "period,models,input_tokens,output_tokens,cache_creation_tokens,cache_read_tokens,total_tokens,cost_usd";
+
+/// Render `periods` as CSV, one row per period in the given order.
+///
+/// Models are sorted and joined with `;` so the column stays a single field.
+/// The output always ends with a newline.
+pub fn periods_to_csv(periods: &[AggregatedPeriod]) -> String {
+    periods_to_csv_for(periods, TokensBasis::All)
+}
+
+/// [`periods_to_csv`] with `total_tokens` counted on `basis`.
+pub fn periods_to_csv_for(periods: &[AggregatedPeriod], basis: TokensBasis) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+
+    for period in periods {
+        let mut models: Vec<&str> = period.models_used.iter().map(String::as_str).collect();
+        models.sort_unstable();
+
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{:.4}\n",
+            csv_field(&period.period_key),
+            csv_field(&models.join(";")),
+            period.stats.input_tokens,
+            period.stats.output_tokens,
+            period.stats.cache_creation_tokens,
+            period.stats.cache_read_tokens,
+            period.stats.total_for(basis),
+            period.stats.cost,
+        ));
+    }
+
+    out
+}
+
+/// Render `periods` as "wide" CSV with one token column per model.
+///
+/// Columns are `period, <model>_tokens..., total_tokens, cost_usd`, where the
+/// model set is the sorted union across all periods and a model that is
+/// absent from a period gets `0`.
+pub fn periods_to_wide_csv(periods: &[AggregatedPeriod]) -> String {
+    periods_to_wide_csv_for(periods, TokensBasis::All)
+}
+
+/// [`periods_to_wide_csv`] with the token columns counted on `basis`.
+pub fn periods_to_wide_csv_for(periods: &[AggregatedPeriod], basis: TokensBasis) -> String {
+    let models: BTreeSet<&str> = periods
+        .iter()
+        .flat_map(|p| p.model_breakdowns.keys().map(String::as_str))
+        .collect();
+
+    let mut header = vec!["period".to_string()];
+    header.extend(models.iter().map(|m| csv_field(&format!("{m}_tokens"))));
+    header.push("total_tokens".to_string());
+    header.push("cost_usd".to_string());
+
+    let mut out = header.join(",");
+    out.push('\n');
+
+    for period in periods {
+        let mut row = vec![csv_field(&period.period_key)];
+        row.extend(models.iter().map(|model| {
+            period
+                .model_breakdowns
+                .get(*model)
+                .map_or(0, |stats| stats.total_for(basis))
+                .to_string()
+        }));
+        row.push(period.stats.total_for(basis).to_string());
+        row.push(format!("{:.4}", period.stats.cost));
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// [`AnalysisResult`] serialized with [`SummaryBlock`]s.
+struct SummaryAnalysis<'a>(&'a AnalysisResult);
+
+impl Serialize for SummaryAnalysis<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let result = self.0;
+        let mut state = serializer.serialize_struct("AnalysisResult", 5)?;
+        state.serialize_field("blocks", &SummaryBlocks(&result.blocks))?;
+        state.serialize_field("metadata", &result.metadata)?;
+        state.serialize_field("entries_count", &result.entries_count)?;
+        state.serialize_field("total_tokens", &result.total_tokens)?;
+        state.serialize_field("total_cost", &result.total_cost)?;
+        state.end()
+    }
+}
+
+/// Write every non-gap block of `result` as one compact JSON object per
+/// line (JSON Lines), ordered by start time.
+///
+/// With [`ExportLevel::Summary`] each line leaves out the block's `entries`.
+pub fn write_blocks_jsonl(
+    mut writer: impl std::io::Write,
+    result: &AnalysisResult,
+    level: ExportLevel,
+) -> serde_json::Result<()> {
+    let mut blocks: Vec<&SessionBlock> = result.blocks.iter().filter(|b| !b.is_gap).collect();
+    blocks.sort_by_key(|b| b.start_time);
+    for block in blocks {
+        match level {
+            ExportLevel::Full => serde_json::to_writer(&mut writer, block)?,
+            ExportLevel::Summary => serde_json::to_writer(&mut writer, &SummaryBlock::from(block))?,
+        }
+        writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+    }
+    Ok(())
+}
+
+/// A block list serialized one [`SummaryBlock`] at a time.
+struct SummaryBlocks<'a>(&'a [SessionBlock]);
+
+impl Serialize for SummaryBlocks<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for block in self.0 {
+            seq.serialize_element(&SummaryBlock::from(block))?;
+        }
+        seq.end()
+    }
+}
+
+/// A [`SessionBlock`] serialized without its `entries`, borrowing every
+/// other field.
+#[derive(Serialize)]
+struct SummaryBlock<'a> {
+    id: &'a str,
+    label: &'a str,
+    start_time: &'a DateTime<Utc>,
+    end_time: &'a DateTime<Utc>,
+    token_counts: &'a TokenCounts,
+    is_active: bool,
+    is_gap: bool,
+    burn_rate: &'a Option<BurnRate>,
+    actual_end_time: &'a Option<DateTime<Utc>>,
+    per_model_stats: &'a HashMap<String, ModelStats>,
+    models: &'a [String],
+    sent_messages_count: u32,
+    cost_usd: f64,
+    limit_messages: &'a [LimitMessage],
+    projection_data: &'a Option<serde_json::Value>,
+    burn_rate_snapshot: &'a Option<BurnRate>,
+}
+
+impl<'a> From<&'a SessionBlock> for SummaryBlock<'a> {
+    fn from(block: &'a SessionBlock) -> Self {
+        Self {
+            id: &block.id,
+            label: &block.label,
+            start_time: &block.start_time,
+            end_time: &block.end_time,
+            token_counts: &block.token_counts,
+            is_active: block.is_active,
+            is_gap: block.is_gap,
+            burn_rate: &block.burn_rate,
+            actual_end_time: &block.actual_end_time,
+            per_model_stats: &block.per_model_stats,
+            models: &block.models,
+            sent_messages_count: block.sent_messages_count,
+            cost_usd: block.cost_usd,
+            limit_messages: &block.limit_messages,
+            projection_data: &block.projection_data,
+            burn_rate_snapshot: &block.burn_rate_snapshot,
+        }
+    }
+}
+
+/// Quote `value` when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregator::UsageAggregator;
+    use chrono::{DateTime, Utc};
+    use monitor_core::models::UsageEntry;
+
+    fn make_entry(ts_str: &str, input: u64, output: u64, cost: f64, model: &str) -> UsageEntry {
+        UsageEntry {
+            timestamp: DateTime::parse_from_rfc3339(ts_str)
+                .unwrap()
+                .with_timezone(&Utc),
+            input_tokens: input,
+            output_tokens: output,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd: cost,
+            model: model.to_string(),
+            message_id: ts_str.to_string(),
+            request_id: ts_str.to_string(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        }
+    }
+
+    // ── periods_to_csv ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_csv_header_only_for_no_periods() {
+        assert_eq!(periods_to_csv(&[]), format!("{CSV_HEADER}\n"));
+    }
+
+    #[test]
+    fn test_csv_one_row_per_period() {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.5, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T09:00:00Z", 10, 5, 0.25, "claude-3-opus"),
+            make_entry("2024-01-16T10:00:00Z", 300, 150, 1.0, "claude-3-5-sonnet"),
+        ];
+        let periods = UsageAggregator::aggregate_daily(&entries);
+        let csv = periods_to_csv(&periods);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("2024-01-15,"));
+        assert!(lines[1].ends_with(",110,55,0,0,165,0.7500"));
+        assert!(lines[2].starts_with("2024-01-16,"));
+    }
+
+    // ── periods_to_wide_csv ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_wide_csv_has_column_per_model() {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.5, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T09:00:00Z", 10, 5, 0.25, "claude-3-opus"),
+            make_entry("2024-01-16T10:00:00Z", 300, 150, 1.0, "claude-3-5-sonnet"),
+        ];
+        let periods = UsageAggregator::aggregate_daily(&entries);
+        let csv = periods_to_wide_csv(&periods);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "period,claude-3-5-sonnet_tokens,claude-3-opus_tokens,total_tokens,cost_usd"
+        );
+        assert_eq!(lines[1], "2024-01-15,150,15,165,0.7500");
+        // Opus was not used on the 16th → 0.
+        assert_eq!(lines[2], "2024-01-16,450,0,450,1.0000");
+    }
+
+    #[test]
+    fn test_csv_totals_follow_tokens_basis() {
+        let mut entry = make_entry("2024-01-15T08:00:00Z", 100, 50, 0.5, "claude-3-5-sonnet");
+        entry.cache_creation_tokens = 1_000;
+        entry.cache_read_tokens = 2_000;
+        let periods = UsageAggregator::aggregate_daily(&[entry]);
+
+        let all = periods_to_csv(&periods);
+        assert!(all.ends_with(",100,50,1000,2000,3150,0.5000\n"), "{all}");
+        let io = periods_to_csv_for(&periods, TokensBasis::Io);
+        assert!(io.ends_with(",100,50,1000,2000,150,0.5000\n"), "{io}");
+
+        assert_eq!(
+            periods_to_wide_csv_for(&periods, TokensBasis::Io)
+                .lines()
+                .nth(1),
+            Some("2024-01-15,150,150,0.5000")
+        );
+    }
+
+    #[test]
+    fn test_wide_csv_header_only_for_no_periods() {
+        assert_eq!(periods_to_wide_csv(&[]), "period,total_tokens,cost_usd\n");
+    }
+
+    // ── write_analysis_json ───────────────────────────────────────────────────
+
+    fn sample_analysis() -> AnalysisResult {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.5, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T09:00:00Z", 10, 5, 0.25, "claude-3-opus"),
+        ];
+        crate::analysis::analyze_entries(
+            entries,
+            None,
+            false,
+            crate::analyzer::BlockStrategy::default(),
+        )
+    }
+
+    fn export_json(result: &AnalysisResult, level: ExportLevel) -> serde_json::Value {
+        let mut out = Vec::new();
+        write_analysis_json(&mut out, result, level).unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn test_full_export_keeps_entries() {
+        let json = export_json(&sample_analysis(), ExportLevel::Full);
+        assert_eq!(json["blocks"][0]["entries"].as_array().unwrap().len(), 2);
+        assert_eq!(json["entries_count"], 2);
+    }
+
+    #[test]
+    fn test_summary_export_drops_only_entries() {
+        let result = sample_analysis();
+        let summary = export_json(&result, ExportLevel::Summary);
+
+        let mut expected = export_json(&result, ExportLevel::Full);
+        for block in expected["blocks"].as_array_mut().unwrap() {
+            block.as_object_mut().unwrap().remove("entries");
+        }
+        assert_eq!(summary, expected);
+    }
+
+    // ── write_blocks_jsonl ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_jsonl_writes_one_line_per_non_gap_block() {
+        let entries = vec![
+            make_entry("2024-01-15T08:00:00Z", 100, 50, 0.5, "claude-3-5-sonnet"),
+            make_entry("2024-01-15T20:00:00Z", 10, 5, 0.25, "claude-3-opus"),
+        ];
+        let result = crate::analysis::analyze_entries(
+            entries,
+            None,
+            false,
+            crate::analyzer::BlockStrategy::default(),
+        );
+        assert!(result.blocks.iter().any(|b| b.is_gap));
+
+        let mut out = Vec::new();
+        write_blocks_jsonl(&mut out, &result, ExportLevel::Summary).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            lines.len(),
+            result.blocks.iter().filter(|b| !b.is_gap).count()
+        );
+        assert!(lines[0]["start_time"].as_str() < lines[1]["start_time"].as_str());
+        assert_eq!(lines[0]["token_counts"]["input_tokens"], 100);
+        assert_eq!(lines[1]["models"][0], "claude-3-opus");
+        assert!(lines[0].get("entries").is_none());
+        assert!(lines[0].get("sent_messages_count").is_some());
+    }
+
+    // ── Model anonymization ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_model_family() {
+        assert_eq!(model_family("claude-3-5-sonnet-20241022"), "sonnet");
+        assert_eq!(model_family("claude-opus-4-20250514"), "opus");
+        assert_eq!(model_family("unknown"), "other");
+    }
+
+    #[test]
+    fn test_anonymized_wide_csv_merges_families() {
+        let entries = vec![
+            make_entry(
+                "2024-01-15T08:00:00Z",
+                100,
+                50,
+                0.5,
+                "claude-3-5-sonnet-20241022",
+            ),
+            make_entry(
+                "2024-01-15T09:00:00Z",
+                10,
+                5,
+                0.25,
+                "claude-sonnet-4-20250514",
+            ),
+        ];
+        let periods = UsageAggregator::aggregate_daily(&entries);
+
+        let family = anonymize_periods(periods.clone(), ModelAnonymization::Family);
+        let csv = periods_to_wide_csv(&family);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "period,sonnet_tokens,total_tokens,cost_usd");
+        assert_eq!(lines[1], "2024-01-15,165,165,0.7500");
+
+        let total = anonymize_periods(periods, ModelAnonymization::Total);
+        assert_eq!(
+            periods_to_wide_csv(&total),
+            "period,total_tokens,cost_usd\n2024-01-15,165,0.7500\n"
+        );
+        assert!(periods_to_csv(&total).contains("\n2024-01-15,,110,55,"));
+    }
+
+    #[test]
+    fn test_anonymized_json_hides_model_ids() {
+        let result = anonymize_analysis(sample_analysis(), ModelAnonymization::Family);
+        let json = export_json(&result, ExportLevel::Full).to_string();
+        assert!(!json.contains("claude-3"), "{json}");
+        let block = &result.blocks[0];
+        assert_eq!(block.models, vec!["sonnet", "opus"]);
+        assert_eq!(block.per_model_stats["opus"].input_tokens, 10);
+        assert_eq!(result.total_tokens, sample_analysis().total_tokens);
+
+        let result = anonymize_analysis(sample_analysis(), ModelAnonymization::Total);
+        assert!(result.blocks[0].per_model_stats.is_empty());
+        assert!(result.blocks[0].entries.iter().all(|e| e.model.is_empty()));
+    }
+
+    #[test]
+    fn test_export_level_from_name() {
+        assert_eq!(ExportLevel::from_name("summary"), ExportLevel::Summary);
+        assert_eq!(ExportLevel::from_name("full"), ExportLevel::Full);
+    }
+
+    #[test]
+    fn test_csv_field_quotes_special_characters() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,294 @@
+//! `tail -f`-style incremental reading of the JSONL usage files.
+//!
+//! [`FollowReader`] remembers how far into each file it has read and, on
+//! every [`FollowReader::poll`], parses only the complete lines appended
+//! since the previous poll.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use monitor_core::models::UsageEntry;
+use tracing::warn;
+
+use crate::reader::{
+    find_jsonl_files, process_lines, project_for_file, resolve_data_path, FileFilter, LoadOptions,
+    ParseState, NO_DATA_PATH,
+};
+
+// ── FollowReader ──────────────────────────────────────────────────────────────
+
+/// Incremental reader that yields only entries not seen on earlier polls.
+pub struct FollowReader {
+    /// Directory scanned for `.jsonl` files on every poll; `None` when no
+    /// path was given and there is no home directory, so nothing is read.
+    data_path: Option<PathBuf>,
+    /// Which files under `data_path` to read.
+    file_filter: FileFilter,
+    /// Byte offset of the first unread line in each known file.
+    offsets: HashMap<PathBuf, u64>,
+    /// Dedup hashes and pricing carried across polls.
+    state: ParseState,
+}
+
+impl FollowReader {
+    /// Create a reader over `data_path` (defaults to `~/.claude/projects`).
+    ///
+    /// The first [`poll`](Self::poll) returns everything already on disk;
+    /// call [`seek_to_end`](Self::seek_to_end) first to only see new lines.
+    pub fn new(data_path: Option<&str>, options: &LoadOptions) -> Self {
+        let data_path = resolve_data_path(data_path);
+        if data_path.is_none() {
+            warn!("{NO_DATA_PATH}");
+        }
+        Self {
+            data_path,
+            file_filter: options.file_filter.clone(),
+            offsets: HashMap::new(),
+            state: ParseState::new(None, options.cost_mode.clone(), false, options),
+        }
+    }
+
+    /// Mark every existing file as fully read without parsing it.
+    pub fn seek_to_end(&mut self) {
+        for file in self.jsonl_files() {
+            let len = std::fs::metadata(&file).map(|m| m.len()).unwrap_or(0);
+            self.offsets.insert(file, len);
+        }
+    }
+
+    /// Parse lines appended since the last poll, sorted by timestamp.
+    ///
+    /// Files that appeared since the last poll are read from the start. A
+    /// trailing line without a newline is left for the next poll, and a file
+    /// that shrank (truncated or rotated) is re-read from the beginning.
+    pub fn poll(&mut self) -> Vec<UsageEntry> {
+        let mut entries = Vec::new();
+        for file in self.jsonl_files() {
+            entries.extend(self.read_new_lines(&file));
+        }
+        entries.sort_by_key(|e| e.timestamp);
+        entries
+    }
+
+    /// The `.jsonl` files currently under the data path.
+    fn jsonl_files(&self) -> Vec<PathBuf> {
+        self.data_path
+            .as_deref()
+            .map(|path| find_jsonl_files(path, &self.file_filter))
+            .unwrap_or_default()
+    }
+
+    /// Read and parse the complete lines past the stored offset of `file`.
+    fn read_new_lines(&mut self, file: &Path) -> Vec<UsageEntry> {
+        let offset = self.offsets.get(file).copied().unwrap_or(0);
+
+        let mut handle = match std::fs::File::open(file) {
+            Ok(f) => f,
+            Err(e) => {
+                warn!("Failed to read file {}: {}", file.display(), e);
+                return Vec::new();
+            }
+        };
+        let len = handle.metadata().map(|m| m.len()).unwrap_or(0);
+        let start = if len < offset { 0 } else { offset };
+        if len == start {
+            return Vec::new();
+        }
+
+        let mut buf = Vec::new();
+        if let Err(e) = handle
+            .seek(SeekFrom::Start(start))
+            .and_then(|_| handle.read_to_end(&mut buf))
+        {
+            warn!("Failed to read file {}: {}", file.display(), e);
+            return Vec::new();
+        }
+
+        // Only consume up to the last newline; a partial line is still being
+        // written and will be picked up on a later poll.
+        let Some(last_newline) = buf.iter().rposition(|&b| b == b'\n') else {
+            return Vec::new();
+        };
+        let complete = &buf[..=last_newline];
+        self.offsets
+            .insert(file.to_path_buf(), start + complete.len() as u64);
+
+        let (entries, _) = process_lines(complete, &file.display().to_string(), &mut self.state);
+        let project = self
+            .data_path
+            .as_deref()
+            .map(|root| project_for_file(root, file))
+            .unwrap_or_default();
+        entries
+            .into_iter()
+            .map(|mut entry| {
+                entry.project.clone_from(&project);
+                entry
+            })
+            .collect()
+    }
+}
+
+// ── Formatting ────────────────────────────────────────────────────────────────
+
+/// Render `entry` as one compact, pipe-friendly line of plain text.
+///
+/// Format: `<rfc3339 timestamp> <model> in=<n> out=<n> cache=<create>/<read> $<cost>`.
+pub fn format_follow_line(entry: &UsageEntry) -> String {
+    let model = if entry.model.is_empty() {
+        "unknown"
+    } else {
+        entry.model.as_str()
+    };
+    format!(
+        "{} {} in={} out={} cache={}/{} ${:.4}",
+        entry.timestamp.format("%Y-%m-%dT%H:%M:%SZ"),
+        model,
+        entry.input_tokens,
+        entry.output_tokens,
+        entry.cache_creation_tokens,
+        entry.cache_read_tokens,
+        entry.cost_usd,
+    )
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn sample_entry(ts: &str, input: u64, msg_id: &str) -> String {
+        serde_json::json!({
+            "timestamp": ts,
+            "input_tokens": input,
+            "output_tokens": 10,
+            "model": "claude-3-5-sonnet-20241022",
+            "message_id": msg_id,
+            "requestId": format!("req-{msg_id}"),
+        })
+        .to_string()
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        write!(file, "{}", text).unwrap();
+    }
+
+    fn reader_for(dir: &TempDir) -> FollowReader {
+        FollowReader::new(Some(dir.path().to_str().unwrap()), &LoadOptions::default())
+    }
+
+    // ── poll ──────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_poll_returns_only_new_entries() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.jsonl");
+        append(
+            &file,
+            &format!("{}\n", sample_entry("2024-01-15T10:00:00Z", 100, "m1")),
+        );
+
+        let mut reader = reader_for(&dir);
+        assert_eq!(reader.poll().len(), 1);
+        assert!(reader.poll().is_empty());
+
+        append(
+            &file,
+            &format!("{}\n", sample_entry("2024-01-15T10:05:00Z", 200, "m2")),
+        );
+        let new = reader.poll();
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].input_tokens, 200);
+    }
+
+    #[test]
+    fn test_seek_to_end_skips_existing_lines() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.jsonl");
+        append(
+            &file,
+            &format!("{}\n", sample_entry("2024-01-15T10:00:00Z", 100, "m1")),
+        );
+
+        let mut reader = reader_for(&dir);
+        reader.seek_to_end();
+        assert!(reader.poll().is_empty());
+
+        // Files created after seeking are read from the start.
+        let other = dir.path().join("b.jsonl");
+        append(
+            &other,
+            &format!("{}\n", sample_entry("2024-01-15T11:00:00Z", 300, "m3")),
+        );
+        assert_eq!(reader.poll().len(), 1);
+    }
+
+    #[test]
+    fn test_partial_line_waits_for_newline() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.jsonl");
+        let line = sample_entry("2024-01-15T10:00:00Z", 100, "m1");
+        let (head, tail) = line.split_at(line.len() / 2);
+
+        let mut reader = reader_for(&dir);
+        append(&file, head);
+        assert!(reader.poll().is_empty());
+
+        append(&file, &format!("{tail}\n"));
+        assert_eq!(reader.poll().len(), 1);
+    }
+
+    #[test]
+    fn test_truncated_file_is_reread() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.jsonl");
+        append(
+            &file,
+            &format!("{}\n", sample_entry("2024-01-15T10:00:00Z", 100, "m1")),
+        );
+        append(
+            &file,
+            &format!("{}\n", sample_entry("2024-01-15T10:01:00Z", 100, "m2")),
+        );
+
+        let mut reader = reader_for(&dir);
+        assert_eq!(reader.poll().len(), 2);
+
+        std::fs::write(
+            &file,
+            format!("{}\n", sample_entry("2024-01-15T12:00:00Z", 5, "m9")),
+        )
+        .unwrap();
+        let new = reader.poll();
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].input_tokens, 5);
+    }
+
+    // ── format_follow_line ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_format_follow_line() {
+        let dir = TempDir::new().unwrap();
+        append(
+            &dir.path().join("a.jsonl"),
+            &format!("{}\n", sample_entry("2024-01-15T10:00:00Z", 100, "m1")),
+        );
+        let entry = reader_for(&dir).poll().remove(0);
+
+        let line = format_follow_line(&entry);
+        assert!(
+            line.starts_with("2024-01-15T10:00:00Z claude-3-5-sonnet"),
+            "{line}"
+        );
+        assert!(line.contains(" in=100 out=10 cache=0/0 $"), "{line}");
+    }
+}
@@ -7,6 +7,14 @@
 pub mod aggregator;
 pub mod analysis;
 pub mod analyzer;
+pub mod export;
+pub mod follow;
+pub mod parse_cache;
+pub mod period;
 pub mod reader;
+#[cfg(any(test, feature = "testutil"))]
+pub mod testutil;
 
+pub use aggregator::{AggregatedPeriod, AggregatedStats, UsageAggregator};
 pub use monitor_core as core;
+pub use period::{Period, PeriodKind};
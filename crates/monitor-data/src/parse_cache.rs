@@ -0,0 +1,414 @@
+//! Parsed JSONL files kept on disk between runs.
+//!
+//! Parsing a long history dominates startup, yet most JSONL files never
+//! change once their session ends. The cache stores each file's parsed
+//! lines keyed by path, size and modification time, so the next load only
+//! re-reads files that are new or changed. The look-back cutoff and
+//! deduplication are applied after the cache, so they work as for a fresh
+//! read.
+//!
+//! The cache is dropped as a whole when the parser version or any option
+//! that affects parsing (cost mode, pricing, deduplication key, …) differs.
+//!
+//! The session being written to changes between almost every pair of loads,
+//! so a load whose only change is one already cached file re-parses it but
+//! leaves the cache file alone rather than rewrite the whole history.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use monitor_core::models::CostMode;
+
+use crate::reader::{LoadOptions, ParsedFile};
+
+/// File name of the cache inside the `cache/` directory of the
+/// [state directory](monitor_core::paths::state_dir).
+pub const PARSE_CACHE_FILE_NAME: &str = "parse-cache.json";
+
+/// Format of the parsed lines; bump whenever parsing or mapping changes so
+/// stale results are not reused.
+const PARSER_VERSION: u32 = 3;
+
+/// Size and modification time identifying one version of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    /// Length in bytes.
+    size: u64,
+    /// Last modification time.
+    modified: SystemTime,
+}
+
+impl FileStamp {
+    /// Stamp of `path`, or `None` when its metadata cannot be read.
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            modified: metadata.modified().ok()?,
+        })
+    }
+}
+
+/// One cached file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFile {
+    /// Version of the file that was parsed.
+    stamp: FileStamp,
+    /// Its parsed lines.
+    parsed: ParsedFile,
+}
+
+/// Serialized form of the cache.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheContents {
+    /// [`PARSER_VERSION`] and crate version that wrote the cache.
+    parser: String,
+    /// [`fingerprint`] of the options the files were parsed with.
+    options: String,
+    /// Parsed files keyed by path.
+    files: HashMap<PathBuf, CachedFile>,
+}
+
+/// Parsed files backed by a JSON file.
+///
+/// Load and save errors are logged and otherwise ignored; a missing or
+/// unreadable cache only means every file is parsed again.
+pub(crate) struct ParseCache {
+    /// Path to the JSON file holding the cache.
+    file: PathBuf,
+    /// Current contents.
+    contents: CacheContents,
+    /// Files looked up during this load.
+    seen: HashSet<PathBuf>,
+    /// Files parsed for the first time during this load.
+    added: usize,
+    /// Cached files parsed again during this load because they changed.
+    refreshed: usize,
+}
+
+impl ParseCache {
+    /// Open the cache at `file` for a load pricing with `mode` and `options`,
+    /// starting empty when it was written by another parser version or with
+    /// other options.
+    pub(crate) fn open(file: &Path, mode: &CostMode, options: &LoadOptions) -> Self {
+        let parser = parser_version();
+        let options = fingerprint(mode, options);
+        let contents = match Self::load(file) {
+            Some(contents) if contents.parser == parser && contents.options == options => contents,
+            Some(_) => {
+                debug!(path = %file.display(), "parse cache is outdated; starting empty");
+                CacheContents {
+                    parser,
+                    options,
+                    files: HashMap::new(),
+                }
+            }
+            None => CacheContents {
+                parser,
+                options,
+                files: HashMap::new(),
+            },
+        };
+        Self {
+            file: file.to_path_buf(),
+            contents,
+            seen: HashSet::new(),
+            added: 0,
+            refreshed: 0,
+        }
+    }
+
+    /// Parsed lines of `path`, from the cache when the file is unchanged,
+    /// otherwise from `parse` (which returns `None` when the file could not
+    /// be read).
+    pub(crate) fn get_or_parse(
+        &mut self,
+        path: &Path,
+        parse: impl FnOnce() -> Option<ParsedFile>,
+    ) -> Option<ParsedFile> {
+        self.seen.insert(path.to_path_buf());
+        let stamp = FileStamp::of(path);
+        if let (Some(stamp), Some(cached)) = (stamp, self.contents.files.get(path)) {
+            if cached.stamp == stamp {
+                return Some(cached.parsed.clone());
+            }
+        }
+
+        let parsed = parse()?;
+        if let Some(stamp) = stamp {
+            let previous = self.contents.files.insert(
+                path.to_path_buf(),
+                CachedFile {
+                    stamp,
+                    parsed: parsed.clone(),
+                },
+            );
+            if previous.is_some() {
+                self.refreshed += 1;
+            } else {
+                self.added += 1;
+            }
+        }
+        Some(parsed)
+    }
+
+    /// Drop files that no longer exist and write the cache if it changed by
+    /// more than one cached file being parsed again.
+    pub(crate) fn save(mut self) {
+        let before = self.contents.files.len();
+        let seen = &self.seen;
+        self.contents
+            .files
+            .retain(|path, _| seen.contains(path) || path.exists());
+        let removed = before != self.contents.files.len();
+        if self.added == 0 && self.refreshed <= 1 && !removed {
+            return;
+        }
+
+        let written = serde_json::to_vec(&self.contents)
+            .map_err(|e| e.to_string())
+            .and_then(|json| Self::write(&self.file, &json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            warn!(
+                error = %e,
+                path = %self.file.display(),
+                "failed to save the parse cache"
+            );
+        }
+    }
+
+    // ── Private helpers ───────────────────────────────────────────────────────
+
+    /// Write `json` to `path` through a temporary file, so an interrupted
+    /// write never leaves a truncated cache behind.
+    fn write(path: &Path, json: &[u8]) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Load the cache from `path`; `None` when it is missing or unreadable.
+    fn load(path: &Path) -> Option<CacheContents> {
+        if !path.exists() {
+            return None;
+        }
+        let parsed = std::fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_slice(&content).map_err(|e| e.to_string()));
+        parsed
+            .map_err(|e| {
+                warn!(
+                    error = %e,
+                    path = %path.display(),
+                    "failed to load the parse cache; parsing every file"
+                );
+            })
+            .ok()
+    }
+}
+
+/// Version tag of the parser, changing with [`PARSER_VERSION`] and with each
+/// release.
+fn parser_version() -> String {
+    format!("{PARSER_VERSION}/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// The options that change how a line is parsed and mapped.
+fn fingerprint(mode: &CostMode, options: &LoadOptions) -> String {
+    let pricing: Option<BTreeMap<_, _>> = options.pricing_overrides.as_ref().map(|overrides| {
+        overrides
+            .iter()
+            .map(|(model, pricing)| (model.as_str(), format!("{pricing:?}")))
+            .collect()
+    });
+    format!(
+        "{:?}|{}|{:?}|{}|{:?}",
+        mode, options.count_empty_messages, options.dedup_key, options.max_entry_tokens, pricing,
+    )
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::load_usage_entries_with_stats;
+    use tempfile::TempDir;
+
+    fn line(ts: &str, input: u64, msg_id: &str) -> String {
+        format!(
+            r#"{{"timestamp":"{ts}","message":{{"id":"{msg_id}","model":"claude-3-5-sonnet","usage":{{"input_tokens":{input},"output_tokens":10}}}},"requestId":"req_{msg_id}"}}"#
+        )
+    }
+
+    fn write(path: &Path, lines: &[String]) {
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    /// Total input tokens and duplicates loaded from `data`.
+    fn load_input(data: &Path, options: &LoadOptions) -> (u64, usize) {
+        let (entries, _, stats) = load_usage_entries_with_stats(
+            data.to_str(),
+            None,
+            options.cost_mode.clone(),
+            false,
+            options,
+            |_| {},
+        );
+        (
+            entries.iter().map(|e| e.input_tokens).sum(),
+            stats.duplicates,
+        )
+    }
+
+    fn cached_options(state: &TempDir) -> LoadOptions {
+        LoadOptions {
+            parse_cache: Some(state.path().join(PARSE_CACHE_FILE_NAME)),
+            ..LoadOptions::default()
+        }
+    }
+
+    /// Rewrite `path` with `lines` of the same length, keeping its
+    /// modification time, so only the cache can tell the versions apart.
+    fn rewrite_in_place(path: &Path, lines: &[String]) {
+        let modified = std::fs::metadata(path).unwrap().modified().unwrap();
+        let before = std::fs::metadata(path).unwrap().len();
+        write(path, lines);
+        assert_eq!(std::fs::metadata(path).unwrap().len(), before);
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_unchanged_files_come_from_the_cache() {
+        let (data, state) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let file = data.path().join("session.jsonl");
+        write(&file, &[line("2024-01-15T10:00:00Z", 100, "a")]);
+        let options = cached_options(&state);
+
+        assert_eq!(load_input(data.path(), &options).0, 100);
+        assert!(state.path().join(PARSE_CACHE_FILE_NAME).exists());
+
+        rewrite_in_place(&file, &[line("2024-01-15T10:00:00Z", 900, "a")]);
+        assert_eq!(load_input(data.path(), &options).0, 100);
+        // Without the cache the new content is read.
+        assert_eq!(load_input(data.path(), &LoadOptions::default()).0, 900);
+    }
+
+    #[test]
+    fn test_changed_files_are_parsed_again() {
+        let (data, state) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let file = data.path().join("session.jsonl");
+        write(&file, &[line("2024-01-15T10:00:00Z", 100, "a")]);
+        let options = cached_options(&state);
+        load_input(data.path(), &options);
+
+        write(
+            &file,
+            &[
+                line("2024-01-15T10:00:00Z", 100, "a"),
+                line("2024-01-15T11:00:00Z", 50, "b"),
+            ],
+        );
+        assert_eq!(load_input(data.path(), &options).0, 150);
+    }
+
+    #[test]
+    fn test_single_changed_file_does_not_rewrite_the_cache() {
+        let (data, state) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let active = data.path().join("active.jsonl");
+        write(&active, &[line("2024-01-15T10:00:00Z", 100, "a")]);
+        write(
+            &data.path().join("old.jsonl"),
+            &[line("2024-01-14T10:00:00Z", 10, "o")],
+        );
+        let options = cached_options(&state);
+        let cache_file = state.path().join(PARSE_CACHE_FILE_NAME);
+        load_input(data.path(), &options);
+        let saved = std::fs::read(&cache_file).unwrap();
+
+        write(
+            &active,
+            &[
+                line("2024-01-15T10:00:00Z", 100, "a"),
+                line("2024-01-15T11:00:00Z", 50, "b"),
+            ],
+        );
+        assert_eq!(load_input(data.path(), &options).0, 160);
+        assert_eq!(std::fs::read(&cache_file).unwrap(), saved);
+
+        // A new file is worth saving.
+        write(
+            &data.path().join("new.jsonl"),
+            &[line("2024-01-15T12:00:00Z", 1, "n")],
+        );
+        assert_eq!(load_input(data.path(), &options).0, 161);
+        assert_ne!(std::fs::read(&cache_file).unwrap(), saved);
+    }
+
+    #[test]
+    fn test_cache_saved_into_missing_directory() {
+        let (data, state) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        write(
+            &data.path().join("session.jsonl"),
+            &[line("2024-01-15T10:00:00Z", 100, "a")],
+        );
+        let cache_file = state.path().join("cache").join(PARSE_CACHE_FILE_NAME);
+        let options = LoadOptions {
+            parse_cache: Some(cache_file.clone()),
+            ..LoadOptions::default()
+        };
+        load_input(data.path(), &options);
+        assert!(cache_file.exists());
+        assert!(!cache_file.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_other_options_invalidate_the_cache() {
+        let (data, state) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let file = data.path().join("session.jsonl");
+        write(&file, &[line("2024-01-15T10:00:00Z", 100, "a")]);
+        load_input(data.path(), &cached_options(&state));
+
+        rewrite_in_place(&file, &[line("2024-01-15T10:00:00Z", 900, "a")]);
+        let options = LoadOptions {
+            cost_mode: CostMode::Calculated,
+            ..cached_options(&state)
+        };
+        assert_eq!(load_input(data.path(), &options).0, 900);
+    }
+
+    #[test]
+    fn test_cached_files_are_still_deduplicated() {
+        let (data, state) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let same = [line("2024-01-15T10:00:00Z", 100, "a")];
+        write(&data.path().join("one.jsonl"), &same);
+        write(&data.path().join("two.jsonl"), &same);
+        let options = cached_options(&state);
+
+        assert_eq!(load_input(data.path(), &options), (100, 1));
+        assert_eq!(load_input(data.path(), &options), (100, 1));
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_ignored() {
+        let (data, state) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        write(
+            &data.path().join("session.jsonl"),
+            &[line("2024-01-15T10:00:00Z", 100, "a")],
+        );
+        std::fs::write(state.path().join(PARSE_CACHE_FILE_NAME), "not json").unwrap();
+        assert_eq!(load_input(data.path(), &cached_options(&state)).0, 100);
+    }
+}
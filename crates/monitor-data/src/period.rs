@@ -0,0 +1,180 @@
+//! Typed time periods for the aggregate views.
+//!
+//! A [`Period`] is the calendar bucket a timestamp falls into. Aggregations
+//! key on the typed value, so periods sort chronologically whatever their
+//! string form, and [`Period::to_key`] / [`Period::parse`] convert to and
+//! from the keys shown in tables and exports.
+
+use chrono::{DateTime, Datelike, IsoWeek, NaiveDate, NaiveDateTime, Timelike, Utc, Weekday};
+
+/// Granularity of a [`Period`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PeriodKind {
+    /// One clock hour, key `"2024-01-15 14:00"`.
+    Hourly,
+    /// One calendar day, key `"2024-01-15"`.
+    #[default]
+    Daily,
+    /// One ISO week (Monday to Sunday), key `"2024-W03"`.
+    Weekly,
+    /// One calendar month, key `"2024-01"`.
+    Monthly,
+}
+
+impl PeriodKind {
+    /// Parse a view name; anything unrecognised is [`Daily`](Self::Daily).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "hourly" => Self::Hourly,
+            "weekly" => Self::Weekly,
+            "monthly" => Self::Monthly,
+            _ => Self::Daily,
+        }
+    }
+}
+
+/// The calendar period containing a timestamp.
+///
+/// Periods of the same kind order chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Period {
+    /// The clock hour starting at this UTC time.
+    Hourly(DateTime<Utc>),
+    /// A calendar day.
+    Daily(NaiveDate),
+    /// An ISO week.
+    Weekly(IsoWeek),
+    /// A calendar month as `(year, month)`, month `1..=12`.
+    Monthly(i32, u32),
+}
+
+impl Period {
+    /// The `kind` period containing `ts`.
+    pub fn of(kind: PeriodKind, ts: DateTime<Utc>) -> Self {
+        match kind {
+            PeriodKind::Hourly => Self::Hourly(
+                ts.with_minute(0)
+                    .and_then(|t| t.with_second(0))
+                    .and_then(|t| t.with_nanosecond(0))
+                    .unwrap_or(ts),
+            ),
+            PeriodKind::Daily => Self::Daily(ts.date_naive()),
+            PeriodKind::Weekly => Self::Weekly(ts.iso_week()),
+            PeriodKind::Monthly => Self::Monthly(ts.year(), ts.month()),
+        }
+    }
+
+    /// Granularity of this period.
+    pub fn kind(&self) -> PeriodKind {
+        match self {
+            Self::Hourly(_) => PeriodKind::Hourly,
+            Self::Daily(_) => PeriodKind::Daily,
+            Self::Weekly(_) => PeriodKind::Weekly,
+            Self::Monthly(..) => PeriodKind::Monthly,
+        }
+    }
+
+    /// The display key, e.g. `"2024-01-15"` or `"2024-W03"`.
+    pub fn to_key(&self) -> String {
+        match self {
+            Self::Hourly(start) => start.format("%Y-%m-%d %H:00").to_string(),
+            Self::Daily(date) => date.format("%Y-%m-%d").to_string(),
+            Self::Weekly(week) => format!("{}-W{:02}", week.year(), week.week()),
+            Self::Monthly(year, month) => format!("{year:04}-{month:02}"),
+        }
+    }
+
+    /// Parse a key produced by [`to_key`](Self::to_key) for `kind`.
+    ///
+    /// Returns `None` when `key` is not a valid key of that kind.
+    pub fn parse(kind: PeriodKind, key: &str) -> Option<Self> {
+        match kind {
+            PeriodKind::Hourly => NaiveDateTime::parse_from_str(key, "%Y-%m-%d %H:%M")
+                .ok()
+                .filter(|t| t.minute() == 0)
+                .map(|t| Self::Hourly(t.and_utc())),
+            PeriodKind::Daily => NaiveDate::parse_from_str(key, "%Y-%m-%d")
+                .ok()
+                .map(Self::Daily),
+            PeriodKind::Weekly => {
+                let (year, week) = key.split_once("-W")?;
+                NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)
+                    .map(|monday| Self::Weekly(monday.iso_week()))
+            }
+            PeriodKind::Monthly => {
+                let (year, month) = key.split_once('-')?;
+                let (year, month) = (year.parse().ok()?, month.parse().ok()?);
+                NaiveDate::from_ymd_opt(year, month, 1).map(|_| Self::Monthly(year, month))
+            }
+        }
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    // ── to_key / parse ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_period_keys_round_trip() {
+        let at = ts("2024-01-15T14:37:12Z");
+        for (kind, key) in [
+            (PeriodKind::Hourly, "2024-01-15 14:00"),
+            (PeriodKind::Daily, "2024-01-15"),
+            (PeriodKind::Weekly, "2024-W03"),
+            (PeriodKind::Monthly, "2024-01"),
+        ] {
+            let period = Period::of(kind, at);
+            assert_eq!(period.kind(), kind);
+            assert_eq!(period.to_key(), key);
+            assert_eq!(Period::parse(kind, key), Some(period), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_weekly_uses_iso_year() {
+        // 2024-12-30 is a Monday in ISO week 1 of 2025.
+        let period = Period::of(PeriodKind::Weekly, ts("2024-12-30T08:00:00Z"));
+        assert_eq!(period.to_key(), "2025-W01");
+        assert_eq!(Period::parse(PeriodKind::Weekly, "2025-W01"), Some(period));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_keys() {
+        assert_eq!(Period::parse(PeriodKind::Daily, "2024-02-30"), None);
+        assert_eq!(Period::parse(PeriodKind::Monthly, "2024-13"), None);
+        assert_eq!(Period::parse(PeriodKind::Weekly, "2024-W54"), None);
+        assert_eq!(Period::parse(PeriodKind::Hourly, "2024-01-15 14:30"), None);
+        assert_eq!(Period::parse(PeriodKind::Monthly, "2024-01-15"), None);
+    }
+
+    // ── Ordering ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_periods_sort_chronologically() {
+        let mut weeks: Vec<Period> = ["2024-W10", "2023-W52", "2024-W02"]
+            .iter()
+            .map(|k| Period::parse(PeriodKind::Weekly, k).unwrap())
+            .collect();
+        weeks.sort();
+        let keys: Vec<String> = weeks.iter().map(Period::to_key).collect();
+        assert_eq!(keys, ["2023-W52", "2024-W02", "2024-W10"]);
+
+        assert!(Period::Monthly(2023, 12) < Period::Monthly(2024, 1));
+    }
+
+    #[test]
+    fn test_period_kind_from_name() {
+        assert_eq!(PeriodKind::from_name("monthly"), PeriodKind::Monthly);
+        assert_eq!(PeriodKind::from_name("weekly"), PeriodKind::Weekly);
+        assert_eq!(PeriodKind::from_name("hourly"), PeriodKind::Hourly);
+        assert_eq!(PeriodKind::from_name("bogus"), PeriodKind::Daily);
+    }
+}
@@ -3,20 +3,267 @@
 //! Reads usage records produced by the Claude CLI from `~/.claude/projects/`
 //! and converts them into [`UsageEntry`] structs for downstream processing.
 
-use std::collections::HashSet;
-use std::io::BufRead;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, Read};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use monitor_core::data_processors::{DataConverter, TimestampProcessor, TokenExtractor};
+use monitor_core::error::MonitorError;
 use monitor_core::models::{CostMode, UsageEntry};
-use monitor_core::pricing::PricingCalculator;
+use monitor_core::pricing::{ModelPricing, PricingCalculator};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
+use crate::analyzer::BlockStrategy;
+use crate::parse_cache::ParseCache;
+
+// ── Public types ──────────────────────────────────────────────────────────────
+
+/// Sentinel `data_path` value that makes the loader read JSONL from stdin.
+pub const STDIN_DATA_PATH: &str = "-";
+
+/// Snapshot of loader progress, reported once per processed file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// Number of JSONL files fully processed so far.
+    pub files_processed: usize,
+    /// Total number of JSONL files discovered for this load.
+    pub files_total: usize,
+    /// Number of usage entries parsed so far across all files.
+    pub entries_parsed: usize,
+}
+
+impl LoadProgress {
+    /// Fraction of files processed in `[0.0, 1.0]`; `1.0` when there are no files.
+    pub fn fraction(&self) -> f64 {
+        if self.files_total == 0 {
+            1.0
+        } else {
+            (self.files_processed as f64 / self.files_total as f64).min(1.0)
+        }
+    }
+}
+
+/// What one load read, kept and skipped, for diagnostics such as
+/// `--validate`.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LoadStats {
+    /// JSONL files found under the data path (`1` for stdin).
+    pub files_found: usize,
+    /// Non-empty lines that parsed as JSON.
+    pub lines_read: usize,
+    /// Non-empty lines that were not valid JSON.
+    pub malformed_lines: usize,
+    /// Lines older than the look-back cutoff.
+    pub filtered_by_cutoff: usize,
+    /// Lines dropped as duplicates of an earlier line.
+    pub duplicates: usize,
+    /// Lines that carry no usable token usage (user messages, tool output,
+    /// empty assistant messages).
+    pub without_usage: usize,
+    /// Lines dropped for a token count above
+    /// [`LoadOptions::max_entry_tokens`].
+    #[serde(default)]
+    pub absurd_tokens: usize,
+    /// Usage entries kept.
+    pub entries_kept: usize,
+    /// Problems that made the loader skip a path or file.
+    pub warnings: Vec<String>,
+    /// Duplicate lines per file, keyed by path. Only filled with
+    /// [`LoadOptions::track_sources`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub duplicates_by_file: BTreeMap<String, usize>,
+}
+
+/// Why [`skip_reason`] rejected a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SkipReason {
+    /// Older than the look-back cutoff.
+    Cutoff,
+    /// Same unique hash as an earlier line.
+    Duplicate,
+}
+
+/// Why [`map_to_usage_entry`] produced no entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum Unmapped {
+    /// No timestamp or no token usage.
+    NoUsage,
+    /// A token field exceeds [`LoadOptions::max_entry_tokens`].
+    AbsurdTokens {
+        /// Name of the offending field, e.g. `"input_tokens"`.
+        field: String,
+        /// Its value.
+        value: u64,
+    },
+}
+
+/// Which fields identify two usage lines as the same entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DedupKey {
+    /// `message_id` plus request id; lines missing either are never
+    /// deduplicated.
+    #[default]
+    MessageRequest,
+    /// `message_id` alone, for CLI versions that write no request id.
+    Message,
+    /// Timestamp, model and token counts, for lines without usable ids.
+    ContentHash,
+}
+
+impl DedupKey {
+    /// Parse a `--dedup-key` value; unknown names are
+    /// [`MessageRequest`](Self::MessageRequest).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "message" => Self::Message,
+            "content-hash" => Self::ContentHash,
+            _ => Self::MessageRequest,
+        }
+    }
+}
+
+/// Default ceiling on any single token count of one entry; larger values
+/// come from corrupted lines.
+pub const DEFAULT_MAX_ENTRY_TOKENS: u64 = 100_000_000;
+
+/// Default limit on how long reading one JSONL file may take.
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `--include` / `--exclude` glob patterns selecting which JSONL files to
+/// read, matched against paths relative to the data root.
+///
+/// A file matches a pattern when its relative path or any parent directory
+/// does, so `-home-me-app` selects every file of that project. With include
+/// patterns only matching files are read; exclude patterns always win. `*`
+/// stays within one path component, `**` crosses them.
+#[derive(Debug, Clone, Default)]
+pub struct FileFilter {
+    /// The include and exclude patterns as given, for comparison.
+    patterns: (Vec<String>, Vec<String>),
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PartialEq for FileFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.patterns == other.patterns
+    }
+}
+
+impl FileFilter {
+    /// Compile `include` and `exclude` patterns; no patterns selects every
+    /// file.
+    pub fn new<S: AsRef<str>>(include: &[S], exclude: &[S]) -> Result<Self, MonitorError> {
+        let owned = |patterns: &[S]| -> Vec<String> {
+            patterns.iter().map(|p| p.as_ref().to_string()).collect()
+        };
+        Ok(Self {
+            patterns: (owned(include), owned(exclude)),
+            include: Self::compile(include)?,
+            exclude: Self::compile(exclude)?,
+        })
+    }
+
+    /// `true` when `relative` (a path under the data root) should be read.
+    pub fn matches(&self, relative: &Path) -> bool {
+        let hit = |set: &GlobSet| {
+            relative
+                .ancestors()
+                .filter(|p| !p.as_os_str().is_empty())
+                .any(|p| set.is_match(p))
+        };
+        self.include.as_ref().is_none_or(hit) && !self.exclude.as_ref().is_some_and(hit)
+    }
+
+    /// Build a glob set from `patterns`, or `None` when there are none.
+    fn compile<S: AsRef<str>>(patterns: &[S]) -> Result<Option<GlobSet>, MonitorError> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| MonitorError::Config(format!("invalid glob {pattern:?}: {e}")))?;
+            builder.add(glob);
+        }
+        builder
+            .build()
+            .map(Some)
+            .map_err(|e| MonitorError::Config(e.to_string()))
+    }
+}
+
+/// Optional loader behaviour on top of the basic load parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadOptions {
+    /// Keep entries whose input and output token counts are both zero.
+    ///
+    /// They are priced at zero, so they only add to message counts.
+    pub count_empty_messages: bool,
+    /// Skip a file (with a warning) when opening it or reading its next chunk
+    /// takes longer than this, e.g. on a hung network mount. `None` waits
+    /// indefinitely.
+    pub read_timeout: Option<Duration>,
+    /// How duplicate lines (e.g. from re-synced files) are recognised.
+    pub dedup_key: DedupKey,
+    /// Count synthetic and system entries as messages and in per-model
+    /// stats. Their tokens always count towards the block totals.
+    pub include_synthetic: bool,
+    /// How entry costs are priced by callers that do not pass a mode
+    /// explicitly ([`analyze_usage_with_progress`](crate::analysis::analyze_usage_with_progress),
+    /// [`FollowReader`](crate::follow::FollowReader)).
+    pub cost_mode: CostMode,
+    /// Record each entry's [`source_file`](UsageEntry::source_file) and
+    /// per-file duplicate counts, for debugging. Off by default as it costs
+    /// one path allocation per entry.
+    pub track_sources: bool,
+    /// Per-model rates replacing the built-in ones (`--pricing-file`).
+    pub pricing_overrides: Option<HashMap<String, ModelPricing>>,
+    /// Drop (with a warning) entries whose input, output or cache token
+    /// count exceeds this, rather than let a corrupted line poison totals.
+    pub max_entry_tokens: u64,
+    /// Which files under the data path to read (`--include` / `--exclude`).
+    pub file_filter: FileFilter,
+    /// File keeping parsed JSONL files between runs, so unchanged files are
+    /// not parsed again (see [`parse_cache`](crate::parse_cache)). `None`
+    /// parses every file. Not used when raw entries are requested.
+    pub parse_cache: Option<PathBuf>,
+    /// How entries are grouped into session windows (`--fixed-blocks`).
+    pub block_strategy: BlockStrategy,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            count_empty_messages: false,
+            read_timeout: Some(DEFAULT_READ_TIMEOUT),
+            dedup_key: DedupKey::default(),
+            include_synthetic: false,
+            cost_mode: CostMode::Auto,
+            track_sources: false,
+            pricing_overrides: None,
+            max_entry_tokens: DEFAULT_MAX_ENTRY_TOKENS,
+            file_filter: FileFilter::default(),
+            parse_cache: None,
+            block_strategy: BlockStrategy::default(),
+        }
+    }
+}
+
 // ── Public API ────────────────────────────────────────────────────────────────
 
-/// Find all `.jsonl` files recursively under `data_path`, sorted by path.
-pub fn find_jsonl_files(data_path: &Path) -> Vec<PathBuf> {
+/// Find all `.jsonl` files recursively under `data_path` that pass `filter`,
+/// sorted by path.
+pub fn find_jsonl_files(data_path: &Path, filter: &FileFilter) -> Vec<PathBuf> {
     if !data_path.exists() {
         warn!("Data path does not exist: {}", data_path.display());
         return Vec::new();
@@ -33,6 +280,10 @@ pub fn find_jsonl_files(data_path: &Path) -> Vec<PathBuf> {
                     .extension()
                     .map(|ext| ext == "jsonl")
                     .unwrap_or(false)
+                && entry
+                    .path()
+                    .strip_prefix(data_path)
+                    .is_ok_and(|relative| filter.matches(relative))
         })
         .map(|entry| entry.into_path())
         .collect();
@@ -41,14 +292,32 @@ pub fn find_jsonl_files(data_path: &Path) -> Vec<PathBuf> {
     files
 }
 
+/// Name of the top-level project directory containing `file`, relative to
+/// `data_root` (e.g. `<root>/-home-me-app/abc.jsonl` → `"-home-me-app"`).
+///
+/// Returns an empty string for files directly in `data_root` or outside it.
+pub fn project_for_file(data_root: &Path, file: &Path) -> String {
+    let Ok(relative) = file.strip_prefix(data_root) else {
+        return String::new();
+    };
+    let mut components = relative.components();
+    match (components.next(), components.next()) {
+        (Some(first), Some(_)) => first.as_os_str().to_string_lossy().into_owned(),
+        _ => String::new(),
+    }
+}
+
 /// Load and parse JSONL files into [`UsageEntry`] objects.
 ///
-/// * `data_path` – directory to scan (defaults to `~/.claude/projects`).
+/// * `data_path` – directory to scan (defaults to `~/.claude/projects`), or
+///   [`STDIN_DATA_PATH`] to read JSONL from standard input instead.
 /// * `hours_back` – when set, only entries within the last N hours are kept.
 /// * `mode` – how to compute the USD cost for each entry.
 /// * `include_raw` – when `true`, the raw [`serde_json::Value`] for every
 ///   processed line is returned alongside the typed entries.
 ///
+/// Each entry is tagged with its [`project_for_file`].
+///
 /// Returns `(entries, raw_entries)`.  `raw_entries` is `None` when
 /// `include_raw` is `false`.
 pub fn load_usage_entries(
@@ -57,38 +326,120 @@ pub fn load_usage_entries(
     mode: CostMode,
     include_raw: bool,
 ) -> (Vec<UsageEntry>, Option<Vec<serde_json::Value>>) {
-    let path = resolve_data_path(data_path);
-    let mut pricing = PricingCalculator::new(None);
+    load_usage_entries_with_progress(
+        data_path,
+        hours_back,
+        mode,
+        include_raw,
+        &LoadOptions::default(),
+        |_| {},
+    )
+}
 
-    let cutoff_time: Option<DateTime<Utc>> =
-        hours_back.map(|h| Utc::now() - chrono::Duration::hours(h as i64));
+/// Like [`load_usage_entries`], but honours `options` and calls `progress`
+/// after every file.
+///
+/// An initial report with `files_processed == 0` is emitted once the file
+/// list is known, so callers can show a total before any parsing happens.
+pub fn load_usage_entries_with_progress(
+    data_path: Option<&str>,
+    hours_back: Option<u64>,
+    mode: CostMode,
+    include_raw: bool,
+    options: &LoadOptions,
+    progress: impl Fn(LoadProgress),
+) -> (Vec<UsageEntry>, Option<Vec<serde_json::Value>>) {
+    let (entries, raw_entries, _) =
+        load_usage_entries_with_stats(data_path, hours_back, mode, include_raw, options, progress);
+    (entries, raw_entries)
+}
 
-    let jsonl_files = find_jsonl_files(&path);
+/// Like [`load_usage_entries_with_progress`], but also returns the
+/// [`LoadStats`] of what was read and skipped.
+pub fn load_usage_entries_with_stats(
+    data_path: Option<&str>,
+    hours_back: Option<u64>,
+    mode: CostMode,
+    include_raw: bool,
+    options: &LoadOptions,
+    progress: impl Fn(LoadProgress),
+) -> (Vec<UsageEntry>, Option<Vec<serde_json::Value>>, LoadStats) {
+    let mut state = ParseState::new(hours_back, mode, include_raw, options);
+
+    if data_path == Some(STDIN_DATA_PATH) {
+        progress(LoadProgress {
+            files_processed: 0,
+            files_total: 1,
+            entries_parsed: 0,
+        });
+        state.stats.files_found = 1;
+        let (mut entries, raw_entries) =
+            process_lines(std::io::stdin().lock(), "<stdin>", &mut state);
+        entries.sort_by_key(|e| e.timestamp);
+        progress(LoadProgress {
+            files_processed: 1,
+            files_total: 1,
+            entries_parsed: entries.len(),
+        });
+        return (entries, raw_entries, state.stats);
+    }
+
+    let Some(path) = resolve_data_path(data_path) else {
+        warn!("{NO_DATA_PATH}");
+        state.stats.warnings.push(NO_DATA_PATH.to_string());
+        progress(LoadProgress::default());
+        return (Vec::new(), None, state.stats);
+    };
+
+    let jsonl_files = find_jsonl_files(&path, &options.file_filter);
     if jsonl_files.is_empty() {
         warn!("No JSONL files found in {}", path.display());
-        return (Vec::new(), None);
+        state
+            .stats
+            .warnings
+            .push(format!("No JSONL files found in {}", path.display()));
+        progress(LoadProgress::default());
+        return (Vec::new(), None, state.stats);
     }
+    state.stats.files_found = jsonl_files.len();
 
     let mut all_entries: Vec<UsageEntry> = Vec::new();
     let mut raw_entries: Option<Vec<serde_json::Value>> =
         if include_raw { Some(Vec::new()) } else { None };
-    let mut processed_hashes: HashSet<String> = HashSet::new();
 
-    for file_path in &jsonl_files {
-        let (entries, raw_data) = process_single_file(
-            file_path,
-            mode.clone(),
-            cutoff_time,
-            &mut processed_hashes,
-            include_raw,
-            &mut pricing,
-        );
-        all_entries.extend(entries);
+    let files_total = jsonl_files.len();
+    progress(LoadProgress {
+        files_processed: 0,
+        files_total,
+        entries_parsed: 0,
+    });
+
+    let mut cache = match &options.parse_cache {
+        Some(file) if !include_raw => Some(ParseCache::open(file, &state.mode, options)),
+        _ => None,
+    };
+
+    for (index, file_path) in jsonl_files.iter().enumerate() {
+        let (entries, raw_data) = process_single_file(file_path, &mut state, cache.as_mut());
+        let project = project_for_file(&path, file_path);
+        all_entries.extend(entries.into_iter().map(|mut entry| {
+            entry.project.clone_from(&project);
+            entry
+        }));
         if include_raw {
             if let (Some(dest), Some(src)) = (raw_entries.as_mut(), raw_data) {
                 dest.extend(src);
             }
         }
+        progress(LoadProgress {
+            files_processed: index + 1,
+            files_total,
+            entries_parsed: all_entries.len(),
+        });
+    }
+
+    if let Some(cache) = cache {
+        cache.save();
     }
 
     all_entries.sort_by_key(|e| e.timestamp);
@@ -99,15 +450,43 @@ pub fn load_usage_entries(
         jsonl_files.len()
     );
 
-    (all_entries, raw_entries)
+    (all_entries, raw_entries, state.stats)
+}
+
+/// Parse JSONL from an arbitrary reader (e.g. stdin) into [`UsageEntry`]
+/// objects.
+///
+/// Applies the same cutoff, deduplication and pricing as
+/// [`load_usage_entries`]; the result is sorted by timestamp.
+pub fn load_usage_entries_from_reader(
+    reader: impl BufRead,
+    hours_back: Option<u64>,
+    mode: CostMode,
+    include_raw: bool,
+    options: &LoadOptions,
+) -> (Vec<UsageEntry>, Option<Vec<serde_json::Value>>) {
+    let mut state = ParseState::new(hours_back, mode, include_raw, options);
+    let (mut entries, raw_entries) = process_lines(reader, "<stdin>", &mut state);
+    entries.sort_by_key(|e| e.timestamp);
+
+    debug!("Processed {} entries from reader", entries.len());
+
+    (entries, raw_entries)
 }
 
 /// Load all raw JSONL entries without any filtering or type mapping.
 ///
-/// Useful for limit-detection downstream which needs the full raw data.
-pub fn load_all_raw_entries(data_path: Option<&str>) -> Vec<serde_json::Value> {
-    let path = resolve_data_path(data_path);
-    let jsonl_files = find_jsonl_files(&path);
+/// Useful for limit-detection downstream which needs the full raw data. Only
+/// the files selected by [`LoadOptions::file_filter`] are read.
+pub fn load_all_raw_entries(
+    data_path: Option<&str>,
+    options: &LoadOptions,
+) -> Vec<serde_json::Value> {
+    let Some(path) = resolve_data_path(data_path) else {
+        warn!("{NO_DATA_PATH}");
+        return Vec::new();
+    };
+    let jsonl_files = find_jsonl_files(&path, &options.file_filter);
 
     let mut all_raw: Vec<serde_json::Value> = Vec::new();
 
@@ -120,7 +499,7 @@ pub fn load_all_raw_entries(data_path: Option<&str>) -> Vec<serde_json::Value> {
                         Ok(l) => l,
                         Err(_) => continue,
                     };
-                    let trimmed = line.trim();
+                    let trimmed = trim_line(&line);
                     if trimmed.is_empty() {
                         continue;
                     }
@@ -145,52 +524,328 @@ pub fn load_all_raw_entries(data_path: Option<&str>) -> Vec<serde_json::Value> {
 
 // ── Internal helpers ──────────────────────────────────────────────────────────
 
+/// Message for when no data path was given and there is no home directory.
+pub(crate) const NO_DATA_PATH: &str =
+    "Cannot locate the Claude data: no home directory found. Pass --data-path";
+
 /// Resolve the data path: use `data_path` when given, otherwise fall back
 /// to `~/.claude/projects` via the `HOME` environment variable or the
 /// platform home dir.
-fn resolve_data_path(data_path: Option<&str>) -> PathBuf {
+///
+/// Returns `None` when neither is available.
+pub fn resolve_data_path(data_path: Option<&str>) -> Option<PathBuf> {
     if let Some(p) = data_path {
-        return PathBuf::from(p);
+        return Some(PathBuf::from(p));
     }
 
     // Use dirs::home_dir() for cross-platform home detection.
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".claude").join("projects")
+    dirs::home_dir().map(|home| home.join(".claude").join("projects"))
+}
+
+/// Settings and mutable state shared by every file in a single load.
+pub(crate) struct ParseState {
+    pub(crate) mode: CostMode,
+    cutoff: Option<DateTime<Utc>>,
+    include_raw: bool,
+    options: LoadOptions,
+    /// Deduplication hashes seen so far, across all files.
+    hashes: HashSet<String>,
+    pricing: PricingCalculator,
+    /// Running counts for [`LoadStats`].
+    pub(crate) stats: LoadStats,
+}
+
+impl ParseState {
+    pub(crate) fn new(
+        hours_back: Option<u64>,
+        mode: CostMode,
+        include_raw: bool,
+        options: &LoadOptions,
+    ) -> Self {
+        Self {
+            mode,
+            cutoff: hours_back.map(|h| Utc::now() - chrono::Duration::hours(h as i64)),
+            include_raw,
+            options: options.clone(),
+            hashes: HashSet::new(),
+            pricing: PricingCalculator::new(options.pricing_overrides.clone()),
+            stats: LoadStats::default(),
+        }
+    }
 }
 
 /// Process a single JSONL file and return parsed entries plus optional raw
 /// JSON values.
+///
+/// With a [`LoadOptions::read_timeout`] the file is streamed from a helper
+/// thread and skipped if it stops making progress. An unchanged file is taken
+/// from `cache` instead. With [`LoadOptions::track_sources`] every entry
+/// records `file_path`.
 fn process_single_file(
     file_path: &Path,
-    mode: CostMode,
-    cutoff: Option<DateTime<Utc>>,
-    hashes: &mut HashSet<String>,
-    include_raw: bool,
-    pricing: &mut PricingCalculator,
+    state: &mut ParseState,
+    cache: Option<&mut ParseCache>,
 ) -> (Vec<UsageEntry>, Option<Vec<serde_json::Value>>) {
-    let mut entries: Vec<UsageEntry> = Vec::new();
-    let mut raw_data: Option<Vec<serde_json::Value>> =
-        if include_raw { Some(Vec::new()) } else { None };
+    let parsed = match cache {
+        Some(cache) => cache
+            .get_or_parse(file_path, || {
+                read_single_file(file_path, state).map(|(parsed, _)| parsed)
+            })
+            .map(|parsed| (parsed, None)),
+        None => read_single_file(file_path, state),
+    };
+    let Some((parsed, raw_data)) = parsed else {
+        return (Vec::new(), None);
+    };
+    let (mut entries, raw_data) =
+        apply_parsed(parsed, raw_data, &file_path.display().to_string(), state);
+    if state.options.track_sources {
+        for entry in &mut entries {
+            entry.source_file = Some(file_path.to_path_buf());
+        }
+    }
+    (entries, raw_data)
+}
+
+/// Read and parse `file_path`, honouring [`LoadOptions::read_timeout`].
+///
+/// Returns `None` (with a warning) when the file cannot be read.
+fn read_single_file(
+    file_path: &Path,
+    state: &mut ParseState,
+) -> Option<(ParsedFile, Option<Vec<serde_json::Value>>)> {
+    let source = file_path.display().to_string();
 
-    let file = match std::fs::File::open(file_path) {
-        Ok(f) => f,
+    let Some(timeout) = state.options.read_timeout else {
+        let file = match std::fs::File::open(file_path) {
+            Ok(f) => f,
+            Err(e) => {
+                warn!("Failed to read file {}: {}", source, e);
+                state
+                    .stats
+                    .warnings
+                    .push(format!("Failed to read file {source}: {e}"));
+                return None;
+            }
+        };
+        return Some(parse_lines(std::io::BufReader::new(file), &source, state));
+    };
+
+    let result = TimedReader::open(file_path, timeout).and_then(|mut reader| {
+        let parsed = parse_lines(std::io::BufReader::new(&mut reader), &source, state);
+        reader.error.take().map_or(Ok(parsed), Err)
+    });
+    match result {
+        Ok(parsed) => Some(parsed),
         Err(e) => {
-            warn!("Failed to read file {}: {}", file_path.display(), e);
-            return (Vec::new(), None);
+            warn!("Failed to read file {}: {}", source, e);
+            state
+                .stats
+                .warnings
+                .push(format!("Failed to read file {source}: {e}"));
+            None
         }
-    };
+    }
+}
 
-    let reader = std::io::BufReader::new(file);
-    let mut entries_read = 0u64;
-    let mut entries_filtered = 0u64;
-    let mut entries_mapped = 0u64;
+/// Most helper threads of [`TimedReader`] alive at once.
+///
+/// A thread stuck in a filesystem call cannot be cancelled, so a hung mount
+/// would otherwise leak one thread per file on every refresh; past this
+/// many, further timed reads fail straight away.
+const MAX_PENDING_READS: usize = 4;
+
+/// Helper threads of [`TimedReader`] currently alive.
+static PENDING_READS: AtomicUsize = AtomicUsize::new(0);
+
+/// Size of the chunks a [`TimedReader`] helper passes back.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A claim on one of `limit` slots of `counter`, released on drop.
+struct ReadSlot(&'static AtomicUsize);
+
+impl ReadSlot {
+    fn acquire(counter: &'static AtomicUsize, limit: usize) -> Option<Self> {
+        counter
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < limit).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Self(counter))
+    }
+}
+
+impl Drop for ReadSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Streams a file from a helper thread, failing with
+/// [`ErrorKind::TimedOut`](std::io::ErrorKind::TimedOut) when the open or a
+/// read makes no progress within `timeout`.
+///
+/// The first error ends the stream and is kept in `error`, since
+/// [`BufRead::lines`] alone would not tell a failed read from the end of the
+/// file. Dropping the reader stops the helper after its current call.
+struct TimedReader {
+    chunks: Receiver<std::io::Result<Vec<u8>>>,
+    timeout: Duration,
+    chunk: Vec<u8>,
+    pos: usize,
+    done: bool,
+    error: Option<std::io::Error>,
+}
+
+impl TimedReader {
+    fn open(path: &Path, timeout: Duration) -> std::io::Result<Self> {
+        let slot = ReadSlot::acquire(&PENDING_READS, MAX_PENDING_READS).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::WouldBlock,
+                "too many earlier reads are still blocked",
+            )
+        })?;
+        let (tx, rx) = std::sync::mpsc::sync_channel(2);
+        let owned = path.to_path_buf();
+        std::thread::Builder::new()
+            .name("jsonl-read".to_string())
+            .spawn(move || {
+                let _slot = slot;
+                let mut file = match std::fs::File::open(owned) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
+                };
+                loop {
+                    let mut chunk = vec![0; READ_CHUNK_SIZE];
+                    let sent = match file.read(&mut chunk) {
+                        Ok(0) => return,
+                        Ok(n) => {
+                            chunk.truncate(n);
+                            tx.send(Ok(chunk))
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                        Err(e) => tx.send(Err(e)),
+                    };
+                    if sent.is_err() {
+                        return;
+                    }
+                }
+            })?;
+
+        let mut reader = Self {
+            chunks: rx,
+            timeout,
+            chunk: Vec::new(),
+            pos: 0,
+            done: false,
+            error: None,
+        };
+        // Surface a failed or hung open now rather than as an empty file.
+        reader.fill()?;
+        Ok(reader)
+    }
+
+    /// Wait for the next chunk once the current one is used up.
+    fn fill(&mut self) -> std::io::Result<()> {
+        if self.done || self.pos < self.chunk.len() {
+            return Ok(());
+        }
+        match self.chunks.recv_timeout(self.timeout) {
+            Ok(Ok(chunk)) => {
+                self.chunk = chunk;
+                self.pos = 0;
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                self.done = true;
+                Err(e)
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                self.done = true;
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("read timed out after {}s", self.timeout.as_secs_f64()),
+                ))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                self.done = true;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Read for TimedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if let Err(e) = self.fill() {
+            self.error = Some(e);
+        }
+        let available = &self.chunk[self.pos..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Parse JSONL lines from `reader` and return mapped entries plus optional
+/// raw JSON values. `source` is only used for log messages.
+pub(crate) fn process_lines(
+    reader: impl BufRead,
+    source: &str,
+    state: &mut ParseState,
+) -> (Vec<UsageEntry>, Option<Vec<serde_json::Value>>) {
+    let (parsed, raw_data) = parse_lines(reader, source, state);
+    apply_parsed(parsed, raw_data, source, state)
+}
+
+/// One JSON line mapped on its own, before the cutoff and deduplication
+/// that depend on the rest of the load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ParsedLine {
+    /// Parsed `timestamp`, compared with the look-back cutoff.
+    timestamp: Option<DateTime<Utc>>,
+    /// Deduplication hash (see [`create_unique_hash`]).
+    hash: Option<String>,
+    /// The mapped entry, or why there is none.
+    mapped: Result<UsageEntry, Unmapped>,
+}
+
+/// Every line of one JSONL file, parsed independently of other files, so it
+/// can be kept in the [parse cache](crate::parse_cache).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct ParsedFile {
+    /// Lines that were not valid JSON.
+    malformed_lines: usize,
+    /// The valid JSON lines, in file order.
+    lines: Vec<ParsedLine>,
+}
+
+/// Parse and map every line of `reader`.
+///
+/// With [`ParseState`]'s `include_raw` the raw JSON of each parsed line is
+/// returned too, in the same order.
+pub(crate) fn parse_lines(
+    reader: impl BufRead,
+    source: &str,
+    state: &mut ParseState,
+) -> (ParsedFile, Option<Vec<serde_json::Value>>) {
+    let mut parsed = ParsedFile::default();
+    let mut raw_data: Option<Vec<serde_json::Value>> = if state.include_raw {
+        Some(Vec::new())
+    } else {
+        None
+    };
 
     for line_result in reader.lines() {
         let line = match line_result {
             Ok(l) => l,
             Err(_) => continue,
         };
-        let trimmed = line.trim();
+        let trimmed = trim_line(&line);
         if trimmed.is_empty() {
             continue;
         }
@@ -198,84 +853,165 @@ fn process_single_file(
         let data: serde_json::Value = match serde_json::from_str(trimmed) {
             Ok(v) => v,
             Err(e) => {
-                debug!(
-                    "Failed to parse JSON line in {}: {}",
-                    file_path.display(),
-                    e
-                );
+                debug!("Failed to parse JSON line in {}: {}", source, e);
+                parsed.malformed_lines += 1;
                 continue;
             }
         };
 
+        parsed.lines.push(ParsedLine {
+            timestamp: data.get("timestamp").and_then(TimestampProcessor::parse),
+            hash: create_unique_hash(&data, state.options.dedup_key),
+            mapped: map_to_usage_entry(
+                &data,
+                state.mode.clone(),
+                &mut state.pricing,
+                &state.options,
+            ),
+        });
+        if let Some(dest) = raw_data.as_mut() {
+            dest.push(data);
+        }
+    }
+
+    (parsed, raw_data)
+}
+
+/// Apply the cutoff and deduplication to the lines of one parsed file and
+/// return the kept entries, plus the raw JSON of the lines not skipped when
+/// `raw_data` is given.
+pub(crate) fn apply_parsed(
+    parsed: ParsedFile,
+    raw_data: Option<Vec<serde_json::Value>>,
+    source: &str,
+    state: &mut ParseState,
+) -> (Vec<UsageEntry>, Option<Vec<serde_json::Value>>) {
+    let mut entries: Vec<UsageEntry> = Vec::new();
+    let mut raw_lines = raw_data.map(Vec::into_iter);
+    let mut kept_raw: Option<Vec<serde_json::Value>> = raw_lines.as_ref().map(|_| Vec::new());
+
+    let mut entries_read = 0u64;
+    let mut entries_filtered = 0u64;
+    let mut entries_mapped = 0u64;
+
+    state.stats.malformed_lines += parsed.malformed_lines;
+
+    for line in parsed.lines {
+        let data = raw_lines.as_mut().and_then(Iterator::next);
         entries_read += 1;
+        state.stats.lines_read += 1;
 
-        if !should_process_entry(&data, cutoff, hashes) {
+        if let Some(reason) = skip_reason(&line, state.cutoff, &state.hashes) {
             entries_filtered += 1;
+            match reason {
+                SkipReason::Cutoff => state.stats.filtered_by_cutoff += 1,
+                SkipReason::Duplicate => {
+                    state.stats.duplicates += 1;
+                    if state.options.track_sources {
+                        *state
+                            .stats
+                            .duplicates_by_file
+                            .entry(source.to_string())
+                            .or_default() += 1;
+                    }
+                }
+            }
             continue;
         }
 
-        if let Some(entry) = map_to_usage_entry(&data, mode.clone(), pricing) {
-            entries_mapped += 1;
-            entries.push(entry);
-            // Register hash so duplicate lines are skipped.
-            if let Some(h) = create_unique_hash(&data) {
-                hashes.insert(h);
+        match line.mapped {
+            Ok(entry) => {
+                entries_mapped += 1;
+                state.stats.entries_kept += 1;
+                entries.push(entry);
+                // Register hash so duplicate lines are skipped.
+                if let Some(h) = line.hash {
+                    state.hashes.insert(h);
+                }
+            }
+            Err(Unmapped::NoUsage) => state.stats.without_usage += 1,
+            Err(Unmapped::AbsurdTokens { field, value }) => {
+                let message = format!(
+                    "Skipped a line in {source} with {field} = {value} (above {})",
+                    state.options.max_entry_tokens
+                );
+                warn!("{}", message);
+                state.stats.absurd_tokens += 1;
+                state.stats.warnings.push(message);
             }
         }
 
-        if include_raw {
-            if let Some(dest) = raw_data.as_mut() {
-                dest.push(data);
-            }
+        if let (Some(dest), Some(data)) = (kept_raw.as_mut(), data) {
+            dest.push(data);
         }
     }
 
     debug!(
         "File {}: {} read, {} filtered, {} mapped",
-        file_path.display(),
-        entries_read,
-        entries_filtered,
-        entries_mapped,
+        source, entries_read, entries_filtered, entries_mapped,
     );
 
-    (entries, raw_data)
+    (entries, kept_raw)
+}
+
+/// Trim surrounding whitespace, including the `\r` of CRLF line endings,
+/// and a UTF-8 byte-order mark (written by some Windows editors at the start
+/// of a file).
+fn trim_line(line: &str) -> &str {
+    let trimmed = line.trim();
+    trimmed
+        .strip_prefix('\u{feff}')
+        .unwrap_or(trimmed)
+        .trim_start()
 }
 
-/// Returns `true` when the entry should be processed.
+/// Why the line should be skipped, or `None` when it should be processed.
 ///
-/// An entry is skipped when:
+/// A line is skipped when:
 /// * It has a timestamp older than the cutoff.
-/// * Its unique hash (message_id:request_id) was already seen.
-fn should_process_entry(
-    data: &serde_json::Value,
+/// * Its unique hash (see [`create_unique_hash`]) was already seen.
+fn skip_reason(
+    line: &ParsedLine,
     cutoff: Option<DateTime<Utc>>,
     hashes: &HashSet<String>,
-) -> bool {
+) -> Option<SkipReason> {
     // Time filter.
-    if let Some(cutoff_ts) = cutoff {
-        if let Some(ts_value) = data.get("timestamp") {
-            if let Some(ts) = TimestampProcessor::parse(ts_value) {
-                if ts < cutoff_ts {
-                    return false;
-                }
-            }
+    if let (Some(cutoff_ts), Some(ts)) = (cutoff, line.timestamp) {
+        if ts < cutoff_ts {
+            return Some(SkipReason::Cutoff);
         }
     }
 
     // Deduplication filter.
-    if let Some(h) = create_unique_hash(data) {
-        if hashes.contains(&h) {
-            return false;
-        }
+    if line.hash.as_ref().is_some_and(|h| hashes.contains(h)) {
+        return Some(SkipReason::Duplicate);
     }
 
-    true
+    None
 }
 
-/// Build the deduplication hash `"{message_id}:{request_id}"`.
+/// Build the deduplication hash for `key`:
 ///
-/// Returns `None` when either component is absent.
-fn create_unique_hash(data: &serde_json::Value) -> Option<String> {
+/// * [`DedupKey::MessageRequest`] → `"{message_id}:{request_id}"`
+/// * [`DedupKey::Message`] → `"{message_id}"`
+/// * [`DedupKey::ContentHash`] → `"{timestamp}|{model}|{input}|{output}|{cache_creation}|{cache_read}"`
+///
+/// Returns `None` when a required component is absent.
+fn create_unique_hash(data: &serde_json::Value, key: DedupKey) -> Option<String> {
+    if key == DedupKey::ContentHash {
+        let timestamp = TimestampProcessor::parse(data.get("timestamp")?)?;
+        let tokens = TokenExtractor::extract(data);
+        return Some(format!(
+            "{}|{}|{}|{}|{}|{}",
+            timestamp.to_rfc3339(),
+            DataConverter::extract_model_name(data),
+            tokens.input_tokens,
+            tokens.output_tokens,
+            tokens.cache_creation_input_tokens,
+            tokens.cache_read_input_tokens,
+        ));
+    }
+
     // message_id: try "message_id", then "message.id"
     let message_id = data
         .get("message_id")
@@ -299,28 +1035,59 @@ fn create_unique_hash(data: &serde_json::Value) -> Option<String> {
                 .map(|s| s.to_string())
         });
 
-    match (message_id, request_id) {
-        (Some(mid), Some(rid)) => Some(format!("{}:{}", mid, rid)),
+    match (message_id, request_id, key) {
+        (Some(mid), _, DedupKey::Message) => Some(mid),
+        (Some(mid), Some(rid), _) => Some(format!("{}:{}", mid, rid)),
         _ => None,
     }
 }
 
-/// Map a raw JSON value to a [`UsageEntry`], returning `None` on failure.
+/// Map a raw JSON value to a [`UsageEntry`], or say why it has none.
 fn map_to_usage_entry(
     data: &serde_json::Value,
     mode: CostMode,
     pricing: &mut PricingCalculator,
-) -> Option<UsageEntry> {
+    options: &LoadOptions,
+) -> Result<UsageEntry, Unmapped> {
     // Require a valid timestamp.
-    let ts_value = data.get("timestamp")?;
-    let timestamp = TimestampProcessor::parse(ts_value)?;
+    let timestamp = data
+        .get("timestamp")
+        .and_then(TimestampProcessor::parse)
+        .ok_or(Unmapped::NoUsage)?;
+
+    // Require at least some token counts, unless empty messages are kept.
+    let mut tokens = TokenExtractor::extract(data);
+    let is_empty = tokens.input_tokens == 0 && tokens.output_tokens == 0;
+    if is_empty && !options.count_empty_messages {
+        return Err(Unmapped::NoUsage);
+    }
+    // A kept empty message carries no tokens at all, so its cache tokens
+    // cannot show up in totals that its zero cost leaves out.
+    if is_empty {
+        tokens.cache_creation_input_tokens = 0;
+        tokens.cache_read_input_tokens = 0;
+    }
 
-    // Require at least some token counts.
-    let tokens = TokenExtractor::extract(data);
-    if tokens.input_tokens == 0 && tokens.output_tokens == 0 {
-        return None;
+    // Reject corrupted counts before they reach any sum.
+    let fields = [
+        ("input_tokens", tokens.input_tokens),
+        ("output_tokens", tokens.output_tokens),
+        ("cache_creation_tokens", tokens.cache_creation_input_tokens),
+        ("cache_read_tokens", tokens.cache_read_input_tokens),
+    ];
+    if let Some(&(field, value)) = fields
+        .iter()
+        .find(|(_, value)| *value > options.max_entry_tokens)
+    {
+        return Err(Unmapped::AbsurdTokens {
+            field: field.to_string(),
+            value,
+        });
     }
 
+    // System lines are written by the CLI; they keep their model and cost
+    // but are not sent messages.
+    let is_system = data.get("type").and_then(|v| v.as_str()) == Some("system");
     let model = DataConverter::extract_model_name(data);
 
     // Build a normalised entry map for the pricing calculator.
@@ -333,7 +1100,12 @@ fn map_to_usage_entry(
         "costUSD": data.get("costUSD").cloned().unwrap_or(serde_json::Value::Null),
         "cost_usd": data.get("cost_usd").cloned().unwrap_or(serde_json::Value::Null),
     });
-    let cost_usd = pricing.calculate_cost_for_entry(&entry_for_pricing, mode);
+    // Empty messages only count towards message totals, never cost.
+    let cost_usd = if is_empty {
+        0.0
+    } else {
+        pricing.calculate_cost_for_entry(&entry_for_pricing, mode)
+    };
 
     // Extract IDs.
     let message_id = data
@@ -359,7 +1131,7 @@ fn map_to_usage_entry(
         })
         .unwrap_or_else(|| "unknown".to_string());
 
-    Some(UsageEntry {
+    Ok(UsageEntry {
         timestamp,
         input_tokens: tokens.input_tokens,
         output_tokens: tokens.output_tokens,
@@ -369,6 +1141,9 @@ fn map_to_usage_entry(
         model,
         message_id,
         request_id,
+        project: String::new(),
+        is_system,
+        source_file: None,
     })
 }
 
@@ -403,6 +1178,16 @@ mod tests {
         .to_string()
     }
 
+    // ── resolve_data_path ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_resolve_data_path_uses_explicit_path() {
+        assert_eq!(
+            resolve_data_path(Some("/data/claude")),
+            Some(PathBuf::from("/data/claude"))
+        );
+    }
+
     // ── find_jsonl_files ──────────────────────────────────────────────────────
 
     #[test]
@@ -411,7 +1196,7 @@ mod tests {
         write_jsonl(dir.path(), "a.jsonl", &["line"]);
         write_jsonl(dir.path(), "b.jsonl", &["line"]);
 
-        let files = find_jsonl_files(dir.path());
+        let files = find_jsonl_files(dir.path(), &FileFilter::default());
         assert_eq!(files.len(), 2);
         assert!(files.iter().all(|p| p.extension().unwrap() == "jsonl"));
     }
@@ -424,13 +1209,16 @@ mod tests {
         write_jsonl(dir.path(), "root.jsonl", &["line"]);
         write_jsonl(&sub, "nested.jsonl", &["line"]);
 
-        let files = find_jsonl_files(dir.path());
+        let files = find_jsonl_files(dir.path(), &FileFilter::default());
         assert_eq!(files.len(), 2);
     }
 
     #[test]
     fn test_find_jsonl_files_nonexistent_path() {
-        let files = find_jsonl_files(Path::new("/tmp/does-not-exist-monitor-test-xyz"));
+        let files = find_jsonl_files(
+            Path::new("/tmp/does-not-exist-monitor-test-xyz"),
+            &FileFilter::default(),
+        );
         assert!(files.is_empty());
     }
 
@@ -441,7 +1229,7 @@ mod tests {
         write_jsonl(dir.path(), "a.jsonl", &["x"]);
         write_jsonl(dir.path(), "b.jsonl", &["x"]);
 
-        let files = find_jsonl_files(dir.path());
+        let files = find_jsonl_files(dir.path(), &FileFilter::default());
         let names: Vec<&str> = files
             .iter()
             .map(|p| p.file_name().unwrap().to_str().unwrap())
@@ -449,6 +1237,115 @@ mod tests {
         assert_eq!(names, vec!["a.jsonl", "b.jsonl", "c.jsonl"]);
     }
 
+    // ── FileFilter ────────────────────────────────────────────────────────────
+
+    /// Relative paths of the files `filter` selects from a small project tree.
+    fn filtered_files(include: &[&str], exclude: &[&str]) -> Vec<String> {
+        let dir = TempDir::new().unwrap();
+        for (project, file) in [
+            ("-home-me-web", "a.jsonl"),
+            ("-home-me-api", "b.jsonl"),
+            ("-home-me-api/archive", "old.jsonl"),
+            ("-work-tool", "c.jsonl"),
+        ] {
+            let project_dir = dir.path().join(project);
+            std::fs::create_dir_all(&project_dir).unwrap();
+            write_jsonl(&project_dir, file, &["x"]);
+        }
+
+        let filter = FileFilter::new(include, exclude).unwrap();
+        find_jsonl_files(dir.path(), &filter)
+            .iter()
+            .map(|p| {
+                let relative = p.strip_prefix(dir.path()).unwrap();
+                relative.to_string_lossy().replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_file_filter_without_patterns_keeps_everything() {
+        assert_eq!(filtered_files(&[], &[]).len(), 4);
+    }
+
+    #[test]
+    fn test_file_filter_include_selects_projects() {
+        assert_eq!(
+            filtered_files(&["-home-me-api"], &[]),
+            vec!["-home-me-api/archive/old.jsonl", "-home-me-api/b.jsonl"]
+        );
+        assert_eq!(
+            filtered_files(&["-home-*", "-work-*"], &[]).len(),
+            4,
+            "several include patterns are combined"
+        );
+        assert_eq!(
+            filtered_files(&["*.jsonl"], &[]),
+            Vec::<String>::new(),
+            "`*` does not cross directories"
+        );
+    }
+
+    #[test]
+    fn test_file_filter_exclude_wins() {
+        assert_eq!(
+            filtered_files(&["-home-*"], &["**/archive"]),
+            vec!["-home-me-api/b.jsonl", "-home-me-web/a.jsonl"]
+        );
+        assert_eq!(filtered_files(&[], &["-work-tool/*.jsonl"]).len(), 3);
+    }
+
+    #[test]
+    fn test_file_filter_rejects_invalid_glob() {
+        let err = FileFilter::new(&["a[b"], &[]).unwrap_err();
+        assert!(err.to_string().contains("invalid glob"), "{err}");
+    }
+
+    // ── project_for_file ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_project_for_file() {
+        let root = Path::new("/data/projects");
+        assert_eq!(
+            project_for_file(root, Path::new("/data/projects/-home-me-app/s1.jsonl")),
+            "-home-me-app"
+        );
+        assert_eq!(
+            project_for_file(root, Path::new("/data/projects/api/sub/s2.jsonl")),
+            "api"
+        );
+        assert_eq!(
+            project_for_file(root, Path::new("/data/projects/root.jsonl")),
+            ""
+        );
+        assert_eq!(
+            project_for_file(root, Path::new("/elsewhere/x/y.jsonl")),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_load_usage_entries_tags_project() {
+        let dir = TempDir::new().unwrap();
+        let sub = dir.path().join("project-abc");
+        std::fs::create_dir_all(&sub).unwrap();
+        let nested = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let top = sample_entry("2024-01-15T11:00:00Z", 10, 5, "msg2", "req2");
+        write_jsonl(&sub, "nested.jsonl", &[&nested]);
+        write_jsonl(dir.path(), "root.jsonl", &[&top]);
+
+        let (entries, _) = load_usage_entries(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+        );
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].project, "project-abc");
+        assert_eq!(entries[1].project, "");
+    }
+
     // ── load_usage_entries ────────────────────────────────────────────────────
 
     #[test]
@@ -506,6 +1403,78 @@ mod tests {
         assert_eq!(entries.len(), 1);
     }
 
+    /// Load a directory holding `lines` twice: once in the original file and
+    /// once in a re-synced copy.
+    fn load_duplicated_file(lines: &[&str], dedup_key: DedupKey) -> Vec<UsageEntry> {
+        let dir = TempDir::new().unwrap();
+        write_jsonl(dir.path(), "usage.jsonl", lines);
+        write_jsonl(dir.path(), "usage (copy).jsonl", lines);
+        let options = LoadOptions {
+            dedup_key,
+            ..LoadOptions::default()
+        };
+        load_usage_entries_with_progress(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &options,
+            |_| {},
+        )
+        .0
+    }
+
+    #[test]
+    fn test_dedup_key_message_request() {
+        let line = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        assert_eq!(
+            load_duplicated_file(&[&line], DedupKey::MessageRequest).len(),
+            1
+        );
+
+        // Without request ids nothing is deduplicated.
+        let line = r#"{"timestamp":"2024-01-15T10:00:00Z","input_tokens":100,"output_tokens":50,"message_id":"msg1"}"#;
+        assert_eq!(
+            load_duplicated_file(&[line], DedupKey::MessageRequest).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_dedup_key_message() {
+        let a = r#"{"timestamp":"2024-01-15T10:00:00Z","input_tokens":100,"output_tokens":50,"message_id":"msg1"}"#;
+        let b = r#"{"timestamp":"2024-01-15T10:05:00Z","input_tokens":100,"output_tokens":50,"message_id":"msg2"}"#;
+        let entries = load_duplicated_file(&[a, b], DedupKey::Message);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message_id, "msg1");
+        assert_eq!(entries[1].message_id, "msg2");
+    }
+
+    #[test]
+    fn test_dedup_key_content_hash() {
+        let a = r#"{"timestamp":"2024-01-15T10:00:00Z","model":"claude-3-5-sonnet","input_tokens":100,"output_tokens":50}"#;
+        // Same time and model but different tokens: a distinct entry.
+        let b = r#"{"timestamp":"2024-01-15T10:00:00Z","model":"claude-3-5-sonnet","input_tokens":100,"output_tokens":60}"#;
+        assert_eq!(
+            load_duplicated_file(&[a, b], DedupKey::ContentHash).len(),
+            2
+        );
+        assert_eq!(
+            load_duplicated_file(&[a, b], DedupKey::MessageRequest).len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_dedup_key_from_name() {
+        assert_eq!(DedupKey::from_name("message"), DedupKey::Message);
+        assert_eq!(DedupKey::from_name("content-hash"), DedupKey::ContentHash);
+        assert_eq!(
+            DedupKey::from_name("message+request"),
+            DedupKey::MessageRequest
+        );
+    }
+
     #[test]
     fn test_load_usage_entries_hours_back_filter() {
         let dir = TempDir::new().unwrap();
@@ -547,6 +1516,28 @@ mod tests {
         assert_eq!(entries.len(), 1);
     }
 
+    #[test]
+    fn test_load_usage_entries_bom_and_crlf() {
+        let dir = TempDir::new().unwrap();
+        let first = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let second = sample_entry("2024-01-15T11:00:00Z", 200, 100, "msg2", "req2");
+        let content = format!("\u{feff}{first}\r\n{second}\r\n");
+        std::fs::write(dir.path().join("windows.jsonl"), content).unwrap();
+
+        let (entries, _, stats) = load_usage_entries_with_stats(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+            |_| {},
+        );
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].input_tokens, 100);
+        assert_eq!(stats.malformed_lines, 0);
+    }
+
     #[test]
     fn test_load_usage_entries_sorted_by_timestamp() {
         let dir = TempDir::new().unwrap();
@@ -579,6 +1570,419 @@ mod tests {
         assert!(raw.is_none());
     }
 
+    // ── load_usage_entries_with_progress ──────────────────────────────────────
+
+    #[test]
+    fn test_load_progress_reported_per_file() {
+        let dir = TempDir::new().unwrap();
+        let a = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let b = sample_entry("2024-01-15T11:00:00Z", 100, 50, "msg2", "req2");
+        let c = sample_entry("2024-01-15T12:00:00Z", 100, 50, "msg3", "req3");
+        write_jsonl(dir.path(), "a.jsonl", &[&a]);
+        write_jsonl(dir.path(), "b.jsonl", &[&b, &c]);
+
+        let reports = std::cell::RefCell::new(Vec::new());
+        let (entries, _) = load_usage_entries_with_progress(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+            |p| reports.borrow_mut().push(p),
+        );
+
+        assert_eq!(entries.len(), 3);
+        let reports = reports.into_inner();
+        assert_eq!(reports.len(), 3);
+        assert_eq!(
+            reports[0],
+            LoadProgress {
+                files_processed: 0,
+                files_total: 2,
+                entries_parsed: 0
+            }
+        );
+        assert_eq!(reports[1].entries_parsed, 1);
+        assert_eq!(
+            reports[2],
+            LoadProgress {
+                files_processed: 2,
+                files_total: 2,
+                entries_parsed: 3
+            }
+        );
+        assert!((reports[2].fraction() - 1.0).abs() < f64::EPSILON);
+    }
+
+    // ── load_usage_entries_with_stats ─────────────────────────────────────────
+
+    #[test]
+    fn test_load_stats_count_skipped_lines() {
+        let dir = TempDir::new().unwrap();
+        let good = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let user = r#"{"type":"user","timestamp":"2024-01-15T10:01:00Z"}"#;
+        write_jsonl(
+            dir.path(),
+            "usage.jsonl",
+            &[&good, &good, "{not json", user],
+        );
+
+        let (entries, _, stats) = load_usage_entries_with_stats(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+            |_| {},
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            stats,
+            LoadStats {
+                files_found: 1,
+                lines_read: 3,
+                malformed_lines: 1,
+                filtered_by_cutoff: 0,
+                duplicates: 1,
+                without_usage: 1,
+                absurd_tokens: 0,
+                entries_kept: 1,
+                warnings: Vec::new(),
+                duplicates_by_file: BTreeMap::new(),
+            }
+        );
+        assert!(entries[0].source_file.is_none());
+    }
+
+    #[test]
+    fn test_absurd_token_counts_are_dropped() {
+        let dir = TempDir::new().unwrap();
+        let good = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        let absurd = sample_entry("2024-01-15T10:01:00Z", u64::MAX, 50, "msg2", "req2");
+        write_jsonl(dir.path(), "usage.jsonl", &[&good, &absurd]);
+
+        let (entries, _, stats) = load_usage_entries_with_stats(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+            |_| {},
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].input_tokens, 100);
+        assert_eq!(stats.absurd_tokens, 1);
+        assert_eq!(stats.without_usage, 0);
+        assert_eq!(stats.warnings.len(), 1);
+        assert!(
+            stats.warnings[0].contains("input_tokens = 18446744073709551615"),
+            "{:?}",
+            stats.warnings
+        );
+
+        // A lower ceiling applies to every token field.
+        let options = LoadOptions {
+            max_entry_tokens: 80,
+            ..LoadOptions::default()
+        };
+        let (entries, _, stats) = load_usage_entries_with_stats(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &options,
+            |_| {},
+        );
+        assert!(entries.is_empty());
+        assert_eq!(stats.absurd_tokens, 2);
+    }
+
+    #[test]
+    fn test_track_sources_records_files() {
+        let dir = TempDir::new().unwrap();
+        let good = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        write_jsonl(dir.path(), "a.jsonl", &[&good]);
+        write_jsonl(dir.path(), "b.jsonl", &[&good]);
+
+        let options = LoadOptions {
+            track_sources: true,
+            ..LoadOptions::default()
+        };
+        let (entries, _, stats) = load_usage_entries_with_stats(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &options,
+            |_| {},
+        );
+
+        assert_eq!(entries.len(), 1);
+        let a = dir.path().join("a.jsonl");
+        let b = dir.path().join("b.jsonl");
+        assert_eq!(entries[0].source_file.as_deref(), Some(a.as_path()));
+        assert_eq!(
+            stats.duplicates_by_file,
+            BTreeMap::from([(b.display().to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn test_load_stats_warn_without_files() {
+        let dir = TempDir::new().unwrap();
+        let (_, _, stats) = load_usage_entries_with_stats(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+            |_| {},
+        );
+        assert_eq!(stats.files_found, 0);
+        assert_eq!(stats.warnings.len(), 1);
+        assert!(stats.warnings[0].starts_with("No JSONL files found"));
+    }
+
+    #[test]
+    fn test_load_progress_empty_directory_reports_once() {
+        let dir = TempDir::new().unwrap();
+        let count = std::cell::Cell::new(0);
+        load_usage_entries_with_progress(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+            |p| {
+                assert_eq!(p, LoadProgress::default());
+                count.set(count.get() + 1);
+            },
+        );
+        assert_eq!(count.get(), 1);
+        assert!((LoadProgress::default().fraction() - 1.0).abs() < f64::EPSILON);
+    }
+
+    // ── LoadOptions::count_empty_messages ─────────────────────────────────────
+
+    #[test]
+    fn test_zero_token_entries_dropped_by_default() {
+        let line = sample_entry("2024-01-15T10:00:00Z", 0, 0, "msg1", "req1");
+        let (entries, _) = load_usage_entries_from_reader(
+            std::io::Cursor::new(line),
+            None,
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+        );
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_zero_token_entries_kept_with_zero_cost() {
+        let line = serde_json::json!({
+            "timestamp": "2024-01-15T10:00:00Z",
+            "input_tokens": 0,
+            "output_tokens": 0,
+            "costUSD": 1.25,
+            "model": "claude-3-5-sonnet-20241022",
+            "message_id": "msg1",
+            "requestId": "req1",
+        })
+        .to_string();
+        let options = LoadOptions {
+            count_empty_messages: true,
+            ..LoadOptions::default()
+        };
+
+        let (entries, _) = load_usage_entries_from_reader(
+            std::io::Cursor::new(line),
+            None,
+            CostMode::Auto,
+            false,
+            &options,
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].input_tokens + entries[0].output_tokens, 0);
+        assert_eq!(entries[0].cost_usd, 0.0);
+    }
+
+    #[test]
+    fn test_cache_only_entries_kept_without_tokens_or_cost() {
+        let line = serde_json::json!({
+            "timestamp": "2024-01-15T10:00:00Z",
+            "input_tokens": 0,
+            "output_tokens": 0,
+            "cache_creation_input_tokens": 2_000,
+            "cache_read_input_tokens": 8_000,
+            "model": "claude-3-5-sonnet-20241022",
+            "message_id": "msg1",
+            "requestId": "req1",
+        })
+        .to_string();
+        let load = |options: &LoadOptions| {
+            load_usage_entries_from_reader(
+                std::io::Cursor::new(line.clone()),
+                None,
+                CostMode::Calculated,
+                false,
+                options,
+            )
+            .0
+        };
+
+        assert!(load(&LoadOptions::default()).is_empty());
+
+        let entries = load(&LoadOptions {
+            count_empty_messages: true,
+            ..LoadOptions::default()
+        });
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].cache_creation_tokens, 0);
+        assert_eq!(entries[0].cache_read_tokens, 0);
+        assert_eq!(entries[0].cost_usd, 0.0);
+    }
+
+    #[test]
+    fn test_system_entries_keep_model_and_cost() {
+        let line = serde_json::json!({
+            "type": "system",
+            "timestamp": "2024-01-15T10:00:00Z",
+            "input_tokens": 10,
+            "output_tokens": 5,
+            "model": "claude-3-5-sonnet-20241022",
+        })
+        .to_string();
+
+        let (entries, _) = load_usage_entries_from_reader(
+            std::io::Cursor::new(line),
+            None,
+            CostMode::Calculated,
+            false,
+            &LoadOptions::default(),
+        );
+
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].is_system);
+        assert!(!entries[0].is_synthetic());
+        assert_eq!(entries[0].model, "claude-3-5-sonnet-20241022");
+        assert!(entries[0].cost_usd > 0.0);
+    }
+
+    // ── LoadOptions::read_timeout ─────────────────────────────────────────────
+
+    #[test]
+    fn test_default_read_timeout() {
+        assert_eq!(
+            LoadOptions::default().read_timeout,
+            Some(DEFAULT_READ_TIMEOUT)
+        );
+    }
+
+    #[test]
+    fn test_timed_reader_streams_regular_file() {
+        let dir = TempDir::new().unwrap();
+        let path = write_jsonl(dir.path(), "a.jsonl", &["{}", "{}"]);
+        let mut reader = TimedReader::open(&path, Duration::from_secs(5)).unwrap();
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).unwrap();
+        assert_eq!(content, b"{}\n{}\n");
+        assert!(reader.error.is_none());
+    }
+
+    #[test]
+    fn test_read_slot_caps_pending_reads() {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let first = ReadSlot::acquire(&COUNTER, 2).unwrap();
+        let second = ReadSlot::acquire(&COUNTER, 2).unwrap();
+        assert!(ReadSlot::acquire(&COUNTER, 2).is_none());
+        drop(first);
+        assert!(ReadSlot::acquire(&COUNTER, 2).is_some());
+        drop(second);
+        assert_eq!(COUNTER.load(Ordering::Acquire), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_timed_reader_gives_up_on_blocked_open() {
+        // Opening a FIFO with no writer blocks, like a hung network mount.
+        let dir = TempDir::new().unwrap();
+        let fifo = dir.path().join("stuck.jsonl");
+        let made = std::process::Command::new("mkfifo").arg(&fifo).status();
+        if !made.is_ok_and(|status| status.success()) {
+            return; // mkfifo unavailable; nothing to test.
+        }
+
+        let err = TimedReader::open(&fifo, Duration::from_millis(50))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+
+        // Unblock the abandoned reader thread.
+        let _ = std::fs::OpenOptions::new().write(true).open(&fifo);
+    }
+
+    #[test]
+    fn test_load_without_read_timeout_still_reads_files() {
+        let dir = TempDir::new().unwrap();
+        write_jsonl(
+            dir.path(),
+            "a.jsonl",
+            &[&sample_entry("2024-01-15T10:00:00Z", 100, 50, "m1", "r1")],
+        );
+        let options = LoadOptions {
+            read_timeout: None,
+            ..LoadOptions::default()
+        };
+        let (entries, _) = load_usage_entries_with_progress(
+            Some(dir.path().to_str().unwrap()),
+            None,
+            CostMode::Auto,
+            false,
+            &options,
+            |_| {},
+        );
+        assert_eq!(entries.len(), 1);
+    }
+
+    // ── load_usage_entries_from_reader ────────────────────────────────────────
+
+    #[test]
+    fn test_load_from_reader_parses_dedups_and_sorts() {
+        let later = sample_entry("2024-01-15T12:00:00Z", 200, 100, "msg2", "req2");
+        let earlier = sample_entry("2024-01-15T08:00:00Z", 100, 50, "msg1", "req1");
+        let input = format!("{later}\n{earlier}\nnot json\n\n{earlier}\n");
+
+        let (entries, raw) = load_usage_entries_from_reader(
+            std::io::Cursor::new(input),
+            None,
+            CostMode::Auto,
+            true,
+            &LoadOptions::default(),
+        );
+
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].timestamp < entries[1].timestamp);
+        assert!(entries[0].cost_usd > 0.0);
+        assert_eq!(raw.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_load_from_reader_applies_cutoff() {
+        let old = sample_entry("2024-01-01T00:00:00Z", 10, 5, "msg-old", "req-old");
+        let (entries, raw) = load_usage_entries_from_reader(
+            std::io::Cursor::new(old),
+            Some(24),
+            CostMode::Auto,
+            false,
+            &LoadOptions::default(),
+        );
+        assert!(entries.is_empty());
+        assert!(raw.is_none());
+    }
+
     // ── load_all_raw_entries ──────────────────────────────────────────────────
 
     #[test]
@@ -588,7 +1992,24 @@ mod tests {
         let line2 = sample_entry("2024-01-15T11:00:00Z", 200, 100, "msg2", "req2");
         write_jsonl(dir.path(), "usage.jsonl", &[&line1, &line2]);
 
-        let raw = load_all_raw_entries(Some(dir.path().to_str().unwrap()));
+        let raw = load_all_raw_entries(Some(dir.path().to_str().unwrap()), &LoadOptions::default());
+        assert_eq!(raw.len(), 2);
+    }
+
+    #[test]
+    fn test_load_all_raw_entries_honours_file_filter() {
+        let dir = TempDir::new().unwrap();
+        let line = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
+        std::fs::create_dir(dir.path().join("api")).unwrap();
+        std::fs::create_dir(dir.path().join("web")).unwrap();
+        write_jsonl(&dir.path().join("api"), "a.jsonl", &[&line]);
+        write_jsonl(&dir.path().join("web"), "b.jsonl", &[&line, &line]);
+
+        let options = LoadOptions {
+            file_filter: FileFilter::new(&["web"], &[]).unwrap(),
+            ..LoadOptions::default()
+        };
+        let raw = load_all_raw_entries(Some(dir.path().to_str().unwrap()), &options);
         assert_eq!(raw.len(), 2);
     }
 
@@ -598,7 +2019,7 @@ mod tests {
         let good = sample_entry("2024-01-15T10:00:00Z", 100, 50, "msg1", "req1");
         write_jsonl(dir.path(), "usage.jsonl", &["{bad", &good]);
 
-        let raw = load_all_raw_entries(Some(dir.path().to_str().unwrap()));
+        let raw = load_all_raw_entries(Some(dir.path().to_str().unwrap()), &LoadOptions::default());
         assert_eq!(raw.len(), 1);
     }
 
@@ -610,7 +2031,7 @@ mod tests {
             "message_id": "abc",
             "requestId": "xyz",
         });
-        let hash = create_unique_hash(&data).unwrap();
+        let hash = create_unique_hash(&data, DedupKey::MessageRequest).unwrap();
         assert_eq!(hash, "abc:xyz");
     }
 
@@ -620,14 +2041,14 @@ mod tests {
             "message": {"id": "nested-id"},
             "requestId": "req-xyz",
         });
-        let hash = create_unique_hash(&data).unwrap();
+        let hash = create_unique_hash(&data, DedupKey::MessageRequest).unwrap();
         assert_eq!(hash, "nested-id:req-xyz");
     }
 
     #[test]
     fn test_create_unique_hash_missing_returns_none() {
         let data = serde_json::json!({"other": "field"});
-        assert!(create_unique_hash(&data).is_none());
+        assert!(create_unique_hash(&data, DedupKey::MessageRequest).is_none());
     }
 
     #[test]
@@ -636,7 +2057,7 @@ mod tests {
             "message_id": "mid",
             "request_id": "rid",
         });
-        let hash = create_unique_hash(&data).unwrap();
+        let hash = create_unique_hash(&data, DedupKey::MessageRequest).unwrap();
         assert_eq!(hash, "mid:rid");
     }
 }
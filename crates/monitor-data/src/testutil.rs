@@ -0,0 +1,198 @@
+//! Synthetic JSONL fixtures for tests.
+//!
+//! Compiled for this crate's own tests and, behind the `testutil` feature,
+//! for other crates' tests. [`UsageFixture`] writes assistant lines in the
+//! same shape the Claude CLI produces, so fixtures go through the real
+//! reader, analyzer and aggregator.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tempfile::TempDir;
+
+/// Builder for a JSONL usage file.
+///
+/// Entry `i` is stamped `start + i * interval`, uses model
+/// `models[i % models.len()]` and carries the configured token counts, so
+/// the expected totals are easy to compute (see
+/// [`total_tokens`](Self::total_tokens)).
+#[derive(Debug, Clone)]
+pub struct UsageFixture {
+    entries: usize,
+    models: Vec<String>,
+    start: DateTime<Utc>,
+    interval: Duration,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_creation_tokens: u64,
+    cache_read_tokens: u64,
+    project: String,
+}
+
+impl Default for UsageFixture {
+    fn default() -> Self {
+        Self {
+            entries: 10,
+            models: vec!["claude-3-5-sonnet-20241022".to_string()],
+            start: Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap(),
+            interval: Duration::minutes(5),
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            project: "fixture-project".to_string(),
+        }
+    }
+}
+
+impl UsageFixture {
+    /// Ten Sonnet entries, five minutes apart from 2024-01-15 10:00 UTC.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of lines to generate.
+    pub fn with_entries(mut self, entries: usize) -> Self {
+        self.entries = entries;
+        self
+    }
+
+    /// Models to rotate through; must not be empty.
+    pub fn with_models(mut self, models: &[&str]) -> Self {
+        assert!(!models.is_empty(), "a fixture needs at least one model");
+        self.models = models.iter().map(|m| m.to_string()).collect();
+        self
+    }
+
+    /// Timestamp of the first entry.
+    pub fn with_start(mut self, start: DateTime<Utc>) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Gap between consecutive entries.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Input and output tokens of every entry.
+    pub fn with_tokens(mut self, input: u64, output: u64) -> Self {
+        self.input_tokens = input;
+        self.output_tokens = output;
+        self
+    }
+
+    /// Cache creation and cache read tokens of every entry.
+    pub fn with_cache_tokens(mut self, creation: u64, read: u64) -> Self {
+        self.cache_creation_tokens = creation;
+        self.cache_read_tokens = read;
+        self
+    }
+
+    /// Project directory the file is written into.
+    pub fn with_project(mut self, project: &str) -> Self {
+        self.project = project.to_string();
+        self
+    }
+
+    /// Timestamp of entry `index`.
+    pub fn timestamp(&self, index: usize) -> DateTime<Utc> {
+        self.start + self.interval * index as i32
+    }
+
+    /// All tokens (including cache tokens) across every entry.
+    pub fn total_tokens(&self) -> u64 {
+        let per_entry = self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_tokens
+            + self.cache_read_tokens;
+        per_entry * self.entries as u64
+    }
+
+    /// The JSONL lines, one per entry, without trailing newlines.
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.entries)
+            .map(|i| {
+                serde_json::json!({
+                    "type": "assistant",
+                    "timestamp": self.timestamp(i).to_rfc3339(),
+                    "sessionId": self.project,
+                    "requestId": format!("req_{}_{i}", self.project),
+                    "message": {
+                        "id": format!("msg_{}_{i}", self.project),
+                        "model": self.models[i % self.models.len()],
+                        "usage": {
+                            "input_tokens": self.input_tokens,
+                            "output_tokens": self.output_tokens,
+                            "cache_creation_input_tokens": self.cache_creation_tokens,
+                            "cache_read_input_tokens": self.cache_read_tokens,
+                        },
+                    },
+                })
+                .to_string()
+            })
+            .collect()
+    }
+
+    /// Write the lines to `<data_root>/<project>/session.jsonl` and return
+    /// the file's path.
+    pub fn write_to(&self, data_root: &Path) -> std::io::Result<PathBuf> {
+        let dir = data_root.join(&self.project);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("session.jsonl");
+        let mut file = std::fs::File::create(&path)?;
+        for line in self.lines() {
+            writeln!(file, "{line}")?;
+        }
+        Ok(path)
+    }
+
+    /// Write the fixture into a fresh temporary data root.
+    ///
+    /// Returns the directory guard (keep it alive for the duration of the
+    /// test) together with the data root to pass as `data_path`.
+    pub fn write_temp(&self) -> std::io::Result<(TempDir, PathBuf)> {
+        let dir = TempDir::new()?;
+        self.write_to(dir.path())?;
+        let root = dir.path().to_path_buf();
+        Ok((dir, root))
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::load_usage_entries;
+    use monitor_core::models::CostMode;
+
+    // ── UsageFixture ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_fixture_round_trips_through_reader() {
+        let fixture = UsageFixture::new()
+            .with_entries(4)
+            .with_models(&["claude-3-5-sonnet-20241022", "claude-3-opus-20240229"])
+            .with_cache_tokens(20, 30);
+        let (_dir, root) = fixture.write_temp().unwrap();
+
+        let (entries, _) =
+            load_usage_entries(Some(root.to_str().unwrap()), None, CostMode::Auto, false);
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[1].timestamp, fixture.timestamp(1));
+        assert!(entries[1].model.contains("opus"), "{}", entries[1].model);
+        assert_eq!(entries[0].cache_read_tokens, 30);
+        assert_eq!(entries[0].project, "fixture-project");
+        let total: u64 = entries
+            .iter()
+            .map(|e| {
+                e.input_tokens + e.output_tokens + e.cache_creation_tokens + e.cache_read_tokens
+            })
+            .sum();
+        assert_eq!(total, fixture.total_tokens());
+    }
+}
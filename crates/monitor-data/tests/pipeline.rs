@@ -0,0 +1,56 @@
+//! End-to-end check of the data pipeline on generated JSONL fixtures:
+//! reader → analysis → aggregator.
+
+use chrono::{Duration, TimeZone, Utc};
+use monitor_core::models::CostMode;
+use monitor_data::aggregator::UsageAggregator;
+use monitor_data::analysis::analyze_usage;
+use monitor_data::reader::load_usage_entries;
+use monitor_data::testutil::UsageFixture;
+
+#[test]
+fn test_pipeline_totals_match_fixture() {
+    // 48 entries an hour apart span two days and several 5-hour blocks.
+    let sonnet = UsageFixture::new()
+        .with_entries(48)
+        .with_models(&["claude-3-5-sonnet-20241022", "claude-3-haiku-20240307"])
+        .with_start(Utc.with_ymd_and_hms(2024, 1, 15, 0, 30, 0).unwrap())
+        .with_interval(Duration::hours(1))
+        .with_tokens(1_000, 250)
+        .with_cache_tokens(400, 2_000);
+    let opus = UsageFixture::new()
+        .with_project("other-project")
+        .with_models(&["claude-3-opus-20240229"])
+        .with_start(Utc.with_ymd_and_hms(2024, 1, 16, 9, 0, 0).unwrap())
+        .with_entries(5);
+    let (dir, root) = sonnet.write_temp().unwrap();
+    opus.write_to(dir.path()).unwrap();
+    let root = root.to_str().unwrap();
+    let expected_tokens = sonnet.total_tokens() + opus.total_tokens();
+
+    let (entries, _) = load_usage_entries(Some(root), None, CostMode::Auto, false);
+    assert_eq!(entries.len(), 53);
+
+    let analysis = analyze_usage(None, false, Some(root));
+    assert_eq!(analysis.entries_count, 53);
+    assert_eq!(analysis.total_tokens, expected_tokens);
+    assert!(analysis.total_cost > 0.0);
+
+    let daily = UsageAggregator::aggregate_from_blocks(&analysis.blocks, "daily");
+    let keys: Vec<&str> = daily.iter().map(|p| p.period_key.as_str()).collect();
+    assert_eq!(keys, ["2024-01-15", "2024-01-16"]);
+
+    let totals = UsageAggregator::calculate_totals(&daily);
+    assert_eq!(totals.count, 53);
+    assert_eq!(totals.total_tokens(), expected_tokens);
+    assert_eq!(totals.cache_read_tokens, 48 * 2_000);
+    assert!((totals.cost - analysis.total_cost).abs() < 1e-9);
+
+    let models = UsageAggregator::calculate_model_totals(&daily);
+    assert_eq!(models.len(), 3);
+
+    let projects = UsageAggregator::aggregate_by_project(&entries);
+    let keys: Vec<&str> = projects.iter().map(|p| p.period_key.as_str()).collect();
+    assert_eq!(keys, ["fixture-project", "other-project"]);
+    assert_eq!(projects[1].stats.total_tokens(), opus.total_tokens());
+}
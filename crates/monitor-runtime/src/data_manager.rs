@@ -1,6 +1,6 @@
 //! TTL-cached data manager for the monitoring runtime.
 //!
-//! Wraps [`analyze_usage`] with a configurable time-to-live cache and
+//! Wraps [`analyze_usage_with_progress`] with a configurable time-to-live cache and
 //! transparent retry logic. Callers use [`DataManager::get_data`] to obtain
 //! a fresh-or-cached [`AnalysisResult`]; the manager handles staleness checks,
 //! up to three fetch attempts with exponential back-off, and graceful fallback
@@ -9,7 +9,9 @@
 use std::thread;
 use std::time::{Duration, Instant};
 
-use monitor_data::analysis::{analyze_usage, AnalysisResult};
+use monitor_data::analysis::{analyze_usage_with_progress, AnalysisResult};
+use monitor_data::reader::{LoadOptions, LoadProgress};
+use tokio::sync::mpsc;
 
 // ── Defaults ──────────────────────────────────────────────────────────────────
 
@@ -50,6 +52,10 @@ pub struct DataManager {
     last_error: Option<String>,
     /// When the last *successful* fetch completed.
     last_successful_fetch: Option<Instant>,
+    /// Optional sink for per-file loader progress during fresh fetches.
+    progress_tx: Option<mpsc::Sender<LoadProgress>>,
+    /// Loader behaviour forwarded to every fresh fetch.
+    load_options: LoadOptions,
 }
 
 impl DataManager {
@@ -68,9 +74,32 @@ impl DataManager {
             cache_timestamp: None,
             last_error: None,
             last_successful_fetch: None,
+            progress_tx: None,
+            load_options: LoadOptions::default(),
         }
     }
 
+    /// Consider cached data stale after `ttl`.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Apply loader `options` (e.g. keeping zero-token messages) to fetches.
+    pub fn with_load_options(mut self, options: LoadOptions) -> Self {
+        self.load_options = options;
+        self
+    }
+
+    /// Forward loader progress to `tx` while fresh data is being fetched.
+    ///
+    /// Reports are sent with `try_send`; when the channel is full the update
+    /// is dropped rather than stalling the load.
+    pub fn with_progress(mut self, tx: mpsc::Sender<LoadProgress>) -> Self {
+        self.progress_tx = Some(tx);
+        self
+    }
+
     // ── Public API ────────────────────────────────────────────────────────
 
     /// Return analysis data, using the cache when it is still valid.
@@ -121,6 +150,11 @@ impl DataManager {
         self.cache_timestamp.map(|ts| ts.elapsed())
     }
 
+    /// JSONL directory override this manager reads from, if any.
+    pub fn data_path(&self) -> Option<&str> {
+        self.data_path.as_deref()
+    }
+
     /// Human-readable description of the last fetch error, or `None`.
     pub fn last_error(&self) -> Option<&str> {
         self.last_error.as_deref()
@@ -167,9 +201,20 @@ impl DataManager {
         // analyze_usage is infallible by design; any I/O issues surface as
         // empty results rather than panics, so we wrap in a catch-unwind for
         // maximum robustness.
-        let result = std::panic::catch_unwind(|| {
-            analyze_usage(Some(self.hours_back), false, self.data_path.as_deref())
-        })
+        let report = |progress: LoadProgress| {
+            if let Some(tx) = &self.progress_tx {
+                let _ = tx.try_send(progress);
+            }
+        };
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            analyze_usage_with_progress(
+                Some(self.hours_back),
+                false,
+                self.data_path.as_deref(),
+                &self.load_options,
+                report,
+            )
+        }))
         .map_err(|e| {
             format!(
                 "analyze_usage panicked: {:?}",
@@ -222,7 +267,7 @@ mod tests {
 
     #[test]
     fn test_cache_miss_on_first_call() {
-        let (mut mgr, _dir) = make_manager_with_dir(30);
+        let (mgr, _dir) = make_manager_with_dir(30);
 
         // No cache yet.
         assert!(!mgr.is_cache_valid());
@@ -330,6 +375,21 @@ mod tests {
         assert!(mgr.last_error().is_none());
     }
 
+    // ── progress forwarding ───────────────────────────────────────────────
+
+    #[test]
+    fn test_progress_forwarded_to_channel() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let (mgr, _dir) = make_manager_with_dir(30);
+        let mut mgr = mgr.with_progress(tx);
+
+        mgr.get_data(true);
+
+        // An empty directory still yields a single "nothing to load" report.
+        let progress = rx.try_recv().expect("progress report sent");
+        assert_eq!(progress, LoadProgress::default());
+    }
+
     // ── make_manager (drop-dir variant) still constructs OK ───────────────
 
     #[test]
@@ -3,18 +3,30 @@
 //! Coordinates [`DataManager`] and [`SessionMonitor`] in a tokio task, sending
 //! periodic [`MonitoringData`] snapshots through an `mpsc` channel so the TUI
 //! event loop can consume them without any shared mutable state.
+//!
+//! Embedders that run their own scheduler can skip [`MonitoringOrchestrator::start`]
+//! and call [`MonitoringOrchestrator::poll_once`] directly; async embedders
+//! can consume [`MonitoringOrchestrator::stream`] instead of the channel.
 
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use monitor_core::plans::Plans;
+use monitor_core::models::SessionBlock;
+use monitor_core::p90::{detect_limit_from_blocks, DetectedLimit, LimitDetect, P90Config};
+use monitor_core::plans::{resolve_token_limit, CustomPlanLimits, PlanType};
 use monitor_data::analysis::AnalysisResult;
+use monitor_data::reader::{resolve_data_path, LoadOptions, LoadProgress};
 use serde_json::Value;
 use tokio::sync::mpsc;
 use tokio::time;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::data_manager::DataManager;
 use crate::session_monitor::SessionMonitor;
 
+/// Default seconds between re-analyses of the usage data.
+pub const DEFAULT_ANALYZE_INTERVAL_SECS: u64 = 30;
+
 // ── Public types ──────────────────────────────────────────────────────────────
 
 /// A single monitoring snapshot forwarded to the TUI layer.
@@ -33,6 +45,14 @@ pub struct MonitoringData {
     pub session_id: Option<String>,
     /// Total number of sessions observed since startup.
     pub session_count: usize,
+    /// Directory the JSONL files are read from, after applying the
+    /// `~/.claude/projects` default; `None` without a home directory.
+    pub data_path: Option<PathBuf>,
+    /// Limit detected for the custom plan when it sits below the peak it was
+    /// detected from, or more than
+    /// [`LOOSE_LIMIT_MARGIN`](monitor_core::p90::LOOSE_LIMIT_MARGIN) above
+    /// it, so the usage bar may read high or low.
+    pub poor_limit_fit: Option<DetectedLimit>,
 }
 
 // ── MonitoringOrchestrator ────────────────────────────────────────────────────
@@ -43,29 +63,150 @@ pub struct MonitoringData {
 /// dedicated tokio task and receive a channel endpoint for [`MonitoringData`]
 /// updates.
 pub struct MonitoringOrchestrator {
-    /// How often to refresh the analysis.
+    /// How often to send a snapshot, reusing the analysis while it is fresh.
     update_interval: Duration,
-    /// Optional override for the JSONL data directory.
-    data_path: Option<String>,
+    /// How often to re-read and re-analyse the usage data.
+    analyze_interval: Duration,
     /// Canonical plan name used for limit look-ups.
     plan: String,
+    /// User overrides applied when `plan` is `"custom"`.
+    custom_limits: CustomPlanLimits,
+    /// How a limit detected for the custom plan snaps to the known steps.
+    limit_detect: LimitDetect,
+    /// Cached analysis pipeline shared by every poll.
+    data_manager: DataManager,
+    /// Session tracker carried across polls.
+    session_monitor: SessionMonitor,
+    /// Requests for an immediate refresh, e.g. from a reload key.
+    refresh_rx: Option<mpsc::Receiver<()>>,
 }
 
 impl MonitoringOrchestrator {
     /// Create a new orchestrator.
     ///
     /// # Parameters
-    /// - `update_interval_secs` – seconds between monitoring refreshes.
+    /// - `update_interval_secs` – seconds between monitoring snapshots; the
+    ///   data itself is re-analysed every
+    ///   [`DEFAULT_ANALYZE_INTERVAL_SECS`] (see
+    ///   [`with_analyze_interval`](Self::with_analyze_interval)).
     /// - `data_path`            – optional JSONL directory override.
     /// - `plan`                 – canonical plan name (e.g. `"pro"`).
     pub fn new(update_interval_secs: u64, data_path: Option<String>, plan: String) -> Self {
         Self {
             update_interval: Duration::from_secs(update_interval_secs),
-            data_path,
+            analyze_interval: Duration::from_secs(DEFAULT_ANALYZE_INTERVAL_SECS),
+            data_manager: DataManager::new(DEFAULT_ANALYZE_INTERVAL_SECS, 192, data_path),
             plan,
+            custom_limits: CustomPlanLimits::default(),
+            limit_detect: LimitDetect::default(),
+            session_monitor: SessionMonitor::new(),
+            refresh_rx: None,
         }
     }
 
+    /// Snap a limit detected for the custom plan with `detect`
+    /// (`--limit-detect`).
+    pub fn with_limit_detect(mut self, detect: LimitDetect) -> Self {
+        self.limit_detect = detect;
+        self
+    }
+
+    /// Re-read and re-analyse the usage data every `interval`, on its own
+    /// timer, whatever the snapshot interval.
+    pub fn with_analyze_interval(mut self, interval: Duration) -> Self {
+        self.analyze_interval = interval;
+        self.data_manager = self.data_manager.with_cache_ttl(interval);
+        self
+    }
+
+    /// Apply user-defined limits for the `"custom"` plan.
+    pub fn with_custom_limits(mut self, custom_limits: CustomPlanLimits) -> Self {
+        self.custom_limits = custom_limits;
+        self
+    }
+
+    /// Report per-file loader progress on `tx` during each fresh fetch.
+    ///
+    /// Lets the TUI show a loading indicator before the first
+    /// [`MonitoringData`] snapshot arrives.
+    pub fn with_progress(mut self, tx: mpsc::Sender<LoadProgress>) -> Self {
+        self.data_manager = self.data_manager.with_progress(tx);
+        self
+    }
+
+    /// Run a forced refresh whenever a request arrives on `rx`, without
+    /// waiting for the next interval tick. The interval restarts after each
+    /// manual refresh; once every sender is dropped only the timer remains.
+    pub fn with_refresh_requests(mut self, rx: mpsc::Receiver<()>) -> Self {
+        self.refresh_rx = Some(rx);
+        self
+    }
+
+    /// Keep session tracking in the state file at `path`, so the session
+    /// history and current session survive a restart (see
+    /// [`SessionMonitor::new_with_state`]).
+    pub fn with_session_state(mut self, path: &Path) -> Self {
+        self.session_monitor = SessionMonitor::new_with_state(path);
+        self
+    }
+
+    /// Apply loader `options` to every analysis run.
+    pub fn with_load_options(mut self, options: LoadOptions) -> Self {
+        self.data_manager = self.data_manager.with_load_options(options);
+        self
+    }
+
+    /// Run a single monitoring cycle and return its snapshot.
+    ///
+    /// Fetches analysis data (from cache unless `force_refresh` is set or
+    /// the cache is stale), updates session tracking, and resolves the token
+    /// limit with [`resolve_token_limit`]. Returns `None` only when no data has ever been loaded.
+    ///
+    /// This blocks while the JSONL files are read; async callers that drive
+    /// their own schedule should run it via `tokio::task::spawn_blocking` or
+    /// accept the short stall. [`MonitoringOrchestrator::start`] calls it on
+    /// every tick.
+    pub fn poll_once(&mut self, force_refresh: bool) -> Option<MonitoringData> {
+        // Obtain analysis result (clone so we can own it for the snapshot).
+        let analysis = match self.data_manager.get_data(force_refresh) {
+            Some(r) => r.clone(),
+            None => {
+                tracing::warn!("no analysis data available");
+                return None;
+            }
+        };
+
+        // Convert to Value so SessionMonitor can validate and track sessions.
+        let as_value = analysis_to_value(&analysis);
+        let (_, errors) = self.session_monitor.update(&as_value);
+        if !errors.is_empty() {
+            tracing::debug!(?errors, "session monitor validation errors");
+        }
+
+        let token_limit = resolve_token_limit(
+            &self.plan,
+            self.custom_limits.token_limit,
+            &analysis.blocks,
+            self.limit_detect,
+        );
+        let poor_limit_fit = self.poor_limit_fit(&analysis.blocks);
+        let session_id = self
+            .session_monitor
+            .current_session_id()
+            .map(|s| s.to_string());
+        let session_count = self.session_monitor.session_count();
+
+        Some(MonitoringData {
+            analysis,
+            token_limit,
+            plan: self.plan.clone(),
+            session_id,
+            session_count,
+            data_path: resolve_data_path(self.data_manager.data_path()),
+            poor_limit_fit,
+        })
+    }
+
     /// Start the monitoring loop.
     ///
     /// Spawns a tokio task that runs the monitoring loop. Returns:
@@ -82,71 +223,89 @@ impl MonitoringOrchestrator {
         (rx, MonitoringHandle { handle })
     }
 
+    /// Start the monitoring loop and return its snapshots as a
+    /// [`Stream`](tokio_stream::Stream), for composing with `select_all`,
+    /// `throttle` and other stream adapters.
+    ///
+    /// The loop stops once the stream is dropped.
+    ///
+    /// ```
+    /// use monitor_runtime::orchestrator::MonitoringOrchestrator;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let dir = tempfile::TempDir::new().unwrap();
+    /// # let data_path = Some(dir.path().to_string_lossy().into_owned());
+    /// let mut updates = MonitoringOrchestrator::new(10, data_path, "pro".to_string()).stream();
+    /// let first = updates.next().await.expect("initial snapshot");
+    /// assert_eq!(first.plan, "pro");
+    /// # }
+    /// ```
+    pub fn stream(self) -> ReceiverStream<MonitoringData> {
+        let (rx, _handle) = self.start();
+        ReceiverStream::new(rx)
+    }
+
     // ── Private implementation ────────────────────────────────────────────
 
     /// The main monitoring loop.
     ///
-    /// Performs an immediate fetch on startup, then repeats on `update_interval`.
-    /// The loop exits when the receiver side of the channel is closed.
-    async fn monitoring_loop(self, tx: mpsc::Sender<MonitoringData>) {
-        let mut data_manager = DataManager::new(30, 192, self.data_path.clone());
-        let mut session_monitor = SessionMonitor::new();
-
+    /// Performs an immediate fetch on startup, then sends a snapshot on
+    /// `update_interval`, a freshly analysed one on `analyze_interval`, and
+    /// one on every refresh request. The loop exits when the receiver side of
+    /// the channel is closed.
+    async fn monitoring_loop(mut self, tx: mpsc::Sender<MonitoringData>) {
         // Initial fetch (force refresh to populate immediately).
-        self.fetch_and_send(&mut data_manager, &mut session_monitor, &tx, true)
-            .await;
+        self.poll_and_send(&tx, true).await;
 
         let mut interval = time::interval(self.update_interval);
-        // Consume the first tick which fires immediately; we already fetched above.
+        let mut analyze = time::interval(self.analyze_interval);
+        // Consume the first ticks which fire immediately; we already fetched above.
         interval.tick().await;
+        analyze.tick().await;
 
         loop {
-            interval.tick().await;
+            let (force, requested) = tokio::select! {
+                _ = interval.tick() => (false, false),
+                _ = analyze.tick() => (true, false),
+                () = next_refresh_request(&mut self.refresh_rx) => (true, true),
+            };
 
             if tx.is_closed() {
                 tracing::debug!("monitoring channel closed; exiting loop");
                 break;
             }
 
-            self.fetch_and_send(&mut data_manager, &mut session_monitor, &tx, false)
-                .await;
+            if requested {
+                tracing::debug!("manual refresh requested");
+                interval.reset();
+            }
+            if force {
+                analyze.reset();
+            }
+            self.poll_and_send(&tx, force).await;
         }
     }
 
-    /// Fetch fresh data and send a [`MonitoringData`] snapshot to the channel.
-    async fn fetch_and_send(
-        &self,
-        data_manager: &mut DataManager,
-        session_monitor: &mut SessionMonitor,
-        tx: &mpsc::Sender<MonitoringData>,
-        force: bool,
-    ) {
-        // Obtain analysis result (clone so we can own it for the snapshot).
-        let analysis = match data_manager.get_data(force) {
-            Some(r) => r.clone(),
-            None => {
-                tracing::warn!("no analysis data available; skipping send");
-                return;
-            }
-        };
-
-        // Convert to Value so SessionMonitor can validate and track sessions.
-        let as_value = analysis_to_value(&analysis);
-        let (_, errors) = session_monitor.update(&as_value);
-        if !errors.is_empty() {
-            tracing::debug!(?errors, "session monitor validation errors");
+    /// The limit detected for the custom plan when it
+    /// [fits the peak poorly](DetectedLimit::is_poor_fit); `None` for other
+    /// plans, an explicit `--custom-limit-tokens` or a limit close to the peak.
+    fn poor_limit_fit(&self, blocks: &[SessionBlock]) -> Option<DetectedLimit> {
+        let detecting = self.plan.parse::<PlanType>().ok() == Some(PlanType::Custom)
+            && self.custom_limits.token_limit.is_none();
+        if !detecting {
+            return None;
         }
+        detect_limit_from_blocks(blocks, &P90Config::default(), self.limit_detect)
+            .filter(DetectedLimit::is_poor_fit)
+    }
 
-        let token_limit = Plans::get_token_limit(&self.plan);
-        let session_id = session_monitor.current_session_id().map(|s| s.to_string());
-        let session_count = session_monitor.session_count();
-
-        let snapshot = MonitoringData {
-            analysis,
-            token_limit,
-            plan: self.plan.clone(),
-            session_id,
-            session_count,
+    /// Run [`poll_once`](Self::poll_once) and send the snapshot, if any.
+    async fn poll_and_send(&mut self, tx: &mpsc::Sender<MonitoringData>, force: bool) {
+        // `poll_once` already logged why there is nothing to send.
+        let Some(snapshot) = self.poll_once(force) else {
+            return;
         };
 
         if let Err(e) = tx.send(snapshot).await {
@@ -173,10 +332,22 @@ impl MonitoringHandle {
 
 // ── Private helpers ───────────────────────────────────────────────────────────
 
+/// Resolve on the next refresh request. Never resolves without a channel,
+/// or once every sender is gone (the channel is then dropped).
+async fn next_refresh_request(rx: &mut Option<mpsc::Receiver<()>>) {
+    if let Some(requests) = rx.as_mut() {
+        if requests.recv().await.is_some() {
+            return;
+        }
+        *rx = None;
+    }
+    std::future::pending().await
+}
+
 /// Convert an [`AnalysisResult`] to the `serde_json::Value` shape that
 /// [`SessionMonitor::validate_data`] expects.
 ///
-/// Shape: `{ "blocks": [ { id, isActive, totalTokens, costUSD, startTime? } ] }`.
+/// Shape: `{ "blocks": [ { id, isActive, isGap, totalTokens, costUSD, startTime? } ] }`.
 fn analysis_to_value(result: &AnalysisResult) -> Value {
     let blocks: Vec<Value> = result
         .blocks
@@ -185,6 +356,7 @@ fn analysis_to_value(result: &AnalysisResult) -> Value {
             serde_json::json!({
                 "id": b.id,
                 "isActive": b.is_active,
+                "isGap": b.is_gap,
                 "totalTokens": b.total_tokens(),
                 "costUSD": b.cost_usd,
                 "startTime": b.start_time.to_rfc3339(),
@@ -200,6 +372,7 @@ fn analysis_to_value(result: &AnalysisResult) -> Value {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use monitor_core::testutil::completed_block;
     use monitor_data::analysis::{AnalysisMetadata, AnalysisResult};
 
     // ── helpers ───────────────────────────────────────────────────────────
@@ -213,8 +386,11 @@ mod tests {
                 entries_processed: 0,
                 blocks_created: 0,
                 limits_detected: 0,
+                session_hours: 5,
+                files_loaded: 0,
                 load_time_seconds: 0.0,
                 transform_time_seconds: 0.0,
+                load_stats: Default::default(),
             },
             entries_count: 0,
             total_tokens: 0,
@@ -229,8 +405,112 @@ mod tests {
         let orch =
             MonitoringOrchestrator::new(5, Some("/tmp/test-data".to_string()), "pro".to_string());
         assert_eq!(orch.update_interval, Duration::from_secs(5));
-        assert_eq!(orch.data_path.as_deref(), Some("/tmp/test-data"));
+        assert_eq!(
+            orch.analyze_interval,
+            Duration::from_secs(DEFAULT_ANALYZE_INTERVAL_SECS)
+        );
+        assert_eq!(orch.data_manager.data_path(), Some("/tmp/test-data"));
         assert_eq!(orch.plan, "pro");
+        assert!(orch.custom_limits.is_empty());
+    }
+
+    #[test]
+    fn test_orchestrator_with_custom_limits() {
+        let limits = CustomPlanLimits {
+            token_limit: Some(123_000),
+            ..Default::default()
+        };
+        let orch = MonitoringOrchestrator::new(5, None, "custom".to_string())
+            .with_custom_limits(limits.clone());
+        assert_eq!(orch.custom_limits, limits);
+    }
+
+    #[test]
+    fn test_poorly_fitting_detected_limit_is_reported() {
+        // 120k snaps up to 220k with ceil, well over 20% above the peak.
+        let blocks = vec![completed_block(120_000)];
+        let orch = MonitoringOrchestrator::new(5, None, "custom".to_string())
+            .with_limit_detect(LimitDetect::Ceil);
+        assert_eq!(
+            orch.poor_limit_fit(&blocks),
+            Some(DetectedLimit {
+                limit: 220_000,
+                peak: 120_000
+            })
+        );
+
+        // The nearest step (88k) is below the peak.
+        let orch = MonitoringOrchestrator::new(5, None, "custom".to_string())
+            .with_limit_detect(LimitDetect::Nearest);
+        assert_eq!(
+            orch.poor_limit_fit(&blocks),
+            Some(DetectedLimit {
+                limit: 88_000,
+                peak: 120_000
+            })
+        );
+
+        // The P90 itself always fits.
+        let orch = MonitoringOrchestrator::new(5, None, "custom".to_string());
+        assert_eq!(orch.poor_limit_fit(&blocks), None);
+
+        // An explicit limit is never second-guessed.
+        let orch = MonitoringOrchestrator::new(5, None, "custom".to_string())
+            .with_custom_limits(CustomPlanLimits {
+                token_limit: Some(500_000),
+                ..Default::default()
+            })
+            .with_limit_detect(LimitDetect::Ceil);
+        assert_eq!(orch.poor_limit_fit(&blocks), None);
+    }
+
+    // ── poll_once ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_poll_once_empty_dir_returns_snapshot() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+
+        let mut orch = MonitoringOrchestrator::new(60, Some(path), "max5".to_string());
+        let data = orch.poll_once(true).expect("snapshot");
+
+        assert_eq!(data.plan, "max5");
+        assert_eq!(data.token_limit, 88_000);
+        assert!(data.analysis.blocks.is_empty());
+        assert!(data.session_id.is_none());
+        assert_eq!(data.session_count, 0);
+        assert_eq!(data.data_path.as_deref(), Some(dir.path()));
+    }
+
+    #[test]
+    fn test_poll_once_tracks_active_session_across_polls() {
+        use std::io::Write;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let ts = (chrono::Utc::now() - chrono::Duration::minutes(5)).to_rfc3339();
+        let line = serde_json::json!({
+            "timestamp": ts,
+            "input_tokens": 100,
+            "output_tokens": 50,
+            "model": "claude-3-5-sonnet-20241022",
+            "message_id": "msg1",
+            "requestId": "req1",
+        });
+        let mut file = std::fs::File::create(dir.path().join("usage.jsonl")).unwrap();
+        writeln!(file, "{}", line).unwrap();
+
+        let path = dir.path().to_str().unwrap().to_string();
+        let mut orch = MonitoringOrchestrator::new(60, Some(path), "pro".to_string());
+
+        let first = orch.poll_once(true).expect("first snapshot");
+        assert!(first.session_id.is_some());
+        assert_eq!(first.session_count, 1);
+        assert_eq!(first.analysis.total_tokens, 150);
+
+        // A second poll sees the same session rather than a new one.
+        let second = orch.poll_once(false).expect("second snapshot");
+        assert_eq!(second.session_id, first.session_id);
+        assert_eq!(second.session_count, 1);
     }
 
     // ── MonitoringData structure ──────────────────────────────────────────
@@ -243,6 +523,8 @@ mod tests {
             plan: "pro".to_string(),
             session_id: Some("test-session".to_string()),
             session_count: 1,
+            data_path: None,
+            poor_limit_fit: None,
         };
 
         assert_eq!(data.token_limit, 19_000);
@@ -261,6 +543,8 @@ mod tests {
             plan: "max5".to_string(),
             session_id: None,
             session_count: 0,
+            data_path: None,
+            poor_limit_fit: None,
         };
         let cloned = data.clone();
         assert_eq!(cloned.token_limit, 88_000);
@@ -287,6 +571,8 @@ mod tests {
             plan: "pro".to_string(),
             session_id: None,
             session_count: 0,
+            data_path: None,
+            poor_limit_fit: None,
         };
         assert_eq!(data.token_limit, 19_000);
         assert_eq!(data.plan, "pro");
@@ -301,6 +587,8 @@ mod tests {
             plan: "max5".to_string(),
             session_id: None,
             session_count: 0,
+            data_path: None,
+            poor_limit_fit: None,
         };
         assert_eq!(data.plan, "max5");
         assert_eq!(data.token_limit, 88_000);
@@ -321,6 +609,25 @@ mod tests {
         handle.abort();
     }
 
+    // ── async: stream ─────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_orchestrator_stream_yields_snapshots() {
+        use tokio_stream::StreamExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+
+        let orch = MonitoringOrchestrator::new(60, Some(path), "max5".to_string());
+        let mut stream = orch.stream();
+
+        let snapshot = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("timed out waiting for snapshot")
+            .expect("stream ended before the first snapshot");
+        assert_eq!(snapshot.plan, "max5");
+    }
+
     // ── async: receives initial snapshot ─────────────────────────────────
 
     #[tokio::test]
@@ -342,4 +649,116 @@ mod tests {
 
         handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_orchestrator_snapshot_uses_custom_token_limit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+
+        let orch = MonitoringOrchestrator::new(60, Some(path), "custom".to_string())
+            .with_custom_limits(CustomPlanLimits {
+                token_limit: Some(123_000),
+                ..Default::default()
+            });
+        let (mut rx, handle) = orch.start();
+
+        let snapshot = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for snapshot")
+            .expect("channel closed before receiving snapshot");
+
+        assert_eq!(snapshot.token_limit, 123_000);
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_reports_progress_before_snapshot() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+
+        let (progress_tx, mut progress_rx) = mpsc::channel(16);
+        let orch = MonitoringOrchestrator::new(60, Some(path), "pro".to_string())
+            .with_progress(progress_tx);
+        let (mut rx, handle) = orch.start();
+
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for snapshot")
+            .expect("channel closed before receiving snapshot");
+
+        // The load completes before the snapshot is sent, so progress is queued.
+        let progress = progress_rx.try_recv().expect("progress report queued");
+        assert_eq!(progress.files_total, 0);
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_refresh_request_sends_snapshot_now() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+
+        let (refresh_tx, refresh_rx) = mpsc::channel(1);
+        let orch = MonitoringOrchestrator::new(3600, Some(path), "pro".to_string())
+            .with_refresh_requests(refresh_rx);
+        let (mut rx, handle) = orch.start();
+
+        let wait = Duration::from_secs(5);
+        tokio::time::timeout(wait, rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Without a request nothing arrives before the hour-long interval.
+        refresh_tx.send(()).await.unwrap();
+        tokio::time::timeout(wait, rx.recv())
+            .await
+            .expect("refresh request did not trigger a snapshot")
+            .expect("channel closed before receiving snapshot");
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_analyze_interval_runs_on_its_own_timer() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+
+        // The snapshot interval alone would wait an hour.
+        let orch = MonitoringOrchestrator::new(3600, Some(path), "pro".to_string())
+            .with_analyze_interval(Duration::from_millis(200));
+        let (mut rx, handle) = orch.start();
+
+        for _ in 0..2 {
+            tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("timed out waiting for snapshot")
+                .expect("channel closed before receiving snapshot");
+        }
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_keeps_running_without_refresh_sender() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+
+        let (refresh_tx, refresh_rx) = mpsc::channel(1);
+        drop(refresh_tx);
+        let orch = MonitoringOrchestrator::new(1, Some(path), "pro".to_string())
+            .with_refresh_requests(refresh_rx);
+        let (mut rx, handle) = orch.start();
+
+        // Initial snapshot, then the timer still drives refreshes.
+        for _ in 0..2 {
+            tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("timed out waiting for snapshot")
+                .expect("channel closed before receiving snapshot");
+        }
+
+        handle.abort();
+    }
 }
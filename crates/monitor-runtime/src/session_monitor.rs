@@ -2,14 +2,28 @@
 //!
 //! [`SessionMonitor`] ingests the raw `serde_json::Value` produced by the
 //! analysis pipeline, validates its structure, detects session boundaries, and
-//! maintains a history of observed sessions.
+//! maintains a history of observed sessions. With a state file (see
+//! [`SessionMonitor::new_with_state`]) the current session and the history
+//! survive restarts.
 
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 // ── Public types ──────────────────────────────────────────────────────────────
 
+/// Name of the session state file in the
+/// [state directory](monitor_core::paths::state_dir).
+pub const SESSION_STATE_FILE_NAME: &str = "session_state.json";
+
+/// Most sessions kept in the history; older ones are dropped first, so the
+/// state file rewritten on each change stays small.
+pub const MAX_SESSION_HISTORY: usize = 500;
+
 /// Summary information about a single Claude session.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionInfo {
     /// Unique session / block identifier.
     pub id: String,
@@ -21,6 +35,27 @@ pub struct SessionInfo {
     pub cost: f64,
 }
 
+/// Which blocks [`SessionMonitor`] records in its session history.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrackMode {
+    /// Record a session only when a block becomes the active one.
+    #[default]
+    ActiveOnly,
+    /// Also record every completed (non-gap) block as it appears, once per
+    /// block id, so the history covers the whole timeline.
+    AllBlocks,
+}
+
+/// The part of a [`SessionMonitor`] kept in its state file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SessionState {
+    /// ID of the active session when the state was saved.
+    current_session_id: Option<String>,
+    /// Sessions observed so far.
+    #[serde(default)]
+    session_history: Vec<SessionInfo>,
+}
+
 // ── SessionMonitor ────────────────────────────────────────────────────────────
 
 /// Tracks active sessions and maintains a history of completed sessions.
@@ -31,19 +66,61 @@ pub struct SessionInfo {
 pub struct SessionMonitor {
     /// ID of the currently active session block, if any.
     current_session_id: Option<String>,
-    /// Ordered log of all sessions that have been observed.
+    /// Ordered log of the last [`MAX_SESSION_HISTORY`] sessions observed.
     session_history: Vec<SessionInfo>,
+    /// Which blocks end up in `session_history`.
+    mode: TrackMode,
+    /// Block ids already recorded, used by [`TrackMode::AllBlocks`].
+    recorded_ids: HashSet<String>,
+    /// JSON file the session state is saved to, if any.
+    state_file: Option<PathBuf>,
+    /// The history changed since the state was last saved.
+    history_changed: bool,
 }
 
 impl SessionMonitor {
-    /// Create a new, empty monitor.
+    /// Create a new, empty monitor that tracks active blocks only.
     pub fn new() -> Self {
+        Self::with_mode(TrackMode::default())
+    }
+
+    /// Create a new, empty monitor using `mode`.
+    pub fn with_mode(mode: TrackMode) -> Self {
         Self {
             current_session_id: None,
             session_history: Vec::new(),
+            mode,
+            recorded_ids: HashSet::new(),
+            state_file: None,
+            history_changed: false,
         }
     }
 
+    /// Create a monitor that tracks active blocks only and resumes from the
+    /// state saved at `path`.
+    ///
+    /// The current session id and history are loaded from `path` when it
+    /// exists, so an already-known session does not start again after a
+    /// restart, and are saved back whenever [`update`](Self::update) changes
+    /// them. Load and save errors are logged; the monitor then starts empty
+    /// or keeps its state in memory only.
+    pub fn new_with_state(path: &Path) -> Self {
+        let state = Self::load_state(path);
+        Self {
+            recorded_ids: state.session_history.iter().map(|s| s.id.clone()).collect(),
+            current_session_id: state.current_session_id,
+            session_history: state.session_history,
+            state_file: Some(path.to_path_buf()),
+            ..Self::new()
+        }
+    }
+
+    /// Default state file: [`SESSION_STATE_FILE_NAME`] in the state
+    /// directory, or `None` when that directory cannot be determined.
+    pub fn default_state_path() -> Option<PathBuf> {
+        monitor_core::paths::state_dir().map(|dir| dir.join(SESSION_STATE_FILE_NAME))
+    }
+
     // ── Public API ────────────────────────────────────────────────────────
 
     /// Update session tracking with fresh monitoring data.
@@ -55,6 +132,8 @@ impl SessionMonitor {
     ///    session, trigger a session-change transition.
     /// 4. If no active block is found but a session is currently tracked,
     ///    trigger a session-end transition.
+    /// 5. In [`TrackMode::AllBlocks`], record every completed block not seen
+    ///    before.
     ///
     /// Returns `(is_valid, errors)` where `is_valid` is `true` when
     /// validation passed and `errors` lists any structural problems.
@@ -64,12 +143,13 @@ impl SessionMonitor {
             return (false, errors);
         }
 
+        let blocks = data["blocks"].as_array().map_or(&[][..], Vec::as_slice);
+        let before = self.current_session_id.clone();
+
         // Find the active block (if any).
-        let active_block = data["blocks"].as_array().and_then(|blocks| {
-            blocks
-                .iter()
-                .find(|b| b["isActive"].as_bool() == Some(true))
-        });
+        let active_block = blocks
+            .iter()
+            .find(|b| b["isActive"].as_bool() == Some(true));
 
         match active_block {
             Some(block) => {
@@ -90,6 +170,21 @@ impl SessionMonitor {
             }
         }
 
+        if self.mode == TrackMode::AllBlocks {
+            for block in blocks {
+                let completed = block["isActive"].as_bool() == Some(false)
+                    && block["isGap"].as_bool() != Some(true);
+                let id = block["id"].as_str().unwrap_or("");
+                if completed && !self.recorded_ids.contains(id) {
+                    self.record_session(id, block);
+                }
+            }
+        }
+
+        if std::mem::take(&mut self.history_changed) || self.current_session_id != before {
+            self.save_state();
+        }
+
         (true, errors)
     }
 
@@ -165,13 +260,13 @@ impl SessionMonitor {
         self.current_session_id.as_deref()
     }
 
-    /// Total number of session transitions recorded (including the current
-    /// session if active).
+    /// Number of sessions in the history (including the current session if
+    /// active), at most [`MAX_SESSION_HISTORY`].
     pub fn session_count(&self) -> usize {
         self.session_history.len()
     }
 
-    /// Ordered history of all observed sessions.
+    /// Ordered history of the last [`MAX_SESSION_HISTORY`] observed sessions.
     pub fn session_history(&self) -> &[SessionInfo] {
         &self.session_history
     }
@@ -182,6 +277,15 @@ impl SessionMonitor {
     fn on_session_change(&mut self, session_id: &str, block: &Value) {
         tracing::info!(session_id, "session started / changed");
 
+        // A block recorded while completed is not recorded again.
+        if self.mode == TrackMode::ActiveOnly || !self.recorded_ids.contains(session_id) {
+            self.record_session(session_id, block);
+        }
+    }
+
+    /// Append `block` to the session history, dropping the oldest entries
+    /// past [`MAX_SESSION_HISTORY`].
+    fn record_session(&mut self, session_id: &str, block: &Value) {
         let tokens = block["totalTokens"].as_u64().unwrap_or(0);
         let cost = block["costUSD"].as_f64().unwrap_or(0.0);
         let started_at = block
@@ -189,12 +293,61 @@ impl SessionMonitor {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        self.recorded_ids.insert(session_id.to_string());
         self.session_history.push(SessionInfo {
             id: session_id.to_string(),
             started_at,
             tokens,
             cost,
         });
+        let excess = self
+            .session_history
+            .len()
+            .saturating_sub(MAX_SESSION_HISTORY);
+        for dropped in self.session_history.drain(..excess) {
+            self.recorded_ids.remove(&dropped.id);
+        }
+        self.history_changed = true;
+    }
+
+    /// Load the state saved at `path`, starting empty on any error.
+    fn load_state(path: &Path) -> SessionState {
+        if !path.exists() {
+            return SessionState::default();
+        }
+        let parsed = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+        parsed.unwrap_or_else(|e| {
+            tracing::warn!(
+                error = %e,
+                path = %path.display(),
+                "failed to load session state; starting empty"
+            );
+            SessionState::default()
+        })
+    }
+
+    /// Write the current session and history to the state file, if any,
+    /// logging any error.
+    fn save_state(&self) {
+        let Some(path) = &self.state_file else {
+            return;
+        };
+        let state = SessionState {
+            current_session_id: self.current_session_id.clone(),
+            session_history: self.session_history.clone(),
+        };
+        let written = serde_json::to_string_pretty(&state)
+            .map_err(|e| e.to_string())
+            .and_then(|json| write_state_file(path, &json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            tracing::warn!(
+                error = %e,
+                path = %path.display(),
+                "failed to save session state"
+            );
+        }
     }
 
     /// Called when the active session ends (no active block found).
@@ -211,6 +364,17 @@ impl Default for SessionMonitor {
     }
 }
 
+/// Write `json` to `path` through a temporary file, creating the parent
+/// directory first, so a crash never leaves a half-written state file.
+fn write_state_file(path: &Path, json: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -411,6 +575,59 @@ mod tests {
         assert_eq!(monitor.session_count(), 1);
     }
 
+    // ── TrackMode::AllBlocks ──────────────────────────────────────────────
+
+    #[test]
+    fn test_all_blocks_records_completed_blocks_once() {
+        let mut monitor = SessionMonitor::with_mode(TrackMode::AllBlocks);
+        let mut gap = valid_block("gap-1", false, 0, 0.0);
+        gap["isGap"] = json!(true);
+
+        let data = valid_data(vec![
+            valid_block("a", false, 100, 0.1),
+            gap,
+            valid_block("b", false, 200, 0.2),
+            valid_block("c", true, 300, 0.3),
+        ]);
+        monitor.update(&data);
+        monitor.update(&data);
+
+        let ids: Vec<&str> = monitor
+            .session_history()
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(ids, ["c", "a", "b"]);
+        assert_eq!(monitor.current_session_id(), Some("c"));
+    }
+
+    #[test]
+    fn test_all_blocks_active_block_completing_is_not_duplicated() {
+        let mut monitor = SessionMonitor::with_mode(TrackMode::AllBlocks);
+        monitor.update(&valid_data(vec![valid_block("a", true, 100, 0.1)]));
+        monitor.update(&valid_data(vec![
+            valid_block("a", false, 150, 0.15),
+            valid_block("b", true, 10, 0.01),
+        ]));
+
+        let ids: Vec<&str> = monitor
+            .session_history()
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_active_only_ignores_completed_blocks() {
+        let mut monitor = SessionMonitor::new();
+        monitor.update(&valid_data(vec![
+            valid_block("a", false, 100, 0.1),
+            valid_block("b", false, 200, 0.2),
+        ]));
+        assert_eq!(monitor.session_count(), 0);
+    }
+
     // ── invalid data returns validation errors ────────────────────────────
 
     #[test]
@@ -423,4 +640,80 @@ mod tests {
         // Session state unchanged.
         assert!(monitor.current_session_id().is_none());
     }
+
+    // ── new_with_state ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_state_survives_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session_state.json");
+        let data = valid_data(vec![
+            valid_block("a", false, 100, 0.1),
+            valid_block("b", true, 200, 0.2),
+        ]);
+
+        let mut monitor = SessionMonitor::new_with_state(&path);
+        monitor.update(&data);
+        assert_eq!(monitor.session_count(), 1);
+        drop(monitor);
+
+        let mut restarted = SessionMonitor::new_with_state(&path);
+        assert_eq!(restarted.current_session_id(), Some("b"));
+        assert_eq!(restarted.session_history()[0].id, "b");
+        assert_eq!(restarted.session_history()[0].tokens, 200);
+
+        // The known session does not start again.
+        restarted.update(&data);
+        assert_eq!(restarted.session_count(), 1);
+    }
+
+    #[test]
+    fn test_history_is_capped_and_saved_at_the_cap() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session_state.json");
+        let mut monitor = SessionMonitor::new_with_state(&path);
+        for i in 0..=MAX_SESSION_HISTORY {
+            monitor.update(&valid_data(vec![valid_block(
+                &format!("s{i}"),
+                true,
+                1,
+                0.0,
+            )]));
+        }
+        assert_eq!(monitor.session_count(), MAX_SESSION_HISTORY);
+        assert_eq!(monitor.session_history()[0].id, "s1");
+
+        // A new session at the cap still reaches the state file.
+        monitor.update(&valid_data(vec![valid_block("last", true, 1, 0.0)]));
+        drop(monitor);
+        let restarted = SessionMonitor::new_with_state(&path);
+        assert_eq!(restarted.session_count(), MAX_SESSION_HISTORY);
+        assert_eq!(restarted.session_history().last().unwrap().id, "last");
+        assert_eq!(restarted.session_history()[0].id, "s2");
+    }
+
+    #[test]
+    fn test_corrupt_state_starts_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("session_state.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let monitor = SessionMonitor::new_with_state(&path);
+        assert!(monitor.current_session_id().is_none());
+        assert_eq!(monitor.session_count(), 0);
+    }
+
+    #[test]
+    fn test_state_saved_into_missing_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("state").join(SESSION_STATE_FILE_NAME);
+
+        let mut monitor = SessionMonitor::new_with_state(&path);
+        monitor.update(&valid_data(vec![valid_block("a", true, 100, 0.1)]));
+
+        assert!(path.exists());
+        assert!(!path.with_extension("json.tmp").exists());
+        let restarted = SessionMonitor::new_with_state(&path);
+        assert_eq!(restarted.current_session_id(), Some("a"));
+    }
 }
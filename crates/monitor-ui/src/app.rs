@@ -3,22 +3,41 @@
 //! [`App`] owns the theme, view mode, and the last received monitoring
 //! snapshot.  It drives both the real-time and table view event loops.
 
-use std::io;
+use std::collections::HashMap;
+use std::io::{self, Write};
 use std::time::Duration;
 
 use crossterm::{
+    cursor,
     event::{self, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Frame, Terminal};
-use tokio::sync::mpsc;
-
-use monitor_core::models::BurnRate;
-use monitor_core::plans::Plans;
-
-use crate::session_view::{self, SessionViewData};
-use crate::table_view::{self, TableRowData, TableTotals};
+use ratatui::{backend::CrosstermBackend, Frame, Terminal, TerminalOptions, Viewport};
+use tokio::sync::{mpsc, watch};
+
+use monitor_core::clock::{SharedClock, SystemClock};
+use monitor_core::formatting::NumberFormatter;
+use monitor_core::lifetime::LifetimeTracker;
+use monitor_core::models::{BurnRate, SessionBlock, TokensBasis};
+use monitor_core::notifications::{
+    NotificationManager, KEY_DAILY_BUDGET_REACHED, KEY_EXCEED_MAX_LIMIT, KEY_TOKENS_WILL_RUN_OUT,
+};
+use monitor_core::plans::{CustomPlanLimits, ModelTokenLimits, PlanType, Plans};
+use monitor_core::pricing::{ModelPricing, PricingCalculator};
+use monitor_core::time_utils::{format_display_time, last_reset_boundary};
+use monitor_runtime::data::reader::LoadProgress;
+
+use crate::glyphs::Glyphs;
+use crate::model_labels::ModelLabels;
+use crate::session_view::{
+    self, DataSourceInfo, HeaderBranding, LimitKind, LimitPrediction, ModelCacheUsage,
+    ModelLimitUsage, NoSessionState, SessionLayout, SessionViewData, ViewNotification,
+};
+use crate::table_view::{
+    self, TableColumns, TableData, TableHeading, TableHeatmap, TableRowData, TableScroll,
+    TableTotals, TableViewParams,
+};
 use crate::themes::Theme;
 
 // ── ViewMode ──────────────────────────────────────────────────────────────────
@@ -32,8 +51,69 @@ pub enum ViewMode {
     Daily,
     /// Monthly aggregate usage table.
     Monthly,
+    /// Per-project aggregate usage table.
+    Projects,
+}
+
+// ── ActiveBlockPolicy ─────────────────────────────────────────────────────────
+
+/// Which block drives the realtime display when several are active at once,
+/// e.g. overlapping fixed-schedule windows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ActiveBlockPolicy {
+    /// The active block listed last (the newest).
+    #[default]
+    MostRecent,
+    /// The active block with the most tokens.
+    HighestTokens,
+    /// The active block listed first (the oldest).
+    Earliest,
+}
+
+impl ActiveBlockPolicy {
+    /// Parse an `--active-block` value; unknown names fall back to
+    /// [`MostRecent`](Self::MostRecent).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "highest-tokens" => Self::HighestTokens,
+            "earliest" => Self::Earliest,
+            _ => Self::MostRecent,
+        }
+    }
+
+    /// Pick the active, non-gap block from `blocks` (oldest first).
+    ///
+    /// Ties on [`HighestTokens`](Self::HighestTokens) go to the newest block.
+    pub fn select(self, blocks: &[SessionBlock]) -> Option<&SessionBlock> {
+        let mut active = blocks.iter().filter(|b| b.is_active && !b.is_gap);
+        match self {
+            Self::MostRecent => active.next_back(),
+            Self::HighestTokens => active.max_by_key(|b| b.total_tokens()),
+            Self::Earliest => active.next(),
+        }
+    }
 }
 
+/// How far back [`AppData::recent_costs`] reaches; a daily reset is never
+/// longer ago than this.
+const RECENT_COST_HOURS: i64 = 24;
+
+/// Default age, in minutes, of the newest entry before the realtime view
+/// warns that its data is stale.
+pub const DEFAULT_STALENESS_MINUTES: u32 = 60;
+
+/// Default minutes a session must run before burn rates and predictions are
+/// shown.
+pub const DEFAULT_PREDICTION_WARMUP_MINUTES: u32 = 5;
+
+/// Usage entries a session needs before burn rates and predictions are
+/// shown; one or two calls say nothing about the pace.
+const PREDICTION_WARMUP_ENTRIES: u64 = 3;
+
+/// Share of the token or cost limit, in percent, past which the realtime
+/// view warns; the same point where the progress bars turn red.
+const LIMIT_WARNING_PCT: f64 = 80.0;
+
 // ── AppData / ActiveBlockData ─────────────────────────────────────────────────
 
 /// Processed monitoring snapshot ready for the UI to consume.
@@ -47,12 +127,42 @@ pub struct AppData {
     pub token_limit: u64,
     /// Active block data, or `None` when there is no ongoing session.
     pub active_block: Option<ActiveBlockData>,
+    /// Number of JSONL files the analysis read.
+    pub files_loaded: usize,
+    /// Number of usage entries the analysis kept.
+    pub entries_loaded: usize,
+    /// Directory the files were read from, for the `i` info line.
+    pub data_path: Option<String>,
+    /// Number of (non-gap) sessions in the analysed window.
+    pub session_count: usize,
+    /// Time of the most recent recorded activity, if any.
+    pub last_activity: Option<chrono::DateTime<chrono::Utc>>,
+    /// Most messages sent in any completed session, rounded up to a
+    /// multiple of [`MESSAGE_LIMIT_STEP`](monitor_core::plans::MESSAGE_LIMIT_STEP).
+    pub observed_message_limit: Option<u32>,
+    /// Entries from the last day, for the cost since the last
+    /// `--reset-hour` boundary.
+    pub recent_costs: Vec<RecentCost>,
+    /// Cost (USD) of the entries dated today in the display timezone, for
+    /// `--daily-budget`; a session spanning midnight counts only its part
+    /// after it.
+    pub today_cost: f64,
+}
+
+/// Cost of one recent usage entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentCost {
+    /// Time of the entry.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Cost of the entry in USD.
+    pub cost_usd: f64,
 }
 
 /// Extracted display values for the currently active session block.
 #[derive(Debug, Clone)]
 pub struct ActiveBlockData {
-    /// Tokens consumed in this session block.
+    /// Tokens consumed in this session block, counted on the app's
+    /// [`TokensBasis`].
     pub tokens_used: u64,
     /// Cost in USD accrued in this block.
     pub cost_usd: f64,
@@ -60,7 +170,7 @@ pub struct ActiveBlockData {
     pub elapsed_minutes: f64,
     /// Total duration of the session window in minutes (5 hours = 300).
     pub total_minutes: f64,
-    /// Tokens-per-minute burn rate, if calculable.
+    /// Tokens-per-minute burn rate, if calculable and past the warm-up.
     pub burn_rate_tokens_per_min: Option<f64>,
     /// Cost-per-hour burn rate, if calculable.
     pub burn_rate_cost_per_hour: Option<f64>,
@@ -68,16 +178,113 @@ pub struct ActiveBlockData {
     pub model_percentages: Vec<(String, f64)>,
     /// Number of user-sent messages in this block.
     pub sent_messages: u32,
+    /// Messages sent per minute over the elapsed time, if any were sent.
+    pub message_rate_per_min: Option<f64>,
+    /// The block is too young or has too few entries for its rates to mean
+    /// anything, so rates and predictions are withheld.
+    pub warming_up: bool,
+    /// Cost (USD) the block is projected to reach by its end, from the
+    /// analysis' stored projection; `None` while warming up.
+    pub projected_cost: Option<f64>,
+    /// Tokens each family with a `--opus-limit` style limit used.
+    pub model_limits: Vec<ModelLimitUsage>,
     /// Formatted start time string.
     pub start_time: String,
     /// Formatted end (reset) time string (for display fallback).
     pub end_time: String,
     /// Raw UTC end time for timezone conversion.
     pub end_time_utc: chrono::DateTime<chrono::Utc>,
+    /// Reset time announced by the most recent limit notification in the
+    /// block, if any.
+    pub detected_reset_utc: Option<chrono::DateTime<chrono::Utc>>,
     /// Cache creation tokens for the block.
     pub cache_creation_tokens: u64,
     /// Cache read tokens for the block.
     pub cache_read_tokens: u64,
+    /// Per-model cache tokens and their cost, most expensive first.
+    pub model_cache: Vec<ModelCacheUsage>,
+    /// Limits hit or nearly hit in the block, most urgent first.
+    pub notifications: Vec<ViewNotification>,
+}
+
+impl ActiveBlockData {
+    /// When the limit resets: the reset announced by a limit notification
+    /// when the data has one, otherwise the block's nominal end.
+    pub fn reset_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.detected_reset_utc.unwrap_or(self.end_time_utc)
+    }
+}
+
+// ── Terminal guard ────────────────────────────────────────────────────────────
+
+/// Leave the alternate screen, disable raw mode, and show the cursor.
+///
+/// Errors are ignored: this runs from drop and panic paths where there is
+/// nothing better to do than try each step.
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+}
+
+/// Write the BEL character so the terminal beeps or flags the window.
+fn ring_bell() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
+
+/// Install a panic hook that restores the terminal before the default hook
+/// prints the panic message, so the message lands on the normal screen.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        previous(info);
+    }));
+}
+
+/// Drop guard that puts the terminal back into cooked mode when it goes out
+/// of scope, including while unwinding from a panic in the render loop.
+pub struct TerminalGuard {
+    restore: Option<Box<dyn FnOnce()>>,
+}
+
+impl TerminalGuard {
+    /// Enable raw mode and enter the alternate screen.
+    pub fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        if let Err(e) = execute!(io::stdout(), EnterAlternateScreen) {
+            let _ = disable_raw_mode();
+            return Err(e);
+        }
+        Ok(Self::with_restore(restore_terminal))
+    }
+
+    /// Enable raw mode only, for an inline viewport drawn on the normal
+    /// screen; dropping it disables raw mode and shows the cursor.
+    pub fn enter_inline() -> io::Result<Self> {
+        enable_raw_mode()?;
+        Ok(Self::with_restore(|| {
+            let _ = disable_raw_mode();
+            let _ = execute!(io::stdout(), cursor::Show);
+        }))
+    }
+
+    /// Create a guard that runs `restore` on drop instead of touching the
+    /// real terminal.
+    fn with_restore(restore: impl FnOnce() + 'static) -> Self {
+        Self {
+            restore: Some(Box::new(restore)),
+        }
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if let Some(restore) = self.restore.take() {
+            restore();
+        }
+    }
 }
 
 // ── App ───────────────────────────────────────────────────────────────────────
@@ -92,10 +299,90 @@ pub struct App {
     pub plan: String,
     /// Human-readable timezone string.
     pub timezone: String,
+    /// Show times in UTC whatever `timezone` says.
+    pub utc: bool,
+    /// User overrides applied when `plan` is `"custom"`.
+    pub custom_limits: CustomPlanLimits,
+    /// Separators used when displaying counts and costs.
+    pub number_format: NumberFormatter,
+    /// Clock style for displayed times: `"12h"` or `"24h"`.
+    pub time_format: String,
+    /// Display labels for the model distribution row.
+    pub model_labels: ModelLabels,
+    /// Models named in the distribution row before the rest collapse into
+    /// "Other".
+    pub max_models: usize,
+    /// Full or compact realtime screen.
+    pub layout: SessionLayout,
+    /// Title text and decoration of the realtime header.
+    pub branding: HeaderBranding,
+    /// `--message-limit` override for the messages bar.
+    pub message_limit: Option<u32>,
+    /// Hour of the daily limit reset (`--reset-hour`); `None` hides the
+    /// since-reset cost.
+    pub reset_hour: Option<u8>,
+    /// Warn once the newest entry is this many minutes old; `0` never warns.
+    pub staleness_minutes: u32,
+    /// Minutes a session must run before burn rates and predictions show.
+    pub prediction_warmup_minutes: u32,
+    /// Minutes of recent entries the model distribution covers; `None`
+    /// covers the whole block.
+    pub model_window_minutes: Option<u32>,
+    /// Count synthetic and system entries in the recent model distribution,
+    /// as the per-model stats do under `--include-synthetic`.
+    pub include_synthetic: bool,
+    /// Token limits for single model families, each drawn as its own bar.
+    pub model_token_limits: ModelTokenLimits,
+    /// "Now" for elapsed times, burn rates, predictions and staleness.
+    clock: SharedClock,
+    /// Columns drawn in the aggregate tables.
+    pub table_columns: TableColumns,
+    /// Colour table totals and costs by their share of the heaviest row.
+    pub heatmap: bool,
+    /// Daily spend target in USD (`--daily-budget`); `None` hides the bar.
+    pub daily_budget: Option<f64>,
+    /// Token categories counted in the session token bar, the burn rate and
+    /// every limit check; input and output only by default, like the plan
+    /// limits.
+    pub tokens_basis: TokensBasis,
+    /// Per-model rates replacing the built-in ones when pricing cache usage
+    /// (`--pricing-file`).
+    pub pricing_overrides: Option<HashMap<String, ModelPricing>>,
+    /// Draw in an inline viewport instead of the alternate screen, leaving
+    /// the last frame in the scrollback.
+    pub inline: bool,
+    /// Emoji or ASCII symbols in the realtime view.
+    pub glyphs: Glyphs,
+    /// Show the progress-bar colour legend; toggled with `l`.
+    pub show_legend: bool,
+    /// Show the data path and file and entry counts; toggled with `i`.
+    pub show_info: bool,
+    /// Which block to show when several are active.
+    pub active_block_policy: ActiveBlockPolicy,
+    /// Table rows left out by `--min-tokens` / `--min-cost`, noted in the
+    /// table title.
+    pub hidden_rows: usize,
+    /// Data directory being monitored, shown when no data is found
+    /// (`None` means the loader default).
+    pub data_path: Option<String>,
     /// Set to `true` to break out of the event loop on the next iteration.
     pub should_quit: bool,
     /// Most recent monitoring snapshot, `None` until the first data arrives.
     pub last_data: Option<AppData>,
+    /// Latest loader progress, shown until the first snapshot arrives.
+    pub load_progress: Option<LoadProgress>,
+    /// Optional channel carrying loader progress from the runtime.
+    progress_rx: Option<mpsc::Receiver<LoadProgress>>,
+    /// Cooldown state for `--bell-on-limit`; `None` keeps the bell off.
+    limit_bell: Option<NotificationManager>,
+    /// Persisted all-time totals; `None` hides the footer.
+    lifetime: Option<LifetimeTracker>,
+    /// Sends `r` / F5 refresh requests to the runtime.
+    refresh_tx: Option<mpsc::Sender<()>>,
+    /// Set to `true` when the process is asked to stop (e.g. SIGTERM).
+    shutdown_rx: Option<watch::Receiver<bool>>,
+    /// A refresh was requested and its snapshot has not arrived yet.
+    pub refreshing: bool,
 }
 
 impl App {
@@ -106,8 +393,275 @@ impl App {
             view_mode,
             plan,
             timezone,
+            utc: false,
+            custom_limits: CustomPlanLimits::default(),
+            number_format: NumberFormatter::default(),
+            time_format: "12h".to_string(),
+            model_labels: ModelLabels::default(),
+            max_models: session_view::DEFAULT_MAX_MODELS,
+            layout: SessionLayout::default(),
+            branding: HeaderBranding::default(),
+            message_limit: None,
+            reset_hour: None,
+            staleness_minutes: DEFAULT_STALENESS_MINUTES,
+            prediction_warmup_minutes: DEFAULT_PREDICTION_WARMUP_MINUTES,
+            model_window_minutes: None,
+            include_synthetic: false,
+            model_token_limits: ModelTokenLimits::default(),
+            clock: SystemClock::shared(),
+            table_columns: TableColumns::default(),
+            heatmap: false,
+            daily_budget: None,
+            tokens_basis: TokensBasis::Io,
+            pricing_overrides: None,
+            inline: false,
+            glyphs: Glyphs::default(),
+            show_legend: false,
+            show_info: false,
+            active_block_policy: ActiveBlockPolicy::default(),
+            hidden_rows: 0,
+            data_path: None,
             should_quit: false,
             last_data: None,
+            load_progress: None,
+            progress_rx: None,
+            limit_bell: None,
+            lifetime: None,
+            refresh_tx: None,
+            shutdown_rx: None,
+            refreshing: false,
+        }
+    }
+
+    /// Apply user-defined limits for the `"custom"` plan.
+    pub fn with_custom_limits(mut self, custom_limits: CustomPlanLimits) -> Self {
+        self.custom_limits = custom_limits;
+        self
+    }
+
+    /// Use `number_format` for all displayed counts.
+    pub fn with_number_format(mut self, number_format: NumberFormatter) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Use `model_labels` to name models in the distribution row.
+    pub fn with_model_labels(mut self, model_labels: ModelLabels) -> Self {
+        self.model_labels = model_labels;
+        self
+    }
+
+    /// Name at most `max_models` models in the distribution row.
+    pub fn with_max_models(mut self, max_models: usize) -> Self {
+        self.max_models = max_models;
+        self
+    }
+
+    /// Warn when the newest entry is at least `minutes` old (`0` disables
+    /// the warning).
+    pub fn with_staleness_minutes(mut self, minutes: u32) -> Self {
+        self.staleness_minutes = minutes;
+        self
+    }
+
+    /// Count synthetic and system entries in the recent model distribution
+    /// (`--include-synthetic`).
+    pub fn with_include_synthetic(mut self, include_synthetic: bool) -> Self {
+        self.include_synthetic = include_synthetic;
+        self
+    }
+
+    /// Take "now" from `clock` instead of the system clock, for the limit
+    /// bell's cooldowns too.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.limit_bell = self
+            .limit_bell
+            .take()
+            .map(|notifier| notifier.with_clock(clock.clone()));
+        self.clock = clock;
+        self
+    }
+
+    /// Draw a bar for each model family in `limits` against its own token
+    /// limit.
+    pub fn with_model_token_limits(mut self, limits: ModelTokenLimits) -> Self {
+        self.model_token_limits = limits;
+        self
+    }
+
+    /// Show "calculating…" instead of burn rates and predictions until a
+    /// session has run for `minutes` (`0` only waits for a few entries).
+    pub fn with_prediction_warmup_minutes(mut self, minutes: u32) -> Self {
+        self.prediction_warmup_minutes = minutes;
+        self
+    }
+
+    /// Compute the model distribution from the last `minutes` of entries
+    /// only, so it follows the current model mix; `None` covers the whole
+    /// block.
+    pub fn with_model_window_minutes(mut self, minutes: Option<u32>) -> Self {
+        self.model_window_minutes = minutes;
+        self
+    }
+
+    /// Show the cost accrued since `reset_hour` o'clock in the display
+    /// timezone.
+    pub fn with_reset_hour(mut self, reset_hour: Option<u8>) -> Self {
+        self.reset_hour = reset_hour;
+        self
+    }
+
+    /// Use `branding` for the realtime header's title line.
+    pub fn with_branding(mut self, branding: HeaderBranding) -> Self {
+        self.branding = branding;
+        self
+    }
+
+    /// Show all times in UTC, ignoring the configured timezone.
+    pub fn with_utc(mut self, utc: bool) -> Self {
+        self.utc = utc;
+        self
+    }
+
+    /// Show today's cost against a `budget` in USD (`--daily-budget`);
+    /// reaching it rings the limit bell.
+    pub fn with_daily_budget(mut self, budget: Option<f64>) -> Self {
+        self.daily_budget = budget;
+        self
+    }
+
+    /// Count the session token bar, burn rate, predictions and limit checks
+    /// on `basis` (`--tokens-basis`).
+    pub fn with_tokens_basis(mut self, basis: TokensBasis) -> Self {
+        self.tokens_basis = basis;
+        self
+    }
+
+    /// Price the per-model cache usage with `overrides` on top of the
+    /// built-in rates (`--pricing-file`), as the loader does.
+    pub fn with_pricing_overrides(
+        mut self,
+        overrides: Option<HashMap<String, ModelPricing>>,
+    ) -> Self {
+        self.pricing_overrides = overrides;
+        self
+    }
+
+    /// Render inline in the scrollback rather than on the alternate screen
+    /// (`--inline`).
+    pub fn with_inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
+    }
+
+    /// Draw the realtime view with `glyphs` (`--ascii`).
+    pub fn with_glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Colour each table row's total and cost by intensity (`--heatmap`).
+    pub fn with_heatmap(mut self, heatmap: bool) -> Self {
+        self.heatmap = heatmap;
+        self
+    }
+
+    /// Choose which block the realtime view shows when several are active.
+    pub fn with_active_block_policy(mut self, policy: ActiveBlockPolicy) -> Self {
+        self.active_block_policy = policy;
+        self
+    }
+
+    /// Draw only `table_columns` in the daily, monthly and projects tables.
+    pub fn with_table_columns(mut self, table_columns: TableColumns) -> Self {
+        self.table_columns = table_columns;
+        self
+    }
+
+    /// Note in the table title that `hidden_rows` rows were filtered out.
+    pub fn with_hidden_rows(mut self, hidden_rows: usize) -> Self {
+        self.hidden_rows = hidden_rows;
+        self
+    }
+
+    /// Draw the realtime screen with `layout`.
+    pub fn with_layout(mut self, layout: SessionLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Measure the messages bar against `message_limit` instead of the
+    /// plan's (or observed) limit. `None` keeps the default.
+    pub fn with_message_limit(mut self, message_limit: Option<u32>) -> Self {
+        self.message_limit = message_limit;
+        self
+    }
+
+    /// Display times with the resolved `--time-format` (`"12h"` or `"24h"`).
+    pub fn with_time_format(mut self, time_format: impl Into<String>) -> Self {
+        self.time_format = time_format.into();
+        self
+    }
+
+    /// Name `data_path` in the "no data found" message.
+    pub fn with_data_path(mut self, data_path: Option<String>) -> Self {
+        self.data_path = data_path;
+        self
+    }
+
+    /// Ring the terminal bell when a token or cost limit is reached, or the
+    /// tokens are predicted to run out before the reset. `notifier` keeps
+    /// the bell to once per limit and its cooldown (see
+    /// [`NotificationManager::with_cooldowns`]).
+    pub fn with_limit_bell(mut self, notifier: NotificationManager) -> Self {
+        self.limit_bell = Some(notifier.with_clock(self.clock.clone()));
+        self
+    }
+
+    /// Add every snapshot's new usage to `tracker` and show the all-time
+    /// totals below the session view.
+    pub fn with_lifetime(mut self, tracker: LifetimeTracker) -> Self {
+        self.lifetime = Some(tracker);
+        self
+    }
+
+    /// Receive loader progress on `rx` to drive the loading indicator.
+    pub fn with_progress(mut self, rx: mpsc::Receiver<LoadProgress>) -> Self {
+        self.progress_rx = Some(rx);
+        self
+    }
+
+    /// Send a request on `tx` whenever `r` or F5 is pressed in the realtime
+    /// view (see
+    /// [`MonitoringOrchestrator::with_refresh_requests`](monitor_runtime::orchestrator::MonitoringOrchestrator::with_refresh_requests)).
+    pub fn with_refresh(mut self, tx: mpsc::Sender<()>) -> Self {
+        self.refresh_tx = Some(tx);
+        self
+    }
+
+    /// Leave the event loops, restoring the terminal as on `q`, once `rx`
+    /// turns `true`. This lets a signal handler running on another task stop
+    /// the TUI cleanly.
+    pub fn with_shutdown(mut self, rx: watch::Receiver<bool>) -> Self {
+        self.shutdown_rx = Some(rx);
+        self
+    }
+
+    /// Whether the [shutdown channel](Self::with_shutdown) asked to stop.
+    pub fn shutdown_requested(&self) -> bool {
+        self.shutdown_rx.as_ref().is_some_and(|rx| *rx.borrow())
+    }
+
+    /// Ask the runtime for an immediate refresh and show "Refreshing…" until
+    /// the next snapshot arrives. Does nothing without a live channel; a
+    /// request already queued counts as in progress.
+    pub fn request_refresh(&mut self) {
+        let Some(tx) = self.refresh_tx.as_ref() else {
+            return;
+        };
+        match tx.try_send(()) {
+            Ok(()) | Err(mpsc::error::TrySendError::Full(())) => self.refreshing = true,
+            Err(mpsc::error::TrySendError::Closed(())) => self.refresh_tx = None,
         }
     }
 
@@ -119,16 +673,14 @@ impl App {
     /// that the terminal event loop stays on the current thread while data
     /// updates arrive on the async channel via `try_recv`.
     ///
-    /// The loop exits on `q`, `Q`, or `Ctrl+C`.
+    /// The loop exits on `q`, `Q`, or `Ctrl+C`; `r` / F5 request an
+    /// immediate refresh (see [`with_refresh`](Self::with_refresh)), `l`
+    /// toggles the progress-bar colour legend and `i` the data source line.
     pub async fn run_realtime(
         mut self,
         mut rx: mpsc::Receiver<monitor_runtime::orchestrator::MonitoringData>,
     ) -> io::Result<()> {
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+        let (guard, mut terminal) = self.open_terminal()?;
 
         let tick_rate = Duration::from_millis(250);
 
@@ -143,11 +695,27 @@ impl App {
                             break Ok(());
                         }
                         KeyCode::Char('q') | KeyCode::Char('Q') => break Ok(()),
+                        KeyCode::Char('r') | KeyCode::Char('R') | KeyCode::F(5) => {
+                            self.request_refresh();
+                        }
+                        KeyCode::Char('l') | KeyCode::Char('L') => {
+                            self.show_legend = !self.show_legend;
+                        }
+                        KeyCode::Char('i') | KeyCode::Char('I') => {
+                            self.show_info = !self.show_info;
+                        }
                         _ => {}
                     }
                 }
             }
 
+            // Keep only the latest loader progress report.
+            if let Some(progress_rx) = self.progress_rx.as_mut() {
+                while let Ok(progress) = progress_rx.try_recv() {
+                    self.load_progress = Some(progress);
+                }
+            }
+
             // Drain any pending data updates (non-blocking).
             loop {
                 match rx.try_recv() {
@@ -160,34 +728,161 @@ impl App {
                 }
             }
 
-            if self.should_quit {
+            if self.take_limit_bell() {
+                ring_bell()?;
+            }
+
+            if self.should_quit || self.shutdown_requested() {
                 break Ok(());
             }
         };
 
-        // Restore terminal state unconditionally.
-        disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-        terminal.show_cursor()?;
+        // Restore terminal state unconditionally (also happens on early
+        // return or panic when the guard is dropped).
+        let closed = self.close_terminal(&mut terminal);
+        drop(guard);
 
-        result
+        result.and(closed)
     }
 
-    /// Run a static table view (daily or monthly), then wait for `q` / `Ctrl+C`.
+    /// Run a static table view (daily, monthly or projects), then wait for
+    /// `q` / `Ctrl+C`.
+    ///
+    /// The table opens scrolled to the last row; arrow keys, PageUp/PageDown
+    /// and Home/End scroll it.
     pub async fn run_table(self, rows: Vec<TableRowData>, totals: TableTotals) -> io::Result<()> {
-        enable_raw_mode()?;
+        let (guard, mut terminal) = self.open_terminal()?;
+
+        let result = self.table_loop(&mut terminal, rows, totals);
+        let closed = self.close_terminal(&mut terminal);
+        drop(guard);
+        result.and(closed)
+    }
+
+    /// Like [`run_table`](Self::run_table), but computes the table with
+    /// `load` on a blocking task after entering the alternate screen,
+    /// showing an "Analyzing usage…" spinner (with any progress received via
+    /// [`with_progress`](Self::with_progress)) until it finishes.
+    ///
+    /// `q` / `Ctrl+C` or a shutdown request during the load restores the
+    /// terminal and returns `false` without waiting for it. The load keeps
+    /// running on its blocking thread, and the runtime waits for it when
+    /// shutting down, so callers should then exit the process. Returns
+    /// `true` once the table was shown and closed.
+    pub async fn run_table_loading<F>(mut self, load: F) -> io::Result<bool>
+    where
+        F: FnOnce() -> TableData + Send + 'static,
+    {
+        let (guard, mut terminal) = self.open_terminal()?;
+
+        let tick_rate = Duration::from_millis(100);
+        let mut task = tokio::task::spawn_blocking(load);
+        let mut tick = 0usize;
+
+        let data = loop {
+            if let Some(progress_rx) = self.progress_rx.as_mut() {
+                while let Ok(progress) = progress_rx.try_recv() {
+                    self.load_progress = Some(progress);
+                }
+            }
+            terminal.draw(|frame| {
+                let area = frame.area();
+                table_view::render_analyzing(
+                    frame,
+                    area,
+                    tick,
+                    self.load_progress.as_ref(),
+                    &self.theme,
+                );
+            })?;
+            tick = tick.wrapping_add(1);
+
+            if event::poll(tick_rate)? {
+                if let Event::Key(key) = event::read()? {
+                    let ctrl_c = key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL);
+                    if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Char('Q')) {
+                        let closed = self.close_terminal(&mut terminal);
+                        drop(guard);
+                        return closed.map(|()| false);
+                    }
+                }
+            }
+
+            if self.shutdown_requested() {
+                let closed = self.close_terminal(&mut terminal);
+                drop(guard);
+                return closed.map(|()| false);
+            }
+
+            if task.is_finished() {
+                break (&mut task).await.map_err(io::Error::other)?;
+            }
+        };
+
+        self.hidden_rows = data.hidden_rows;
+        let result = self.table_loop(&mut terminal, data.rows, data.totals);
+        let closed = self.close_terminal(&mut terminal);
+        drop(guard);
+        result.and(closed).map(|()| true)
+    }
+
+    // ── Private helpers ───────────────────────────────────────────────────────
+
+    /// Set up the terminal for an event loop: the alternate screen, or with
+    /// [`inline`](Self::inline) a viewport as tall as the terminal on the
+    /// normal screen.
+    fn open_terminal(&self) -> io::Result<(TerminalGuard, Terminal<CrosstermBackend<io::Stdout>>)> {
+        let backend = CrosstermBackend::new(io::stdout());
+        if !self.inline {
+            let guard = TerminalGuard::enter()?;
+            return Ok((guard, Terminal::new(backend)?));
+        }
+        let guard = TerminalGuard::enter_inline()?;
+        let (_, height) = crossterm::terminal::size()?;
+        let options = TerminalOptions {
+            viewport: Viewport::Inline(height),
+        };
+        Ok((guard, Terminal::with_options(backend, options)?))
+    }
+
+    /// Move the cursor below an inline viewport so the last frame stays in
+    /// the scrollback; nothing to do on the alternate screen.
+    fn close_terminal(
+        &self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    ) -> io::Result<()> {
+        if !self.inline {
+            return Ok(());
+        }
+        let bottom = terminal.get_frame().area().bottom();
+        terminal.set_cursor_position((0, bottom.saturating_sub(1)))?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
-
-        let title = match self.view_mode {
-            ViewMode::Daily => "Daily Usage",
-            ViewMode::Monthly => "Monthly Usage",
-            ViewMode::Realtime => "Usage",
+        stdout.write_all(b"\r\n")?;
+        stdout.flush()
+    }
+
+    /// Draw the table and handle scrolling until `q` / `Ctrl+C`.
+    fn table_loop(
+        &self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        rows: Vec<TableRowData>,
+        totals: TableTotals,
+    ) -> io::Result<()> {
+        let mut heading = match self.view_mode {
+            ViewMode::Daily => TableHeading::from("Daily Usage"),
+            ViewMode::Monthly => TableHeading::from("Monthly Usage"),
+            ViewMode::Projects => TableHeading::new("Projects Usage", "Project", "projects"),
+            ViewMode::Realtime => TableHeading::from("Usage"),
         };
+        let title = table_title(heading.title, self.hidden_rows);
+        heading.title = &title;
+
+        let heatmap = self.heatmap.then(|| TableHeatmap::from_rows(&rows));
 
         let tick_rate = Duration::from_millis(250);
+        // Open on the most recent period; Home jumps back to the oldest.
+        let mut scroll = TableScroll::default();
 
         loop {
             terminal.draw(|frame| {
@@ -195,7 +890,21 @@ impl App {
                 if rows.is_empty() {
                     table_view::render_no_data(frame, area, &self.theme);
                 } else {
-                    table_view::render_table_view(frame, area, title, &rows, &totals, &self.theme);
+                    let visible = table_view::visible_row_count(area, &totals);
+                    let first = scroll.fit(rows.len(), visible);
+                    table_view::render_table_view(
+                        frame,
+                        area,
+                        &TableViewParams {
+                            heading,
+                            rows: &rows[first..],
+                            totals: &totals,
+                            columns: &self.table_columns,
+                            heatmap: heatmap.as_ref(),
+                            numbers: &self.number_format,
+                            theme: &self.theme,
+                        },
+                    );
                 }
             })?;
 
@@ -206,19 +915,172 @@ impl App {
                             break;
                         }
                         KeyCode::Char('q') | KeyCode::Char('Q') => break,
+                        KeyCode::Up | KeyCode::Char('k') => scroll.up(1),
+                        KeyCode::Down | KeyCode::Char('j') => scroll.down(1),
+                        KeyCode::PageUp => scroll.up(scroll.page()),
+                        KeyCode::PageDown => scroll.down(scroll.page()),
+                        KeyCode::Home | KeyCode::Char('g') => scroll.home(),
+                        KeyCode::End | KeyCode::Char('G') => scroll.end(),
                         _ => {}
                     }
                 }
             }
+
+            if self.shutdown_requested() {
+                break;
+            }
         }
 
-        disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-        terminal.show_cursor()?;
         Ok(())
     }
 
-    // ── Private helpers ───────────────────────────────────────────────────────
+    /// Timezone times are displayed in: UTC with `--utc`, otherwise the
+    /// configured timezone (UTC when it does not parse).
+    fn display_timezone(&self) -> chrono_tz::Tz {
+        if self.utc {
+            return chrono_tz::Tz::UTC;
+        }
+        self.timezone.parse().unwrap_or(chrono_tz::Tz::UTC)
+    }
+
+    /// Format `dt` as a time of day using the configured clock style.
+    fn format_clock(&self, dt: &chrono::DateTime<chrono_tz::Tz>, include_seconds: bool) -> String {
+        format_display_time(dt, Some(self.time_format != "24h"), include_seconds)
+    }
+
+    /// Format a reset time, adding the day when it is not on `now`'s date:
+    /// `"2:00 AM"`, `"tomorrow 2:00 AM"` or `"Jan 16 2:00 AM"`.
+    fn format_reset(
+        &self,
+        reset: &chrono::DateTime<chrono_tz::Tz>,
+        now: &chrono::DateTime<chrono_tz::Tz>,
+    ) -> String {
+        let clock = self.format_clock(reset, false);
+        let (day, today) = (reset.date_naive(), now.date_naive());
+        if day == today {
+            clock
+        } else if today.succ_opt() == Some(day) {
+            format!("tomorrow {clock}")
+        } else {
+            format!("{} {clock}", reset.format("%b %-d"))
+        }
+    }
+
+    /// [`KEY_DAILY_BUDGET_REACHED`] once today's cost has reached
+    /// `--daily-budget`.
+    fn budget_alert(&self) -> Option<&'static str> {
+        let app_data = self.last_data.as_ref()?;
+        self.daily_budget
+            .is_some_and(|budget| budget > 0.0 && app_data.today_cost >= budget)
+            .then_some(KEY_DAILY_BUDGET_REACHED)
+    }
+
+    /// Notification key for the limit the active session has hit (or is
+    /// predicted to hit before it resets), if any.
+    fn limit_alert(&self) -> Option<&'static str> {
+        let app_data = self.last_data.as_ref()?;
+        let active = app_data.active_block.as_ref()?;
+        let cost_limit = Plans::resolve_plan(&self.plan, &self.custom_limits)
+            .map(|p| p.cost_limit)
+            .unwrap_or(Plans::DEFAULT_COST_LIMIT);
+
+        let tokens_exceeded =
+            app_data.token_limit > 0 && active.tokens_used >= app_data.token_limit;
+        let cost_exceeded = cost_limit > 0.0 && active.cost_usd >= cost_limit;
+        if tokens_exceeded || cost_exceeded {
+            return Some(KEY_EXCEED_MAX_LIMIT);
+        }
+
+        let tpm = active.burn_rate_tokens_per_min.filter(|t| *t > 0.0)?;
+        let remaining_tokens = app_data.token_limit.saturating_sub(active.tokens_used);
+        let minutes_to_reset = (active.total_minutes - active.elapsed_minutes).max(0.0);
+        (remaining_tokens as f64 / tpm < minutes_to_reset).then_some(KEY_TOKENS_WILL_RUN_OUT)
+    }
+
+    /// `true` when a limit or budget alert is due and its bell is out of
+    /// cooldown. Marks each such alert as notified so it does not ring again
+    /// right away.
+    fn take_limit_bell(&mut self) -> bool {
+        let due = [self.limit_alert(), self.budget_alert()];
+        let Some(notifier) = self.limit_bell.as_mut() else {
+            return false;
+        };
+        let mut ring = false;
+        for key in due.into_iter().flatten() {
+            ring |= notifier.notify_if_due(key);
+        }
+        ring
+    }
+
+    /// Age in whole minutes of the newest entry, when it has reached
+    /// [`staleness_minutes`](Self::staleness_minutes).
+    fn stale_minutes(&self, app_data: &AppData, now: chrono::DateTime<chrono::Utc>) -> Option<u64> {
+        if self.staleness_minutes == 0 {
+            return None;
+        }
+        let age = (now - app_data.last_activity?).num_minutes();
+        u64::try_from(age)
+            .ok()
+            .filter(|age| *age >= u64::from(self.staleness_minutes))
+    }
+
+    /// Cost of the sessions active since the last reset boundary, with the
+    /// boundary; `None` without a `--reset-hour`.
+    ///
+    /// Only entries at or after the boundary count, so a session spanning it
+    /// contributes just its part after the reset.
+    fn cost_since_reset(
+        &self,
+        app_data: &AppData,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<(f64, chrono::DateTime<chrono::Utc>)> {
+        let boundary = last_reset_boundary(now, self.reset_hour?, self.display_timezone())?;
+        let cost = app_data
+            .recent_costs
+            .iter()
+            .filter(|e| e.timestamp >= boundary)
+            .map(|e| e.cost_usd)
+            .sum();
+        Some((cost, boundary))
+    }
+
+    /// Limit for the messages bar: `--message-limit`, else the observed
+    /// maximum for a custom plan without `--custom-message-limit`, else the
+    /// plan's limit.
+    fn message_limit_for(&self, app_data: &AppData) -> u32 {
+        if let Some(limit) = self.message_limit {
+            return limit;
+        }
+        let is_custom = self.plan.parse::<PlanType>().ok() == Some(PlanType::Custom);
+        if is_custom && self.custom_limits.message_limit.is_none() {
+            if let Some(observed) = app_data.observed_message_limit {
+                return observed;
+            }
+        }
+        Plans::resolve_plan(&self.plan, &self.custom_limits)
+            .map(|p| p.message_limit)
+            .unwrap_or(Plans::DEFAULT_MESSAGE_LIMIT)
+    }
+
+    /// Explain why `app_data` has no active session to show.
+    fn no_session_state(&self, app_data: &AppData) -> NoSessionState {
+        if app_data.files_loaded == 0 {
+            return NoSessionState::NoData {
+                path: self
+                    .data_path
+                    .clone()
+                    .unwrap_or_else(|| "~/.claude/projects".to_string()),
+            };
+        }
+        NoSessionState::Idle {
+            files: app_data.files_loaded,
+            sessions: app_data.session_count,
+            minutes_since_activity: app_data
+                .last_activity
+                .map(|t| (self.clock.now() - t).num_seconds() as f64 / 60.0),
+            stale_minutes: self.stale_minutes(app_data, self.clock.now()),
+        }
+    }
 
     /// Render the current application state into `frame`.
     fn render(&self, frame: &mut Frame) {
@@ -228,57 +1090,61 @@ impl App {
             ViewMode::Realtime => {
                 if let Some(ref app_data) = self.last_data {
                     if let Some(ref active) = app_data.active_block {
-                        let plan_config = Plans::get_plan_by_name(&self.plan);
+                        let plan_config = Plans::resolve_plan(&self.plan, &self.custom_limits);
                         let cost_limit = plan_config
                             .as_ref()
                             .map(|p| p.cost_limit)
                             .unwrap_or(Plans::DEFAULT_COST_LIMIT);
-                        let message_limit = plan_config
-                            .as_ref()
-                            .map(|p| p.message_limit)
-                            .unwrap_or(Plans::DEFAULT_MESSAGE_LIMIT);
+                        let message_limit = self.message_limit_for(app_data);
 
                         let burn_rate = active.burn_rate_tokens_per_min.map(|tpm| BurnRate {
                             tokens_per_minute: tpm,
                             cost_per_hour: active.burn_rate_cost_per_hour.unwrap_or(0.0),
                         });
 
-                        // Resolve display timezone (fallback to UTC).
-                        let now_utc = chrono::Utc::now();
-                        let tz: chrono_tz::Tz = self.timezone.parse().unwrap_or(chrono_tz::Tz::UTC);
+                        let now_utc = self.clock.now();
+                        let tz = self.display_timezone();
                         let now_local = now_utc.with_timezone(&tz);
 
                         // Format current time in user's timezone.
-                        let current_time = now_local.format("%I:%M:%S %p").to_string();
+                        let current_time = self.format_clock(&now_local, true);
 
                         // Format reset time in user's timezone.
-                        let reset_dt = active.end_time_utc;
+                        let reset_dt = active.reset_at();
                         let reset_local = reset_dt.with_timezone(&tz);
-                        let reset_time = reset_local.format("%I:%M %p").to_string();
-
-                        // Compute predicted token exhaustion time.
-                        let predicted_end = if let Some(ref br) = burn_rate {
-                            if br.tokens_per_minute > 0.0
-                                && app_data.token_limit > active.tokens_used
-                            {
-                                let remaining = app_data.token_limit - active.tokens_used;
-                                let mins_left = remaining as f64 / br.tokens_per_minute;
-                                let pred_utc =
-                                    now_utc + chrono::Duration::seconds((mins_left * 60.0) as i64);
-                                let pred_local = pred_utc.with_timezone(&tz);
-                                Some(pred_local.format("%I:%M %p").to_string())
-                            } else if active.tokens_used >= app_data.token_limit {
-                                Some("Exceeded".to_string())
-                            } else {
-                                None
+                        let reset_time = self.format_reset(&reset_local, &now_local);
+
+                        let cost_since_reset =
+                            self.cost_since_reset(app_data, now_utc)
+                                .map(|(cost, boundary)| {
+                                    let boundary = boundary.with_timezone(&tz);
+                                    (cost, self.format_clock(&boundary, false))
+                                });
+
+                        // Whichever of tokens, cost and messages runs out first.
+                        let predicted_end = earliest_exhaustion(
+                            active,
+                            app_data.token_limit,
+                            cost_limit,
+                            message_limit,
+                        )
+                        .map(|(kind, minutes_left)| {
+                            let pred_utc =
+                                now_utc + chrono::Duration::seconds((minutes_left * 60.0) as i64);
+                            LimitPrediction {
+                                kind,
+                                minutes_left,
+                                at: self.format_clock(&pred_utc.with_timezone(&tz), false),
                             }
-                        } else {
-                            None
-                        };
+                        });
 
                         let view_data = SessionViewData {
                             plan: self.plan.clone(),
-                            timezone: self.timezone.clone(),
+                            timezone: if self.utc {
+                                "UTC".to_string()
+                            } else {
+                                self.timezone.clone()
+                            },
                             tokens_used: active.tokens_used,
                             token_limit: app_data.token_limit,
                             cost_usd: active.cost_usd,
@@ -293,22 +1159,80 @@ impl App {
                             reset_time,
                             predicted_end,
                             is_active: true,
-                            notifications: Vec::new(),
+                            notifications: active.notifications.clone(),
                             cache_creation_tokens: active.cache_creation_tokens,
                             cache_read_tokens: active.cache_read_tokens,
+                            model_cache: active.model_cache.clone(),
+                            number_format: self.number_format,
+                            model_labels: self.model_labels.clone(),
+                            max_models: self.max_models,
+                            lifetime: self.lifetime.as_ref().map(|t| t.stats().clone()),
+                            branding: self.branding.clone(),
+                            cost_since_reset,
+                            refreshing: self.refreshing,
+                            stale_minutes: self.stale_minutes(app_data, now_utc),
+                            glyphs: self.glyphs,
+                            daily_budget: self
+                                .daily_budget
+                                .map(|budget| (app_data.today_cost, budget)),
+                            show_legend: self.show_legend,
+                            warming_up: active.warming_up,
+                            projected_cost: active.projected_cost,
+                            model_limits: active.model_limits.clone(),
+                            data_source: self.show_info.then(|| DataSourceInfo {
+                                path: app_data
+                                    .data_path
+                                    .clone()
+                                    .unwrap_or_else(|| self.data_path.clone().unwrap_or_default()),
+                                files: app_data.files_loaded,
+                                entries: app_data.entries_loaded,
+                            }),
                         };
-                        session_view::render_session_view(frame, area, &view_data, &self.theme);
+                        match self.layout {
+                            SessionLayout::Full => session_view::render_session_view(
+                                frame,
+                                area,
+                                &view_data,
+                                &self.theme,
+                            ),
+                            SessionLayout::Compact => session_view::render_compact_view(
+                                frame,
+                                area,
+                                &view_data,
+                                &self.theme,
+                            ),
+                        }
                     } else {
-                        session_view::render_no_session(frame, area, &self.theme);
+                        session_view::render_no_session(
+                            frame,
+                            area,
+                            &self.no_session_state(app_data),
+                            &self.theme,
+                            self.glyphs,
+                        );
                     }
+                } else if let Some(ref progress) = self.load_progress {
+                    session_view::render_loading(frame, area, progress, &self.theme);
                 } else {
-                    session_view::render_no_session(frame, area, &self.theme);
+                    session_view::render_no_session(
+                        frame,
+                        area,
+                        &NoSessionState::Waiting,
+                        &self.theme,
+                        self.glyphs,
+                    );
                 }
             }
             // Table views are handled by `run_table`; render a blank frame
             // if this method is called unexpectedly in that mode.
-            ViewMode::Daily | ViewMode::Monthly => {
-                session_view::render_no_session(frame, area, &self.theme);
+            ViewMode::Daily | ViewMode::Monthly | ViewMode::Projects => {
+                session_view::render_no_session(
+                    frame,
+                    area,
+                    &NoSessionState::Waiting,
+                    &self.theme,
+                    self.glyphs,
+                );
             }
         }
     }
@@ -319,17 +1243,16 @@ impl App {
     /// percentages, elapsed time, and formats display strings.
     pub fn update_from_monitoring(&mut self, data: monitor_runtime::orchestrator::MonitoringData) {
         let analysis = &data.analysis;
+        self.refreshing = false;
+        let now = self.clock.now();
 
-        // Find the first active, non-gap block (most recent takes priority).
-        let active_block_opt = analysis
-            .blocks
-            .iter()
-            .rev()
-            .find(|b| b.is_active && !b.is_gap);
+        let active_block_opt = self.active_block_policy.select(&analysis.blocks);
+        let cost_limit = Plans::resolve_plan(&self.plan, &self.custom_limits)
+            .map(|p| p.cost_limit)
+            .unwrap_or(Plans::DEFAULT_COST_LIMIT);
 
         let active = active_block_opt.map(|block| {
             // Elapsed time: now - block.start_time, capped to window.
-            let now = chrono::Utc::now();
             let elapsed_secs = (now - block.start_time).num_seconds().max(0) as f64;
             let elapsed_minutes = elapsed_secs / 60.0;
 
@@ -337,48 +1260,96 @@ impl App {
             let window_secs = (block.end_time - block.start_time).num_seconds() as f64;
             let total_minutes = (window_secs / 60.0).max(1.0);
 
-            // For the token progress bar, only count input + output tokens.
-            // Cache tokens are displayed separately in the "Cache Tokens" row.
-            let display_tokens = block.token_counts.input_tokens + block.token_counts.output_tokens;
-
-            // Compute burn rate from the active session's actual tokens and
-            // elapsed time (require at least 30s to avoid division spikes).
-            let burn_rate_tokens_per_min = if elapsed_minutes > 0.5 {
-                Some(display_tokens as f64 / elapsed_minutes)
-            } else {
-                None
-            };
-            let burn_rate_cost_per_hour = if elapsed_minutes > 0.5 {
-                Some((block.cost_usd / elapsed_minutes) * 60.0)
-            } else {
-                None
-            };
+            // The token progress bar counts tokens on the configured basis,
+            // so it reconciles with the table totals and exports.
+            let display_tokens = block.token_counts.total_for(self.tokens_basis);
 
-            // Per-model percentages: compute relative to input+output tokens
-            // only (cache tokens are shown separately).
-            let io_total: u64 = block
+            // Rates over a few minutes or a couple of calls swing wildly, so
+            // they wait for the warm-up to pass.
+            let entries: u64 = block
                 .per_model_stats
                 .values()
-                .map(|s| s.input_tokens + s.output_tokens)
+                .map(|s| u64::from(s.entries_count))
                 .sum();
-            let model_percentages: Vec<(String, f64)> = if io_total > 0 {
-                block
-                    .per_model_stats
-                    .iter()
-                    .map(|(model, stats)| {
-                        let model_io = stats.input_tokens + stats.output_tokens;
-                        let pct = (model_io as f64 / io_total as f64) * 100.0;
-                        (model.clone(), pct)
-                    })
-                    .collect::<Vec<_>>()
-            } else {
-                Vec::new()
-            };
-
-            // Sort descending so the bar renders the largest segment first.
-            let mut model_percentages = model_percentages;
-            model_percentages
-                .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            let warming_up = elapsed_minutes < f64::from(self.prediction_warmup_minutes)
+                || entries < PREDICTION_WARMUP_ENTRIES;
+
+            // Burn rate over the elapsed time; a non-finite cost rate is
+            // treated as "no rate yet".
+            let burn_rate =
+                BurnRate::from_block(block, now, None, self.tokens_basis).filter(|_| !warming_up);
+            let burn_rate_tokens_per_min = burn_rate.as_ref().map(|br| br.tokens_per_minute);
+            let burn_rate_cost_per_hour = burn_rate.and_then(|br| finite(br.cost_per_hour));
+            let projected_cost = block
+                .projection()
+                .and_then(|p| finite(p.projected_total_cost))
+                .filter(|_| !warming_up);
+            let message_rate_per_min =
+                (!warming_up && elapsed_minutes > 0.0 && block.sent_messages_count > 0)
+                    .then(|| f64::from(block.sent_messages_count) / elapsed_minutes);
+
+            // Per-model percentages over the whole block, or over the
+            // recent window when one is set.
+            let recent_since = self
+                .model_window_minutes
+                .map(|minutes| now - chrono::Duration::minutes(i64::from(minutes)));
+            let model_percentages = model_percentages(block, recent_since, self.include_synthetic);
+
+            // Families with their own limit, counted like the token bar.
+            let model_limits = self
+                .model_token_limits
+                .configured()
+                .into_iter()
+                .map(|(family, limit)| ModelLimitUsage {
+                    family: family.to_string(),
+                    used: block
+                        .per_model_stats
+                        .iter()
+                        .filter(|(model, _)| ModelTokenLimits::in_family(model, family))
+                        .map(|(_, s)| {
+                            self.tokens_basis.total(
+                                s.input_tokens,
+                                s.output_tokens,
+                                s.cache_creation_tokens,
+                                s.cache_read_tokens,
+                            )
+                        })
+                        .sum(),
+                    limit,
+                })
+                .collect();
+
+            // Per-model cache usage priced at each model's cache rates.
+            let mut pricing = PricingCalculator::new(self.pricing_overrides.clone());
+            let mut model_cache: Vec<ModelCacheUsage> = block
+                .per_model_stats
+                .iter()
+                .filter(|(_, stats)| {
+                    stats
+                        .cache_creation_tokens
+                        .saturating_add(stats.cache_read_tokens)
+                        > 0
+                })
+                .map(|(model, stats)| {
+                    let (creation_cost, read_cost) = pricing.calculate_cache_costs(
+                        model,
+                        stats.cache_creation_tokens,
+                        stats.cache_read_tokens,
+                    );
+                    ModelCacheUsage {
+                        model: model.clone(),
+                        creation_tokens: stats.cache_creation_tokens,
+                        read_tokens: stats.cache_read_tokens,
+                        creation_cost,
+                        read_cost,
+                    }
+                })
+                .collect();
+            model_cache.sort_by(|a, b| {
+                (b.creation_cost + b.read_cost)
+                    .total_cmp(&(a.creation_cost + a.read_cost))
+                    .then_with(|| a.model.cmp(&b.model))
+            });
 
             ActiveBlockData {
                 tokens_used: display_tokens,
@@ -389,28 +1360,277 @@ impl App {
                 burn_rate_cost_per_hour,
                 model_percentages,
                 sent_messages: block.sent_messages_count,
+                message_rate_per_min,
+                warming_up,
+                projected_cost,
+                model_limits,
                 start_time: block.start_time.format("%H:%M:%S").to_string(),
                 end_time: block.end_time.format("%H:%M:%S").to_string(),
                 end_time_utc: block.end_time,
+                detected_reset_utc: detected_reset(block, now),
                 cache_creation_tokens: block.token_counts.cache_creation_tokens,
                 cache_read_tokens: block.token_counts.cache_read_tokens,
+                model_cache,
+                notifications: session_notifications(
+                    block,
+                    display_tokens,
+                    data.token_limit,
+                    cost_limit,
+                )
+                .into_iter()
+                .chain(data.poor_limit_fit.map(|detected| {
+                    let (position, reading) = if detected.is_tight() {
+                        ("below", "high")
+                    } else {
+                        ("well above", "low")
+                    };
+                    ViewNotification::warning(format!(
+                        "Detected limit {} is {position} the session peak {}; \
+                         set --custom-limit-tokens if the bar reads {reading}",
+                        self.number_format.format_tokens(detected.limit),
+                        self.number_format.format_tokens(detected.peak),
+                    ))
+                }))
+                .collect(),
             }
         });
 
+        let sessions = analysis.blocks.iter().filter(|b| !b.is_gap);
+        if let Some(tracker) = self.lifetime.as_mut() {
+            tracker.record(sessions.clone().flat_map(|b| b.entries.iter()));
+        }
+        let last_activity = sessions
+            .clone()
+            .map(|b| b.actual_end_time.unwrap_or(b.start_time))
+            .max();
+        let observed_message_limit = sessions
+            .clone()
+            .filter(|b| !b.is_active)
+            .map(|b| b.sent_messages_count)
+            .max()
+            .and_then(Plans::observed_message_limit);
+        let recent_since = now - chrono::Duration::hours(RECENT_COST_HOURS);
+        let recent_costs = sessions
+            .clone()
+            .flat_map(|b| b.entries.iter())
+            .filter(|e| e.timestamp >= recent_since)
+            .map(|e| RecentCost {
+                timestamp: e.timestamp,
+                cost_usd: e.cost_usd,
+            })
+            .collect();
+        let tz = self.display_timezone();
+        let today = now.with_timezone(&tz).date_naive();
+        let today_cost = sessions
+            .clone()
+            .flat_map(|b| b.entries.iter())
+            .filter(|e| e.timestamp.with_timezone(&tz).date_naive() == today)
+            .map(|e| e.cost_usd)
+            .sum();
+
         self.last_data = Some(AppData {
             total_tokens: analysis.total_tokens,
             total_cost: analysis.total_cost,
             token_limit: data.token_limit,
             active_block: active,
+            files_loaded: analysis.metadata.files_loaded,
+            entries_loaded: analysis.entries_count,
+            data_path: data.data_path.as_ref().map(|p| p.display().to_string()),
+            session_count: sessions.count(),
+            last_activity,
+            observed_message_limit,
+            recent_costs,
+            today_cost,
         });
     }
 }
 
+/// Table title with a note on rows hidden by the `--min-*` filters.
+fn table_title(base: &str, hidden_rows: usize) -> String {
+    match hidden_rows {
+        0 => base.to_string(),
+        1 => format!("{base} (1 row below threshold hidden)"),
+        n => format!("{base} ({n} rows below threshold hidden)"),
+    }
+}
+
+/// Reset time of the most recent limit notification in `block` that
+/// carries one still after `now`.
+fn detected_reset(
+    block: &SessionBlock,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    block
+        .limit_messages
+        .iter()
+        .filter_map(|m| {
+            let at = chrono::DateTime::parse_from_rfc3339(&m.timestamp).ok()?;
+            let reset = chrono::DateTime::parse_from_rfc3339(m.reset_time.as_deref()?).ok()?;
+            Some((at, reset.with_timezone(&chrono::Utc)))
+        })
+        .filter(|(_, reset)| *reset > now)
+        .max_by_key(|(at, _)| *at)
+        .map(|(_, reset)| reset)
+}
+
+/// Notifications for the active `block`: the latest limit the data reports
+/// hitting, then each token or cost limit used up or past
+/// [`LIMIT_WARNING_PCT`].
+fn session_notifications(
+    block: &SessionBlock,
+    tokens_used: u64,
+    token_limit: u64,
+    cost_limit: f64,
+) -> Vec<ViewNotification> {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    if let Some(hit) = block.limit_messages.last() {
+        errors.push(ViewNotification::error(match hit.limit_type.as_str() {
+            "opus_limit" => "Opus limit reached",
+            _ => "Usage limit reached",
+        }));
+    }
+    let usage = [
+        ("token", tokens_used as f64, token_limit as f64),
+        ("cost", block.cost_usd, cost_limit),
+    ];
+    for (name, used, limit) in usage {
+        if limit <= 0.0 {
+            continue;
+        }
+        let pct = used / limit * 100.0;
+        if pct >= 100.0 {
+            errors.push(ViewNotification::error(format!(
+                "{pct:.0}% of the {name} limit used"
+            )));
+        } else if pct >= LIMIT_WARNING_PCT {
+            warnings.push(ViewNotification::warning(format!(
+                "{pct:.0}% of the {name} limit used"
+            )));
+        }
+    }
+    errors.extend(warnings);
+    errors
+}
+
+/// Each model's share, in percent, of the input and output tokens in
+/// `block` (cache tokens are shown separately), largest first.
+///
+/// With `since`, only entries from then on count, grouped and filtered as
+/// the per-model stats are (`include_synthetic` as for the analyzer); a
+/// block with no entries that recent falls back to its whole-block totals.
+fn model_percentages(
+    block: &SessionBlock,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    include_synthetic: bool,
+) -> Vec<(String, f64)> {
+    let mut io_by_model: HashMap<String, u64> = HashMap::new();
+    if let Some(since) = since {
+        for entry in block
+            .entries
+            .iter()
+            .filter(|e| e.timestamp >= since && e.counts_as_message(include_synthetic))
+        {
+            let io = io_by_model.entry(entry.stats_model()).or_default();
+            *io = io
+                .saturating_add(entry.input_tokens)
+                .saturating_add(entry.output_tokens);
+        }
+    }
+    if io_by_model.values().all(|&io| io == 0) {
+        io_by_model = block
+            .per_model_stats
+            .iter()
+            .map(|(model, s)| {
+                (
+                    model.clone(),
+                    s.input_tokens.saturating_add(s.output_tokens),
+                )
+            })
+            .collect();
+    }
+
+    let io_total = io_by_model
+        .values()
+        .fold(0u64, |total, &io| total.saturating_add(io));
+    if io_total == 0 {
+        return Vec::new();
+    }
+    let mut percentages: Vec<(String, f64)> = io_by_model
+        .into_iter()
+        .map(|(model, io)| (model, io as f64 / io_total as f64 * 100.0))
+        .collect();
+    // Sort descending so the bar renders the largest segment first.
+    percentages.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    percentages
+}
+
+/// `Some(value)` when `value` is finite, otherwise `None`.
+fn finite(value: f64) -> Option<f64> {
+    value.is_finite().then_some(value)
+}
+
+/// The limit that runs out first at the block's current token, cost and
+/// message rates, with the minutes until it does (`0` when already reached).
+///
+/// Limits of zero and rates that are missing or zero are ignored; `None`
+/// when no limit can be projected.
+fn earliest_exhaustion(
+    active: &ActiveBlockData,
+    token_limit: u64,
+    cost_limit: f64,
+    message_limit: u32,
+) -> Option<(LimitKind, f64)> {
+    let minutes_to = |used: f64, limit: f64, per_minute: Option<f64>| -> Option<f64> {
+        if limit <= 0.0 {
+            return None;
+        }
+        if used >= limit {
+            return Some(0.0);
+        }
+        let rate = per_minute.filter(|r| *r > 0.0)?;
+        finite((limit - used) / rate)
+    };
+
+    [
+        (
+            LimitKind::Tokens,
+            minutes_to(
+                active.tokens_used as f64,
+                token_limit as f64,
+                active.burn_rate_tokens_per_min,
+            ),
+        ),
+        (
+            LimitKind::Cost,
+            minutes_to(
+                active.cost_usd,
+                cost_limit,
+                active.burn_rate_cost_per_hour.map(|c| c / 60.0),
+            ),
+        ),
+        (
+            LimitKind::Messages,
+            minutes_to(
+                f64::from(active.sent_messages),
+                f64::from(message_limit),
+                active.message_rate_per_min,
+            ),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(kind, minutes)| Some((kind, minutes?)))
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
 // ── Tests ──────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::session_view::NotificationLevel;
+    use monitor_core::clock::MockClock;
+    use monitor_core::models::{normalize_model_name, SYNTHETIC_MODEL};
     use monitor_runtime::data::analysis::{AnalysisMetadata, AnalysisResult};
 
     // ── ViewMode ──────────────────────────────────────────────────────────────
@@ -431,15 +1651,134 @@ mod tests {
         assert_eq!(mode, clone);
     }
 
-    // ── App::new ──────────────────────────────────────────────────────────────
+    // ── format_clock ──────────────────────────────────────────────────────────
 
     #[test]
-    fn test_app_creation_defaults() {
-        let app = App::new(
-            "dark",
-            ViewMode::Realtime,
-            "pro".to_string(),
-            "UTC".to_string(),
+    fn test_format_clock_24h_has_no_am_pm() {
+        let app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_time_format("24h");
+        let dt = chrono::DateTime::parse_from_rfc3339("2024-01-15T14:30:45Z")
+            .unwrap()
+            .with_timezone(&chrono_tz::Tz::UTC);
+
+        let with_seconds = app.format_clock(&dt, true);
+        let without = app.format_clock(&dt, false);
+        assert_eq!(with_seconds, "14:30:45");
+        assert_eq!(without, "14:30");
+        for s in [with_seconds, without] {
+            assert!(!s.contains("AM") && !s.contains("PM"), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_format_reset_names_other_days() {
+        let app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_time_format("24h");
+        let at = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .unwrap()
+                .with_timezone(&chrono_tz::Tz::UTC)
+        };
+        let now = at("2024-01-15T22:30:00Z");
+
+        assert_eq!(app.format_reset(&at("2024-01-15T23:00:00Z"), &now), "23:00");
+        assert_eq!(
+            app.format_reset(&at("2024-01-16T02:00:00Z"), &now),
+            "tomorrow 02:00"
+        );
+        assert_eq!(
+            app.format_reset(&at("2024-01-17T02:00:00Z"), &now),
+            "Jan 17 02:00"
+        );
+    }
+
+    #[test]
+    fn test_format_reset_uses_display_timezone_day() {
+        let app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        let tz: chrono_tz::Tz = "America/New_York".parse().unwrap();
+        let at = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .unwrap()
+                .with_timezone(&tz)
+        };
+        // 03:00 UTC on the 16th is still the 15th in New York.
+        let now = at("2024-01-16T01:00:00Z");
+        assert_eq!(
+            app.format_reset(&at("2024-01-16T03:00:00Z"), &now),
+            "10:00 PM"
+        );
+    }
+
+    #[test]
+    fn test_format_clock_defaults_to_12h() {
+        let app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        let dt = chrono::DateTime::parse_from_rfc3339("2024-01-15T14:30:45Z")
+            .unwrap()
+            .with_timezone(&chrono_tz::Tz::UTC);
+        assert_eq!(app.format_clock(&dt, false), "02:30 PM");
+    }
+
+    // ── TerminalGuard ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_terminal_guard_restores_on_drop() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let restored = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&restored);
+        let guard = TerminalGuard::with_restore(move || flag.store(true, Ordering::SeqCst));
+        assert!(!restored.load(Ordering::SeqCst));
+        drop(guard);
+        assert!(restored.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_terminal_guard_restores_when_draw_panics() {
+        use ratatui::backend::TestBackend;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let raw_mode = Arc::new(AtomicBool::new(true));
+        let flag = Arc::clone(&raw_mode);
+
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = TerminalGuard::with_restore(move || flag.store(false, Ordering::SeqCst));
+            let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+            terminal.draw(|_frame| panic!("render failure")).unwrap();
+        }));
+
+        assert!(outcome.is_err(), "draw closure must have panicked");
+        assert!(
+            !raw_mode.load(Ordering::SeqCst),
+            "raw mode must be disabled after a panic inside draw"
+        );
+    }
+
+    // ── App::new ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_display_timezone_utc_overrides_timezone() {
+        let app = App::new(
+            "dark",
+            ViewMode::Realtime,
+            "pro".to_string(),
+            "Asia/Tokyo".to_string(),
+        );
+        assert_eq!(app.display_timezone(), chrono_tz::Tz::Asia__Tokyo);
+        assert_eq!(app.with_utc(true).display_timezone(), chrono_tz::Tz::UTC);
+
+        let invalid = App::new("dark", ViewMode::Realtime, "pro".into(), "Mars/Base".into());
+        assert_eq!(invalid.display_timezone(), chrono_tz::Tz::UTC);
+    }
+
+    #[test]
+    fn test_app_creation_defaults() {
+        let app = App::new(
+            "dark",
+            ViewMode::Realtime,
+            "pro".to_string(),
+            "UTC".to_string(),
         );
         assert_eq!(app.plan, "pro");
         assert_eq!(app.timezone, "UTC");
@@ -472,6 +1811,55 @@ mod tests {
         assert_eq!(app.view_mode, ViewMode::Monthly);
     }
 
+    #[test]
+    fn test_app_with_custom_limits() {
+        let limits = CustomPlanLimits {
+            cost_limit: Some(12.0),
+            message_limit: Some(80),
+            ..Default::default()
+        };
+        let app = App::new(
+            "dark",
+            ViewMode::Realtime,
+            "custom".to_string(),
+            "UTC".to_string(),
+        )
+        .with_custom_limits(limits.clone());
+        assert_eq!(app.custom_limits, limits);
+    }
+
+    // ── loading indicator ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_render_shows_loading_before_first_snapshot() {
+        use ratatui::backend::TestBackend;
+
+        let mut app = App::new(
+            "dark",
+            ViewMode::Realtime,
+            "pro".to_string(),
+            "UTC".to_string(),
+        );
+        app.load_progress = Some(LoadProgress {
+            files_processed: 1,
+            files_total: 4,
+            entries_parsed: 10,
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("Loading usage data"));
+        assert!(content.contains("1 / 4"));
+    }
+
     // ── update_from_monitoring ────────────────────────────────────────────────
 
     fn make_empty_analysis() -> AnalysisResult {
@@ -483,8 +1871,11 @@ mod tests {
                 entries_processed: 0,
                 blocks_created: 0,
                 limits_detected: 0,
+                session_hours: 5,
+                files_loaded: 0,
                 load_time_seconds: 0.0,
                 transform_time_seconds: 0.0,
+                load_stats: Default::default(),
             },
             entries_count: 0,
             total_tokens: 0,
@@ -499,6 +1890,8 @@ mod tests {
             plan: "pro".to_string(),
             session_id: None,
             session_count: 0,
+            data_path: None,
+            poor_limit_fit: None,
         }
     }
 
@@ -526,6 +1919,7 @@ mod tests {
 
         let block = SessionBlock {
             id: "active-1".to_string(),
+            label: String::new(),
             start_time: start,
             end_time: end,
             entries: vec![],
@@ -562,9 +1956,308 @@ mod tests {
             plan: "pro".to_string(),
             session_id: Some("active-1".to_string()),
             session_count: 1,
+            data_path: None,
+            poor_limit_fit: None,
         }
     }
 
+    // ── Limit bell ───────────────────────────────────────────────────────────
+
+    fn bell_app(dir: &tempfile::TempDir) -> App {
+        App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_limit_bell(NotificationManager::new(dir.path()))
+    }
+
+    #[test]
+    fn test_limit_bell_silent_below_limits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = bell_app(&dir);
+        app.update_from_monitoring(make_monitoring_data_with_active());
+        assert_eq!(app.limit_alert(), None);
+        assert!(!app.take_limit_bell());
+    }
+
+    #[test]
+    fn test_limit_bell_rings_once_when_tokens_exceeded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = bell_app(&dir);
+        let mut data = make_monitoring_data_with_active();
+        data.token_limit = 500;
+        app.update_from_monitoring(data);
+
+        assert_eq!(app.limit_alert(), Some(KEY_EXCEED_MAX_LIMIT));
+        assert!(app.take_limit_bell());
+        assert!(
+            !app.take_limit_bell(),
+            "cooldown suppresses the second bell"
+        );
+    }
+
+    #[test]
+    fn test_limit_bell_rings_when_tokens_will_run_out() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = bell_app(&dir);
+        let mut data = make_monitoring_data_with_active();
+        // 1 000 tokens in 90 minutes against a 1 500 limit runs out in ~45
+        // minutes, well before the reset 3.5 hours away.
+        data.token_limit = 1_500;
+        app.update_from_monitoring(data);
+        assert_eq!(app.limit_alert(), Some(KEY_TOKENS_WILL_RUN_OUT));
+    }
+
+    #[test]
+    fn test_daily_budget_sums_today_and_rings_when_reached() {
+        let at = |ts: &str| ts.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let entry = |ts: &str, cost_usd: f64| monitor_core::models::UsageEntry {
+            timestamp: at(ts),
+            input_tokens: 100,
+            output_tokens: 0,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd,
+            model: "claude-3-5-sonnet".to_string(),
+            message_id: ts.to_string(),
+            request_id: ts.to_string(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        };
+        // A session spanning midnight: only its part after it counts.
+        let mut data = make_monitoring_data_with_active();
+        let block = &mut data.analysis.blocks[0];
+        block.start_time = at("2024-01-14T22:00:00Z");
+        block.end_time = at("2024-01-15T03:00:00Z");
+        block.entries = vec![
+            entry("2024-01-14T23:00:00Z", 10.0),
+            entry("2024-01-15T01:00:00Z", 2.0),
+        ];
+        block.cost_usd = 12.0;
+        let clock = MockClock::new(at("2024-01-15T02:00:00Z"));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app = bell_app(&dir)
+            .with_clock(clock.shared())
+            .with_daily_budget(Some(3.0));
+        app.update_from_monitoring(data.clone());
+        let app_data = app.last_data.as_ref().unwrap();
+        assert!((app_data.today_cost - 2.0).abs() < 1e-9);
+        assert_eq!(app.budget_alert(), None);
+
+        let mut app = bell_app(&dir)
+            .with_clock(clock.shared())
+            .with_daily_budget(Some(2.0));
+        app.update_from_monitoring(data);
+        assert_eq!(app.budget_alert(), Some(KEY_DAILY_BUDGET_REACHED));
+        assert_eq!(app.limit_alert(), None);
+        assert!(app.take_limit_bell());
+        assert!(!app.take_limit_bell(), "the budget has its own cooldown");
+
+        // A session limit still rings while the budget bell cools down.
+        app.last_data.as_mut().unwrap().token_limit = 100;
+        assert_eq!(app.limit_alert(), Some(KEY_EXCEED_MAX_LIMIT));
+        assert!(app.take_limit_bell());
+    }
+
+    #[test]
+    fn test_limit_bell_uses_per_key_cooldown() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cooldowns =
+            monitor_core::notifications::NotificationCooldowns::from_rules(&["exceed_max_limit=0"])
+                .unwrap();
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_limit_bell(NotificationManager::new(dir.path()).with_cooldowns(cooldowns));
+        let mut data = make_monitoring_data_with_active();
+        data.token_limit = 500;
+        app.update_from_monitoring(data);
+
+        assert!(app.take_limit_bell());
+        assert!(app.take_limit_bell(), "zero cooldown rings every time");
+    }
+
+    #[test]
+    fn test_limit_bell_cooldown_follows_app_clock() {
+        let clock = MockClock::new(chrono::Utc::now());
+        let mut data = make_monitoring_data_with_active();
+        data.token_limit = 500;
+
+        // Either order of the builders hands the clock to the bell.
+        let dir = tempfile::TempDir::new().unwrap();
+        let early = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared())
+            .with_limit_bell(NotificationManager::new(dir.path()));
+        let other_dir = tempfile::TempDir::new().unwrap();
+        let late = bell_app(&other_dir).with_clock(clock.shared());
+
+        let mut apps = [early, late];
+        for app in &mut apps {
+            app.update_from_monitoring(data.clone());
+            assert!(app.take_limit_bell());
+            assert!(!app.take_limit_bell());
+        }
+        // Past the half-hour cooldown of an exceeded limit.
+        clock.advance(chrono::Duration::minutes(31));
+        for app in &mut apps {
+            assert!(app.take_limit_bell());
+        }
+    }
+
+    #[test]
+    fn test_limit_bell_off_without_notifier() {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        let mut data = make_monitoring_data_with_active();
+        data.token_limit = 500;
+        app.update_from_monitoring(data);
+        assert!(!app.take_limit_bell());
+    }
+
+    // ── table_title ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_table_title_notes_hidden_rows() {
+        assert_eq!(table_title("Daily Usage", 0), "Daily Usage");
+        assert_eq!(
+            table_title("Daily Usage", 1),
+            "Daily Usage (1 row below threshold hidden)"
+        );
+        assert_eq!(
+            table_title("Monthly Usage", 4),
+            "Monthly Usage (4 rows below threshold hidden)"
+        );
+    }
+
+    // ── Cost since reset ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_cost_since_reset_sums_entries_after_boundary() {
+        let at = |ts: &str| ts.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let entry = |ts: &str, cost_usd: f64| monitor_core::models::UsageEntry {
+            timestamp: at(ts),
+            input_tokens: 100,
+            output_tokens: 0,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd,
+            model: "claude-3-5-sonnet".to_string(),
+            message_id: ts.to_string(),
+            request_id: ts.to_string(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        };
+        let now = at("2024-01-15T12:00:00Z");
+        let mut data = make_monitoring_data_with_active();
+        let mut earlier = data.analysis.blocks[0].clone();
+        earlier.is_active = false;
+        earlier.entries = vec![entry("2024-01-15T02:00:00Z", 4.0)];
+        earlier.cost_usd = 4.0;
+        // The active session spans the 10:00 reset.
+        let block = &mut data.analysis.blocks[0];
+        block.start_time = at("2024-01-15T08:00:00Z");
+        block.end_time = at("2024-01-15T13:00:00Z");
+        block.entries = vec![
+            entry("2024-01-15T09:00:00Z", 1.0),
+            entry("2024-01-15T11:00:00Z", 0.5),
+        ];
+        block.cost_usd = 1.5;
+        data.analysis.blocks.insert(0, earlier);
+
+        let clock = MockClock::new(now);
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared());
+        app.update_from_monitoring(data);
+        let app_data = app.last_data.as_ref().unwrap();
+        assert_eq!(app_data.recent_costs.len(), 3);
+        assert!(app.cost_since_reset(app_data, now).is_none());
+
+        // Only the part of the active session after the reset counts.
+        let app = app.with_reset_hour(Some(10));
+        let (cost, boundary) = app
+            .cost_since_reset(app.last_data.as_ref().unwrap(), now)
+            .unwrap();
+        assert!((cost - 0.5).abs() < 1e-9, "cost: {cost}");
+        assert_eq!(boundary, at("2024-01-15T10:00:00Z"));
+    }
+
+    // ── Staleness ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_stale_minutes_after_threshold() {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(make_monitoring_data_with_active());
+        let last = app.last_data.as_ref().unwrap().last_activity.unwrap();
+
+        let app_data = app.last_data.as_ref().unwrap();
+        let soon = last + chrono::Duration::minutes(59);
+        assert_eq!(app.stale_minutes(app_data, soon), None);
+        let later = last + chrono::Duration::minutes(95);
+        assert_eq!(app.stale_minutes(app_data, later), Some(95));
+
+        let app = app.with_staleness_minutes(0);
+        let app_data = app.last_data.as_ref().unwrap();
+        assert_eq!(app.stale_minutes(app_data, later), None);
+    }
+
+    // ── Message limit ─────────────────────────────────────────────────────────
+
+    /// Realtime data with a completed session of 143 messages before the
+    /// active one.
+    fn make_monitoring_data_with_history() -> monitor_runtime::orchestrator::MonitoringData {
+        let mut data = make_monitoring_data_with_active();
+        let mut past = data.analysis.blocks[0].clone();
+        past.id = "past-1".to_string();
+        past.is_active = false;
+        past.sent_messages_count = 143;
+        data.analysis.blocks.insert(0, past);
+        data
+    }
+
+    #[test]
+    fn test_message_limit_custom_plan_uses_observed_max() {
+        let mut app = App::new("dark", ViewMode::Realtime, "custom".into(), "UTC".into());
+        app.update_from_monitoring(make_monitoring_data_with_history());
+
+        let app_data = app.last_data.as_ref().unwrap();
+        assert_eq!(app_data.observed_message_limit, Some(150));
+        assert_eq!(app.message_limit_for(app_data), 150);
+    }
+
+    #[test]
+    fn test_message_limit_ignores_active_block() {
+        let mut app = App::new("dark", ViewMode::Realtime, "custom".into(), "UTC".into());
+        app.update_from_monitoring(make_monitoring_data_with_active());
+
+        let app_data = app.last_data.as_ref().unwrap();
+        assert_eq!(app_data.observed_message_limit, None);
+        assert_eq!(app.message_limit_for(app_data), 250);
+    }
+
+    #[test]
+    fn test_message_limit_named_plan_keeps_plan_limit() {
+        let mut app = App::new("dark", ViewMode::Realtime, "max5".into(), "UTC".into());
+        app.update_from_monitoring(make_monitoring_data_with_history());
+        assert_eq!(
+            app.message_limit_for(app.last_data.as_ref().unwrap()),
+            1_000
+        );
+    }
+
+    #[test]
+    fn test_message_limit_explicit_values_win() {
+        let data = make_monitoring_data_with_history();
+
+        let mut app = App::new("dark", ViewMode::Realtime, "custom".into(), "UTC".into())
+            .with_custom_limits(CustomPlanLimits {
+                message_limit: Some(80),
+                ..Default::default()
+            });
+        app.update_from_monitoring(data.clone());
+        assert_eq!(app.message_limit_for(app.last_data.as_ref().unwrap()), 80);
+
+        let mut app = app.with_message_limit(Some(500));
+        app.update_from_monitoring(data);
+        assert_eq!(app.message_limit_for(app.last_data.as_ref().unwrap()), 500);
+    }
+
     #[test]
     fn test_update_from_monitoring_no_active_block() {
         let mut app = App::new(
@@ -581,6 +2274,66 @@ mod tests {
         assert_eq!(data.token_limit, 19_000);
     }
 
+    #[test]
+    fn test_update_from_monitoring_keeps_data_source() {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        let mut data = make_monitoring_data_with_active();
+        data.data_path = Some(std::path::PathBuf::from("/data/claude"));
+        data.analysis.metadata.files_loaded = 3;
+        data.analysis.entries_count = 42;
+        app.update_from_monitoring(data);
+
+        let data = app.last_data.as_ref().unwrap();
+        assert_eq!(data.data_path.as_deref(), Some("/data/claude"));
+        assert_eq!(data.files_loaded, 3);
+        assert_eq!(data.entries_loaded, 42);
+    }
+
+    #[test]
+    fn test_no_session_state_without_files_names_data_path() {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_data_path(Some("/data/claude".to_string()));
+        app.update_from_monitoring(make_monitoring_data_no_active());
+
+        let state = app.no_session_state(app.last_data.as_ref().unwrap());
+        assert_eq!(
+            state,
+            NoSessionState::NoData {
+                path: "/data/claude".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_no_session_state_with_inactive_sessions() {
+        let mut data = make_monitoring_data_with_active();
+        data.analysis.metadata.files_loaded = 2;
+        let block = &mut data.analysis.blocks[0];
+        block.is_active = false;
+        block.actual_end_time = Some(chrono::Utc::now() - chrono::Duration::minutes(30));
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data);
+
+        let app_data = app.last_data.as_ref().unwrap();
+        assert!(app_data.active_block.is_none());
+        match app.no_session_state(app_data) {
+            NoSessionState::Idle {
+                files,
+                sessions,
+                minutes_since_activity,
+                stale_minutes,
+            } => {
+                assert_eq!(files, 2);
+                assert_eq!(sessions, 1);
+                let mins = minutes_since_activity.unwrap();
+                assert!((29.0..32.0).contains(&mins), "mins = {mins}");
+                assert_eq!(stale_minutes, None);
+            }
+            other => panic!("unexpected state {other:?}"),
+        }
+    }
+
     #[test]
     fn test_update_from_monitoring_with_active_block() {
         let mut app = App::new(
@@ -624,16 +2377,394 @@ mod tests {
         assert!((pct - 100.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_update_from_monitoring_prices_cache_per_model() {
+        let mut data = make_monitoring_data_with_active();
+        let stats = data.analysis.blocks[0]
+            .per_model_stats
+            .get_mut("claude-3-5-sonnet")
+            .unwrap();
+        stats.cache_creation_tokens = 1_000_000;
+        stats.cache_read_tokens = 1_000_000;
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data);
+
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.model_cache.len(), 1);
+        let usage = &active.model_cache[0];
+        assert_eq!(usage.model, "claude-3-5-sonnet");
+        assert!((usage.creation_cost - 3.75).abs() < 1e-6);
+        assert!((usage.read_cost - 0.30).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_update_from_monitoring_prices_cache_with_overrides() {
+        let mut data = make_monitoring_data_with_active();
+        let stats = data.analysis.blocks[0]
+            .per_model_stats
+            .get_mut("claude-3-5-sonnet")
+            .unwrap();
+        stats.cache_creation_tokens = 1_000_000;
+        stats.cache_read_tokens = 1_000_000;
+        let overrides = HashMap::from([(
+            "claude-3-5-sonnet".to_string(),
+            ModelPricing {
+                input: 1.0,
+                output: 2.0,
+                cache_creation: 5.0,
+                cache_read: 0.5,
+            },
+        )]);
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_pricing_overrides(Some(overrides));
+        app.update_from_monitoring(data);
+
+        let active = app.last_data.unwrap().active_block.unwrap();
+        let usage = &active.model_cache[0];
+        assert!((usage.creation_cost - 5.0).abs() < 1e-6);
+        assert!((usage.read_cost - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_update_from_monitoring_counts_tokens_on_basis() {
+        let mut data = make_monitoring_data_with_active();
+        data.analysis.blocks[0].token_counts.cache_creation_tokens = 300;
+        data.analysis.blocks[0].token_counts.cache_read_tokens = 700;
+
+        let clock =
+            MockClock::new(data.analysis.blocks[0].start_time + chrono::Duration::minutes(100));
+
+        // Input and output only by default, like the plan limits.
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared());
+        app.update_from_monitoring(data.clone());
+        let active = app
+            .last_data
+            .as_ref()
+            .unwrap()
+            .active_block
+            .clone()
+            .unwrap();
+        assert_eq!(active.tokens_used, 1_000);
+        assert_eq!(active.burn_rate_tokens_per_min, Some(10.0));
+
+        // The burn rate follows the chosen basis too.
+        let mut app = app.with_tokens_basis(TokensBasis::All);
+        app.update_from_monitoring(data);
+        let active = app
+            .last_data
+            .as_ref()
+            .unwrap()
+            .active_block
+            .clone()
+            .unwrap();
+        assert_eq!(active.tokens_used, 2_000);
+        assert_eq!(active.burn_rate_tokens_per_min, Some(20.0));
+
+        // The safe rate compares the same basis: 2,000 tokens over 100
+        // minutes is exactly the rate seen.
+        let tpm = active.burn_rate_tokens_per_min.unwrap();
+        assert!((active.tokens_used as f64 / active.elapsed_minutes - tpm).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_update_from_monitoring_reads_stored_projection() {
+        let mut data = make_monitoring_data_with_active();
+        let mut unprojected = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        unprojected.update_from_monitoring(data.clone());
+        let active = unprojected.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.projected_cost, None);
+
+        data.analysis.blocks[0].set_projection(&monitor_core::models::UsageProjection {
+            projected_total_tokens: 5_000,
+            projected_total_cost: 0.25,
+            remaining_minutes: 210.0,
+        });
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data);
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.projected_cost, Some(0.25));
+    }
+
     #[test]
     fn test_update_from_monitoring_burn_rate_extracted() {
+        let data = make_monitoring_data_with_active();
+        // Exactly 90 minutes into the block.
+        let clock =
+            MockClock::new(data.analysis.blocks[0].start_time + chrono::Duration::minutes(90));
         let mut app = App::new(
             "dark",
             ViewMode::Realtime,
             "pro".to_string(),
             "UTC".to_string(),
+        )
+        .with_clock(clock.shared());
+        app.update_from_monitoring(data);
+
+        let active = app
+            .last_data
+            .as_ref()
+            .unwrap()
+            .active_block
+            .as_ref()
+            .unwrap();
+        assert_eq!(active.elapsed_minutes, 90.0);
+        // 1000 tokens over 90 min (computed, not snapshot).
+        let tpm = active.burn_rate_tokens_per_min.unwrap();
+        assert!((tpm - 1000.0 / 90.0).abs() < 1e-9, "got {tpm}");
+        assert!(!active.warming_up);
+    }
+
+    // ── Per-model limits ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_model_token_limits_sum_family_usage() {
+        let mut data = make_monitoring_data_with_active();
+        data.analysis.blocks[0].per_model_stats.insert(
+            "claude-opus-4-20250514".to_string(),
+            monitor_core::models::ModelStats {
+                input_tokens: 300,
+                output_tokens: 100,
+                cache_creation_tokens: 0,
+                cache_read_tokens: 5_000,
+                cost_usd: 0.5,
+                entries_count: 2,
+            },
         );
+        // On the default io basis the 5,000 cache-read tokens do not count.
+        let mut app = App::new("dark", ViewMode::Realtime, "max5".into(), "UTC".into())
+            .with_model_token_limits(ModelTokenLimits {
+                opus: Some(1_000),
+                sonnet: None,
+                haiku: Some(50),
+            });
+        app.update_from_monitoring(data);
+
+        let active = app
+            .last_data
+            .as_ref()
+            .unwrap()
+            .active_block
+            .clone()
+            .unwrap();
+        assert_eq!(
+            active.model_limits,
+            [
+                ModelLimitUsage {
+                    family: "Opus".to_string(),
+                    used: 400,
+                    limit: 1_000,
+                },
+                ModelLimitUsage {
+                    family: "Haiku".to_string(),
+                    used: 0,
+                    limit: 50,
+                },
+            ]
+        );
+    }
+
+    // ── Prediction warm-up ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_fresh_block_withholds_rates_during_warmup() {
+        let mut data = make_monitoring_data_with_active();
+        let block = &mut data.analysis.blocks[0];
+        block.start_time = chrono::Utc::now() - chrono::Duration::minutes(2);
+        block.end_time = block.start_time + chrono::Duration::hours(5);
+        block.sent_messages_count = 4;
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data.clone());
+        let active = app
+            .last_data
+            .as_ref()
+            .unwrap()
+            .active_block
+            .clone()
+            .unwrap();
+        assert!(active.warming_up);
+        assert_eq!(active.burn_rate_tokens_per_min, None);
+        assert_eq!(active.burn_rate_cost_per_hour, None);
+        assert_eq!(active.message_rate_per_min, None);
+        assert_eq!(earliest_exhaustion(&active, 19_000, 18.0, 250), None);
+
+        let mut app = app.with_prediction_warmup_minutes(1);
+        app.update_from_monitoring(data);
+        let active = app
+            .last_data
+            .as_ref()
+            .unwrap()
+            .active_block
+            .clone()
+            .unwrap();
+        assert!(!active.warming_up);
+        assert!(active.burn_rate_tokens_per_min.is_some());
+    }
+
+    #[test]
+    fn test_block_with_few_entries_is_warming_up() {
+        let mut data = make_monitoring_data_with_active();
+        for stats in data.analysis.blocks[0].per_model_stats.values_mut() {
+            stats.entries_count = 2;
+        }
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data);
+        let active = app
+            .last_data
+            .as_ref()
+            .unwrap()
+            .active_block
+            .clone()
+            .unwrap();
+        assert!(active.warming_up);
+        assert_eq!(active.burn_rate_tokens_per_min, None);
+    }
+
+    // ── request_refresh ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_request_refresh_sends_and_clears_on_snapshot() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let mut app =
+            App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into()).with_refresh(tx);
+
+        app.request_refresh();
+        assert!(app.refreshing);
+        assert_eq!(rx.try_recv(), Ok(()));
+
+        app.update_from_monitoring(make_monitoring_data_with_active());
+        assert!(!app.refreshing);
+    }
+
+    #[test]
+    fn test_request_refresh_without_runtime_is_noop() {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.request_refresh();
+        assert!(!app.refreshing);
+
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        let mut app = app.with_refresh(tx);
+        app.request_refresh();
+        assert!(!app.refreshing);
+    }
+
+    // ── with_shutdown ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_shutdown_requested_follows_the_channel() {
+        let app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        assert!(!app.shutdown_requested());
+
+        let (tx, rx) = watch::channel(false);
+        let app = app.with_shutdown(rx);
+        assert!(!app.shutdown_requested());
+        tx.send(true).unwrap();
+        assert!(app.shutdown_requested());
+    }
+
+    #[test]
+    fn test_update_from_monitoring_zero_elapsed_has_no_burn_rate() {
+        let mut data = make_monitoring_data_with_active();
+        let block = &mut data.analysis.blocks[0];
+        block.start_time = chrono::Utc::now();
+        block.end_time = block.start_time + chrono::Duration::hours(5);
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data);
+
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.burn_rate_tokens_per_min, None);
+        assert_eq!(active.burn_rate_cost_per_hour, None);
+    }
+
+    #[test]
+    fn test_detected_limit_reset_overrides_nominal_end() {
+        let mut data = make_monitoring_data_with_active();
+        let block = &mut data.analysis.blocks[0];
+        let nominal_end = block.end_time;
+        let hit_at = block.start_time + chrono::Duration::minutes(30);
+        let announced = nominal_end - chrono::Duration::minutes(45);
+        block.limit_messages = vec![
+            monitor_core::models::LimitMessage {
+                limit_type: "general_limit".to_string(),
+                timestamp: (hit_at - chrono::Duration::minutes(10)).to_rfc3339(),
+                content: "older notice".to_string(),
+                reset_time: Some(nominal_end.to_rfc3339()),
+            },
+            monitor_core::models::LimitMessage {
+                limit_type: "general_limit".to_string(),
+                timestamp: hit_at.to_rfc3339(),
+                content: "limit reached".to_string(),
+                reset_time: Some(announced.to_rfc3339()),
+            },
+        ];
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data);
+
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.end_time_utc, nominal_end);
+        assert_eq!(active.detected_reset_utc, Some(announced));
+        assert_eq!(active.reset_at(), announced);
+    }
+
+    #[test]
+    fn test_detected_reset_in_the_past_is_ignored() {
+        let mut data = make_monitoring_data_with_active();
+        let block = &mut data.analysis.blocks[0];
+        let start = block.start_time;
+        let nominal_end = block.end_time;
+        let notice = |minutes: i64, reset: chrono::DateTime<chrono::Utc>| {
+            monitor_core::models::LimitMessage {
+                limit_type: "general_limit".to_string(),
+                timestamp: (start + chrono::Duration::minutes(minutes)).to_rfc3339(),
+                content: "limit reached".to_string(),
+                reset_time: Some(reset.to_rfc3339()),
+            }
+        };
+        // The latest notice announced a reset an hour into the block, which
+        // has passed 90 minutes in; the older one's reset is still ahead.
+        block.limit_messages = vec![
+            notice(20, nominal_end),
+            notice(30, start + chrono::Duration::minutes(60)),
+        ];
+        let clock = MockClock::new(start + chrono::Duration::minutes(90));
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared());
+        app.update_from_monitoring(data.clone());
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.detected_reset_utc, Some(nominal_end));
+
+        data.analysis.blocks[0].limit_messages.remove(0);
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared());
+        app.update_from_monitoring(data);
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.detected_reset_utc, None);
+        assert_eq!(active.reset_at(), nominal_end);
+    }
+
+    #[test]
+    fn test_reset_at_without_detection_uses_nominal_end() {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
         app.update_from_monitoring(make_monitoring_data_with_active());
 
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.detected_reset_utc, None);
+        assert_eq!(active.reset_at(), active.end_time_utc);
+    }
+
+    // ── Notifications ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_notifications_from_limit_hits_and_thresholds() {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(make_monitoring_data_with_active());
         let active = app
             .last_data
             .as_ref()
@@ -641,12 +2772,119 @@ mod tests {
             .active_block
             .as_ref()
             .unwrap();
+        assert!(active.notifications.is_empty());
+
+        // 1,000 of 1,200 tokens is past the warning threshold; the cost is
+        // over the plan's limit and the data reports a limit hit.
+        let mut data = make_monitoring_data_with_active();
+        data.token_limit = 1_200;
+        let block = &mut data.analysis.blocks[0];
+        block.cost_usd = 1_000.0;
+        block.limit_messages = vec![monitor_core::models::LimitMessage {
+            limit_type: "opus_limit".to_string(),
+            timestamp: block.start_time.to_rfc3339(),
+            content: "Opus limit reached".to_string(),
+            reset_time: None,
+        }];
+        app.update_from_monitoring(data);
+
+        let active = app.last_data.unwrap().active_block.unwrap();
+        let messages: Vec<(NotificationLevel, &str)> = active
+            .notifications
+            .iter()
+            .map(|n| (n.level, n.message.as_str()))
+            .collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert_eq!(
+            messages[0],
+            (NotificationLevel::Error, "Opus limit reached")
+        );
+        assert_eq!(messages[1].0, NotificationLevel::Error);
+        assert!(messages[1].1.ends_with("of the cost limit used"));
+        assert_eq!(
+            messages[2],
+            (NotificationLevel::Warning, "83% of the token limit used")
+        );
+    }
+
+    #[test]
+    fn test_poorly_fitting_detected_limit_shown_as_warning() {
+        let mut data = make_monitoring_data_with_active();
+        data.poor_limit_fit = Some(monitor_core::p90::DetectedLimit {
+            limit: 220_000,
+            peak: 120_000,
+        });
+        let mut app = App::new("dark", ViewMode::Realtime, "custom".into(), "UTC".into());
+        app.update_from_monitoring(data);
+
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert_eq!(active.notifications.len(), 1);
+        assert_eq!(active.notifications[0].level, NotificationLevel::Warning);
+        assert!(active.notifications[0]
+            .message
+            .starts_with("Detected limit 220,000 is well above the session peak 120,000"));
+
+        let mut data = make_monitoring_data_with_active();
+        data.poor_limit_fit = Some(monitor_core::p90::DetectedLimit {
+            limit: 88_000,
+            peak: 120_000,
+        });
+        let mut app = App::new("dark", ViewMode::Realtime, "custom".into(), "UTC".into());
+        app.update_from_monitoring(data);
+        let active = app.last_data.unwrap().active_block.unwrap();
+        assert!(active.notifications[0]
+            .message
+            .starts_with("Detected limit 88,000 is below the session peak 120,000"));
+    }
+
+    #[test]
+    fn test_update_from_monitoring_non_finite_cost_rate_is_none() {
+        let mut data = make_monitoring_data_with_active();
+        data.analysis.blocks[0].cost_usd = f64::INFINITY;
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(data);
+
+        let active = app.last_data.unwrap().active_block.unwrap();
         assert!(active.burn_rate_tokens_per_min.is_some());
-        let tpm = active.burn_rate_tokens_per_min.unwrap();
-        // 1000 tokens over ~90 min ≈ 11.1 tokens/min (computed, not snapshot).
-        assert!(
-            tpm > 10.0 && tpm < 13.0,
-            "burn rate should be ~11, got {tpm}"
+        assert_eq!(active.burn_rate_cost_per_hour, None);
+    }
+
+    // ── earliest_exhaustion ───────────────────────────────────────────────────
+
+    fn active_with_rates(tpm: f64, cost_per_hour: f64, msgs_per_min: f64) -> ActiveBlockData {
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into());
+        app.update_from_monitoring(make_monitoring_data_with_active());
+        let mut active = app.last_data.unwrap().active_block.unwrap();
+        active.tokens_used = 1_000;
+        active.cost_usd = 1.0;
+        active.sent_messages = 10;
+        active.burn_rate_tokens_per_min = Some(tpm);
+        active.burn_rate_cost_per_hour = Some(cost_per_hour);
+        active.message_rate_per_min = Some(msgs_per_min);
+        active
+    }
+
+    #[test]
+    fn test_earliest_exhaustion_picks_binding_limit() {
+        // Tokens: 9,000 left at 100/min = 90m. Cost: $7 left at $10/h = 42m.
+        // Messages: 90 left at 1/min = 90m.
+        let active = active_with_rates(100.0, 10.0, 1.0);
+        let (kind, minutes) = earliest_exhaustion(&active, 10_000, 8.0, 100).unwrap();
+        assert_eq!(kind, LimitKind::Cost);
+        assert!((minutes - 42.0).abs() < 1e-9, "{minutes}");
+
+        let (kind, _) = earliest_exhaustion(&active, 10_000, 8.0, 20).unwrap();
+        assert_eq!(kind, LimitKind::Messages);
+    }
+
+    #[test]
+    fn test_earliest_exhaustion_reached_and_missing_rates() {
+        let active = active_with_rates(0.0, 0.0, 0.0);
+        assert_eq!(earliest_exhaustion(&active, 10_000, 8.0, 100), None);
+        assert_eq!(
+            earliest_exhaustion(&active, 500, 8.0, 100),
+            Some((LimitKind::Tokens, 0.0))
         );
     }
 
@@ -705,6 +2943,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_update_from_monitoring_records_lifetime_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut data = make_monitoring_data_with_active();
+        data.analysis.blocks[0].entries = vec![monitor_core::models::UsageEntry {
+            timestamp: chrono::Utc::now() - chrono::Duration::minutes(30),
+            input_tokens: 800,
+            output_tokens: 200,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd: 0.05,
+            model: "claude-3-5-sonnet".to_string(),
+            message_id: "m1".to_string(),
+            request_id: "r1".to_string(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        }];
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_lifetime(LifetimeTracker::new(dir.path()));
+        app.update_from_monitoring(data.clone());
+        app.update_from_monitoring(data);
+
+        let stats = app.lifetime.as_ref().unwrap().stats();
+        assert_eq!(stats.total_tokens, 1_000);
+        assert!((stats.total_cost - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_model_window_follows_recent_entries() {
+        let mut data = make_monitoring_data_with_active();
+        let now = data.analysis.blocks[0].start_time + chrono::Duration::minutes(90);
+        let clock = MockClock::new(now);
+        let entry = |minutes_ago: i64, model: &str, tokens: u64| monitor_core::models::UsageEntry {
+            timestamp: now - chrono::Duration::minutes(minutes_ago),
+            input_tokens: tokens,
+            output_tokens: 0,
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            cost_usd: 0.0,
+            model: model.to_string(),
+            message_id: String::new(),
+            request_id: String::new(),
+            project: String::new(),
+            is_system: false,
+            source_file: None,
+        };
+        data.analysis.blocks[0].entries = vec![
+            entry(80, "claude-3-opus-20240229", 9_000),
+            entry(20, "claude-3-haiku-20240307", 300),
+            entry(10, "claude-3-opus-20240229", 100),
+            entry(5, SYNTHETIC_MODEL, 1_000),
+        ];
+        let percentages = |app: App| {
+            app.last_data
+                .unwrap()
+                .active_block
+                .unwrap()
+                .model_percentages
+        };
+
+        // The whole block only knows its per-model stats (all Sonnet).
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared());
+        app.update_from_monitoring(data.clone());
+        assert_eq!(
+            percentages(app),
+            vec![("claude-3-5-sonnet".to_string(), 100.0)]
+        );
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared())
+            .with_model_window_minutes(Some(30));
+        // Dated ids group like the per-model stats; synthetic entries are left out.
+        app.update_from_monitoring(data.clone());
+        assert_eq!(
+            percentages(app),
+            vec![
+                ("claude-3-haiku".to_string(), 75.0),
+                ("claude-3-opus".to_string(), 25.0)
+            ]
+        );
+
+        // Only a synthetic entry in the last five minutes: fall back to the
+        // whole block, unless synthetic entries are counted.
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared())
+            .with_model_window_minutes(Some(5));
+        app.update_from_monitoring(data.clone());
+        assert_eq!(
+            percentages(app),
+            vec![("claude-3-5-sonnet".to_string(), 100.0)]
+        );
+
+        let mut app = App::new("dark", ViewMode::Realtime, "pro".into(), "UTC".into())
+            .with_clock(clock.shared())
+            .with_model_window_minutes(Some(5))
+            .with_include_synthetic(true);
+        app.update_from_monitoring(data);
+        assert_eq!(
+            percentages(app),
+            vec![(normalize_model_name(SYNTHETIC_MODEL), 100.0)]
+        );
+    }
+
     #[test]
     fn test_update_from_monitoring_overwrites_previous_data() {
         let mut app = App::new(
@@ -731,6 +3075,7 @@ mod tests {
 
         let gap_block = SessionBlock {
             id: "gap-1".to_string(),
+            label: String::new(),
             start_time: start,
             end_time: end,
             entries: vec![],
@@ -757,6 +3102,8 @@ mod tests {
             plan: "pro".to_string(),
             session_id: None,
             session_count: 0,
+            data_path: None,
+            poor_limit_fit: None,
         };
 
         let mut app = App::new(
@@ -770,4 +3117,61 @@ mod tests {
         // Gap blocks must not be treated as active sessions.
         assert!(app.last_data.as_ref().unwrap().active_block.is_none());
     }
+
+    // ── ActiveBlockPolicy ─────────────────────────────────────────────────────
+
+    /// Two overlapping active blocks: an older heavy one and a newer light one.
+    fn make_overlapping_blocks() -> Vec<SessionBlock> {
+        let data = make_monitoring_data_with_active();
+        let mut older = data.analysis.blocks[0].clone();
+        older.id = "older".to_string();
+        older.start_time -= chrono::Duration::hours(1);
+        older.token_counts.input_tokens = 50_000;
+        let mut newer = data.analysis.blocks[0].clone();
+        newer.id = "newer".to_string();
+        let mut gap = newer.clone();
+        gap.id = "gap".to_string();
+        gap.is_gap = true;
+        vec![older, newer, gap]
+    }
+
+    #[test]
+    fn test_active_block_policy_select() {
+        let blocks = make_overlapping_blocks();
+        let pick = |policy: ActiveBlockPolicy| policy.select(&blocks).map(|b| b.id.as_str());
+
+        assert_eq!(pick(ActiveBlockPolicy::MostRecent), Some("newer"));
+        assert_eq!(pick(ActiveBlockPolicy::HighestTokens), Some("older"));
+        assert_eq!(pick(ActiveBlockPolicy::Earliest), Some("older"));
+        assert!(ActiveBlockPolicy::MostRecent.select(&[]).is_none());
+        assert_eq!(
+            ActiveBlockPolicy::from_name("highest-tokens"),
+            ActiveBlockPolicy::HighestTokens
+        );
+        assert_eq!(
+            ActiveBlockPolicy::from_name("bogus"),
+            ActiveBlockPolicy::MostRecent
+        );
+    }
+
+    #[test]
+    fn test_update_from_monitoring_uses_active_block_policy() {
+        let mut data = make_monitoring_data_with_active();
+        data.analysis.blocks = make_overlapping_blocks();
+
+        let mut app = App::new(
+            "dark",
+            ViewMode::Realtime,
+            "pro".to_string(),
+            "UTC".to_string(),
+        );
+        app.update_from_monitoring(data.clone());
+        let active = app.last_data.as_ref().unwrap().active_block.as_ref();
+        assert_eq!(active.unwrap().tokens_used, 1_000);
+
+        let mut app = app.with_active_block_policy(ActiveBlockPolicy::HighestTokens);
+        app.update_from_monitoring(data);
+        let active = app.last_data.as_ref().unwrap().active_block.as_ref();
+        assert_eq!(active.unwrap().tokens_used, 50_200);
+    }
 }
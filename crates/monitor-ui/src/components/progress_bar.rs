@@ -1,4 +1,5 @@
 use crate::themes::Theme;
+use monitor_core::formatting::NumberFormatter;
 use ratatui::text::{Line, Span};
 
 /// Configuration controlling visual appearance of a progress bar.
@@ -45,6 +46,8 @@ pub struct TokenProgressBar<'a> {
     pub theme: &'a Theme,
     /// Visual configuration.
     pub config: ProgressBarConfig,
+    /// Separators used for the `current / limit` counts.
+    pub number_format: NumberFormatter,
 }
 
 impl<'a> TokenProgressBar<'a> {
@@ -61,9 +64,16 @@ impl<'a> TokenProgressBar<'a> {
             limit,
             theme,
             config: ProgressBarConfig::default(),
+            number_format: NumberFormatter::default(),
         }
     }
 
+    /// Use `number_format` for the count label.
+    pub fn with_number_format(mut self, number_format: NumberFormatter) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
     /// Render the progress bar as a [`Line`] suitable for embedding in any
     /// ratatui widget that accepts `Line` values.
     pub fn to_line(&self) -> Line<'a> {
@@ -80,8 +90,8 @@ impl<'a> TokenProgressBar<'a> {
         let label = format!(
             " {:.1}% ({}/{})",
             self.percentage,
-            monitor_core::formatting::format_number(self.current as f64, 0),
-            monitor_core::formatting::format_number(self.limit as f64, 0),
+            self.number_format.format_count(self.current),
+            self.number_format.format_count(self.limit),
         );
 
         Line::from(vec![
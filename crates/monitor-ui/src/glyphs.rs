@@ -0,0 +1,103 @@
+//! Emoji or plain-ASCII symbols for the realtime session view.
+//!
+//! Many terminals and fonts, especially over SSH, draw emoji as boxes or at
+//! the wrong width, which wrecks the column alignment. [`Glyphs::Ascii`]
+//! swaps the emoji, block and box-drawing characters for ASCII.
+
+/// Title decoration used instead of the sparkles in ASCII mode.
+pub const ASCII_DECORATION: &str = "* * * *";
+
+/// Symbol set of the session view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Glyphs {
+    /// Emoji icons and Unicode bars.
+    #[default]
+    Emoji,
+    /// ASCII substitutes such as `[$]`, `#` and `-`.
+    Ascii,
+}
+
+impl Glyphs {
+    /// `Ascii` when `--ascii` was given, otherwise [`detect`](Self::detect).
+    pub fn resolve(ascii: bool) -> Self {
+        if ascii {
+            Self::Ascii
+        } else {
+            Self::detect()
+        }
+    }
+
+    /// Pick from the locale in `LC_ALL`, `LC_CTYPE` or `LANG`, the first
+    /// that is set (see [`from_locale`](Self::from_locale)).
+    pub fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty());
+        Self::from_locale(locale.as_deref())
+    }
+
+    /// `Ascii` for a locale that is not UTF-8, such as `C` or
+    /// `en_US.ISO-8859-1`; `Emoji` for UTF-8 or no locale at all.
+    pub fn from_locale(locale: Option<&str>) -> Self {
+        match locale {
+            Some(locale) => {
+                let locale = locale.to_ascii_lowercase();
+                if locale.contains("utf-8") || locale.contains("utf8") {
+                    Self::Emoji
+                } else {
+                    Self::Ascii
+                }
+            }
+            None => Self::Emoji,
+        }
+    }
+
+    /// `emoji` or its `ascii` substitute.
+    pub fn pick(self, emoji: &'static str, ascii: &'static str) -> &'static str {
+        match self {
+            Self::Emoji => emoji,
+            Self::Ascii => ascii,
+        }
+    }
+
+    /// Filled and empty cells of a progress bar.
+    pub fn bar(self) -> (&'static str, &'static str) {
+        (self.pick("█", "#"), self.pick("░", "-"))
+    }
+
+    /// Character of the thin separators between sections.
+    pub fn thin_separator(self) -> &'static str {
+        self.pick("─", "-")
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(Glyphs::from_locale(Some("en_US.UTF-8")), Glyphs::Emoji);
+        assert_eq!(Glyphs::from_locale(Some("de_DE.utf8")), Glyphs::Emoji);
+        assert_eq!(Glyphs::from_locale(Some("C")), Glyphs::Ascii);
+        assert_eq!(Glyphs::from_locale(Some("POSIX")), Glyphs::Ascii);
+        assert_eq!(Glyphs::from_locale(Some("en_US.ISO-8859-1")), Glyphs::Ascii);
+        assert_eq!(Glyphs::from_locale(None), Glyphs::Emoji);
+    }
+
+    #[test]
+    fn test_ascii_flag_wins() {
+        assert_eq!(Glyphs::resolve(true), Glyphs::Ascii);
+    }
+
+    #[test]
+    fn test_ascii_symbols_are_ascii() {
+        let (filled, empty) = Glyphs::Ascii.bar();
+        assert!(filled.is_ascii() && empty.is_ascii());
+        assert!(Glyphs::Ascii.thin_separator().is_ascii());
+        assert!(ASCII_DECORATION.is_ascii());
+    }
+}
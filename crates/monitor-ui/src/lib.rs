@@ -6,6 +6,9 @@
 
 pub mod app;
 pub mod components;
+pub mod glyphs;
+pub mod markdown;
+pub mod model_labels;
 pub mod session_view;
 pub mod table_view;
 pub mod themes;
@@ -0,0 +1,165 @@
+//! GitHub-flavored Markdown report for the aggregate table views.
+//!
+//! Used by `--output markdown`; produces plain text so the report can be
+//! pasted into a PR, issue or wiki page.
+
+use monitor_core::formatting::{format_currency, format_number};
+
+use crate::table_view::{TableRowData, TableTotals};
+
+/// Column headers of the report table.
+const HEADERS: [&str; 8] = [
+    "Period",
+    "Models",
+    "Input",
+    "Output",
+    "Cache Create",
+    "Cache Read",
+    "Total",
+    "Cost",
+];
+
+/// Alignment row: text columns left-aligned, numeric columns right-aligned.
+const ALIGNMENT: &str = "|---|---|---:|---:|---:|---:|---:|---:|";
+
+/// Render `rows` and `totals` as a Markdown report.
+///
+/// The report starts with a heading naming the period range (first to last
+/// period key) and a grand-total line, followed by a table with one row per
+/// period and a bold totals row. Pipe characters in cell text are escaped.
+/// The output always ends with a newline.
+pub fn format_markdown_table(rows: &[TableRowData], totals: &TableTotals) -> String {
+    let first = rows.iter().map(|r| r.period.as_str()).min();
+    let last = rows.iter().map(|r| r.period.as_str()).max();
+    let mut out = match (first, last) {
+        (Some(first), Some(last)) if first != last => {
+            format!("## Claude usage: {first} to {last}\n\n")
+        }
+        (Some(only), _) => format!("## Claude usage: {only}\n\n"),
+        _ => return "## Claude usage\n\nNo usage recorded.\n".to_string(),
+    };
+
+    out.push_str(&format!(
+        "Grand total: **{} tokens**, **{}**\n\n",
+        format_number(totals.total_tokens as f64, 0),
+        format_currency(totals.total_cost),
+    ));
+
+    out.push_str(&table_line(HEADERS.iter().map(|h| h.to_string())));
+    out.push_str(ALIGNMENT);
+    out.push('\n');
+
+    for row in rows {
+        out.push_str(&table_line([
+            escape_cell(&row.period),
+            escape_cell(&row.models.join(", ")),
+            format_number(row.input_tokens as f64, 0),
+            format_number(row.output_tokens as f64, 0),
+            format_number(row.cache_creation as f64, 0),
+            format_number(row.cache_read as f64, 0),
+            format_number(row.total_tokens as f64, 0),
+            format_currency(row.cost),
+        ]));
+    }
+
+    let bold = |value: String| format!("**{value}**");
+    out.push_str(&table_line([
+        bold("Total".to_string()),
+        format!("{} periods", totals.entries_count),
+        bold(format_number(totals.input_tokens as f64, 0)),
+        bold(format_number(totals.output_tokens as f64, 0)),
+        bold(format_number(totals.cache_creation as f64, 0)),
+        bold(format_number(totals.cache_read as f64, 0)),
+        bold(format_number(totals.total_tokens as f64, 0)),
+        bold(format_currency(totals.total_cost)),
+    ]));
+
+    out
+}
+
+/// Join `cells` into a `| a | b |` table line ending in a newline.
+fn table_line(cells: impl IntoIterator<Item = String>) -> String {
+    let cells: Vec<String> = cells.into_iter().collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// Escape characters that would end a table cell early.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_row(period: &str, models: &[&str], input: u64, output: u64, cost: f64) -> TableRowData {
+        TableRowData {
+            period: period.to_string(),
+            models: models.iter().map(|m| m.to_string()).collect(),
+            input_tokens: input,
+            output_tokens: output,
+            cache_creation: 0,
+            cache_read: 0,
+            total_tokens: input + output,
+            cost,
+        }
+    }
+
+    fn make_totals(rows: &[TableRowData]) -> TableTotals {
+        TableTotals {
+            input_tokens: rows.iter().map(|r| r.input_tokens).sum(),
+            output_tokens: rows.iter().map(|r| r.output_tokens).sum(),
+            cache_creation: 0,
+            cache_read: 0,
+            total_tokens: rows.iter().map(|r| r.total_tokens).sum(),
+            total_cost: rows.iter().map(|r| r.cost).sum(),
+            entries_count: rows.len() as u32,
+            model_shares: Vec::new(),
+        }
+    }
+
+    // ── format_markdown_table ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_markdown_report_layout() {
+        let rows = vec![
+            make_row("2024-01-15", &["claude-3-5-sonnet"], 1_200, 300, 0.75),
+            make_row("2024-01-16", &["claude-3-opus"], 10, 5, 1.0),
+        ];
+        let md = format_markdown_table(&rows, &make_totals(&rows));
+        let lines: Vec<&str> = md.lines().collect();
+
+        assert_eq!(lines[0], "## Claude usage: 2024-01-15 to 2024-01-16");
+        assert_eq!(lines[2], "Grand total: **1,515 tokens**, **$1.75**");
+        assert_eq!(
+            lines[4],
+            "| Period | Models | Input | Output | Cache Create | Cache Read | Total | Cost |"
+        );
+        assert_eq!(lines[5], ALIGNMENT);
+        assert_eq!(
+            lines[6],
+            "| 2024-01-15 | claude-3-5-sonnet | 1,200 | 300 | 0 | 0 | 1,500 | $0.75 |"
+        );
+        assert_eq!(
+            lines[8],
+            "| **Total** | 2 periods | **1,210** | **305** | **0** | **0** | **1,515** | **$1.75** |"
+        );
+        assert!(md.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_markdown_escapes_pipes_in_models() {
+        let rows = vec![make_row("2024-01-15", &["weird|model"], 1, 1, 0.0)];
+        let md = format_markdown_table(&rows, &make_totals(&rows));
+        assert!(md.contains("| weird\\|model |"), "{md}");
+        assert_eq!(md.lines().next(), Some("## Claude usage: 2024-01-15"));
+    }
+
+    #[test]
+    fn test_markdown_without_rows() {
+        let md = format_markdown_table(&[], &make_totals(&[]));
+        assert_eq!(md, "## Claude usage\n\nNo usage recorded.\n");
+    }
+}
@@ -0,0 +1,109 @@
+//! Short display labels for model names.
+//!
+//! The session view and its per-model breakdown show `Opus` / `Sonnet` /
+//! `Haiku` / `Other` by default; `--model-label` rules override them.
+
+use monitor_core::error::MonitorError;
+
+/// User-defined short display labels for models (`--model-label`).
+///
+/// Rules are `(substring, label)` pairs checked in order against the
+/// lowercased model name; the first match wins. Models matching no rule fall
+/// back to the built-in `Opus` / `Sonnet` / `Haiku` / `Other` labels.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelLabels {
+    rules: Vec<(String, String)>,
+}
+
+impl ModelLabels {
+    /// Parse one `SUBSTRING=LABEL` rule, e.g. `"opus-4-5=Opus 4.5"`.
+    pub fn parse_rule(spec: &str) -> Result<(String, String), MonitorError> {
+        match spec.split_once('=') {
+            Some((pattern, label)) if !pattern.trim().is_empty() && !label.trim().is_empty() => {
+                Ok((pattern.trim().to_lowercase(), label.trim().to_string()))
+            }
+            _ => Err(MonitorError::Config(format!(
+                "invalid model label '{spec}', expected SUBSTRING=LABEL"
+            ))),
+        }
+    }
+
+    /// Build a mapping from `SUBSTRING=LABEL` rules, keeping their order.
+    pub fn from_rules<S: AsRef<str>>(specs: &[S]) -> Result<Self, MonitorError> {
+        let rules = specs
+            .iter()
+            .map(|spec| Self::parse_rule(spec.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Short display label for `model`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monitor_ui::model_labels::ModelLabels;
+    ///
+    /// let labels = ModelLabels::from_rules(&["opus-4-5=Opus 4.5"]).unwrap();
+    /// assert_eq!(labels.label("claude-opus-4-5-20251101"), "Opus 4.5");
+    /// assert_eq!(labels.label("claude-3-opus"), "Opus");
+    /// assert_eq!(labels.label("gpt-4"), "Other");
+    /// ```
+    pub fn label(&self, model: &str) -> String {
+        let lower = model.to_lowercase();
+        if let Some((_, label)) = self
+            .rules
+            .iter()
+            .find(|(pattern, _)| lower.contains(pattern))
+        {
+            return label.clone();
+        }
+        let builtin = if lower.contains("opus") {
+            "Opus"
+        } else if lower.contains("sonnet") {
+            "Sonnet"
+        } else if lower.contains("haiku") {
+            "Haiku"
+        } else {
+            "Other"
+        };
+        builtin.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── ModelLabels ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_model_labels_default_is_four_way() {
+        let labels = ModelLabels::default();
+        assert_eq!(labels.label("claude-3-5-sonnet"), "Sonnet");
+        assert_eq!(labels.label("claude-opus-4"), "Opus");
+        assert_eq!(labels.label("claude-3-haiku"), "Haiku");
+        assert_eq!(labels.label("gpt-4"), "Other");
+    }
+
+    #[test]
+    fn test_model_labels_user_rules_first_match_wins() {
+        let labels =
+            ModelLabels::from_rules(&["opus-4-5=Opus 4.5", "3-opus=Opus 3", "GPT=GPT"]).unwrap();
+        assert_eq!(labels.label("claude-opus-4-5-20251101"), "Opus 4.5");
+        assert_eq!(labels.label("claude-3-opus-20240229"), "Opus 3");
+        assert_eq!(labels.label("gpt-4o"), "GPT");
+        assert_eq!(labels.label("claude-3-5-sonnet"), "Sonnet");
+    }
+
+    #[test]
+    fn test_model_labels_rejects_malformed_rules() {
+        assert!(ModelLabels::parse_rule("no-equals").is_err());
+        assert!(ModelLabels::parse_rule("=Label").is_err());
+        assert!(ModelLabels::parse_rule("opus=").is_err());
+        assert_eq!(
+            ModelLabels::parse_rule(" Opus-4 = Opus 4 ").unwrap(),
+            ("opus-4".to_string(), "Opus 4".to_string())
+        );
+    }
+}
@@ -10,18 +10,128 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
+use monitor_core::formatting::{format_number, format_time, NumberFormatter};
+use monitor_core::lifetime::LifetimeStats;
 use monitor_core::models::BurnRate;
+use monitor_core::plans::{DEFAULT_COST_LIMIT, DEFAULT_MESSAGE_LIMIT, DEFAULT_TOKEN_LIMIT};
+use monitor_runtime::data::reader::LoadProgress;
 
+use crate::components::header::SPARKLES;
+use crate::glyphs::Glyphs;
+use crate::model_labels::ModelLabels;
 use crate::themes::Theme;
 
+/// Title text of the realtime header unless `--title` replaces it.
+pub const DEFAULT_TITLE: &str = "CLAUDE CODE USAGE MONITOR";
+
+/// Models named in the distribution row unless `--max-models` says otherwise.
+pub const DEFAULT_MAX_MODELS: usize = 4;
+
+/// Separator width when the render area is unknown (zero-width), as in
+/// [`build_session_lines`].
+pub const DEFAULT_SEPARATOR_WIDTH: usize = 78;
+
+/// Display columns taken by a row label, values start after it.
+const LABEL_WIDTH: usize = 25;
+
+/// Title text and decoration of the realtime header, for rebranding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderBranding {
+    /// Text in the middle of the title line.
+    pub title: String,
+    /// Decoration shown either side of the title; empty for none.
+    pub decoration: String,
+}
+
+impl Default for HeaderBranding {
+    fn default() -> Self {
+        Self {
+            title: DEFAULT_TITLE.to_string(),
+            decoration: SPARKLES.to_string(),
+        }
+    }
+}
+
+impl HeaderBranding {
+    /// Branding with the given overrides, keeping the defaults for `None`.
+    pub fn new(title: Option<String>, decoration: Option<String>) -> Self {
+        let default = Self::default();
+        Self {
+            title: title.unwrap_or(default.title),
+            decoration: decoration.unwrap_or(default.decoration),
+        }
+    }
+}
+
+/// Cache tokens one model used in the session, with their cost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelCacheUsage {
+    /// Raw model name as recorded in the usage data.
+    pub model: String,
+    /// Cache-creation tokens attributed to the model.
+    pub creation_tokens: u64,
+    /// Cache-read tokens attributed to the model.
+    pub read_tokens: u64,
+    /// Cost (USD) of the cache-creation tokens.
+    pub creation_cost: f64,
+    /// Cost (USD) of the cache-read tokens.
+    pub read_cost: f64,
+}
+
+/// Tokens one model family used in the session against its own limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelLimitUsage {
+    /// Family name, e.g. `"Opus"`.
+    pub family: String,
+    /// Tokens the family's models used, on the session bar's basis.
+    pub used: u64,
+    /// The family's token limit.
+    pub limit: u64,
+}
+
+/// Plan limit a prediction refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    /// Input + output tokens.
+    Tokens,
+    /// Cost in USD.
+    Cost,
+    /// Sent messages.
+    Messages,
+}
+
+impl LimitKind {
+    /// Display name used in "… limit in 42m".
+    pub fn label(self) -> &'static str {
+        match self {
+            LimitKind::Tokens => "Token",
+            LimitKind::Cost => "Cost",
+            LimitKind::Messages => "Message",
+        }
+    }
+}
+
+/// The plan limit projected to run out first at the current rates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitPrediction {
+    /// Which limit binds first.
+    pub kind: LimitKind,
+    /// Minutes until the limit is reached; `0` when it already is.
+    pub minutes_left: f64,
+    /// Formatted wall-clock time the limit is reached.
+    pub at: String,
+}
+
 /// All data required to render the session view.
 pub struct SessionViewData {
     /// Plan name (e.g. `"pro"`, `"max5"`).
     pub plan: String,
     /// Human-readable timezone string.
     pub timezone: String,
-    /// Tokens consumed in the current session.
+    /// Tokens consumed in the current session, on the same basis as
+    /// `burn_rate`.
     pub tokens_used: u64,
     /// Token limit for the current plan.
     pub token_limit: u64,
@@ -33,7 +143,8 @@ pub struct SessionViewData {
     pub elapsed_minutes: f64,
     /// Total session window duration in minutes (e.g. 300 for 5 hours).
     pub total_minutes: f64,
-    /// Current token and cost burn rates, if calculable.
+    /// Current token and cost burn rates, if calculable, counting tokens on
+    /// the same basis as `tokens_used`.
     pub burn_rate: Option<BurnRate>,
     /// Per-model token usage as `(model_name, percentage)` pairs.
     pub per_model_stats: Vec<(String, f64)>,
@@ -45,116 +156,590 @@ pub struct SessionViewData {
     pub current_time: String,
     /// Formatted session reset time string.
     pub reset_time: String,
-    /// Optional predicted token exhaustion time string.
-    pub predicted_end: Option<String>,
+    /// Earliest predicted token, cost or message exhaustion, if any.
+    pub predicted_end: Option<LimitPrediction>,
     /// Whether the session is currently active.
     pub is_active: bool,
-    /// Notification strings to display at the bottom of the view.
-    pub notifications: Vec<String>,
+    /// Notifications shown above the status bar, most urgent first.
+    pub notifications: Vec<ViewNotification>,
     /// Cache creation tokens for the current session block.
     pub cache_creation_tokens: u64,
     /// Cache read tokens for the current session block.
     pub cache_read_tokens: u64,
+    /// Per-model cache usage, most expensive first; models without cache
+    /// tokens are omitted.
+    pub model_cache: Vec<ModelCacheUsage>,
+    /// Separators used when displaying token and message counts.
+    pub number_format: NumberFormatter,
+    /// Display labels for the model distribution row.
+    pub model_labels: ModelLabels,
+    /// Models named in the distribution row; the rest share an "Other"
+    /// segment.
+    pub max_models: usize,
+    /// All-time totals shown in the footer, if tracked.
+    pub lifetime: Option<LifetimeStats>,
+    /// Title text and decoration of the header line.
+    pub branding: HeaderBranding,
+    /// Cost (USD) of the sessions since the last daily reset, with the
+    /// formatted reset time; `None` hides the row.
+    pub cost_since_reset: Option<(f64, String)>,
+    /// A manual refresh is in progress; the status bar says so.
+    pub refreshing: bool,
+    /// Age in minutes of the newest entry when it is past the staleness
+    /// threshold; shows a warning banner under the header.
+    pub stale_minutes: Option<u64>,
+    /// Emoji or ASCII symbols.
+    pub glyphs: Glyphs,
+    /// Cost (USD) spent today and the `--daily-budget`; `None` hides the
+    /// row.
+    pub daily_budget: Option<(f64, f64)>,
+    /// Show the legend of the progress-bar colours under the status bar.
+    pub show_legend: bool,
+    /// Where the data comes from; `None` hides the info line.
+    pub data_source: Option<DataSourceInfo>,
+    /// The session is too young for burn rates and predictions, which show
+    /// "calculating…" instead.
+    pub warming_up: bool,
+    /// Cost (USD) the session is projected to reach by its reset.
+    pub projected_cost: Option<f64>,
+    /// Model families with their own token limit, each drawn as a bar under
+    /// the token usage.
+    pub model_limits: Vec<ModelLimitUsage>,
 }
 
-// ── Formatting helpers ────────────────────────────────────────────────────────
+/// How urgent a [`ViewNotification`] is; picks its style and marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    /// Something worth knowing, e.g. a usage threshold was crossed.
+    Info,
+    /// A limit is close.
+    Warning,
+    /// A limit was reached.
+    Error,
+}
+
+/// A message shown above the status bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewNotification {
+    /// How urgent the message is.
+    pub level: NotificationLevel,
+    /// The message text.
+    pub message: String,
+}
+
+impl ViewNotification {
+    /// An informational message.
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            level: NotificationLevel::Info,
+            message: message.into(),
+        }
+    }
+
+    /// A warning.
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            level: NotificationLevel::Warning,
+            message: message.into(),
+        }
+    }
+
+    /// An error, e.g. a limit that was hit.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            level: NotificationLevel::Error,
+            message: message.into(),
+        }
+    }
+}
+
+/// The data directory and how much was read from it, for the info line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataSourceInfo {
+    /// Directory the JSONL files are read from.
+    pub path: String,
+    /// Number of JSONL files found.
+    pub files: usize,
+    /// Number of usage entries loaded.
+    pub entries: usize,
+}
+
+impl SessionViewData {
+    /// Start a [`SessionViewDataBuilder`] from an empty, inactive session.
+    pub fn builder() -> SessionViewDataBuilder {
+        SessionViewDataBuilder::new()
+    }
+}
+
+// ── SessionViewDataBuilder ────────────────────────────────────────────────────
+
+/// Fluent builder for [`SessionViewData`].
+///
+/// Starts from an empty, inactive session on the default plan limits with a
+/// 5-hour window, so only the fields that matter need setting.
+pub struct SessionViewDataBuilder {
+    data: SessionViewData,
+}
+
+impl Default for SessionViewDataBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-/// Format a number with thousands separators (e.g. 1234567 → "1,234,567").
-fn format_with_commas(n: u64) -> String {
-    let s = n.to_string();
-    let mut result = String::with_capacity(s.len() + s.len() / 3);
-    for (i, ch) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
+impl SessionViewDataBuilder {
+    /// A builder for an empty, inactive `"pro"` session in UTC.
+    pub fn new() -> Self {
+        Self {
+            data: SessionViewData {
+                plan: "pro".to_string(),
+                timezone: "UTC".to_string(),
+                tokens_used: 0,
+                token_limit: DEFAULT_TOKEN_LIMIT,
+                cost_usd: 0.0,
+                cost_limit: DEFAULT_COST_LIMIT,
+                elapsed_minutes: 0.0,
+                total_minutes: 300.0,
+                burn_rate: None,
+                projected_cost: None,
+                per_model_stats: Vec::new(),
+                sent_messages: 0,
+                message_limit: DEFAULT_MESSAGE_LIMIT,
+                current_time: String::new(),
+                reset_time: String::new(),
+                predicted_end: None,
+                is_active: false,
+                notifications: Vec::new(),
+                cache_creation_tokens: 0,
+                cache_read_tokens: 0,
+                model_cache: Vec::new(),
+                number_format: NumberFormatter::default(),
+                model_labels: ModelLabels::default(),
+                max_models: DEFAULT_MAX_MODELS,
+                lifetime: None,
+                branding: HeaderBranding::default(),
+                cost_since_reset: None,
+                refreshing: false,
+                stale_minutes: None,
+                glyphs: Glyphs::default(),
+                daily_budget: None,
+                show_legend: false,
+                data_source: None,
+                warming_up: false,
+                model_limits: Vec::new(),
+            },
         }
-        result.push(ch);
     }
-    result.chars().rev().collect()
+
+    /// Plan name and timezone shown in the header.
+    pub fn plan(mut self, plan: impl Into<String>, timezone: impl Into<String>) -> Self {
+        self.data.plan = plan.into();
+        self.data.timezone = timezone.into();
+        self
+    }
+
+    /// Tokens used and the token limit.
+    pub fn tokens(mut self, used: u64, limit: u64) -> Self {
+        self.data.tokens_used = used;
+        self.data.token_limit = limit;
+        self
+    }
+
+    /// Cost in USD and the cost limit.
+    pub fn cost(mut self, usd: f64, limit: f64) -> Self {
+        self.data.cost_usd = usd;
+        self.data.cost_limit = limit;
+        self
+    }
+
+    /// Messages sent and the message limit.
+    pub fn messages(mut self, sent: u32, limit: u32) -> Self {
+        self.data.sent_messages = sent;
+        self.data.message_limit = limit;
+        self
+    }
+
+    /// Minutes elapsed in the session window and the window's length.
+    pub fn window(mut self, elapsed_minutes: f64, total_minutes: f64) -> Self {
+        self.data.elapsed_minutes = elapsed_minutes;
+        self.data.total_minutes = total_minutes;
+        self
+    }
+
+    /// Formatted current time and reset time.
+    pub fn times(mut self, current: impl Into<String>, reset: impl Into<String>) -> Self {
+        self.data.current_time = current.into();
+        self.data.reset_time = reset.into();
+        self
+    }
+
+    /// Current burn rate.
+    pub fn burn_rate(mut self, burn_rate: BurnRate) -> Self {
+        self.data.burn_rate = Some(burn_rate);
+        self
+    }
+
+    /// Cost projected for the end of the session.
+    pub fn projected_cost(mut self, cost: f64) -> Self {
+        self.data.projected_cost = Some(cost);
+        self
+    }
+
+    /// Per-model `(model_name, percentage)` token shares.
+    pub fn per_model_stats(mut self, stats: Vec<(String, f64)>) -> Self {
+        self.data.per_model_stats = stats;
+        self
+    }
+
+    /// Cache creation and read tokens.
+    pub fn cache(mut self, creation_tokens: u64, read_tokens: u64) -> Self {
+        self.data.cache_creation_tokens = creation_tokens;
+        self.data.cache_read_tokens = read_tokens;
+        self
+    }
+
+    /// Per-model cache rows, most expensive first.
+    pub fn model_cache(mut self, model_cache: Vec<ModelCacheUsage>) -> Self {
+        self.data.model_cache = model_cache;
+        self
+    }
+
+    /// Predicted limit exhaustion.
+    pub fn predicted_end(mut self, prediction: LimitPrediction) -> Self {
+        self.data.predicted_end = Some(prediction);
+        self
+    }
+
+    /// Mark the session as active.
+    pub fn active(mut self, is_active: bool) -> Self {
+        self.data.is_active = is_active;
+        self
+    }
+
+    /// Notifications shown above the status bar.
+    pub fn notifications(mut self, notifications: Vec<ViewNotification>) -> Self {
+        self.data.notifications = notifications;
+        self
+    }
+
+    /// Separators for token and message counts.
+    pub fn number_format(mut self, number_format: NumberFormatter) -> Self {
+        self.data.number_format = number_format;
+        self
+    }
+
+    /// Display labels for the model distribution row.
+    pub fn model_labels(mut self, model_labels: ModelLabels) -> Self {
+        self.data.model_labels = model_labels;
+        self
+    }
+
+    /// Models named in the distribution row before "Other".
+    pub fn max_models(mut self, max_models: usize) -> Self {
+        self.data.max_models = max_models;
+        self
+    }
+
+    /// All-time totals for the footer.
+    pub fn lifetime(mut self, lifetime: LifetimeStats) -> Self {
+        self.data.lifetime = Some(lifetime);
+        self
+    }
+
+    /// Header title and decoration.
+    pub fn branding(mut self, branding: HeaderBranding) -> Self {
+        self.data.branding = branding;
+        self
+    }
+
+    /// Cost since the last daily reset, with the formatted reset time.
+    pub fn cost_since_reset(mut self, cost: f64, since: impl Into<String>) -> Self {
+        self.data.cost_since_reset = Some((cost, since.into()));
+        self
+    }
+
+    /// Show "Refreshing…" in the status bar.
+    pub fn refreshing(mut self, refreshing: bool) -> Self {
+        self.data.refreshing = refreshing;
+        self
+    }
+
+    /// Show the stale-data banner for data this many minutes old.
+    pub fn stale_minutes(mut self, minutes: u64) -> Self {
+        self.data.stale_minutes = Some(minutes);
+        self
+    }
+
+    /// Cost spent today and the daily budget, both in USD.
+    pub fn daily_budget(mut self, spent: f64, budget: f64) -> Self {
+        self.data.daily_budget = Some((spent, budget));
+        self
+    }
+
+    /// Show the progress-bar colour legend under the status bar.
+    pub fn show_legend(mut self, show: bool) -> Self {
+        self.data.show_legend = show;
+        self
+    }
+
+    /// Add a bar for `family` using `used` of its own token `limit`.
+    pub fn model_limit(mut self, family: impl Into<String>, used: u64, limit: u64) -> Self {
+        self.data.model_limits.push(ModelLimitUsage {
+            family: family.into(),
+            used,
+            limit,
+        });
+        self
+    }
+
+    /// Show "calculating…" instead of burn rates and predictions.
+    pub fn warming_up(mut self, warming_up: bool) -> Self {
+        self.data.warming_up = warming_up;
+        self
+    }
+
+    /// Show the data directory and the file and entry counts in the footer.
+    pub fn data_source(mut self, path: impl Into<String>, files: usize, entries: usize) -> Self {
+        self.data.data_source = Some(DataSourceInfo {
+            path: path.into(),
+            files,
+            entries,
+        });
+        self
+    }
+
+    /// Emoji or ASCII symbols.
+    pub fn glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.data.glyphs = glyphs;
+        self
+    }
+
+    /// Finish building.
+    pub fn build(self) -> SessionViewData {
+        self.data
+    }
+}
+
+// ── Formatting helpers ────────────────────────────────────────────────────────
+
+/// Share of cache tokens that were reads rather than writes, as a
+/// percentage; `None` without cache tokens.
+fn cache_efficiency(read: u64, creation: u64) -> Option<f64> {
+    let total = read + creation;
+    (total > 0).then(|| read as f64 / total as f64 * 100.0)
+}
+
+/// Indicator for a cache read share; unlike [`pct_indicator`], higher is
+/// better.
+///
+/// * `≥ 80 %`  → 🟢 (`-` in ASCII)
+/// * `50–80 %` → 🟡 (`+`)
+/// * `< 50 %`  → 🔴 (`*`)
+fn cache_efficiency_indicator(pct: f64, glyphs: Glyphs) -> &'static str {
+    if pct >= 80.0 {
+        glyphs.pick("🟢", "-")
+    } else if pct >= 50.0 {
+        glyphs.pick("🟡", "+")
+    } else {
+        glyphs.pick("🔴", "*")
+    }
 }
 
 /// Return the colour-indicator emoji for a given percentage.
 ///
-/// * `< 50 %`  → 🟢
-/// * `50–80 %` → 🟡
-/// * `≥ 80 %`  → 🔴
-fn pct_indicator(pct: f64) -> &'static str {
+/// * `< 50 %`  → 🟢 (`-` in ASCII)
+/// * `50–80 %` → 🟡 (`+`)
+/// * `≥ 80 %`  → 🔴 (`*`)
+///
+/// Non-finite percentages count as 0 %.
+fn pct_indicator(pct: f64, glyphs: Glyphs) -> &'static str {
+    let pct = finite_or_zero(pct);
     if pct >= 80.0 {
-        "🔴"
+        glyphs.pick("🔴", "*")
     } else if pct >= 50.0 {
-        "🟡"
+        glyphs.pick("🟡", "+")
     } else {
-        "🟢"
+        glyphs.pick("🟢", "-")
     }
 }
 
 /// Build a 50-character bar string, capping fill at 100 %.
 ///
 /// Returns a tuple `(filled_str, empty_str)` each ready for display.
-fn build_bar(pct: f64, width: usize) -> (String, String) {
-    let capped = pct.clamp(0.0, 100.0);
+/// Non-finite percentages render as an empty bar.
+fn build_bar(pct: f64, width: usize, glyphs: Glyphs) -> (String, String) {
+    let capped = finite_or_zero(pct).clamp(0.0, 100.0);
     let filled = ((capped / 100.0) * width as f64).round() as usize;
     let empty = width.saturating_sub(filled);
-    ("█".repeat(filled), "░".repeat(empty))
-}
-
-/// Return the short display name for a model.
-fn short_model_name(model: &str) -> &'static str {
-    let lower = model.to_lowercase();
-    if lower.contains("opus") {
-        "Opus"
-    } else if lower.contains("sonnet") {
-        "Sonnet"
-    } else if lower.contains("haiku") {
-        "Haiku"
+    let (filled_cell, empty_cell) = glyphs.bar();
+    (filled_cell.repeat(filled), empty_cell.repeat(empty))
+}
+
+/// Replace `NaN` and infinities with `0.0`.
+fn finite_or_zero(value: f64) -> f64 {
+    if value.is_finite() {
+        value
     } else {
-        "Other"
+        0.0
+    }
+}
+
+/// Return the short display name for a model (user labels first).
+fn short_model_name(model: &str, labels: &ModelLabels) -> String {
+    labels.label(model)
+}
+
+/// Time left in the session window as `"<h>h <m>m"`.
+fn time_remaining(data: &SessionViewData) -> String {
+    let remaining_mins = (data.total_minutes - data.elapsed_minutes).max(0.0);
+    let hours = (remaining_mins / 60.0) as u64;
+    let mins = (remaining_mins % 60.0) as u64;
+    format!("{}h {}m", hours, mins)
+}
+
+/// Placeholder for burn rates and predictions during the warm-up.
+fn calculating(g: Glyphs) -> &'static str {
+    g.pick("calculating…", "calculating...")
+}
+
+/// "Cost limit in 42m (14:30)", "Token limit exceeded" or "N/A".
+fn prediction_text(prediction: Option<&LimitPrediction>) -> String {
+    match prediction {
+        None => "N/A".to_string(),
+        Some(p) if p.minutes_left <= 0.0 => format!("{} limit exceeded", p.kind.label()),
+        Some(p) => format!(
+            "{} limit in {} ({})",
+            p.kind.label(),
+            format_time(p.minutes_left),
+            p.at
+        ),
     }
 }
 
+/// Style for the reset countdown, by how much of the window is left.
+fn reset_style(data: &SessionViewData, theme: &Theme) -> ratatui::style::Style {
+    let remaining_pct = if data.total_minutes > 0.0 {
+        (data.total_minutes - data.elapsed_minutes).max(0.0) / data.total_minutes * 100.0
+    } else {
+        100.0
+    };
+    theme.reset_style(finite_or_zero(remaining_pct))
+}
+
 /// Return the burn-rate tier emoji for a given tokens/min rate.
-fn burn_emoji(tokens_per_minute: f64) -> &'static str {
+fn burn_emoji(tokens_per_minute: f64, glyphs: Glyphs) -> &'static str {
     if tokens_per_minute >= 1000.0 {
-        "⚡"
+        glyphs.pick("⚡", "*")
     } else if tokens_per_minute >= 500.0 {
-        "🚀"
+        glyphs.pick("🚀", "+")
     } else if tokens_per_minute >= 100.0 {
-        "➡️"
+        glyphs.pick("➡️", ">")
     } else {
-        "🐌"
+        glyphs.pick("🐌", "-")
     }
 }
 
 /// Return the theme style for a model's bar segment.
-fn model_bar_style(model: &str, theme: &Theme) -> ratatui::style::Style {
-    let lower = model.to_lowercase();
-    if lower.contains("opus") {
-        theme.model_opus
-    } else if lower.contains("sonnet") {
-        theme.model_sonnet
-    } else if lower.contains("haiku") {
-        theme.model_haiku
-    } else {
-        theme.model_unknown
+///
+/// Colors follow the display label, so a `--model-label` rule that renames a
+/// model also decides which family color it gets.
+fn model_bar_style(model: &str, labels: &ModelLabels, theme: &Theme) -> ratatui::style::Style {
+    theme.model_style(&labels.label(model))
+}
+
+/// Distribution row entries as `(label, percentage, style)`.
+///
+/// Models are ordered largest first (ties by name). Past `data.max_models`
+/// the remaining models collapse into one "Other" entry holding the sum of
+/// their percentages.
+fn model_segments(
+    data: &SessionViewData,
+    theme: &Theme,
+) -> Vec<(String, f64, ratatui::style::Style)> {
+    let mut active_models: Vec<&(String, f64)> = data
+        .per_model_stats
+        .iter()
+        .filter(|(_, pct)| *pct > 0.0)
+        .collect();
+    active_models.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let shown = data.max_models.max(1);
+    let mut segments: Vec<(String, f64, ratatui::style::Style)> = active_models
+        .iter()
+        .take(shown)
+        .map(|(model, pct)| {
+            (
+                short_model_name(model, &data.model_labels),
+                *pct,
+                model_bar_style(model, &data.model_labels, theme),
+            )
+        })
+        .collect();
+    if active_models.len() > shown {
+        let other: f64 = active_models[shown..].iter().map(|(_, pct)| pct).sum();
+        segments.push(("Other".to_string(), other, theme.model_unknown));
     }
+    segments
 }
 
 // ── Row builders ──────────────────────────────────────────────────────────────
 
-/// Pad an emoji + label to 25 display columns.
+/// Title line: the decoration either side of the title.
+///
+/// An empty decoration leaves just the title, without padding spaces.
+fn title_line<'a>(branding: &HeaderBranding, theme: &'a Theme) -> Line<'a> {
+    if branding.decoration.is_empty() {
+        return Line::from(Span::styled(branding.title.clone(), theme.header));
+    }
+    let mut spans = vec![Span::styled(
+        branding.decoration.clone(),
+        theme.header_sparkle,
+    )];
+    if !branding.title.is_empty() {
+        spans.push(Span::styled(format!(" {} ", branding.title), theme.header));
+        spans.push(Span::styled(
+            branding.decoration.clone(),
+            theme.header_sparkle,
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Pad an emoji + label to [`LABEL_WIDTH`] display columns.
 ///
-/// Most emoji occupy 2 display columns; the space after them is 1 column; the
-/// label text is purely ASCII-width.  The function appends trailing spaces so
-/// the total reaches 25 columns.
+/// Widths come from `unicode-width`, so a label keeps its alignment whether
+/// its emoji is one or two columns wide. Labels that are already too long
+/// get a single trailing space.
 fn pad_label(emoji: &str, label: &str) -> String {
-    // Emoji takes ~2 display columns, space = 1, then label text.
-    let emoji_width: usize = 2;
-    let content_width = emoji_width + 1 + label.len();
-    let padding = if content_width < 25 {
-        25 - content_width
-    } else {
-        1
-    };
-    format!("{} {}{}", emoji, label, " ".repeat(padding))
+    let text = format!("{emoji} {label}");
+    let padding = LABEL_WIDTH.saturating_sub(text.width()).max(1);
+    text + &" ".repeat(padding)
+}
+
+/// Tokens per minute that would use exactly the remaining token budget by
+/// the end of the window.
+///
+/// The remaining budget is counted from `tokens_used`, so the result compares
+/// directly with `burn_rate`, which is on the same basis.
+///
+/// `None` without a token limit or once the window is over; `0.0` when the
+/// budget is already spent.
+fn safe_burn_rate(data: &SessionViewData) -> Option<f64> {
+    let remaining_minutes = data.total_minutes - data.elapsed_minutes;
+    if data.token_limit == 0 || remaining_minutes.is_nan() || remaining_minutes <= 0.0 {
+        return None;
+    }
+    let remaining_tokens = data.token_limit.saturating_sub(data.tokens_used);
+    Some(remaining_tokens as f64 / remaining_minutes)
+}
+
+/// Separator width for `area`: its full width, or
+/// [`DEFAULT_SEPARATOR_WIDTH`] when it has none.
+fn separator_width(area: Rect) -> usize {
+    match area.width {
+        0 => DEFAULT_SEPARATOR_WIDTH,
+        width => width as usize,
+    }
 }
 
 /// Build a progress row with styled components matching the Python output:
@@ -168,12 +753,37 @@ fn progress_row<'a>(
     percentage: f64,
     current_str: String,
     limit_str: String,
+    glyphs: Glyphs,
     theme: &'a Theme,
 ) -> Line<'a> {
-    let padded = pad_label(emoji, label);
-    let indicator = pct_indicator(percentage);
-    let (filled, empty) = build_bar(percentage, 50);
     let bar_style = theme.progress_style(percentage.min(100.0));
+    styled_progress_row(
+        emoji,
+        label,
+        percentage,
+        current_str,
+        limit_str,
+        bar_style,
+        glyphs,
+        theme,
+    )
+}
+
+/// [`progress_row`] with the filled part of the bar drawn in `bar_style`.
+#[allow(clippy::too_many_arguments)]
+fn styled_progress_row<'a>(
+    emoji: &str,
+    label: &str,
+    percentage: f64,
+    current_str: String,
+    limit_str: String,
+    bar_style: ratatui::style::Style,
+    glyphs: Glyphs,
+    theme: &'a Theme,
+) -> Line<'a> {
+    let padded = pad_label(emoji, label);
+    let indicator = pct_indicator(percentage, glyphs);
+    let (filled, empty) = build_bar(percentage, 50, glyphs);
     let pct_style = theme.cost_style(percentage);
 
     Line::from(vec![
@@ -191,32 +801,88 @@ fn progress_row<'a>(
     ])
 }
 
+/// One indented per-model row under "Cache Tokens", aligned with its values.
+fn model_cache_row<'a>(
+    usage: &ModelCacheUsage,
+    data: &SessionViewData,
+    theme: &'a Theme,
+) -> Line<'a> {
+    let label = format!("   {}", short_model_name(&usage.model, &data.model_labels));
+    let padding = LABEL_WIDTH.saturating_sub(label.width());
+    let numbers = &data.number_format;
+    Line::from(vec![
+        Span::styled(
+            label + &" ".repeat(padding),
+            model_bar_style(&usage.model, &data.model_labels, theme),
+        ),
+        Span::styled("Creation: ", theme.dim),
+        Span::styled(numbers.format_count(usage.creation_tokens), theme.value),
+        Span::styled(
+            format!(" ({})", numbers.format_currency(usage.creation_cost)),
+            theme.dim,
+        ),
+        Span::styled("  Read: ", theme.dim),
+        Span::styled(numbers.format_count(usage.read_tokens), theme.value),
+        Span::styled(
+            format!(" ({})", numbers.format_currency(usage.read_cost)),
+            theme.dim,
+        ),
+    ])
+}
+
 // ── Main render ───────────────────────────────────────────────────────────────
 
+/// Which session-view builder the realtime screen uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionLayout {
+    /// The full multi-section screen from [`build_session_lines`].
+    #[default]
+    Full,
+    /// A few short lines from [`build_compact_lines`] for narrow panes.
+    Compact,
+}
+
+impl SessionLayout {
+    /// Parse a `--layout` value; anything but `"compact"` is [`Full`](Self::Full).
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "compact" => Self::Compact,
+            _ => Self::Full,
+        }
+    }
+}
+
 /// Render the real-time session view into `area`.
 ///
 /// Everything is drawn as a single [`Paragraph`] whose lines are built to
 /// exactly match the Python reference output.
 pub fn render_session_view(frame: &mut Frame, area: Rect, data: &SessionViewData, theme: &Theme) {
-    let lines = build_session_lines(data, theme);
+    let lines = build_session_lines_with_width(data, theme, separator_width(area));
     let paragraph = Paragraph::new(Text::from(lines));
     frame.render_widget(paragraph, area);
 }
 
-/// Build the full `Vec<Line>` for the session view (extracted for testability).
+/// Build the full `Vec<Line>` for the session view (extracted for testability),
+/// with [`DEFAULT_SEPARATOR_WIDTH`]-column separators.
 pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<Line<'a>> {
+    build_session_lines_with_width(data, theme, DEFAULT_SEPARATOR_WIDTH)
+}
+
+/// [`build_session_lines`] with separators `width` columns wide.
+pub fn build_session_lines_with_width<'a>(
+    data: &SessionViewData,
+    theme: &'a Theme,
+    width: usize,
+) -> Vec<Line<'a>> {
+    let g = data.glyphs;
     // Pre-allocate with enough capacity for all rows.
     let mut lines: Vec<Line<'a>> = Vec::with_capacity(32);
 
     // ── Header ────────────────────────────────────────────────────────────────
     // Line 1: title
-    lines.push(Line::from(vec![
-        Span::styled("✦ ✧ ✦ ✧", theme.header_sparkle),
-        Span::styled(" CLAUDE CODE USAGE MONITOR ", theme.header),
-        Span::styled("✦ ✧ ✦ ✧", theme.header_sparkle),
-    ]));
+    lines.push(title_line(&data.branding, theme));
     // Line 2: separator
-    lines.push(Line::from(Span::styled("=".repeat(78), theme.separator)));
+    lines.push(Line::from(Span::styled("=".repeat(width), theme.separator)));
     // Line 3: plan | timezone
     lines.push(Line::from(vec![
         Span::styled("[ ", theme.label),
@@ -225,8 +891,12 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
         Span::styled(data.timezone.to_lowercase(), theme.value),
         Span::styled(" ]", theme.label),
     ]));
-    // Lines 4-6: three empty lines (Python output has blank lines here)
-    lines.push(Line::from(""));
+    // Lines 4-6: three empty lines (Python output has blank lines here); the
+    // first carries the staleness warning, if any.
+    match data.stale_minutes {
+        Some(minutes) => lines.push(stale_banner(minutes, theme, g)),
+        None => lines.push(Line::from("")),
+    }
     lines.push(Line::from(""));
     lines.push(Line::from(""));
 
@@ -237,13 +907,37 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
         0.0
     };
     lines.push(progress_row(
-        "💰",
+        g.pick("💰", "[$]"),
         "Cost Usage:",
         cost_pct,
-        format!("${:.2}", data.cost_usd),
-        format!("${:.2}", data.cost_limit),
+        data.number_format.format_currency(data.cost_usd),
+        data.number_format.format_currency(data.cost_limit),
+        g,
         theme,
     ));
+    if let Some((cost, ref reset)) = data.cost_since_reset {
+        lines.push(Line::from(vec![
+            Span::styled(pad_label(g.pick("🔁", "[~]"), "Since Reset:"), theme.label),
+            Span::styled(data.number_format.format_currency(cost), theme.value),
+            Span::styled(format!(" since {reset}"), theme.dim),
+        ]));
+    }
+    if let Some((spent, budget)) = data.daily_budget {
+        let budget_pct = if budget > 0.0 {
+            spent / budget * 100.0
+        } else {
+            0.0
+        };
+        lines.push(progress_row(
+            g.pick("📅", "[d]"),
+            "Daily Budget:",
+            budget_pct,
+            data.number_format.format_currency(spent),
+            data.number_format.format_currency(budget),
+            g,
+            theme,
+        ));
+    }
     lines.push(Line::from(""));
 
     // ── Messages Usage ────────────────────────────────────────────────────────
@@ -253,11 +947,13 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
         0.0
     };
     lines.push(progress_row(
-        "📨",
+        g.pick("📨", "[@]"),
         "Messages Usage:",
         msg_pct,
-        format_with_commas(data.sent_messages as u64),
-        format_with_commas(data.message_limit as u64),
+        data.number_format.format_count(data.sent_messages as u64),
+        data.number_format
+            .format_count(u64::from(data.message_limit)),
+        g,
         theme,
     ));
     lines.push(Line::from(""));
@@ -269,11 +965,24 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
     } else {
         0.0
     };
-    let padded_token = pad_label("📊", "Token Usage:");
-    let token_indicator = pct_indicator(token_pct);
-    let (filled_tok, empty_tok) = build_bar(token_pct, 50);
+    let padded_token = pad_label(g.pick("📊", "[#]"), "Token Usage:");
+    let token_indicator = pct_indicator(token_pct, g);
+    let (filled_tok, empty_tok) = build_bar(token_pct, 50, g);
     let bar_style_tok = theme.progress_style(token_pct.min(100.0));
-    let token_pct_style = theme.cost_style(token_pct);
+    // Past the limit the percentage is emphasised and names the overage.
+    let (token_pct_text, token_pct_style) = if token_pct > 100.0 {
+        let overage = data.tokens_used.saturating_sub(data.token_limit);
+        (
+            format!(
+                "{:>5.1}% (+{})",
+                token_pct,
+                data.number_format.format_count(overage)
+            ),
+            theme.over_limit,
+        )
+    } else {
+        (format!("{:>5.1}%", token_pct), theme.cost_style(token_pct))
+    };
     lines.push(Line::from(vec![
         Span::styled(padded_token, theme.label),
         Span::raw(token_indicator),
@@ -281,26 +990,74 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
         Span::styled(filled_tok, bar_style_tok),
         Span::styled(empty_tok, theme.progress_empty),
         Span::styled("] ", theme.dim),
-        Span::styled(format!("{:>5.1}%", token_pct), token_pct_style),
+        Span::styled(token_pct_text, token_pct_style),
         Span::raw("    "),
-        Span::styled(format_with_commas(data.tokens_used), theme.value),
+        Span::styled(
+            data.number_format.format_count(data.tokens_used),
+            theme.value,
+        ),
         Span::styled(" / ", theme.dim),
-        Span::styled(format_with_commas(data.token_limit), theme.dim),
+        Span::styled(
+            data.number_format.format_tokens(data.token_limit),
+            theme.dim,
+        ),
     ]));
+
+    // ── Per-model limits ──────────────────────────────────────────────────────
+    for usage in &data.model_limits {
+        let pct = if usage.limit > 0 {
+            usage.used as f64 / usage.limit as f64 * 100.0
+        } else {
+            0.0
+        };
+        lines.push(styled_progress_row(
+            g.pick("🤖", "[m]"),
+            &format!("{} Usage:", usage.family),
+            pct,
+            data.number_format.format_count(usage.used),
+            data.number_format.format_tokens(usage.limit),
+            model_bar_style(&usage.family, &data.model_labels, theme),
+            g,
+            theme,
+        ));
+    }
     lines.push(Line::from(""));
 
     // ── Cache Tokens ──────────────────────────────────────────────────────────
     lines.push(Line::from(vec![
-        Span::styled(pad_label("💾", "Cache Tokens:"), theme.label),
+        Span::styled(pad_label(g.pick("💾", "[=]"), "Cache Tokens:"), theme.label),
         Span::styled("Creation: ", theme.dim),
-        Span::styled(format_with_commas(data.cache_creation_tokens), theme.value),
+        Span::styled(
+            data.number_format.format_count(data.cache_creation_tokens),
+            theme.value,
+        ),
         Span::styled("  Read: ", theme.dim),
-        Span::styled(format_with_commas(data.cache_read_tokens), theme.value),
+        Span::styled(
+            data.number_format.format_count(data.cache_read_tokens),
+            theme.value,
+        ),
     ]));
+    for usage in &data.model_cache {
+        lines.push(model_cache_row(usage, data, theme));
+    }
+    if let Some(pct) = cache_efficiency(data.cache_read_tokens, data.cache_creation_tokens) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                pad_label(g.pick("♻️", "[%]"), "Cache Efficiency:"),
+                theme.label,
+            ),
+            Span::raw(format!("{} ", cache_efficiency_indicator(pct, g))),
+            Span::styled(format!("{pct:.1}%"), theme.value),
+            Span::styled(" of cache tokens were reads", theme.dim),
+        ]));
+    }
     lines.push(Line::from(""));
 
     // ── Thin separator ────────────────────────────────────────────────────────
-    lines.push(Line::from(Span::styled("─".repeat(78), theme.separator)));
+    lines.push(Line::from(Span::styled(
+        g.thin_separator().repeat(width),
+        theme.separator,
+    )));
 
     // ── Time to Reset ─────────────────────────────────────────────────────────
     let time_pct = if data.total_minutes > 0.0 {
@@ -308,14 +1065,11 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
     } else {
         0.0
     };
-    let remaining_mins = (data.total_minutes - data.elapsed_minutes).max(0.0);
-    let hours = (remaining_mins / 60.0) as u64;
-    let mins = (remaining_mins % 60.0) as u64;
-    let time_suffix = format!("{}h {}m", hours, mins);
+    let time_suffix = time_remaining(data);
 
-    let padded_time = pad_label("⏱️", "Time to Reset:");
-    let time_indicator = pct_indicator(time_pct);
-    let (filled_time, empty_time) = build_bar(time_pct, 50);
+    let padded_time = pad_label(g.pick("⏱️", "[t]"), "Time to Reset:");
+    let time_indicator = pct_indicator(time_pct, g);
+    let (filled_time, empty_time) = build_bar(time_pct, 50, g);
     let bar_style_time = theme.progress_style(time_pct);
     lines.push(Line::from(vec![
         Span::styled(padded_time, theme.label),
@@ -324,84 +1078,81 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
         Span::styled(filled_time, bar_style_time),
         Span::styled(empty_time, theme.progress_empty),
         Span::styled("] ", theme.dim),
-        Span::styled(time_suffix, theme.value),
+        Span::styled(time_suffix, reset_style(data, theme)),
     ]));
     lines.push(Line::from(""));
 
     // ── Model Distribution ────────────────────────────────────────────────────
-    let padded_model = pad_label("🤖", "Model Distribution:");
+    let padded_model = pad_label(g.pick("🤖", "[m]"), "Model Distribution:");
+
+    // Bar segments and legend both follow this order so colours line up
+    // left to right.
+    let segments = model_segments(data, theme);
 
     // Build proportionally coloured bar segments per model.
     let bar_width: usize = 50;
     let mut model_spans: Vec<Span<'a>> = Vec::new();
     let mut total_filled: usize = 0;
-    let active_models: Vec<&(String, f64)> = data
-        .per_model_stats
-        .iter()
-        .filter(|(_, pct)| *pct > 0.0)
-        .collect();
 
-    for (i, (model, pct)) in active_models.iter().enumerate() {
+    for (i, (_, pct, style)) in segments.iter().enumerate() {
         let chars = ((*pct / 100.0) * bar_width as f64).floor() as usize;
         // Last model gets the remaining chars to fill exactly bar_width.
-        let chars = if i == active_models.len() - 1 {
+        let chars = if i == segments.len() - 1 {
             bar_width.saturating_sub(total_filled)
         } else {
             chars.min(bar_width.saturating_sub(total_filled))
         };
         if chars > 0 {
-            let segment = "█".repeat(chars);
-            let style = model_bar_style(model, theme);
-            model_spans.push(Span::styled(segment, style));
+            let segment = g.bar().0.repeat(chars);
+            model_spans.push(Span::styled(segment, *style));
             total_filled += chars;
         }
     }
     // If no models, fill with empty.
     if total_filled < bar_width {
         model_spans.push(Span::styled(
-            "░".repeat(bar_width - total_filled),
+            g.bar().1.repeat(bar_width - total_filled),
             theme.progress_empty,
         ));
     }
 
     let mut row_spans: Vec<Span<'a>> = Vec::with_capacity(6 + model_spans.len());
     row_spans.push(Span::styled(padded_model, theme.label));
-    row_spans.push(Span::raw("🤖"));
+    row_spans.push(Span::raw(g.pick("🤖", "[m]")));
     row_spans.push(Span::styled(" [", theme.dim));
     row_spans.extend(model_spans);
     row_spans.push(Span::styled("] ", theme.dim));
 
     // Build model summary with per-model colors and dimmed separators.
-    let visible_models: Vec<&(String, f64)> = data
-        .per_model_stats
-        .iter()
-        .filter(|(_, pct)| *pct > 0.0)
-        .collect();
-    if visible_models.is_empty() {
+    if segments.is_empty() {
         row_spans.push(Span::styled("No data", theme.dim));
     } else {
-        for (i, (model, pct)) in visible_models.iter().enumerate() {
+        for (i, (label, pct, style)) in segments.iter().enumerate() {
             if i > 0 {
                 row_spans.push(Span::styled(" | ", theme.dim));
             }
-            let style = model_bar_style(model, theme);
-            row_spans.push(Span::styled(
-                format!("{} {:.1}%", short_model_name(model), pct),
-                style,
-            ));
+            row_spans.push(Span::styled(format!("{} {:.1}%", label, pct), *style));
         }
     }
     lines.push(Line::from(row_spans));
 
     // ── Second thin separator ─────────────────────────────────────────────────
-    lines.push(Line::from(Span::styled("─".repeat(78), theme.separator)));
+    lines.push(Line::from(Span::styled(
+        g.thin_separator().repeat(width),
+        theme.separator,
+    )));
 
     // ── Burn Rate ─────────────────────────────────────────────────────────────
-    if let Some(ref br) = data.burn_rate {
-        let emoji = burn_emoji(br.tokens_per_minute);
+    // A non-finite rate (e.g. from a zero-length window) is shown as "--".
+    let burn_rate = data
+        .burn_rate
+        .as_ref()
+        .filter(|br| br.tokens_per_minute.is_finite());
+    if let Some(br) = burn_rate {
+        let emoji = burn_emoji(br.tokens_per_minute, g);
         let velocity_style = theme.velocity_style(br.tokens_per_minute);
         lines.push(Line::from(vec![
-            Span::styled(pad_label("🔥", "Burn Rate:"), theme.label),
+            Span::styled(pad_label(g.pick("🔥", "[>]"), "Burn Rate:"), theme.label),
             Span::styled(
                 format!("{:.1} tokens/min", br.tokens_per_minute),
                 velocity_style,
@@ -416,64 +1167,404 @@ pub fn build_session_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<
         } else {
             0.0
         };
-        lines.push(Line::from(vec![
-            Span::styled(pad_label("💲", "Cost Rate:"), theme.label),
-            Span::styled(format!("${:.4} $/min", cost_per_min), theme.value),
-        ]));
+        let cost_rate = if cost_per_min.is_finite() {
+            Span::styled(
+                format!(
+                    "{} $/min",
+                    data.number_format.format_cost_rate(cost_per_min)
+                ),
+                theme.value,
+            )
+        } else {
+            Span::styled("--", theme.dim)
+        };
+        let mut spans = vec![
+            Span::styled(pad_label(g.pick("💲", "[$]"), "Cost Rate:"), theme.label),
+            cost_rate,
+        ];
+        if let Some(projected) = data.projected_cost {
+            spans.push(Span::styled(
+                format!(
+                    " ({} {} by reset)",
+                    g.pick("→", "->"),
+                    data.number_format.format_currency(projected)
+                ),
+                theme.dim,
+            ));
+        }
+        lines.push(Line::from(spans));
     } else {
+        let placeholder = if data.warming_up {
+            calculating(g)
+        } else {
+            "--"
+        };
         lines.push(Line::from(vec![
-            Span::styled(pad_label("🔥", "Burn Rate:"), theme.label),
-            Span::styled("--", theme.dim),
+            Span::styled(pad_label(g.pick("🔥", "[>]"), "Burn Rate:"), theme.label),
+            Span::styled(placeholder, theme.dim),
         ]));
         lines.push(Line::from(vec![
-            Span::styled(pad_label("💲", "Cost Rate:"), theme.label),
+            Span::styled(pad_label(g.pick("💲", "[$]"), "Cost Rate:"), theme.label),
             Span::styled("--", theme.dim),
         ]));
     }
-    lines.push(Line::from(""));
 
-    // ── Predictions ───────────────────────────────────────────────────────────
-    lines.push(Line::from(Span::styled("🔮 Predictions:", theme.info)));
-    let predicted_end_str = data.predicted_end.as_deref().unwrap_or("N/A").to_string();
-    lines.push(Line::from(vec![
-        Span::styled("  Tokens will run out:  ", theme.dim),
-        Span::styled(predicted_end_str, theme.warning),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("  Limit resets at:      ", theme.dim),
-        Span::styled(data.reset_time.clone(), theme.value),
-    ]));
+    // ── Safe rate ─────────────────────────────────────────────────────────────
+    if let Some(safe) = safe_burn_rate(data) {
+        let mut spans = vec![
+            Span::styled(pad_label(g.pick("🎯", "[o]"), "Safe rate:"), theme.label),
+            Span::styled(
+                format!(
+                    "{} tok/min",
+                    data.number_format.format_count(safe.round() as u64)
+                ),
+                theme.value,
+            ),
+        ];
+        if let Some(br) = burn_rate {
+            let style = if br.tokens_per_minute > safe {
+                theme.error
+            } else {
+                theme.success
+            };
+            spans.push(Span::styled(
+                format!(
+                    " (you: {})",
+                    data.number_format
+                        .format_count(br.tokens_per_minute.round() as u64)
+                ),
+                style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // ── Tokens per message ────────────────────────────────────────────────────
+    if data.sent_messages > 0 {
+        let per_message = data.tokens_used / u64::from(data.sent_messages);
+        lines.push(Line::from(vec![
+            Span::styled(
+                pad_label(g.pick("📏", "[/]"), "Avg tokens/msg:"),
+                theme.label,
+            ),
+            Span::styled(data.number_format.format_count(per_message), theme.value),
+        ]));
+    }
     lines.push(Line::from(""));
 
+    // ── Predictions ───────────────────────────────────────────────────────────
+    lines.push(Line::from(Span::styled(
+        format!("{} Predictions:", g.pick("🔮", "[?]")),
+        theme.info,
+    )));
+    let next_limit = match data.predicted_end.as_ref() {
+        None if data.warming_up => Span::styled(calculating(g), theme.dim),
+        prediction => Span::styled(prediction_text(prediction), theme.warning),
+    };
+    lines.push(Line::from(vec![
+        Span::styled("  Next limit:           ", theme.dim),
+        next_limit,
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Limit resets at:      ", theme.dim),
+        Span::styled(data.reset_time.clone(), reset_style(data, theme)),
+    ]));
+    lines.push(Line::from(""));
+
+    // ── Notifications ─────────────────────────────────────────────────────────
+    if !data.notifications.is_empty() {
+        lines.extend(
+            data.notifications
+                .iter()
+                .map(|n| notification_line(n, theme, g)),
+        );
+        lines.push(Line::from(""));
+    }
+
     // ── Status bar ────────────────────────────────────────────────────────────
-    let (status_text, status_style) = if data.is_active {
+    let (status_text, status_style) = if data.refreshing {
+        ("Refreshing…", theme.info)
+    } else if data.is_active {
         ("Active session", theme.success)
     } else {
         ("Inactive", theme.dim)
     };
     lines.push(Line::from(vec![
-        Span::styled("⏰ ", theme.info),
+        Span::styled(g.pick("⏰ ", "[t] "), theme.info),
         Span::styled(data.current_time.clone(), theme.info),
         Span::raw("          "),
-        Span::styled("📝 ", theme.dim),
+        Span::styled(g.pick("📝 ", "[i] "), theme.dim),
         Span::styled(status_text, status_style),
-        Span::styled(" | Ctrl+C to exit ", theme.dim),
-        Span::styled("🟢", theme.success),
+        Span::styled(" | l legend | Ctrl+C to exit ", theme.dim),
+        Span::styled(g.pick("🟢", "*"), theme.success),
     ]));
+    if data.show_legend {
+        lines.push(legend_line(theme, g));
+    }
+
+    // ── All-time footer ───────────────────────────────────────────────────────
+    if let Some(lifetime) = data.lifetime.as_ref().filter(|l| l.first_seen.is_some()) {
+        lines.push(Line::from(Span::styled(
+            lifetime_footer(lifetime, &data.number_format),
+            theme.dim,
+        )));
+    }
+
+    // ── Data source footer ────────────────────────────────────────────────────
+    if let Some(source) = &data.data_source {
+        lines.push(Line::from(Span::styled(
+            data_source_footer(source, &data.number_format),
+            theme.dim,
+        )));
+    }
 
     lines
 }
 
-/// Render the "no active session" waiting screen.
+/// One notification: a marker for its level, then the message.
+fn notification_line<'a>(notification: &ViewNotification, theme: &Theme, g: Glyphs) -> Line<'a> {
+    let (marker, style) = match notification.level {
+        NotificationLevel::Info => (g.pick("ℹ️  ", "[i] "), theme.notification_info),
+        NotificationLevel::Warning => (g.pick("⚠️  ", "[!] "), theme.notification_warning),
+        NotificationLevel::Error => (g.pick("🚨 ", "[!!] "), theme.notification_error),
+    };
+    Line::from(vec![
+        Span::styled(marker, style),
+        Span::styled(notification.message.clone(), style),
+    ])
+}
+
+/// `"Data: ~/.claude/projects · 12 files · 3,456 entries"`.
+fn data_source_footer(source: &DataSourceInfo, numbers: &NumberFormatter) -> String {
+    let count = |n: usize, one: &str, many: &str| {
+        let word = if n == 1 { one } else { many };
+        format!("{} {word}", numbers.format_count(n as u64))
+    };
+    format!(
+        "Data: {} · {} · {}",
+        source.path,
+        count(source.files, "file", "files"),
+        count(source.entries, "entry", "entries"),
+    )
+}
+
+/// Swatches of the three progress-bar tiers with the usage they stand for.
 ///
-/// Used when there is no [`SessionViewData`] available yet (first startup or
-/// between sessions).
-pub fn render_no_session(frame: &mut Frame, area: Rect, theme: &Theme) {
+/// The labels carry the meaning too, so the legend still reads in the plain
+/// theme.
+fn legend_line(theme: &Theme, g: Glyphs) -> Line<'static> {
+    let (swatch, _) = g.bar();
+    let swatch = swatch.repeat(2);
+    Line::from(vec![
+        Span::styled("  Legend: ", theme.dim),
+        Span::styled(swatch.clone(), theme.progress_low),
+        Span::styled(" < 50%   ", theme.dim),
+        Span::styled(swatch.clone(), theme.progress_medium),
+        Span::styled(g.pick(" 50–80%   ", " 50-80%   "), theme.dim),
+        Span::styled(swatch, theme.progress_high),
+        Span::styled(g.pick(" ≥ 80%", " >= 80%"), theme.dim),
+    ])
+}
+
+/// `"All time: 1,234 tokens · $5.67 since 2024-01-15"`.
+fn lifetime_footer(lifetime: &LifetimeStats, numbers: &NumberFormatter) -> String {
+    let mut footer = format!(
+        "All time: {} tokens · {}",
+        numbers.format_count(lifetime.total_tokens),
+        numbers.format_currency(lifetime.total_cost),
+    );
+    if let Some(first_seen) = lifetime.first_seen {
+        footer.push_str(&format!(" since {}", first_seen.format("%Y-%m-%d")));
+    }
+    footer
+}
+
+/// Width of the token bar in the compact layout.
+const COMPACT_BAR_WIDTH: usize = 20;
+
+/// Render the compact session view into `area`.
+pub fn render_compact_view(frame: &mut Frame, area: Rect, data: &SessionViewData, theme: &Theme) {
+    let lines = build_compact_lines(data, theme);
+    frame.render_widget(Paragraph::new(Text::from(lines)), area);
+}
+
+/// Build the compact session view: token bar, cost, burn rate and reset
+/// countdown, each on one line of at most ~40 columns.
+pub fn build_compact_lines<'a>(data: &SessionViewData, theme: &'a Theme) -> Vec<Line<'a>> {
+    let token_pct = if data.token_limit > 0 {
+        (data.tokens_used as f64 / data.token_limit as f64) * 100.0
+    } else {
+        0.0
+    };
+    let (filled, empty) = build_bar(token_pct, COMPACT_BAR_WIDTH, data.glyphs);
+    let tokens = Line::from(vec![
+        Span::styled("Tokens ", theme.label),
+        Span::styled("[", theme.dim),
+        Span::styled(filled, theme.progress_style(token_pct.min(100.0))),
+        Span::styled(empty, theme.progress_empty),
+        Span::styled("] ", theme.dim),
+        Span::styled(
+            format!("{:>5.1}%", finite_or_zero(token_pct)),
+            theme.cost_style(token_pct),
+        ),
+    ]);
+
+    let cost = Line::from(vec![
+        Span::styled("Cost   ", theme.label),
+        Span::styled(
+            data.number_format.format_currency(data.cost_usd),
+            theme.value,
+        ),
+        Span::styled(" / ", theme.dim),
+        Span::styled(
+            data.number_format.format_currency(data.cost_limit),
+            theme.dim,
+        ),
+    ]);
+
+    let burn_rate = data
+        .burn_rate
+        .as_ref()
+        .filter(|br| br.tokens_per_minute.is_finite());
+    let burn = match burn_rate {
+        Some(br) => Line::from(vec![
+            Span::styled("Burn   ", theme.label),
+            Span::styled(
+                format!("{:.1} tok/min", br.tokens_per_minute),
+                theme.velocity_style(br.tokens_per_minute),
+            ),
+        ]),
+        None => Line::from(vec![
+            Span::styled("Burn   ", theme.label),
+            Span::styled("--", theme.dim),
+        ]),
+    };
+
+    let reset = Line::from(vec![
+        Span::styled("Reset  ", theme.label),
+        Span::styled(time_remaining(data), reset_style(data, theme)),
+        Span::styled(format!(" ({})", data.reset_time), theme.dim),
+    ]);
+
+    vec![tokens, cost, burn, reset]
+}
+
+/// Why the realtime view has no active session to show.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NoSessionState {
+    /// Nothing has been loaded yet.
+    Waiting,
+    /// No JSONL files were found at `path`.
+    NoData {
+        /// Directory that was searched.
+        path: String,
+    },
+    /// Files were found, but none of their sessions is active.
+    Idle {
+        /// Number of JSONL files read.
+        files: usize,
+        /// Number of sessions in the analysed window.
+        sessions: usize,
+        /// Minutes since the last recorded activity, when known.
+        minutes_since_activity: Option<f64>,
+        /// Age in minutes of the newest entry when it is past the staleness
+        /// threshold; shows the stale-data banner.
+        stale_minutes: Option<u64>,
+    },
+}
+
+/// Warning that the newest entry is `minutes` old.
+fn stale_banner(minutes: u64, theme: &Theme, g: Glyphs) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(
+            "{} Data is {minutes} minutes old {} is Claude Code still writing logs?",
+            g.pick("⚠", "!"),
+            g.pick("—", "-"),
+        ),
+        theme.warning,
+    ))
+}
+
+/// Render the placeholder shown when there is no active session, with a
+/// message explaining why.
+pub fn render_no_session(
+    frame: &mut Frame,
+    area: Rect,
+    state: &NoSessionState,
+    theme: &Theme,
+    g: Glyphs,
+) {
+    let mut banner = Line::from("");
+    let (headline, detail) = match state {
+        NoSessionState::Waiting => (
+            "No active session detected".to_string(),
+            "Waiting for Claude activity...".to_string(),
+        ),
+        NoSessionState::NoData { path } => (
+            format!("No Claude data found at {path}"),
+            "Check --data-path or run with --print-data-path".to_string(),
+        ),
+        NoSessionState::Idle {
+            files,
+            sessions,
+            minutes_since_activity,
+            stale_minutes,
+        } => {
+            if let Some(minutes) = stale_minutes {
+                banner = stale_banner(*minutes, theme, g);
+            }
+            let headline = if *sessions == 0 {
+                format!("Found {files} data files, no recent sessions")
+            } else {
+                let ago = minutes_since_activity
+                    .map(|m| format!(" (last activity {} ago)", format_time(m.max(0.0))))
+                    .unwrap_or_default();
+                format!("Found {sessions} sessions, none active{ago}")
+            };
+            (headline, "Waiting for Claude activity...".to_string())
+        }
+    };
+
+    let text = vec![
+        banner,
+        Line::from(Span::styled(headline, theme.dim)),
+        Line::from(""),
+        Line::from(Span::styled(detail, theme.info)),
+        Line::from(Span::styled("Press 'q' or Ctrl+C to exit", theme.dim)),
+    ];
+    let paragraph = Paragraph::new(Text::from(text)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Claude Monitor "),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// Render a loading screen while the first analysis is still running.
+pub fn render_loading(frame: &mut Frame, area: Rect, progress: &LoadProgress, theme: &Theme) {
+    let pct = progress.fraction() * 100.0;
     let text = vec![
         Line::from(""),
-        Line::from(Span::styled("No active session detected", theme.dim)),
+        Line::from(Span::styled("Loading usage data...", theme.info)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Files:   ", theme.label),
+            Span::styled(
+                format!(
+                    "{} / {} ({:.0}%)",
+                    progress.files_processed, progress.files_total, pct
+                ),
+                theme.value,
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Entries: ", theme.label),
+            Span::styled(
+                format_number(progress.entries_parsed as f64, 0),
+                theme.value,
+            ),
+        ]),
         Line::from(""),
-        Line::from(Span::styled("Waiting for Claude activity...", theme.info)),
         Line::from(Span::styled("Press 'q' or Ctrl+C to exit", theme.dim)),
     ];
     let paragraph = Paragraph::new(Text::from(text)).block(
@@ -494,33 +1585,29 @@ mod tests {
     use ratatui::Terminal;
 
     fn make_session_data() -> SessionViewData {
-        SessionViewData {
-            plan: "pro".to_string(),
-            timezone: "UTC".to_string(),
-            tokens_used: 5_000,
-            token_limit: 19_000,
-            cost_usd: 2.50,
-            cost_limit: 18.0,
-            elapsed_minutes: 90.0,
-            total_minutes: 300.0,
-            burn_rate: Some(BurnRate {
+        SessionViewData::builder()
+            .tokens(5_000, 19_000)
+            .cost(2.50, 18.0)
+            .window(90.0, 300.0)
+            .burn_rate(BurnRate {
                 tokens_per_minute: 55.5,
                 cost_per_hour: 1.67,
-            }),
-            per_model_stats: vec![
+            })
+            .per_model_stats(vec![
                 ("claude-3-5-sonnet".to_string(), 75.0),
                 ("claude-3-haiku".to_string(), 25.0),
-            ],
-            sent_messages: 42,
-            message_limit: 250,
-            current_time: "12:00:00".to_string(),
-            reset_time: "17:00:00".to_string(),
-            predicted_end: Some("14:30:00".to_string()),
-            is_active: true,
-            notifications: vec!["80% token limit reached".to_string()],
-            cache_creation_tokens: 1_000,
-            cache_read_tokens: 5_000,
-        }
+            ])
+            .messages(42, 250)
+            .times("12:00:00", "17:00:00")
+            .predicted_end(LimitPrediction {
+                kind: LimitKind::Tokens,
+                minutes_left: 150.0,
+                at: "14:30:00".to_string(),
+            })
+            .active(true)
+            .notifications(vec![ViewNotification::warning("80% token limit reached")])
+            .cache(1_000, 5_000)
+            .build()
     }
 
     // ── Data construction ─────────────────────────────────────────────────────
@@ -538,6 +1625,42 @@ mod tests {
         assert_eq!(data.cache_read_tokens, 5_000);
     }
 
+    #[test]
+    fn test_builder_defaults_to_empty_inactive_session() {
+        let data = SessionViewData::builder().build();
+        assert_eq!(data.plan, "pro");
+        assert_eq!(data.tokens_used, 0);
+        assert_eq!(data.token_limit, DEFAULT_TOKEN_LIMIT);
+        assert_eq!(data.message_limit, DEFAULT_MESSAGE_LIMIT);
+        assert!(!data.is_active);
+        assert!(data.burn_rate.is_none());
+
+        let theme = Theme::dark();
+        let text: String = build_session_lines(&data, &theme)
+            .iter()
+            .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref().to_string()))
+            .collect();
+        assert!(text.contains("Inactive"), "{text}");
+        assert!(!text.contains("Cache Efficiency"), "{text}");
+    }
+
+    #[test]
+    fn test_builder_sets_paired_fields() {
+        let data = SessionViewData::builder()
+            .plan("max5", "Europe/Berlin")
+            .tokens(10, 88_000)
+            .messages(3, 1_000)
+            .cost_since_reset(1.5, "06:00")
+            .build();
+        assert_eq!(
+            (data.plan.as_str(), data.timezone.as_str()),
+            ("max5", "Europe/Berlin")
+        );
+        assert_eq!((data.tokens_used, data.token_limit), (10, 88_000));
+        assert_eq!((data.sent_messages, data.message_limit), (3, 1_000));
+        assert_eq!(data.cost_since_reset, Some((1.5, "06:00".to_string())));
+    }
+
     #[test]
     fn test_session_view_data_no_burn_rate() {
         let mut data = make_session_data();
@@ -552,6 +1675,24 @@ mod tests {
         assert!(data.predicted_end.is_none());
     }
 
+    #[test]
+    fn test_prediction_text_names_binding_limit() {
+        let cost = LimitPrediction {
+            kind: LimitKind::Cost,
+            minutes_left: 42.0,
+            at: "14:30".to_string(),
+        };
+        assert_eq!(prediction_text(Some(&cost)), "Cost limit in 42m (14:30)");
+
+        let exceeded = LimitPrediction {
+            kind: LimitKind::Messages,
+            minutes_left: 0.0,
+            at: "12:00".to_string(),
+        };
+        assert_eq!(prediction_text(Some(&exceeded)), "Message limit exceeded");
+        assert_eq!(prediction_text(None), "N/A");
+    }
+
     #[test]
     fn test_session_view_data_empty_notifications() {
         let mut data = make_session_data();
@@ -573,6 +1714,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lines_use_custom_branding() {
+        let theme = Theme::dark();
+        let data = SessionViewData {
+            branding: HeaderBranding::new(Some("ACME USAGE".to_string()), Some("**".to_string())),
+            ..make_session_data()
+        };
+        let lines = build_session_lines(&data, &theme);
+        let title: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(title, "** ACME USAGE **");
+        let sep: String = lines[1].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(sep.chars().count(), 78, "separator width");
+    }
+
+    #[test]
+    fn test_stale_data_banner_replaces_blank_line() {
+        let theme = Theme::dark();
+        let fresh = build_session_lines(&make_session_data(), &theme);
+        let data = SessionViewData {
+            stale_minutes: Some(95),
+            ..make_session_data()
+        };
+        let stale = build_session_lines(&data, &theme);
+
+        assert_eq!(stale.len(), fresh.len());
+        assert!(fresh[3].spans.is_empty());
+        let banner: String = stale[3].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(banner.starts_with("⚠ Data is 95 minutes old"), "{banner}");
+        assert_eq!(stale[3].spans[0].style, theme.warning);
+    }
+
+    #[test]
+    fn test_status_bar_shows_refreshing() {
+        let theme = Theme::dark();
+        let status = |data: &SessionViewData| -> String {
+            build_session_lines(data, &theme)
+                .iter()
+                .map(|l| {
+                    l.spans
+                        .iter()
+                        .map(|s| s.content.as_ref())
+                        .collect::<String>()
+                })
+                .find(|l| l.contains("Ctrl+C to exit"))
+                .unwrap()
+        };
+        assert!(status(&make_session_data()).contains("Active session"));
+        let data = SessionViewData {
+            refreshing: true,
+            ..make_session_data()
+        };
+        assert!(status(&data).contains("Refreshing…"));
+    }
+
+    #[test]
+    fn test_message_limit_is_a_count_even_with_compact_numbers() {
+        let theme = Theme::dark();
+        let data = SessionViewData {
+            sent_messages: 1_200,
+            message_limit: 1_500,
+            number_format: NumberFormatter::default().with_compact(true),
+            ..make_session_data()
+        };
+        let row = build_session_lines(&data, &theme)
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .find(|l| l.contains("Messages Usage:"))
+            .unwrap();
+        assert!(row.contains("1,200"), "{row}");
+        assert!(row.contains("1,500"), "{row}");
+        assert!(!row.contains("1.5k"), "{row}");
+    }
+
+    #[test]
+    fn test_empty_decoration_leaves_only_title() {
+        let theme = Theme::dark();
+        let data = SessionViewData {
+            branding: HeaderBranding::new(Some("ACME USAGE".to_string()), Some(String::new())),
+            ..make_session_data()
+        };
+        let lines = build_session_lines(&data, &theme);
+        assert_eq!(lines[0].spans.len(), 1);
+        assert_eq!(lines[0].spans[0].content, "ACME USAGE");
+    }
+
+    #[test]
+    fn test_lines_show_cost_since_reset() {
+        let theme = Theme::dark();
+        let text = |data: &SessionViewData| -> String {
+            build_session_lines(data, &theme)
+                .iter()
+                .flat_map(|l| l.spans.iter().map(|s| s.content.to_string()))
+                .collect()
+        };
+        assert!(!text(&make_session_data()).contains("Since Reset:"));
+
+        let data = SessionViewData {
+            cost_since_reset: Some((7.25, "06:00".to_string())),
+            ..make_session_data()
+        };
+        assert!(
+            text(&data).contains("Since Reset:          $7.25 since 06:00"),
+            "{}",
+            text(&data)
+        );
+    }
+
     #[test]
     fn test_lines_contain_separator_equals() {
         let theme = Theme::dark();
@@ -626,6 +1879,288 @@ mod tests {
         assert!(all_text.contains("tokens/min"), "no tokens/min: {all_text}");
     }
 
+    #[test]
+    fn test_lines_show_tokens_per_message() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        let per_message_row = |data: &SessionViewData| {
+            build_session_lines(data, &theme)
+                .iter()
+                .map(|l| {
+                    l.spans
+                        .iter()
+                        .map(|s| s.content.as_ref())
+                        .collect::<String>()
+                })
+                .find(|l| l.contains("Avg tokens/msg:"))
+        };
+
+        data.tokens_used = 12_345;
+        data.sent_messages = 10;
+        let row = per_message_row(&data).expect("no tokens-per-message row");
+        assert!(row.ends_with("1,234"), "{row}");
+
+        data.sent_messages = 0;
+        assert_eq!(per_message_row(&data), None);
+    }
+
+    #[test]
+    fn test_lines_show_safe_rate_against_actual() {
+        let theme = Theme::dark();
+        let safe_row = |tokens_per_minute: f64| {
+            let data = SessionViewData::builder()
+                .tokens(7_400, 20_000)
+                .window(270.0, 300.0)
+                .burn_rate(BurnRate {
+                    tokens_per_minute,
+                    cost_per_hour: 1.0,
+                })
+                .build();
+            build_session_lines(&data, &theme)
+                .into_iter()
+                .find(|l| l.spans.iter().any(|s| s.content.contains("Safe rate:")))
+                .expect("no safe rate row")
+        };
+
+        let fast = safe_row(680.0);
+        let text: String = fast.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.ends_with("420 tok/min (you: 680)"), "{text}");
+        assert_eq!(fast.spans.last().unwrap().style, theme.error);
+
+        let slow = safe_row(100.0);
+        assert_eq!(slow.spans.last().unwrap().style, theme.success);
+    }
+
+    #[test]
+    fn test_lines_show_daily_budget_bar() {
+        let theme = Theme::dark();
+        let row = |data: &SessionViewData| {
+            build_session_lines(data, &theme)
+                .into_iter()
+                .find(|l| l.spans.iter().any(|s| s.content.contains("Daily Budget:")))
+        };
+
+        assert!(row(&make_session_data()).is_none());
+
+        let data = SessionViewData::builder().daily_budget(6.0, 5.0).build();
+        let line = row(&data).expect("no daily budget row");
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.contains("120.0%"), "{text}");
+        assert!(text.ends_with("$6.00 / $5.00"), "{text}");
+        let pct = line.spans.iter().find(|s| s.content.contains('%')).unwrap();
+        assert_eq!(pct.style, theme.cost_style(120.0));
+    }
+
+    #[test]
+    fn test_model_limit_bars_follow_token_usage() {
+        let text =
+            |line: &Line| -> String { line.spans.iter().map(|s| s.content.as_ref()).collect() };
+        let theme = Theme::dark();
+        let plain = build_session_lines(&make_session_data(), &theme);
+        assert!(!plain.iter().any(|l| text(l).contains("Opus Usage:")));
+
+        let data = SessionViewData::builder()
+            .tokens(2_000, 10_000)
+            .model_limit("Opus", 1_500, 3_000)
+            .build();
+        let lines = build_session_lines(&data, &theme);
+        let at = lines
+            .iter()
+            .position(|l| text(l).contains("Opus Usage:"))
+            .expect("no Opus bar");
+        assert!(text(&lines[at - 1]).contains("Token Usage:"));
+        let row = text(&lines[at]);
+        assert!(
+            row.contains(" 50.0%") && row.contains("1,500 / 3,000"),
+            "{row}"
+        );
+        let filled = &lines[at].spans[3];
+        assert!(filled.content.contains('█'));
+        assert_eq!(filled.style, theme.model_opus);
+    }
+
+    #[test]
+    fn test_warming_up_shows_calculating() {
+        let text = |lines: &[Line]| -> String {
+            lines
+                .iter()
+                .map(|l| {
+                    l.spans
+                        .iter()
+                        .map(|s| s.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let mut data = make_session_data();
+        data.burn_rate = None;
+        data.predicted_end = None;
+        let settled = text(&build_session_lines(&data, &Theme::dark()));
+        assert!(!settled.contains("calculating"));
+        assert!(settled.contains("N/A"));
+
+        data.warming_up = true;
+        let warming = text(&build_session_lines(&data, &Theme::dark()));
+        assert_eq!(warming.matches("calculating…").count(), 2, "{warming}");
+        assert!(!warming.contains("N/A"));
+
+        data.glyphs = Glyphs::Ascii;
+        let ascii = text(&build_session_lines(&data, &Theme::dark()));
+        assert_eq!(ascii.matches("calculating...").count(), 2);
+    }
+
+    #[test]
+    fn test_data_source_footer_is_optional() {
+        let text = |lines: &[Line]| -> Vec<String> {
+            lines
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        let theme = Theme::dark();
+        let hidden = text(&build_session_lines(&make_session_data(), &theme));
+        assert!(!hidden.iter().any(|l| l.starts_with("Data: ")));
+
+        let data = SessionViewData::builder()
+            .data_source("/home/me/.claude/projects", 1, 1234)
+            .build();
+        let lines = build_session_lines(&data, &theme);
+        assert_eq!(
+            text(&lines).last().unwrap(),
+            "Data: /home/me/.claude/projects · 1 file · 1,234 entries"
+        );
+        assert_eq!(lines.last().unwrap().spans[0].style, theme.dim);
+    }
+
+    #[test]
+    fn test_notifications_render_above_status_bar() {
+        let text =
+            |line: &Line| -> String { line.spans.iter().map(|s| s.content.as_ref()).collect() };
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.notifications = vec![
+            ViewNotification::error("Usage limit reached"),
+            ViewNotification::warning("80% of the token limit used"),
+        ];
+        let lines = build_session_lines(&data, &theme);
+        let at = |needle: &str| lines.iter().position(|l| text(l).contains(needle));
+        let error = at("Usage limit reached").expect("no error line");
+        let warning = at("80% of the token limit used").expect("no warning line");
+        let status = at("Ctrl+C to exit").unwrap();
+        assert!(error < warning && warning < status);
+        assert_eq!(lines[error].spans[1].style, theme.notification_error);
+        assert_eq!(lines[warning].spans[1].style, theme.notification_warning);
+
+        data.glyphs = Glyphs::Ascii;
+        let lines = build_session_lines(&data, &theme);
+        assert!(lines.iter().any(|l| text(l) == "[!!] Usage limit reached"));
+
+        data.notifications.clear();
+        let lines = build_session_lines(&data, &theme);
+        assert!(!lines
+            .iter()
+            .any(|l| text(l).contains("Usage limit reached")));
+    }
+
+    #[test]
+    fn test_legend_under_status_bar_uses_theme_tiers() {
+        let text =
+            |line: &Line| -> String { line.spans.iter().map(|s| s.content.as_ref()).collect() };
+        let legend = |lines: &[Line]| lines.iter().position(|l| text(l).contains("Legend:"));
+        let dark = Theme::dark();
+        let lines = build_session_lines(&make_session_data(), &dark);
+        assert!(legend(&lines).is_none());
+        assert!(lines
+            .iter()
+            .any(|l| text(l).contains("| l legend | Ctrl+C to exit")));
+
+        let mut data = make_session_data();
+        data.show_legend = true;
+        for theme in [
+            Theme::dark(),
+            Theme::light(),
+            Theme::classic(),
+            Theme::plain(),
+        ] {
+            let lines = build_session_lines(&data, &theme);
+            let at = legend(&lines).expect("no legend line");
+            assert!(text(&lines[at - 1]).contains("Ctrl+C to exit"));
+            let styles: Vec<ratatui::style::Style> = lines[at]
+                .spans
+                .iter()
+                .filter(|s| s.content.contains('█'))
+                .map(|s| s.style)
+                .collect();
+            assert_eq!(
+                styles,
+                [
+                    theme.progress_low,
+                    theme.progress_medium,
+                    theme.progress_high
+                ]
+            );
+        }
+
+        data.glyphs = Glyphs::Ascii;
+        let theme = Theme::dark();
+        let lines = build_session_lines(&data, &theme);
+        assert!(text(&lines[legend(&lines).unwrap()]).is_ascii());
+    }
+
+    #[test]
+    fn test_safe_burn_rate_guards() {
+        let over = SessionViewData::builder()
+            .tokens(25_000, 20_000)
+            .window(60.0, 300.0)
+            .build();
+        assert_eq!(super::safe_burn_rate(&over), Some(0.0));
+
+        let ended = SessionViewData::builder().window(300.0, 300.0).build();
+        assert_eq!(super::safe_burn_rate(&ended), None);
+
+        let unlimited = SessionViewData::builder().tokens(10, 0).build();
+        assert_eq!(super::safe_burn_rate(&unlimited), None);
+    }
+
+    /// The styled span showing the token percentage.
+    fn token_pct_span(data: &SessionViewData, theme: &Theme) -> Span<'static> {
+        let lines = build_session_lines(data, theme);
+        let token_line = lines
+            .iter()
+            .find(|l| l.spans.iter().any(|s| s.content.contains("Token Usage:")))
+            .unwrap();
+        let span = token_line
+            .spans
+            .iter()
+            .find(|s| s.content.contains('%'))
+            .unwrap();
+        Span::styled(span.content.to_string(), span.style)
+    }
+
+    #[test]
+    fn test_token_pct_over_limit_shows_overage() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.tokens_used = 315_310;
+        data.token_limit = 220_000;
+
+        let span = token_pct_span(&data, &theme);
+        assert_eq!(span.content, "143.3% (+95,310)");
+        assert_eq!(span.style, theme.over_limit);
+    }
+
+    #[test]
+    fn test_token_pct_at_limit_unchanged() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.tokens_used = 19_000;
+
+        let span = token_pct_span(&data, &theme);
+        assert_eq!(span.content, "100.0%");
+        assert_eq!(span.style, theme.cost_high);
+    }
+
     #[test]
     fn test_lines_contain_cost_rate() {
         let theme = Theme::dark();
@@ -638,46 +2173,182 @@ mod tests {
             .join("");
         assert!(all_text.contains("Cost Rate"), "no cost rate: {all_text}");
         assert!(all_text.contains("$/min"), "no $/min: {all_text}");
+        assert!(!all_text.contains("by reset"), "{all_text}");
+
+        let data = SessionViewData {
+            projected_cost: Some(4.5),
+            ..make_session_data()
+        };
+        let lines = build_session_lines(&data, &theme);
+        let row = lines
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .find(|t| t.contains("Cost Rate"))
+            .unwrap();
+        assert!(row.ends_with("(→ $4.50 by reset)"), "{row}");
+    }
+
+    #[test]
+    fn test_lines_use_cost_precision() {
+        let theme = Theme::dark();
+        let data = SessionViewData {
+            number_format: NumberFormatter::default().with_cost_precision(4),
+            model_labels: ModelLabels::default(),
+            ..make_session_data()
+        };
+        let lines = build_session_lines(&data, &theme);
+        let all_text: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref().to_string()))
+            .collect::<Vec<_>>()
+            .join("");
+        assert!(all_text.contains("$2.5000"), "cost not 4dp: {all_text}");
+        assert!(all_text.contains("$18.0000"), "limit not 4dp: {all_text}");
+        // The rate line carries two extra decimals.
+        assert!(
+            all_text.contains(&format!(
+                "${:.6} $/min",
+                data.cost_usd / data.elapsed_minutes
+            )),
+            "rate not 6dp: {all_text}"
+        );
     }
 
     #[test]
     fn test_format_with_commas() {
-        assert_eq!(super::format_with_commas(0), "0");
-        assert_eq!(super::format_with_commas(999), "999");
-        assert_eq!(super::format_with_commas(1_000), "1,000");
-        assert_eq!(super::format_with_commas(1_234_567), "1,234,567");
+        let fmt = NumberFormatter::default();
+        assert_eq!(fmt.format_count(0), "0");
+        assert_eq!(fmt.format_count(999), "999");
+        assert_eq!(fmt.format_count(1_000), "1,000");
+        assert_eq!(fmt.format_count(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_render_session_view_uses_number_format() {
+        let backend = TestBackend::new(120, 40);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let data = SessionViewData {
+            number_format: NumberFormatter::from_name("dot"),
+            model_labels: ModelLabels::default(),
+            ..make_session_data()
+        };
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_session_view(frame, area, &data, &theme);
+            })
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("19.000"), "token limit not dot-grouped");
+        assert!(!content.contains("19,000"));
+    }
+
+    #[test]
+    fn test_render_session_view_separator_follows_area() {
+        let backend = TestBackend::new(100, 40);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let data = make_session_data();
+
+        terminal
+            .draw(|frame| render_session_view(frame, frame.area(), &data, &theme))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row: String = (0..100).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert_eq!(row, "=".repeat(100));
+    }
+
+    #[test]
+    fn test_pad_label_uses_display_width() {
+        // "🔥" is two columns wide, "⚠" one: both labels end at column 25.
+        for (emoji, label) in [("🔥", "Burn Rate:"), ("⚠", "Burn Rate:"), ("♻️", "Cache:")]
+        {
+            let padded = super::pad_label(emoji, label);
+            assert_eq!(padded.width(), LABEL_WIDTH, "{padded:?}");
+        }
+        let long = super::pad_label("🤖", "A label far longer than the column:");
+        assert!(long.ends_with(": "));
     }
 
     #[test]
     fn test_pct_indicator() {
-        assert_eq!(super::pct_indicator(0.0), "🟢");
-        assert_eq!(super::pct_indicator(49.9), "🟢");
-        assert_eq!(super::pct_indicator(50.0), "🟡");
-        assert_eq!(super::pct_indicator(79.9), "🟡");
-        assert_eq!(super::pct_indicator(80.0), "🔴");
-        assert_eq!(super::pct_indicator(143.3), "🔴");
+        assert_eq!(super::pct_indicator(0.0, Glyphs::Emoji), "🟢");
+        assert_eq!(super::pct_indicator(49.9, Glyphs::Emoji), "🟢");
+        assert_eq!(super::pct_indicator(50.0, Glyphs::Emoji), "🟡");
+        assert_eq!(super::pct_indicator(79.9, Glyphs::Emoji), "🟡");
+        assert_eq!(super::pct_indicator(80.0, Glyphs::Emoji), "🔴");
+        assert_eq!(super::pct_indicator(143.3, Glyphs::Emoji), "🔴");
+        assert_eq!(super::pct_indicator(f64::NAN, Glyphs::Emoji), "🟢");
+        assert_eq!(super::pct_indicator(f64::INFINITY, Glyphs::Emoji), "🟢");
+    }
+
+    #[test]
+    fn test_build_bar_non_finite_is_empty() {
+        for pct in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let (filled, empty) = super::build_bar(pct, 50, Glyphs::Emoji);
+            assert!(filled.is_empty(), "pct {pct}");
+            assert_eq!(empty.chars().count(), 50);
+        }
+    }
+
+    #[test]
+    fn test_lines_non_finite_rates_show_dashes() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.burn_rate = Some(BurnRate {
+            tokens_per_minute: f64::INFINITY,
+            cost_per_hour: f64::NAN,
+        });
+        let lines = build_session_lines(&data, &theme);
+        let all_text: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter().map(|s| s.content.as_ref().to_string()))
+            .collect::<Vec<_>>()
+            .join("");
+        assert!(!all_text.contains("inf"), "{all_text}");
+        assert!(!all_text.contains("NaN"), "{all_text}");
+        assert!(all_text.contains("--"), "{all_text}");
     }
 
     #[test]
     fn test_build_bar_full_when_over_100() {
-        let (filled, empty) = super::build_bar(143.3, 50);
+        let (filled, empty) = super::build_bar(143.3, 50, Glyphs::Emoji);
         assert_eq!(filled.chars().count(), 50, "bar should be full at 143%");
         assert!(empty.is_empty(), "empty portion should be empty at 143%");
     }
 
     #[test]
     fn test_build_bar_partial() {
-        let (filled, empty) = super::build_bar(50.0, 50);
+        let (filled, empty) = super::build_bar(50.0, 50, Glyphs::Emoji);
         assert_eq!(filled.chars().count(), 25);
         assert_eq!(empty.chars().count(), 25);
     }
 
     #[test]
     fn test_short_model_name() {
-        assert_eq!(super::short_model_name("claude-3-5-sonnet"), "Sonnet");
-        assert_eq!(super::short_model_name("claude-opus-4"), "Opus");
-        assert_eq!(super::short_model_name("claude-3-haiku"), "Haiku");
-        assert_eq!(super::short_model_name("gpt-4"), "Other");
+        let labels = ModelLabels::default();
+        assert_eq!(
+            super::short_model_name("claude-3-5-sonnet", &labels),
+            "Sonnet"
+        );
+        assert_eq!(super::short_model_name("claude-opus-4", &labels), "Opus");
+        assert_eq!(super::short_model_name("claude-3-haiku", &labels), "Haiku");
+        assert_eq!(super::short_model_name("gpt-4", &labels), "Other");
     }
 
     // ── Model Distribution styled spans ─────────────────────────────────────
@@ -740,6 +2411,315 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_model_distribution_legend_matches_bar_order() {
+        use ratatui::style::Style;
+
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.per_model_stats = vec![
+            ("claude-3-haiku".to_string(), 10.0),
+            ("claude-3-opus".to_string(), 60.0),
+            ("claude-3-5-sonnet".to_string(), 30.0),
+        ];
+        let lines = build_session_lines(&data, &theme);
+        let model_line = lines
+            .iter()
+            .find(|l| {
+                l.spans
+                    .iter()
+                    .any(|s| s.content.contains("Model Distribution"))
+            })
+            .expect("model distribution line must exist");
+
+        let segment_styles: Vec<Style> = model_line
+            .spans
+            .iter()
+            .filter(|s| s.content.starts_with('█'))
+            .map(|s| s.style)
+            .collect();
+        let legend: Vec<(&str, Style)> = model_line
+            .spans
+            .iter()
+            .filter(|s| s.content.ends_with('%'))
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect();
+
+        let labels: Vec<&str> = legend.iter().map(|(text, _)| *text).collect();
+        assert_eq!(labels, ["Opus 60.0%", "Sonnet 30.0%", "Haiku 10.0%"]);
+        let legend_styles: Vec<Style> = legend.iter().map(|(_, style)| *style).collect();
+        assert_eq!(segment_styles, legend_styles);
+    }
+
+    #[test]
+    fn test_model_distribution_collapses_past_max_models() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.per_model_stats = vec![
+            ("claude-3-opus".to_string(), 40.0),
+            ("claude-3-5-sonnet".to_string(), 25.0),
+            ("claude-3-haiku".to_string(), 15.0),
+            ("model-a".to_string(), 10.0),
+            ("model-b".to_string(), 6.0),
+            ("model-c".to_string(), 4.0),
+        ];
+        let lines = build_session_lines(&data, &theme);
+        let model_line = lines
+            .iter()
+            .find(|l| {
+                l.spans
+                    .iter()
+                    .any(|s| s.content.contains("Model Distribution"))
+            })
+            .expect("model distribution line must exist");
+
+        let legend: Vec<_> = model_line
+            .spans
+            .iter()
+            .filter(|s| s.content.ends_with('%'))
+            .collect();
+        assert_eq!(legend.len(), DEFAULT_MAX_MODELS + 1);
+        let other = legend.last().unwrap();
+        assert_eq!(other.content, "Other 10.0%");
+        assert_eq!(other.style, theme.model_unknown);
+
+        // The bar still fills all 50 cells, ending in the "Other" colour.
+        let segments: Vec<_> = model_line
+            .spans
+            .iter()
+            .filter(|s| s.content.starts_with('█'))
+            .collect();
+        let filled: usize = segments.iter().map(|s| s.content.chars().count()).sum();
+        assert_eq!(filled, 50);
+        assert_eq!(segments.len(), DEFAULT_MAX_MODELS + 1);
+        assert_eq!(segments.last().unwrap().style, theme.model_unknown);
+    }
+
+    #[test]
+    fn test_model_distribution_uses_custom_labels() {
+        use ratatui::style::Color;
+
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.model_labels = ModelLabels::from_rules(&["sonnet=Sonnet 3.5", "haiku=Fast"]).unwrap();
+        let lines = build_session_lines(&data, &theme);
+        let spans: Vec<_> = lines.iter().flat_map(|l| l.spans.iter()).collect();
+
+        let sonnet = spans
+            .iter()
+            .find(|s| s.content.starts_with("Sonnet 3.5 "))
+            .expect("custom Sonnet label must be shown");
+        assert_eq!(sonnet.style.fg, Some(Color::Cyan));
+
+        // A label without a family name falls back to the unknown-model color.
+        let fast = spans
+            .iter()
+            .find(|s| s.content.starts_with("Fast "))
+            .expect("custom Haiku label must be shown");
+        assert_eq!(fast.style, theme.model_unknown);
+    }
+
+    // ── Per-model cache rows ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_cache_rows_per_model() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.model_cache = vec![ModelCacheUsage {
+            model: "claude-opus-4".to_string(),
+            creation_tokens: 12_000,
+            read_tokens: 50_000,
+            creation_cost: 0.225,
+            read_cost: 0.075,
+        }];
+        let lines = build_session_lines(&data, &theme);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        let cache_idx = text
+            .iter()
+            .position(|t| t.contains("Cache Tokens:"))
+            .unwrap();
+        let row = &text[cache_idx + 1];
+        assert!(row.starts_with("   Opus"), "{row}");
+        assert!(
+            row.contains("Creation: 12,000 ($0.23)  Read: 50,000 ($0.08)"),
+            "{row}"
+        );
+    }
+
+    #[test]
+    fn test_no_cache_rows_without_model_cache() {
+        let theme = Theme::dark();
+        let lines = build_session_lines(&make_session_data(), &theme);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        let cache_idx = text
+            .iter()
+            .position(|t| t.contains("Cache Tokens:"))
+            .unwrap();
+        assert!(text[cache_idx + 1].contains("Cache Efficiency:"));
+        assert!(text[cache_idx + 2].is_empty());
+    }
+
+    #[test]
+    fn test_cache_efficiency_row() {
+        let theme = Theme::dark();
+        let text = |data: &SessionViewData| -> Vec<String> {
+            build_session_lines(data, &theme)
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+
+        // 5,000 read of 6,000 cache tokens.
+        let lines = text(&make_session_data());
+        let row = lines
+            .iter()
+            .find(|t| t.contains("Cache Efficiency:"))
+            .unwrap();
+        assert!(
+            row.ends_with("🟢 83.3% of cache tokens were reads"),
+            "{row}"
+        );
+
+        let data = SessionViewData {
+            cache_creation_tokens: 0,
+            cache_read_tokens: 0,
+            ..make_session_data()
+        };
+        assert!(!text(&data).iter().any(|t| t.contains("Cache Efficiency:")));
+    }
+
+    #[test]
+    fn test_ascii_glyphs_render_plain_ascii() {
+        let theme = Theme::dark();
+        let data = SessionViewData {
+            glyphs: Glyphs::Ascii,
+            branding: HeaderBranding::new(None, Some(crate::glyphs::ASCII_DECORATION.to_string())),
+            stale_minutes: Some(95),
+            ..make_session_data()
+        };
+        let lines: Vec<String> = build_session_lines(&data, &theme)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        for line in &lines {
+            assert!(line.is_ascii(), "non-ASCII line: {line:?}");
+        }
+        assert!(lines.iter().any(|l| l.starts_with("[$] Cost Usage:")));
+        assert!(lines.iter().any(|l| l.contains("[#####")));
+    }
+
+    #[test]
+    fn test_cache_efficiency_tiers() {
+        assert_eq!(cache_efficiency(0, 0), None);
+        assert_eq!(cache_efficiency(3, 1), Some(75.0));
+        assert_eq!(cache_efficiency_indicator(90.0, Glyphs::Emoji), "🟢");
+        assert_eq!(cache_efficiency_indicator(75.0, Glyphs::Emoji), "🟡");
+        assert_eq!(cache_efficiency_indicator(10.0, Glyphs::Emoji), "🔴");
+    }
+
+    // ── All-time footer ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_lifetime_footer_after_status_bar() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.lifetime = Some(LifetimeStats {
+            total_tokens: 12_345_678,
+            total_cost: 456.789,
+            first_seen: Some("2024-01-15T10:00:00Z".parse().unwrap()),
+            ..LifetimeStats::default()
+        });
+        let lines = build_session_lines(&data, &theme);
+        let last = lines.last().unwrap();
+        let text: String = last.spans.iter().map(|s| s.content.as_ref()).collect();
+
+        assert_eq!(
+            text,
+            "All time: 12,345,678 tokens · $456.79 since 2024-01-15"
+        );
+        assert_eq!(last.spans[0].style, theme.dim);
+    }
+
+    #[test]
+    fn test_no_lifetime_footer_without_counted_usage() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        let without = build_session_lines(&data, &theme).len();
+        data.lifetime = Some(LifetimeStats::default());
+        assert_eq!(build_session_lines(&data, &theme).len(), without);
+    }
+
+    // ── Reset urgency ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_reset_time_highlighted_when_window_nearly_over() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        let reset_span = |data: &SessionViewData| {
+            build_session_lines(data, &theme)
+                .iter()
+                .flat_map(|l| l.spans.clone())
+                .find(|s| s.content == data.reset_time)
+                .expect("reset time span must exist")
+                .style
+        };
+
+        data.elapsed_minutes = 60.0;
+        assert_eq!(reset_span(&data), theme.value);
+
+        data.elapsed_minutes = 250.0;
+        assert_eq!(reset_span(&data), theme.warning);
+
+        data.elapsed_minutes = 290.0;
+        assert_eq!(reset_span(&data), theme.error);
+    }
+
+    // ── Compact layout ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_session_layout_from_name() {
+        assert_eq!(SessionLayout::from_name("compact"), SessionLayout::Compact);
+        assert_eq!(SessionLayout::from_name("full"), SessionLayout::Full);
+        assert_eq!(SessionLayout::from_name("bogus"), SessionLayout::Full);
+    }
+
+    #[test]
+    fn test_compact_lines_fit_forty_columns() {
+        let theme = Theme::dark();
+        let data = make_session_data();
+        let lines = build_compact_lines(&data, &theme);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert_eq!(text.len(), 4);
+        assert!(text[0].starts_with("Tokens ["), "{}", text[0]);
+        assert!(text[1].starts_with("Cost   $"), "{}", text[1]);
+        assert!(text[2].starts_with("Burn   "), "{}", text[2]);
+        assert!(text[3].starts_with("Reset  "), "{}", text[3]);
+        for line in &lines {
+            assert!(line.width() <= 40, "line too wide: {:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_compact_lines_without_burn_rate() {
+        let theme = Theme::dark();
+        let mut data = make_session_data();
+        data.burn_rate = None;
+        let lines = build_compact_lines(&data, &theme);
+        let burn: String = lines[2].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(burn, "Burn   --");
+    }
+
     // ── Render (does not panic) ───────────────────────────────────────────────
 
     #[test]
@@ -798,9 +2778,89 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_no_session(frame, area, &theme);
+                render_no_session(frame, area, &NoSessionState::Waiting, &theme, Glyphs::Emoji);
+            })
+            .unwrap();
+    }
+
+    fn render_no_session_text(state: &NoSessionState) -> String {
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_no_session(frame, area, state, &theme, Glyphs::Emoji);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_no_session_no_data_names_path() {
+        let content = render_no_session_text(&NoSessionState::NoData {
+            path: "/tmp/nowhere".to_string(),
+        });
+        assert!(content.contains("No Claude data found at /tmp/nowhere"));
+    }
+
+    #[test]
+    fn test_render_no_session_idle_shows_last_activity() {
+        let content = render_no_session_text(&NoSessionState::Idle {
+            files: 3,
+            sessions: 4,
+            minutes_since_activity: Some(135.0),
+            stale_minutes: None,
+        });
+        assert!(content.contains("Found 4 sessions, none active (last activity 2h 15m ago)"));
+
+        let content = render_no_session_text(&NoSessionState::Idle {
+            files: 3,
+            sessions: 0,
+            minutes_since_activity: None,
+            stale_minutes: None,
+        });
+        assert!(content.contains("Found 3 data files, no recent sessions"));
+        assert!(!content.contains("minutes old"));
+
+        let content = render_no_session_text(&NoSessionState::Idle {
+            files: 3,
+            sessions: 4,
+            minutes_since_activity: Some(135.0),
+            stale_minutes: Some(135),
+        });
+        assert!(content.contains("Data is 135 minutes old"));
+    }
+
+    #[test]
+    fn test_render_loading_shows_file_counts() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let progress = LoadProgress {
+            files_processed: 3,
+            files_total: 12,
+            entries_parsed: 4_500,
+        };
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_loading(frame, area, &progress, &theme);
             })
             .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("Loading usage data"));
+        assert!(content.contains("3 / 12 (25%)"));
+        assert!(content.contains("4,500"));
     }
 
     #[test]
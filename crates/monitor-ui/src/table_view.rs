@@ -1,23 +1,30 @@
-//! Aggregate table views (daily / monthly) for the Claude Monitor TUI.
+//! Aggregate table views (daily / monthly / projects) for the Claude Monitor
+//! TUI.
 //!
 //! Renders a bordered [`ratatui::widgets::Table`] with one row per time
-//! period plus a highlighted totals row at the bottom.
+//! period (or project) plus a highlighted totals row at the bottom.
 
 use ratatui::{
-    layout::{Constraint, Rect},
+    layout::{Alignment, Constraint, Layout, Rect},
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
     Frame,
 };
 
-use monitor_core::formatting;
+use monitor_core::error::{MonitorError, Result};
+use monitor_core::formatting::{format_number, NumberFormatter};
+use monitor_runtime::data::reader::LoadProgress;
 
 use crate::themes::Theme;
 
+/// Braille spinner frames for [`render_analyzing`], one per tick.
+pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 /// Data for a single row in the aggregate table.
 #[derive(Debug, Clone)]
 pub struct TableRowData {
-    /// Period label, e.g. `"2024-02-22"` (daily) or `"2024-02"` (monthly).
+    /// Period label, e.g. `"2024-02-22"` (daily) or `"2024-02"` (monthly),
+    /// or the project name in the projects view.
     pub period: String,
     /// Canonical model names seen in this period.
     pub models: Vec<String>,
@@ -35,6 +42,268 @@ pub struct TableRowData {
     pub cost: f64,
 }
 
+/// Title and first-column labels of an aggregate table.
+///
+/// A plain `&str` converts into a period table heading, so
+/// `heading: "Daily Usage".into()` keeps working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableHeading<'a> {
+    /// Block title, e.g. `"Daily Usage"`.
+    pub title: &'a str,
+    /// Header of the first column, e.g. `"Period"`.
+    pub key_column: &'a str,
+    /// Plural noun used in the totals row, e.g. `"periods"`.
+    pub row_noun: &'a str,
+}
+
+impl<'a> TableHeading<'a> {
+    /// Create a heading with explicit labels.
+    pub const fn new(title: &'a str, key_column: &'a str, row_noun: &'a str) -> Self {
+        Self {
+            title,
+            key_column,
+            row_noun,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for TableHeading<'a> {
+    fn from(title: &'a str) -> Self {
+        Self::new(title, "Period", "periods")
+    }
+}
+
+/// A column of the aggregate table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableColumn {
+    /// Period label (or project name); always shown.
+    Period,
+    /// Models seen in the period.
+    Models,
+    /// Input tokens.
+    Input,
+    /// Output tokens.
+    Output,
+    /// Cache-creation tokens.
+    CacheCreate,
+    /// Cache-read tokens.
+    CacheRead,
+    /// All tokens.
+    Total,
+    /// Cost in USD.
+    Cost,
+}
+
+impl TableColumn {
+    /// Every column, in display order.
+    pub const ALL: [TableColumn; 8] = [
+        TableColumn::Period,
+        TableColumn::Models,
+        TableColumn::Input,
+        TableColumn::Output,
+        TableColumn::CacheCreate,
+        TableColumn::CacheRead,
+        TableColumn::Total,
+        TableColumn::Cost,
+    ];
+
+    /// Name of the column in a `--table-columns` list.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Period => "period",
+            Self::Models => "models",
+            Self::Input => "input",
+            Self::Output => "output",
+            Self::CacheCreate => "cache-create",
+            Self::CacheRead => "cache-read",
+            Self::Total => "total",
+            Self::Cost => "cost",
+        }
+    }
+
+    /// Parse a `--table-columns` entry such as `"cache-read"`, ignoring case;
+    /// `project` is accepted for the period column.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        if name == "project" {
+            return Some(Self::Period);
+        }
+        Self::ALL.into_iter().find(|column| column.name() == name)
+    }
+
+    /// Header text; the first column is labelled by `key_column`.
+    fn header(self, key_column: &str) -> &str {
+        match self {
+            Self::Period => key_column,
+            Self::Models => "Models",
+            Self::Input => "Input",
+            Self::Output => "Output",
+            Self::CacheCreate => "Cache Create",
+            Self::CacheRead => "Cache Read",
+            Self::Total => "Total",
+            Self::Cost => "Cost",
+        }
+    }
+
+    /// Column width; the first column is `key_width` wide.
+    fn width(self, key_width: u16) -> Constraint {
+        Constraint::Length(match self {
+            Self::Period => key_width,
+            Self::Models => 25,
+            Self::CacheCreate => 14,
+            _ => 12,
+        })
+    }
+}
+
+/// Columns drawn by [`render_table_view`], from `--table-columns`.
+///
+/// The period column is always drawn first; the rest keep the order of
+/// [`TableColumn::ALL`] whatever order they were listed in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableColumns(Vec<TableColumn>);
+
+impl Default for TableColumns {
+    fn default() -> Self {
+        Self(TableColumn::ALL.to_vec())
+    }
+}
+
+impl TableColumns {
+    /// Parse a `--table-columns` value: `all`, `compact` (period, total and
+    /// cost) or a comma-separated list of column names.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MonitorError::Config`] naming the first unknown column.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let wanted: Vec<TableColumn> = match spec.trim() {
+            "all" | "" => return Ok(Self::default()),
+            "compact" => vec![TableColumn::Total, TableColumn::Cost],
+            list => list
+                .split(',')
+                .map(|name| {
+                    TableColumn::from_name(name).ok_or_else(|| {
+                        let names: Vec<&str> = TableColumn::ALL
+                            .into_iter()
+                            .map(TableColumn::name)
+                            .collect();
+                        MonitorError::Config(format!(
+                            "unknown --table-columns column {:?}; expected `all`, `compact` \
+                             or a list of {}",
+                            name.trim(),
+                            names.join(", ")
+                        ))
+                    })
+                })
+                .collect::<Result<_>>()?,
+        };
+        Ok(Self(
+            TableColumn::ALL
+                .into_iter()
+                .filter(|c| *c == TableColumn::Period || wanted.contains(c))
+                .collect(),
+        ))
+    }
+
+    /// The selected columns, in display order.
+    pub fn columns(&self) -> &[TableColumn] {
+        &self.0
+    }
+}
+
+/// Scale for `--heatmap`: the largest per-row values, so each row's total
+/// and cost cells can be coloured by their share of the heaviest row.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TableHeatmap {
+    /// Largest [`TableRowData::total_tokens`] of any row.
+    pub max_tokens: u64,
+    /// Largest [`TableRowData::cost`] of any row.
+    pub max_cost: f64,
+}
+
+impl TableHeatmap {
+    /// Scale over every row, including those scrolled out of view.
+    pub fn from_rows(rows: &[TableRowData]) -> Self {
+        Self {
+            max_tokens: rows.iter().map(|r| r.total_tokens).max().unwrap_or(0),
+            max_cost: rows.iter().map(|r| r.cost).fold(0.0, f64::max),
+        }
+    }
+
+    /// `value` as a percentage of `max`, `0` when `max` is zero.
+    fn percent(value: f64, max: f64) -> f64 {
+        if max > 0.0 {
+            value / max * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Scroll position of the aggregate table.
+///
+/// Starts at the end so the most recent period is visible on launch; the
+/// offset is clamped to the rows that fit on every [`fit`](Self::fit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableScroll {
+    /// Index of the first visible row (may exceed the maximum until fitted).
+    offset: usize,
+    /// Number of data rows that fit, as of the last [`fit`](Self::fit).
+    visible: usize,
+}
+
+impl Default for TableScroll {
+    fn default() -> Self {
+        Self {
+            offset: usize::MAX,
+            visible: 1,
+        }
+    }
+}
+
+impl TableScroll {
+    /// Clamp the offset for `total_rows` rows of which `visible` fit, and
+    /// return the index of the first row to draw.
+    pub fn fit(&mut self, total_rows: usize, visible: usize) -> usize {
+        self.visible = visible.max(1);
+        self.offset = self.offset.min(total_rows.saturating_sub(visible));
+        self.offset
+    }
+
+    /// Move `n` rows towards the oldest period.
+    pub fn up(&mut self, n: usize) {
+        self.offset = self.offset.saturating_sub(n);
+    }
+
+    /// Move `n` rows towards the newest period.
+    pub fn down(&mut self, n: usize) {
+        self.offset = self.offset.saturating_add(n);
+    }
+
+    /// Number of rows a page-up/page-down moves by.
+    pub fn page(&self) -> usize {
+        self.visible
+    }
+
+    /// Jump to the first (oldest) row.
+    pub fn home(&mut self) {
+        self.offset = 0;
+    }
+
+    /// Jump so the last (newest) row is visible.
+    pub fn end(&mut self) {
+        self.offset = usize::MAX;
+    }
+}
+
+/// Number of data rows that fit in `area` once the borders, header, totals
+/// row and per-model line are accounted for.
+pub fn visible_row_count(area: Rect, totals: &TableTotals) -> usize {
+    let chrome = 4 + u16::from(!totals.model_shares.is_empty());
+    usize::from(area.height.saturating_sub(chrome))
+}
+
 /// Aggregated totals across all rows in the table.
 #[derive(Debug, Clone)]
 pub struct TableTotals {
@@ -52,32 +321,66 @@ pub struct TableTotals {
     pub total_cost: f64,
     /// Number of periods (rows) represented.
     pub entries_count: u32,
+    /// Share of total tokens per model as `(model_name, percentage)` pairs,
+    /// largest first.
+    pub model_shares: Vec<(String, f64)>,
+}
+
+/// Everything a table view shows, produced by the loader passed to
+/// [`App::run_table_loading`](crate::app::App::run_table_loading).
+#[derive(Debug, Clone)]
+pub struct TableData {
+    /// One row per period (or project), oldest first.
+    pub rows: Vec<TableRowData>,
+    /// Totals over every period, including hidden ones.
+    pub totals: TableTotals,
+    /// Rows left out by `--min-tokens` / `--min-cost`.
+    pub hidden_rows: usize,
 }
 
-/// Render the daily or monthly aggregate table into `area`.
+/// Everything [`render_table_view`] draws.
+#[derive(Clone, Copy)]
+pub struct TableViewParams<'a> {
+    /// Title and first-column labels.
+    pub heading: TableHeading<'a>,
+    /// The data rows, in display order.
+    pub rows: &'a [TableRowData],
+    /// Totals for the summary row and the model shares line.
+    pub totals: &'a TableTotals,
+    /// The columns to draw.
+    pub columns: &'a TableColumns,
+    /// Colours each row's total and cost by its share of the heaviest row.
+    pub heatmap: Option<&'a TableHeatmap>,
+    /// Separators and compact formatting for counts and costs.
+    pub numbers: &'a NumberFormatter,
+    /// Colour theme.
+    pub theme: &'a Theme,
+}
+
+/// Render an aggregate table (daily, monthly or per project) into `area`.
 ///
 /// The table has one data row per [`TableRowData`] entry, followed by a
-/// highlighted totals row, all within a bordered block titled `title`.
-pub fn render_table_view(
-    frame: &mut Frame,
-    area: Rect,
-    title: &str,
-    rows: &[TableRowData],
-    totals: &TableTotals,
-    theme: &Theme,
-) {
-    let header_cells = [
-        "Period",
-        "Models",
-        "Input",
-        "Output",
-        "Cache Create",
-        "Cache Read",
-        "Total",
-        "Cost",
-    ]
-    .iter()
-    .map(|h| Cell::from(*h).style(theme.table_header));
+/// highlighted totals row, all within a bordered block labelled by `heading`.
+/// Counts and costs use the separators from `numbers`; with
+/// `numbers.compact` set, per-period token counts are abbreviated while the
+/// totals row stays exact. Only the `columns` selected are drawn, in both the
+/// data rows and the totals row. With a `heatmap`, each row's total and cost
+/// are coloured by [`Theme::cost_style`] according to their share of the
+/// heaviest row, over the alternating row background.
+pub fn render_table_view(frame: &mut Frame, area: Rect, params: &TableViewParams<'_>) {
+    let TableViewParams {
+        heading,
+        rows,
+        totals,
+        columns,
+        heatmap,
+        numbers,
+        theme,
+    } = *params;
+    let columns = columns.columns();
+    let header_cells = columns
+        .iter()
+        .map(|c| Cell::from(c.header(heading.key_column)).style(theme.table_header));
     let header = Row::new(header_cells).height(1);
 
     let data_rows: Vec<Row> = rows
@@ -89,57 +392,92 @@ pub fn render_table_view(
             } else {
                 theme.table_row_alt
             };
-            Row::new(vec![
-                Cell::from(row.period.clone()),
-                Cell::from(row.models.join(", ")),
-                Cell::from(formatting::format_number(row.input_tokens as f64, 0)),
-                Cell::from(formatting::format_number(row.output_tokens as f64, 0)),
-                Cell::from(formatting::format_number(row.cache_creation as f64, 0)),
-                Cell::from(formatting::format_number(row.cache_read as f64, 0)),
-                Cell::from(formatting::format_number(row.total_tokens as f64, 0)),
-                Cell::from(formatting::format_currency(row.cost)),
-            ])
+            let (token_heat, cost_heat) = heatmap.map_or(Default::default(), |h| {
+                let tokens = TableHeatmap::percent(row.total_tokens as f64, h.max_tokens as f64);
+                let cost = TableHeatmap::percent(row.cost, h.max_cost);
+                (theme.cost_style(tokens), theme.cost_style(cost))
+            });
+            Row::new(columns.iter().map(|column| match column {
+                TableColumn::Period => Cell::from(row.period.clone()),
+                TableColumn::Models => Cell::from(row.models.join(", ")),
+                TableColumn::Input => Cell::from(numbers.format_tokens(row.input_tokens)),
+                TableColumn::Output => Cell::from(numbers.format_tokens(row.output_tokens)),
+                TableColumn::CacheCreate => Cell::from(numbers.format_tokens(row.cache_creation)),
+                TableColumn::CacheRead => Cell::from(numbers.format_tokens(row.cache_read)),
+                TableColumn::Total => {
+                    Cell::from(numbers.format_tokens(row.total_tokens)).style(token_heat)
+                }
+                TableColumn::Cost => Cell::from(numbers.format_currency(row.cost)).style(cost_heat),
+            }))
             .style(style)
         })
         .collect();
 
     // Totals row – styled separately to stand out.
-    let total_row = Row::new(vec![
-        Cell::from("TOTAL").style(theme.table_total),
-        Cell::from(format!("{} periods", totals.entries_count)),
-        Cell::from(formatting::format_number(totals.input_tokens as f64, 0)),
-        Cell::from(formatting::format_number(totals.output_tokens as f64, 0)),
-        Cell::from(formatting::format_number(totals.cache_creation as f64, 0)),
-        Cell::from(formatting::format_number(totals.cache_read as f64, 0)),
-        Cell::from(formatting::format_number(totals.total_tokens as f64, 0)),
-        Cell::from(formatting::format_currency(totals.total_cost)),
-    ])
+    let exact = |n: u64| Cell::from(numbers.format_number(n as f64, 0));
+    let total_row = Row::new(columns.iter().map(|column| match column {
+        TableColumn::Period => Cell::from("TOTAL").style(theme.table_total),
+        TableColumn::Models => Cell::from(format!("{} {}", totals.entries_count, heading.row_noun)),
+        TableColumn::Input => exact(totals.input_tokens),
+        TableColumn::Output => exact(totals.output_tokens),
+        TableColumn::CacheCreate => exact(totals.cache_creation),
+        TableColumn::CacheRead => exact(totals.cache_read),
+        TableColumn::Total => exact(totals.total_tokens),
+        TableColumn::Cost => Cell::from(numbers.format_currency(totals.total_cost)),
+    }))
     .style(theme.table_total);
 
     let mut all_rows = data_rows;
     all_rows.push(total_row);
 
-    let widths = [
-        Constraint::Length(12),
-        Constraint::Length(25),
-        Constraint::Length(12),
-        Constraint::Length(12),
-        Constraint::Length(14),
-        Constraint::Length(12),
-        Constraint::Length(12),
-        Constraint::Length(12),
-    ];
+    // Project names can be long; dates always fit the minimum width.
+    let key_width = rows
+        .iter()
+        .map(|r| r.period.chars().count() as u16 + 1)
+        .max()
+        .unwrap_or(0)
+        .clamp(12, 40);
+
+    let widths: Vec<Constraint> = columns.iter().map(|c| c.width(key_width)).collect();
 
     let table = Table::new(all_rows, widths)
         .header(header)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!(" {} ", title)),
+                .border_style(theme.table_border)
+                .title(format!(" {} ", heading.title)),
         )
         .style(theme.text);
 
-    frame.render_widget(table, area);
+    if totals.model_shares.is_empty() {
+        frame.render_widget(table, area);
+        return;
+    }
+
+    let [table_area, models_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+    frame.render_widget(table, table_area);
+    frame.render_widget(
+        Paragraph::new(model_shares_line(&totals.model_shares, theme)),
+        models_area,
+    );
+}
+
+/// Build the per-model totals line shown under the table, e.g.
+/// `By model: claude-3-opus 62.5% | claude-3-5-sonnet 37.5%`.
+fn model_shares_line<'a>(shares: &[(String, f64)], theme: &Theme) -> Line<'a> {
+    let mut spans = vec![Span::styled(" By model: ", theme.table_total)];
+    for (i, (model, pct)) in shares.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" | ", theme.dim));
+        }
+        spans.push(Span::styled(
+            format!("{} {:.1}%", model, pct),
+            theme.model_style(model),
+        ));
+    }
+    Line::from(spans)
 }
 
 /// Render a "no data" placeholder when there are no periods to show.
@@ -164,6 +502,57 @@ pub fn render_no_data(frame: &mut Frame, area: Rect, theme: &Theme) {
     );
 }
 
+/// Render a centered "Analyzing usage…" spinner while the table data loads.
+///
+/// `tick` picks the spinner frame; `progress`, once the loader has reported
+/// any, adds a files / entries line below.
+pub fn render_analyzing(
+    frame: &mut Frame,
+    area: Rect,
+    tick: usize,
+    progress: Option<&LoadProgress>,
+    theme: &Theme,
+) {
+    let spinner = SPINNER_FRAMES[tick % SPINNER_FRAMES.len()];
+    let mut text = vec![Line::from(Span::styled(
+        format!("{spinner} Analyzing usage…"),
+        theme.info,
+    ))];
+    if let Some(progress) = progress.filter(|p| p.files_total > 0) {
+        text.push(Line::from(Span::styled(
+            format!(
+                "{} / {} files · {} entries",
+                progress.files_processed,
+                progress.files_total,
+                format_number(progress.entries_parsed as f64, 0)
+            ),
+            theme.dim,
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "Press 'q' or Ctrl+C to exit",
+        theme.dim,
+    )));
+
+    // Borders take two rows; pad the rest so the text sits mid-screen.
+    let inner_height = usize::from(area.height.saturating_sub(2));
+    let top = inner_height.saturating_sub(text.len()) / 2;
+    let mut lines = vec![Line::from(""); top];
+    lines.extend(text);
+
+    frame.render_widget(
+        Paragraph::new(ratatui::text::Text::from(lines))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Claude Monitor "),
+            ),
+        area,
+    );
+}
+
 // ── Tests ──────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -210,6 +599,10 @@ mod tests {
             total_tokens: rows.iter().map(|r| r.total_tokens).sum(),
             total_cost: rows.iter().map(|r| r.cost).sum(),
             entries_count: rows.len() as u32,
+            model_shares: vec![
+                ("claude-3-5-sonnet".to_string(), 80.0),
+                ("claude-3-haiku".to_string(), 20.0),
+            ],
         }
     }
 
@@ -247,7 +640,19 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_table_view(frame, area, "Daily Usage", &rows, &totals, &theme);
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Daily Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::default(),
+                        heatmap: None,
+                        numbers: &NumberFormatter::default(),
+                        theme: &theme,
+                    },
+                );
             })
             .unwrap();
     }
@@ -266,16 +671,381 @@ mod tests {
             total_tokens: 0,
             total_cost: 0.0,
             entries_count: 0,
+            model_shares: vec![],
         };
 
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_table_view(frame, area, "Daily Usage", &rows, &totals, &theme);
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Daily Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::default(),
+                        heatmap: None,
+                        numbers: &NumberFormatter::default(),
+                        theme: &theme,
+                    },
+                );
             })
             .unwrap();
     }
 
+    #[test]
+    fn test_model_shares_line_lists_each_model() {
+        let theme = Theme::dark();
+        let shares = vec![
+            ("claude-3-opus".to_string(), 62.5),
+            ("claude-3-5-sonnet".to_string(), 37.5),
+        ];
+        let line = model_shares_line(&shares, &theme);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.contains("claude-3-opus 62.5%"), "line: {text}");
+        assert!(text.contains(" | claude-3-5-sonnet 37.5%"), "line: {text}");
+    }
+
+    #[test]
+    fn test_render_table_view_shows_model_shares() {
+        let backend = TestBackend::new(130, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let rows = make_rows();
+        let totals = make_totals(&rows);
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Daily Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::default(),
+                        heatmap: None,
+                        numbers: &NumberFormatter::default(),
+                        theme: &theme,
+                    },
+                );
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let last_line: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, buffer.area.height - 1)].symbol().to_string())
+            .collect();
+        assert!(last_line.contains("By model:"), "last line: {last_line}");
+        assert!(last_line.contains("80.0%"), "last line: {last_line}");
+    }
+
+    #[test]
+    fn test_render_table_view_uses_number_format() {
+        let backend = TestBackend::new(130, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let rows = make_rows();
+        let totals = make_totals(&rows);
+        let numbers = NumberFormatter::from_name("dot");
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Daily Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::default(),
+                        heatmap: None,
+                        numbers: &numbers,
+                        theme: &theme,
+                    },
+                );
+            })
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("29.400"), "total not dot-grouped");
+        assert!(content.contains("$2,45"), "cost not comma-decimal");
+    }
+
+    #[test]
+    fn test_render_table_view_compact_rows_exact_totals() {
+        let backend = TestBackend::new(130, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let rows = make_rows();
+        let totals = make_totals(&rows);
+        let numbers = NumberFormatter::default().with_compact(true);
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Daily Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::default(),
+                        heatmap: None,
+                        numbers: &numbers,
+                        theme: &theme,
+                    },
+                );
+            })
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("15.7k"), "row total not abbreviated");
+        assert!(!content.contains("29,400"), "row total not abbreviated");
+        assert!(content.contains("45,100"), "totals row not exact");
+    }
+
+    #[test]
+    fn test_render_table_view_project_heading() {
+        let backend = TestBackend::new(160, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let mut rows = make_rows();
+        rows[0].period = "-home-me-projects-web-frontend".to_string();
+        let totals = make_totals(&rows);
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: TableHeading::new("Projects Usage", "Project", "projects"),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::default(),
+                        heatmap: None,
+                        numbers: &NumberFormatter::default(),
+                        theme: &theme,
+                    },
+                );
+            })
+            .unwrap();
+
+        let content: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(content.contains("Project"), "missing key column header");
+        assert!(!content.contains("Period"));
+        assert!(content.contains("2 projects"));
+        assert!(
+            content.contains("-home-me-projects-web-frontend"),
+            "project name truncated"
+        );
+    }
+
+    // ── TableColumns ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_table_columns_parse() {
+        assert_eq!(TableColumns::parse("all").unwrap(), TableColumns::default());
+        assert_eq!(
+            TableColumns::parse("compact").unwrap().columns(),
+            [TableColumn::Period, TableColumn::Total, TableColumn::Cost]
+        );
+        // Period is always kept and the display order is fixed.
+        assert_eq!(
+            TableColumns::parse("cost, Input").unwrap().columns(),
+            [TableColumn::Period, TableColumn::Input, TableColumn::Cost]
+        );
+        assert_eq!(
+            TableColumns::parse("project,total").unwrap().columns(),
+            [TableColumn::Period, TableColumn::Total]
+        );
+        let err = TableColumns::parse("total,tokens").unwrap_err();
+        assert!(err.to_string().contains("\"tokens\""), "{err}");
+        assert_eq!(
+            TableColumn::from_name("cache-read"),
+            Some(TableColumn::CacheRead)
+        );
+        assert_eq!(TableColumn::from_name("cache"), None);
+        for column in TableColumn::ALL {
+            assert_eq!(TableColumn::from_name(column.name()), Some(column));
+        }
+    }
+
+    #[test]
+    fn test_render_table_view_compact_columns_fit_80_cols() {
+        let backend = TestBackend::new(80, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let rows = make_rows();
+        let totals = make_totals(&rows);
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Daily Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::parse("compact").unwrap(),
+                        heatmap: None,
+                        numbers: &NumberFormatter::default(),
+                        theme: &theme,
+                    },
+                );
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let lines: Vec<String> = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        let content = lines.join("\n");
+        assert!(content.contains("Total"), "{content}");
+        assert!(content.contains("Cost"), "{content}");
+        assert!(!content.contains("Models"), "{content}");
+        assert!(!content.contains("Cache Read"), "{content}");
+        let total_line = lines.iter().find(|l| l.contains("TOTAL")).unwrap();
+        assert!(total_line.contains("45,100"), "{total_line}");
+        assert!(total_line.contains("$3.68"), "{total_line}");
+        assert!(!total_line.contains("30,000"), "{total_line}");
+    }
+
+    // ── TableHeatmap ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_table_heatmap_from_rows() {
+        let heatmap = TableHeatmap::from_rows(&make_rows());
+        assert_eq!(heatmap.max_tokens, 29_400);
+        assert!((heatmap.max_cost - 2.45).abs() < 1e-9);
+        assert_eq!(TableHeatmap::from_rows(&[]), TableHeatmap::default());
+        assert_eq!(TableHeatmap::percent(5.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_render_table_view_heatmap_colours_totals() {
+        let backend = TestBackend::new(130, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let mut rows = make_rows();
+        rows[0].total_tokens = 2_000;
+        rows[0].cost = 0.10;
+        let totals = make_totals(&rows);
+        let heatmap = TableHeatmap::from_rows(&rows);
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Daily Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::parse("compact").unwrap(),
+                        heatmap: Some(&heatmap),
+                        numbers: &NumberFormatter::default(),
+                        theme: &theme,
+                    },
+                );
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let find = |text: &str| -> (u16, u16) {
+            (0..buffer.area.height)
+                .find_map(|y| {
+                    let line: String = (0..buffer.area.width)
+                        .map(|x| buffer[(x, y)].symbol().to_string())
+                        .collect();
+                    line.find(text).map(|x| (x as u16, y))
+                })
+                .unwrap_or_else(|| panic!("{text} not rendered"))
+        };
+        let fg = |(x, y): (u16, u16)| buffer[(x, y)].fg;
+
+        // Light day green, heaviest day red.
+        assert_eq!(fg(find("2,000")), theme.cost_low.fg.unwrap());
+        assert_eq!(fg(find("$0.10")), theme.cost_low.fg.unwrap());
+        assert_eq!(fg(find("29,400")), theme.cost_high.fg.unwrap());
+        assert_eq!(fg(find("$2.45")), theme.cost_high.fg.unwrap());
+        // The period cells keep the alternating row styles.
+        assert_eq!(fg(find("2024-01-15")), theme.table_row.fg.unwrap());
+        assert_eq!(fg(find("2024-01-16")), theme.table_row_alt.fg.unwrap());
+    }
+
+    // ── TableScroll ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_table_scroll_starts_at_last_row() {
+        let mut scroll = TableScroll::default();
+        assert_eq!(scroll.fit(30, 10), 20);
+        // Fewer rows than fit → nothing to scroll.
+        let mut scroll = TableScroll::default();
+        assert_eq!(scroll.fit(5, 10), 0);
+    }
+
+    #[test]
+    fn test_table_scroll_navigation() {
+        let mut scroll = TableScroll::default();
+        scroll.fit(30, 10);
+
+        scroll.up(3);
+        assert_eq!(scroll.fit(30, 10), 17);
+        scroll.up(scroll.page());
+        assert_eq!(scroll.fit(30, 10), 7);
+        scroll.home();
+        assert_eq!(scroll.fit(30, 10), 0);
+        scroll.up(1);
+        assert_eq!(scroll.fit(30, 10), 0);
+        scroll.down(100);
+        assert_eq!(scroll.fit(30, 10), 20);
+        scroll.home();
+        scroll.end();
+        assert_eq!(scroll.fit(30, 10), 20);
+    }
+
+    #[test]
+    fn test_visible_row_count() {
+        let rows = make_rows();
+        let mut totals = make_totals(&rows);
+        assert_eq!(visible_row_count(Rect::new(0, 0, 80, 20), &totals), 15);
+        totals.model_shares.clear();
+        assert_eq!(visible_row_count(Rect::new(0, 0, 80, 20), &totals), 16);
+        assert_eq!(visible_row_count(Rect::new(0, 0, 80, 3), &totals), 0);
+    }
+
     #[test]
     fn test_render_no_data_does_not_panic() {
         let backend = TestBackend::new(80, 24);
@@ -290,6 +1060,34 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_render_analyzing_shows_spinner_and_progress() {
+        let backend = TestBackend::new(60, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let theme = Theme::dark();
+        let progress = LoadProgress {
+            files_processed: 3,
+            files_total: 8,
+            entries_parsed: 12_345,
+        };
+
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_analyzing(frame, area, 1, Some(&progress), &theme);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| -> String { (0..60).map(|x| buffer[(x, y)].symbol()).collect() };
+        let spinner_row = (0..12)
+            .find(|&y| row(y).contains("Analyzing usage"))
+            .expect("spinner line");
+        assert!(row(spinner_row).contains(SPINNER_FRAMES[1]));
+        assert!(spinner_row >= 3, "text is vertically centered");
+        assert!(row(spinner_row + 1).contains("3 / 8 files · 12,345 entries"));
+    }
+
     #[test]
     fn test_render_table_view_monthly_title_does_not_panic() {
         let backend = TestBackend::new(130, 30);
@@ -310,7 +1108,19 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_table_view(frame, area, "Monthly Usage", &rows, &totals, &theme);
+                render_table_view(
+                    frame,
+                    area,
+                    &TableViewParams {
+                        heading: "Monthly Usage".into(),
+                        rows: &rows,
+                        totals: &totals,
+                        columns: &TableColumns::default(),
+                        heatmap: None,
+                        numbers: &NumberFormatter::default(),
+                        theme: &theme,
+                    },
+                );
             })
             .unwrap();
     }
@@ -29,6 +29,20 @@ pub fn detect_background() -> BackgroundType {
     BackgroundType::Dark
 }
 
+/// Whether styling should be turned off: `NO_COLOR` is set to a non-empty
+/// value (see <https://no-color.org>) or `TERM` is `dumb`.
+pub fn colors_disabled() -> bool {
+    colors_disabled_from(
+        std::env::var_os("NO_COLOR"),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+/// [`colors_disabled`] for explicit `NO_COLOR` and `TERM` values.
+pub fn colors_disabled_from(no_color: Option<std::ffi::OsString>, term: Option<&str>) -> bool {
+    no_color.is_some_and(|value| !value.is_empty()) || term == Some("dumb")
+}
+
 /// Complete theme definition carrying all UI styles used by monitor-ui
 /// components.
 #[derive(Debug, Clone)]
@@ -66,6 +80,8 @@ pub struct Theme {
     pub cost_low: Style,
     pub cost_medium: Style,
     pub cost_high: Style,
+    /// Token percentage once usage is past the limit.
+    pub over_limit: Style,
 
     // ── Models ───────────────────────────────────────────────────────────────
     pub model_opus: Style,
@@ -132,6 +148,9 @@ impl Theme {
             cost_low: Style::default().fg(Color::Green),
             cost_medium: Style::default().fg(Color::Yellow),
             cost_high: Style::default().fg(Color::Red),
+            over_limit: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
 
             model_opus: Style::default().fg(Color::Magenta),
             model_sonnet: Style::default().fg(Color::Cyan),
@@ -195,6 +214,9 @@ impl Theme {
             cost_low: Style::default().fg(Color::Green),
             cost_medium: Style::default().fg(Color::Yellow),
             cost_high: Style::default().fg(Color::Red),
+            over_limit: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
 
             model_opus: Style::default().fg(Color::Magenta),
             model_sonnet: Style::default().fg(Color::Blue),
@@ -252,6 +274,9 @@ impl Theme {
             cost_low: Style::default().fg(Color::Green),
             cost_medium: Style::default().fg(Color::Yellow),
             cost_high: Style::default().fg(Color::Red),
+            over_limit: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::REVERSED),
 
             model_opus: Style::default().fg(Color::Magenta),
             model_sonnet: Style::default().fg(Color::Cyan),
@@ -275,8 +300,66 @@ impl Theme {
         }
     }
 
+    /// Theme without any colours or modifiers, used when
+    /// [`colors_disabled`] is true.
+    pub fn plain() -> Self {
+        let none = Style::default();
+        Self {
+            header: none,
+            header_sparkle: none,
+            separator: none,
+
+            text: none,
+            dim: none,
+            bold: none,
+            label: none,
+            value: none,
+
+            info: none,
+            success: none,
+            warning: none,
+            error: none,
+
+            progress_low: none,
+            progress_medium: none,
+            progress_high: none,
+            progress_empty: none,
+            progress_label: none,
+
+            cost_low: none,
+            cost_medium: none,
+            cost_high: none,
+            over_limit: none,
+
+            model_opus: none,
+            model_sonnet: none,
+            model_haiku: none,
+            model_unknown: none,
+
+            table_header: none,
+            table_border: none,
+            table_row: none,
+            table_row_alt: none,
+            table_total: none,
+
+            notification_info: none,
+            notification_warning: none,
+            notification_error: none,
+
+            velocity_slow: none,
+            velocity_normal: none,
+            velocity_fast: none,
+            velocity_extreme: none,
+        }
+    }
+
     /// Choose a theme automatically based on the detected terminal background.
+    ///
+    /// Returns [`Theme::plain`] when [`colors_disabled`].
     pub fn auto_detect() -> Self {
+        if colors_disabled() {
+            return Self::plain();
+        }
         match detect_background() {
             BackgroundType::Light => Self::light(),
             _ => Self::dark(),
@@ -285,7 +368,12 @@ impl Theme {
 
     /// Construct a theme by name.  Falls back to `auto_detect` for unknown
     /// names.
+    ///
+    /// `NO_COLOR` and `TERM=dumb` win over the name: see [`colors_disabled`].
     pub fn from_name(name: &str) -> Self {
+        if colors_disabled() {
+            return Self::plain();
+        }
         match name {
             "light" => Self::light(),
             "dark" => Self::dark(),
@@ -324,6 +412,22 @@ impl Theme {
         }
     }
 
+    /// Return the style for a reset time given the percentage of the session
+    /// window still remaining.
+    ///
+    /// * `< 10 %` → `error`
+    /// * `< 25 %` → `warning`
+    /// * otherwise → `value`
+    pub fn reset_style(&self, remaining_percentage: f64) -> Style {
+        if remaining_percentage < 10.0 {
+            self.error
+        } else if remaining_percentage < 25.0 {
+            self.warning
+        } else {
+            self.value
+        }
+    }
+
     /// Return the model-colour style that best matches a raw model name string.
     pub fn model_style(&self, model: &str) -> Style {
         let lower = model.to_lowercase();
@@ -432,6 +536,31 @@ mod tests {
         assert!(t.header.fg.is_some());
     }
 
+    // ── colors_disabled_from / plain ─────────────────────────────────────────
+
+    #[test]
+    fn test_colors_disabled_from_env_values() {
+        assert!(colors_disabled_from(Some("1".into()), Some("xterm")));
+        assert!(colors_disabled_from(None, Some("dumb")));
+        assert!(!colors_disabled_from(Some("".into()), Some("xterm")));
+        assert!(!colors_disabled_from(None, None));
+    }
+
+    #[test]
+    fn test_plain_theme_has_no_styles() {
+        let t = Theme::plain();
+        for style in [
+            t.header,
+            t.table_header,
+            t.table_border,
+            t.over_limit,
+            t.progress_style(90.0),
+            t.model_style("claude-3-opus"),
+        ] {
+            assert_eq!(style, Style::default());
+        }
+    }
+
     // ── progress_style thresholds ────────────────────────────────────────────
 
     #[test]
@@ -478,6 +607,19 @@ mod tests {
         assert_eq!(t.cost_style(100.0).fg, Some(Color::Red));
     }
 
+    // ── reset_style thresholds ───────────────────────────────────────────────
+
+    #[test]
+    fn test_reset_style_thresholds() {
+        let t = Theme::dark();
+        assert_eq!(t.reset_style(100.0), t.value);
+        assert_eq!(t.reset_style(25.0), t.value);
+        assert_eq!(t.reset_style(24.9), t.warning);
+        assert_eq!(t.reset_style(10.0), t.warning);
+        assert_eq!(t.reset_style(9.9), t.error);
+        assert_eq!(t.reset_style(0.0), t.error);
+    }
+
     // ── model_style ──────────────────────────────────────────────────────────
 
     #[test]